@@ -240,15 +240,30 @@ fn main(){
     // about 1.68 bar
     // what is the water quality?
     // as in how much liquid water remains?
-    // we can use the quality calculation
+    // now that we know the final pressure and specific volume, 
+    // the pv flash (pressure and specific volume) gives us the 
+    // quality and enthalpy directly
 
-    use tampines_steam_tables::interfaces::functional_programming::ph_flash_eqm::x_ph_flash;
-    let final_quality = x_ph_flash(final_pressure, water_enthalpy);
+    use tampines_steam_tables::interfaces::functional_programming::pv_flash_eqm::{h_pv_eqm, x_pv_flash};
+    let final_quality = x_pv_flash(final_pressure, final_specific_volume);
 
     println!("the final quality is:");
     println!("{:?}",final_quality);
-    assert_eq!(final_quality,
-        0.38614681771539744
+    approx::assert_relative_eq!(final_quality,
+        0.38614681771539744,
+        max_relative = 1e-9
+    );
+
+    // the enthalpy from the pv flash should be the initial 
+    // water enthalpy
+    let final_enthalpy: AvailableEnergy = 
+        h_pv_eqm(final_pressure, final_specific_volume);
+
+    println!("the final enthalpy is:");
+    println!("{:?}",final_enthalpy);
+    approx::assert_relative_eq!(final_enthalpy.value,
+        water_enthalpy.value,
+        max_relative = 1e-9
     );
     // which means we have a saturated vapour/liquid equilibrium
     // quality is (mass of vap)/(mass of vap + mass of liq)
@@ -1,6 +1,8 @@
 use uom::si::{dynamic_viscosity::pascal_second, f64::*, ratio::ratio};
+use uom::si::length::nanometer;
 
-use crate::{constants::{rho_crit_water, t_crit_water}, interfaces::functional_programming::{ph_flash_eqm::{t_ph_eqm, v_ph_eqm}, pt_flash_eqm::{v_tp_eqm_single_phase, v_tp_eqm_two_phase}}};
+use crate::{constants::{p_crit_water, rho_crit_water, t_crit_water}, interfaces::functional_programming::{ph_flash_eqm::{ph_flash_region, t_ph_eqm, v_ph_eqm}, pt_flash_eqm::{v_tp_eqm_single_phase, v_tp_eqm_two_phase, FwdEqnRegion}}};
+use crate::region_3_single_phase_plus_supercritical_steam::kappa_t_rho_t_3;
 
 
 const PSI_0_COEFFS: [[f64; 2]; 4] = [
@@ -55,6 +57,16 @@ pub fn mu_tp_eqm_single_phase(t: ThermodynamicTemperature,
 
     return psi * eta_star;
 
+}
+/// for viscosity estimates in single phase region
+/// including the critical enhancement 
+/// (see mu_rho_t_eqm_with_crit_enhancement)
+pub fn mu_tp_eqm_single_phase_with_crit_enhancement(t: ThermodynamicTemperature,
+    p: Pressure) -> DynamicViscosity {
+    let rho = v_tp_eqm_single_phase(t, p).recip();
+
+    return mu_rho_t_eqm_with_crit_enhancement(t, rho);
+
 }
 /// for viscosity estimates in two phase region
 /// and single phase region
@@ -66,6 +78,22 @@ pub fn mu_rho_t_eqm(t: ThermodynamicTemperature,
 
     return psi * eta_star;
 
+}
+/// for viscosity estimates including the critical enhancement,
+/// this is the full IAPWS 2008 formulation, 
+/// mu = mu_0 * mu_1 * mu_2
+/// where mu_2 is the critical enhancement factor
+/// (psi_2_viscosity_crit_enhancement)
+///
+/// mu_rho_t_eqm leaves out mu_2 (mu_2 = 1), which is the 
+/// IAPWS 2008 recommendation for industrial use, 
+/// mu_2 only differs significantly from 1 within a few kelvin 
+/// of the critical point
+pub fn mu_rho_t_eqm_with_crit_enhancement(t: ThermodynamicTemperature,
+    rho: MassDensity,) -> DynamicViscosity {
+
+    return mu_rho_t_eqm(t, rho) * psi_2_viscosity_crit_enhancement(t, rho);
+
 }
 /// for viscosity estimates in two phase region
 /// and single phase region
@@ -78,6 +106,24 @@ pub fn mu_ph_eqm(p: Pressure,
 
     return mu_rho_t_eqm(t, rho);
 
+}
+/// for viscosity estimates using enthalpy and pressure,
+/// including the critical enhancement 
+/// (see mu_rho_t_eqm_with_crit_enhancement)
+///
+/// in region 4, the mixture density is not meaningful 
+/// for the correlation length, so this is the same as mu_ph_eqm
+pub fn mu_ph_eqm_with_crit_enhancement(p: Pressure,
+    h: AvailableEnergy) -> DynamicViscosity {
+
+    let t = t_ph_eqm(p, h);
+    let rho = v_ph_eqm(p, h).recip();
+
+    match ph_flash_region(p, h) {
+        FwdEqnRegion::Region4 => return mu_rho_t_eqm(t, rho),
+        _ => return mu_rho_t_eqm_with_crit_enhancement(t, rho),
+    }
+
 }
 
 pub(crate) fn psi_0_viscosity(t: ThermodynamicTemperature) -> f64 {
@@ -129,5 +175,112 @@ pub(crate) fn psi_1_viscosity(
 
 }
 
+/// critical enhancement factor (mu_2) for viscosity
+/// from the IAPWS 2008 formulation
+///
+/// mu_2 = exp(x_mu * Y)
+///
+/// where Y is a function of the correlation length xi, 
+/// and xi is found from the difference in the 
+/// (reduced) isothermal compressibility at T and 
+/// at the reference temperature T_R = 1.5 T_c
+///
+/// the isothermal compressibility is from the region 3 equation 
+/// everywhere, including at T_R (even though T_R is outside 
+/// region 3) as recommended for use with IAPWS-IF97, 
+/// away from the critical point, the correlation length is small 
+/// and mu_2 is close to 1 (see viscosity_correlation_length)
+pub(crate) fn psi_2_viscosity_crit_enhancement(
+    t: ThermodynamicTemperature,
+    rho: MassDensity) -> f64 {
+
+    let xi = viscosity_correlation_length(t, rho);
+
+    return viscosity_crit_enhancement_mu_2_given_xi(xi);
+}
+
+/// correlation length (xi) for the critical enhancement 
+/// of viscosity, with delta_chi found from the 
+/// region 3 isothermal compressibility 
+///
+/// zeta = (d rho/ d p)_T reduced = delta * p_c * kappa_t
+///
+/// delta_chi = delta (zeta(T, rho) - zeta(T_R, rho) T_R/T)
+///
+/// xi = xi_0 (delta_chi/ gamma_0)^(nu/gamma)
+pub(crate) fn viscosity_correlation_length(
+    t: ThermodynamicTemperature,
+    rho: MassDensity) -> Length {
+
+    let t_c = t_crit_water();
+    let theta_f64: f64 = (t/t_c).get::<ratio>();
+    let rho_c = rho_crit_water();
+    let delta_f64: f64 = (rho/rho_c).get::<ratio>();
+    let p_c = p_crit_water();
+
+    // reference temperature 
+    let theta_r: f64 = 1.5;
+    let t_r = t_c * theta_r;
+
+    // critical exponents and amplitudes
+    let nu: f64 = 0.630;
+    let gamma: f64 = 1.239;
+    let xi_0 = Length::new::<nanometer>(0.13);
+    let captial_gamma_0: f64 = 0.06;
+
+    let zeta: f64 = (delta_f64 * p_c * kappa_t_rho_t_3(rho, t))
+        .get::<ratio>();
+    let zeta_r: f64 = (delta_f64 * p_c * kappa_t_rho_t_3(rho, t_r))
+        .get::<ratio>();
+
+    let mut delta_chi: f64 = delta_f64 * (zeta - zeta_r * theta_r/theta_f64);
+
+    if delta_chi < 0.0 {
+        delta_chi = 0.0;
+    };
+
+    return xi_0 * (delta_chi/captial_gamma_0).powf(nu/gamma);
+}
+
+/// critical enhancement factor (mu_2) for viscosity given
+/// the correlation length xi
+///
+/// this is equation 12 onwards from the IAPWS 2008 formulation
+pub(crate) fn viscosity_crit_enhancement_mu_2_given_xi(
+    xi: Length) -> f64 {
+
+    let x_mu: f64 = 0.068;
+    let q_c_xi: f64 = xi.get::<nanometer>()/1.9;
+    let q_d_xi: f64 = xi.get::<nanometer>()/1.1;
+
+    // for small correlation lengths, the full expression 
+    // for Y suffers from round off error, so the 
+    // series expansion is used
+    let captial_y: f64 = if xi.get::<nanometer>() <= 0.381_701_641_6 {
+        0.2 * q_c_xi * q_d_xi.powi(5) 
+            * (1.0 - q_c_xi + q_c_xi.powi(2) 
+                - 765.0/504.0 * q_d_xi.powi(2))
+    } else {
+        let psi_d: f64 = (1.0 + q_d_xi.powi(2)).sqrt().recip().acos();
+        let w: f64 = ((q_c_xi - 1.0)/(q_c_xi + 1.0)).abs().sqrt() 
+            * (0.5 * psi_d).tan();
+
+        let captial_l_w: f64 = if q_c_xi > 1.0 {
+            ((1.0 + w)/(1.0 - w)).ln()
+        } else {
+            2.0 * w.abs().atan()
+        };
+
+        (3.0 * psi_d).sin()/12.0 
+            - (2.0 * psi_d).sin()/(4.0 * q_c_xi)
+            + (1.0 - 1.25 * q_c_xi.powi(2)) * psi_d.sin()/q_c_xi.powi(2)
+            - ((1.0 - 1.5 * q_c_xi.powi(2)) * psi_d 
+                - (q_c_xi.powi(2) - 1.0).abs().powf(1.5) * captial_l_w)
+            /q_c_xi.powi(3)
+    };
+
+    return (x_mu * captial_y).exp();
+}
+
 #[cfg(test)]
 mod tests;
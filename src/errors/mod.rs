@@ -0,0 +1,187 @@
+use thiserror::Error;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+use uom::si::pressure::megapascal;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+
+/// errors returned by the try_* flash functions 
+///
+/// the non try_* flash functions panic with the same message 
+/// as these errors. 
+///
+/// each variant carries the offending input and the 
+/// bound it violated so that the calling code (eg. a solver 
+/// which overshot for one iteration) can decide what to do
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum SteamTableError {
+    /// pressure is below the lowest pressure allowed 
+    /// (usually saturation pressure at 273.15 K)
+    #[error("pressure {} MPa is below the lower bound of {} MPa", 
+        .p.get::<megapascal>(), .bound.get::<megapascal>())]
+    PressureBelowBound {
+        /// offending pressure
+        p: Pressure,
+        /// lowest allowable pressure
+        bound: Pressure,
+    },
+    /// pressure is above the highest pressure allowed 
+    /// (usually 100 MPa, or 50 MPa in region 5)
+    #[error("pressure {} MPa is above the upper bound of {} MPa", 
+        .p.get::<megapascal>(), .bound.get::<megapascal>())]
+    PressureAboveBound {
+        /// offending pressure
+        p: Pressure,
+        /// highest allowable pressure
+        bound: Pressure,
+    },
+    /// temperature is below 273.15 K
+    #[error("temperature {} K is below the lower bound of {} K", 
+        .t.get::<kelvin>(), .bound.get::<kelvin>())]
+    TemperatureBelowBound {
+        /// offending temperature
+        t: ThermodynamicTemperature,
+        /// lowest allowable temperature
+        bound: ThermodynamicTemperature,
+    },
+    /// temperature is above the highest temperature allowed
+    #[error("temperature {} K is above the upper bound of {} K", 
+        .t.get::<kelvin>(), .bound.get::<kelvin>())]
+    TemperatureAboveBound {
+        /// offending temperature
+        t: ThermodynamicTemperature,
+        /// highest allowable temperature
+        bound: ThermodynamicTemperature,
+    },
+    /// specific enthalpy is below the lowest allowable enthalpy 
+    /// (eg. enthalpy of the 273.15 K isotherm at that pressure)
+    #[error("specific enthalpy {} kJ/kg is below the lower bound of {} kJ/kg", 
+        .h.get::<kilojoule_per_kilogram>(), .bound.get::<kilojoule_per_kilogram>())]
+    EnthalpyBelowBound {
+        /// offending specific enthalpy
+        h: AvailableEnergy,
+        /// lowest allowable specific enthalpy
+        bound: AvailableEnergy,
+    },
+    /// specific enthalpy is above the highest allowable enthalpy 
+    /// (eg. enthalpy of the 1073.15 K isotherm at that pressure)
+    #[error("specific enthalpy {} kJ/kg is above the upper bound of {} kJ/kg", 
+        .h.get::<kilojoule_per_kilogram>(), .bound.get::<kilojoule_per_kilogram>())]
+    EnthalpyAboveBound {
+        /// offending specific enthalpy
+        h: AvailableEnergy,
+        /// highest allowable specific enthalpy
+        bound: AvailableEnergy,
+    },
+    /// specific internal energy is below the lowest allowable internal energy
+    /// (eg. internal energy of the 273.15 K isotherm at that specific volume)
+    #[error("specific internal energy {} kJ/kg is below the lower bound of {} kJ/kg",
+        .u.get::<kilojoule_per_kilogram>(), .bound.get::<kilojoule_per_kilogram>())]
+    InternalEnergyBelowBound {
+        /// offending specific internal energy
+        u: AvailableEnergy,
+        /// lowest allowable specific internal energy
+        bound: AvailableEnergy,
+    },
+    /// specific internal energy is above the highest allowable internal energy
+    /// (eg. internal energy at the highest temperature or pressure
+    /// allowable at that specific volume)
+    #[error("specific internal energy {} kJ/kg is above the upper bound of {} kJ/kg",
+        .u.get::<kilojoule_per_kilogram>(), .bound.get::<kilojoule_per_kilogram>())]
+    InternalEnergyAboveBound {
+        /// offending specific internal energy
+        u: AvailableEnergy,
+        /// highest allowable specific internal energy
+        bound: AvailableEnergy,
+    },
+    /// specific entropy is below the lowest allowable entropy
+    #[error("specific entropy {} kJ/(kg K) is below the lower bound of {} kJ/(kg K)", 
+        .s.get::<kilojoule_per_kilogram_kelvin>(), 
+        .bound.get::<kilojoule_per_kilogram_kelvin>())]
+    EntropyBelowBound {
+        /// offending specific entropy
+        s: SpecificHeatCapacity,
+        /// lowest allowable specific entropy
+        bound: SpecificHeatCapacity,
+    },
+    /// specific entropy is above the highest allowable entropy
+    #[error("specific entropy {} kJ/(kg K) is above the upper bound of {} kJ/(kg K)", 
+        .s.get::<kilojoule_per_kilogram_kelvin>(), 
+        .bound.get::<kilojoule_per_kilogram_kelvin>())]
+    EntropyAboveBound {
+        /// offending specific entropy
+        s: SpecificHeatCapacity,
+        /// highest allowable specific entropy
+        bound: SpecificHeatCapacity,
+    },
+    /// density is below the lowest allowable density 
+    /// (density must be positive)
+    #[error("density {} kg/m3 is below the lower bound of {} kg/m3", 
+        .rho.get::<kilogram_per_cubic_meter>(), 
+        .bound.get::<kilogram_per_cubic_meter>())]
+    DensityBelowBound {
+        /// offending density
+        rho: MassDensity,
+        /// lowest allowable density
+        bound: MassDensity,
+    },
+    /// specific volume is below the lowest allowable specific volume 
+    /// (eg. the specific volume of liquid at maximum density)
+    #[error("specific volume {} m3/kg is below the lower bound of {} m3/kg", 
+        .v.get::<cubic_meter_per_kilogram>(), 
+        .bound.get::<cubic_meter_per_kilogram>())]
+    SpecificVolumeBelowBound {
+        /// offending specific volume
+        v: SpecificVolume,
+        /// lowest allowable specific volume
+        bound: SpecificVolume,
+    },
+    /// specific volume is above the highest allowable specific volume 
+    /// (eg. the specific volume along the highest isotherm)
+    #[error("specific volume {} m3/kg is above the upper bound of {} m3/kg", 
+        .v.get::<cubic_meter_per_kilogram>(), 
+        .bound.get::<cubic_meter_per_kilogram>())]
+    SpecificVolumeAboveBound {
+        /// offending specific volume
+        v: SpecificVolume,
+        /// highest allowable specific volume
+        bound: SpecificVolume,
+    },
+    /// steam quality is below 0 (eg. for a saturated mixture)
+    #[error("steam quality {x} is below the lower bound of {bound}")]
+    QualityBelowBound {
+        /// offending steam quality
+        x: f64,
+        /// lowest allowable steam quality
+        bound: f64,
+    },
+    /// steam quality is above 1 (eg. for a saturated mixture)
+    #[error("steam quality {x} is above the upper bound of {bound}")]
+    QualityAboveBound {
+        /// offending steam quality
+        x: f64,
+        /// highest allowable steam quality
+        bound: f64,
+    },
+    /// the iterative solver used by the flash did not converge
+    #[error("{flash} flash did not converge after {iterations} iterations")]
+    FlashDidNotConverge {
+        /// which flash was attempted, eg. "(h,s)"
+        flash: &'static str,
+        /// number of iterations attempted
+        iterations: usize,
+    },
+    /// the point lies within the IAPWS-IF97 but the flash 
+    /// is not (yet) implemented for this region
+    #[error("{flash} flash is not implemented for {region:?}")]
+    RegionNotImplemented {
+        /// which flash was attempted, eg. "(p,h)"
+        flash: &'static str,
+        /// region in which the point lies
+        region: FwdEqnRegion,
+    },
+}
@@ -0,0 +1,174 @@
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+
+use crate::errors::SteamTableError;
+use crate::region_2_vapour::p_2b2c;
+use crate::region_3_single_phase_plus_supercritical_steam::{is_3a_when_in_region_3, s_3a3b_backwards_ps_boundary};
+
+use super::hs_flash_eqm::BackwdEqnSubRegion;
+use super::pt_flash_eqm::FwdEqnRegion;
+use super::trho_flash_eqm::{h_trho_single_phase, s_trho_single_phase, try_h_trho_eqm, try_s_trho_eqm, try_u_trho_eqm, u_trho_single_phase};
+
+/// final residuals of a flash, ie. the forward equations evaluated
+/// at the flashed state minus the input property
+///
+/// only the properties which were inputs to the flash
+/// (or which the flash iterated on) have residuals,
+/// the rest are None
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FlashResiduals {
+    /// pressure residual
+    pub p: Option<Pressure>,
+    /// specific enthalpy residual
+    pub h: Option<AvailableEnergy>,
+    /// specific entropy residual
+    pub s: Option<SpecificHeatCapacity>,
+    /// specific volume residual
+    pub v: Option<SpecificVolume>,
+    /// specific internal energy residual
+    pub u: Option<AvailableEnergy>,
+}
+
+/// reports how a flash result was obtained,
+/// returned by the *_with_diagnostics flash functions
+///
+/// this is meant for debugging solver stalls and
+/// inconsistencies near region boundaries
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlashReport {
+    /// temperature
+    pub t: ThermodynamicTemperature,
+    /// pressure
+    pub p: Pressure,
+    /// specific volume
+    pub v: SpecificVolume,
+    /// steam quality
+    pub x: f64,
+    /// the IF97 region of the forward equations used
+    pub region: FwdEqnRegion,
+    /// the backward equation subregion used (eg. 2a, 2b, 2c, 3a or 3b),
+    /// None if the flash does not use backward equations
+    /// (eg. the (T,p), (T,rho) and (u,v) flashes)
+    pub subregion: Option<BackwdEqnSubRegion>,
+    /// number of iterations taken by the iterative solver,
+    /// zero if the result was obtained directly
+    pub iterations: usize,
+    /// residuals of the forward equations at the flashed state
+    pub residuals: FlashResiduals,
+    /// true if the point was assigned to its region only because
+    /// of the corrections applied to the region boundaries,
+    /// this only happens in the (h,s) flash
+    pub boundary_correction_applied: bool,
+}
+
+/// backward equation subregion used by the (p,h) flash
+/// given the forward equation region
+pub(crate) fn ph_backward_subregion(p: Pressure,
+    h: AvailableEnergy,
+    region: FwdEqnRegion) -> BackwdEqnSubRegion {
+
+    match region {
+        FwdEqnRegion::Region1 => BackwdEqnSubRegion::Region1,
+        FwdEqnRegion::Region2 => {
+            // same as t_ph_2
+            if p <= Pressure::new::<megapascal>(4.0) {
+                BackwdEqnSubRegion::Region2a
+            } else if p < p_2b2c(h) {
+                BackwdEqnSubRegion::Region2b
+            } else {
+                BackwdEqnSubRegion::Region2c
+            }
+        },
+        FwdEqnRegion::Region3 => {
+            if is_3a_when_in_region_3(p, h) {
+                BackwdEqnSubRegion::Region3a
+            } else {
+                BackwdEqnSubRegion::Region3b
+            }
+        },
+        FwdEqnRegion::Region4 => BackwdEqnSubRegion::Region4,
+        FwdEqnRegion::Region5 => BackwdEqnSubRegion::Region5,
+    }
+}
+
+/// backward equation subregion used by the (p,s) flash
+/// given the forward equation region
+pub(crate) fn ps_backward_subregion(p: Pressure,
+    s: SpecificHeatCapacity,
+    region: FwdEqnRegion) -> BackwdEqnSubRegion {
+
+    match region {
+        FwdEqnRegion::Region1 => BackwdEqnSubRegion::Region1,
+        FwdEqnRegion::Region2 => {
+            // same as t_ps_2
+            let s_boundary_2b2c =
+                SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(5.85);
+            if p <= Pressure::new::<megapascal>(4.0) {
+                BackwdEqnSubRegion::Region2a
+            } else if s >= s_boundary_2b2c {
+                BackwdEqnSubRegion::Region2b
+            } else {
+                BackwdEqnSubRegion::Region2c
+            }
+        },
+        FwdEqnRegion::Region3 => {
+            // same as t_ps_3
+            if s > s_3a3b_backwards_ps_boundary() {
+                BackwdEqnSubRegion::Region3b
+            } else {
+                BackwdEqnSubRegion::Region3a
+            }
+        },
+        FwdEqnRegion::Region4 => BackwdEqnSubRegion::Region4,
+        FwdEqnRegion::Region5 => BackwdEqnSubRegion::Region5,
+    }
+}
+
+/// evaluates the residuals of the enthalpy, entropy and internal
+/// energy given the flashed temperature, pressure and specific volume
+///
+/// the single phase regions use the forward equations directly
+/// (regions 1, 2 and 5 in terms of (T,p), region 3 in terms
+/// of (T,rho)),
+/// region 4 uses the (T,rho) flash, which interpolates between
+/// the saturated liquid and vapour using the specific volume
+///
+/// only properties which are Some are evaluated
+pub(crate) fn try_forward_equation_residuals(region: FwdEqnRegion,
+    t: ThermodynamicTemperature,
+    p: Pressure,
+    v: SpecificVolume,
+    h: Option<AvailableEnergy>,
+    s: Option<SpecificHeatCapacity>,
+    u: Option<AvailableEnergy>) -> Result<FlashResiduals, SteamTableError> {
+
+    let rho: MassDensity = v.recip();
+    let mut residuals = FlashResiduals::default();
+
+    if let Some(h) = h {
+        let h_fwd = match region {
+            FwdEqnRegion::Region4 => try_h_trho_eqm(t, rho)?,
+            _ => h_trho_single_phase(region, t, p, rho),
+        };
+        residuals.h = Some(h_fwd - h);
+    };
+
+    if let Some(s) = s {
+        let s_fwd = match region {
+            FwdEqnRegion::Region4 => try_s_trho_eqm(t, rho)?,
+            _ => s_trho_single_phase(region, t, p, rho),
+        };
+        residuals.s = Some(s_fwd - s);
+    };
+
+    if let Some(u) = u {
+        let u_fwd = match region {
+            FwdEqnRegion::Region4 => try_u_trho_eqm(t, rho)?,
+            _ => u_trho_single_phase(region, t, p, rho),
+        };
+        residuals.u = Some(u_fwd - u);
+    };
+
+    return Ok(residuals);
+}
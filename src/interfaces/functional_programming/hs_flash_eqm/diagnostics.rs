@@ -0,0 +1,54 @@
+use uom::si::f64::*;
+use uom::si::ratio::ratio;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::flash_diagnostics::*;
+
+use super::*;
+
+/// (h,s) flash which also reports the backward equation 
+/// subregion used, the number of iterations, the enthalpy 
+/// and entropy residuals of the forward equations at the 
+/// flashed state, and whether the point was assigned to its 
+/// region only because of the boundary corrections on 
+/// pages 80 to 85
+///
+/// iterations are taken in regions 1 and 5 
+/// (tp_hs_newton_raphson), and in region 4 above 623.15 K 
+/// or below the threshold entropy (tsat_hs_4_iterative)
+pub fn try_hs_flash_with_diagnostics(h: AvailableEnergy,
+    s: SpecificHeatCapacity) -> Result<FlashReport, SteamTableError> {
+
+    let (t, p, v, x, subregion, iterations) = 
+        try_tpvx_hs_flash_eqm_with_iterations(h, s)?;
+
+    let region: FwdEqnRegion = subregion.clone().into();
+
+    let residuals = try_forward_equation_residuals(region, t, p, v,
+        Some(h), Some(s), None)?;
+
+    let boundary_correction_applied = 
+        hs_boundary_correction_applied(h, s, &subregion);
+
+    return Ok(FlashReport {
+        t,
+        p,
+        v,
+        x: x.get::<ratio>(),
+        region,
+        subregion: Some(subregion),
+        iterations,
+        residuals,
+        boundary_correction_applied,
+    });
+}
+
+/// (h,s) flash which also reports how the result was obtained,
+/// panics if the (h,s) point is outside the validity range
+pub fn hs_flash_with_diagnostics(h: AvailableEnergy,
+    s: SpecificHeatCapacity) -> FlashReport {
+    match try_hs_flash_with_diagnostics(h, s) {
+        Ok(report) => report,
+        Err(error) => panic!("h,s flash failed: {}", error),
+    }
+}
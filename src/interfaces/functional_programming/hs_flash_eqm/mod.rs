@@ -5,10 +5,11 @@ use uom::si::thermodynamic_temperature::kelvin;
 use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
 use uom::si::pressure::megapascal;
 use uom::si::available_energy::kilojoule_per_kilogram;
-use validity_range::s_crit;
+use validity_range::{s_crit, try_h_isobar_p_100_mpa};
 
 
-use crate::region_1_subcooled_liquid::{p_hs_1, t_ph_1};
+use crate::region_1_subcooled_liquid::{p_hs_1, t_ph_1, v_tp_1};
+use crate::region_5_steam_at_800_plus_degc::{h_tp_5, s_tp_5, v_tp_5};
 use crate::region_4_vap_liq_equilibrium::{sat_pressure_4, tsat_hs_4};
 use crate::region_3_single_phase_plus_supercritical_steam::v_ps_flash::v_ps_3b;
 use crate::region_3_single_phase_plus_supercritical_steam::v_ps_flash::v_ps_3a;
@@ -17,8 +18,7 @@ use crate::region_3_single_phase_plus_supercritical_steam::p_hs_3b;
 use crate::region_3_single_phase_plus_supercritical_steam::p_hs_3a;
 use crate::region_3_single_phase_plus_supercritical_steam::p_boundary_2_3;
 use crate::region_2_vapour::{h_2a2b, p_hs_2a, p_hs_2b, p_hs_2c, t_ph_2};
-use crate::interfaces::functional_programming::ps_flash_eqm::h_ps_eqm;
-use crate::interfaces::functional_programming::ph_flash_eqm::x_ph_flash;
+use crate::interfaces::functional_programming::ps_flash_eqm::try_h_ps_eqm;
 use crate::backward_eqn_hs_region_1_to_4::saturated_vapour_line::h2c3b_prime_s_boundary_enthalpy;
 use crate::backward_eqn_hs_region_1_to_4::saturated_vapour_line::h2ab_double_prime_s_boundary_enthalpy;
 use crate::backward_eqn_hs_region_1_to_4::saturated_liquid_line::h3a_prime_s_boundary_enthalpy;
@@ -26,13 +26,17 @@ use crate::backward_eqn_hs_region_1_to_4::saturated_liquid_line::h1_prime_s_boun
 use crate::backward_eqn_hs_region_1_to_4::region_2_and_3::tb23_s_boundary_enthalpy;
 use crate::backward_eqn_hs_region_1_to_4::region_1_and_3::hb13_s_boundary_enthalpy;
 
-use super::ph_flash_eqm::{cp_ph_eqm, kappa_ph_eqm, lambda_ph_eqm, mu_ph_eqm, t_ph_eqm, w_ph_eqm};
+use super::ph_flash_eqm::{cp_ph_eqm, kappa_ph_eqm, lambda_ph_eqm, mu_ph_eqm, w_ph_eqm};
+use crate::dynamic_viscosity::mu_ph_eqm_with_crit_enhancement;
 use super::pt_flash_eqm::{s_tp_eqm_two_phase, FwdEqnRegion};
 use super::pt_flash_eqm::s_tp_eqm_single_phase;
 use super::pt_flash_eqm::h_tp_eqm_single_phase;
-use super::ps_flash_eqm::v_ps_eqm;
+use super::ps_flash_eqm::try_v_ps_eqm;
+use super::ph_flash_eqm::{try_t_ph_eqm, try_x_ph_flash};
+use crate::errors::SteamTableError;
 
 #[derive(Debug,PartialEq, Eq, PartialOrd, Ord,Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// an enum to help represent the appropriate 
 /// regions in the forward equations
 pub enum BackwdEqnSubRegion {
@@ -175,6 +179,17 @@ pub fn mu_hs_eqm(h: AvailableEnergy, s: SpecificHeatCapacity,) -> DynamicViscosi
 
     return mu;
 
+}
+/// returns mu, or sometimes eta (dynamic viscosity) given 
+/// enthalpy and entropy point, including the critical enhancement 
+/// uses ph flash (see mu_ph_eqm_with_crit_enhancement)
+pub fn mu_hs_eqm_with_crit_enhancement(h: AvailableEnergy, s: SpecificHeatCapacity,) -> DynamicViscosity {
+    let (_t,p,_v,_x) = tpvx_hs_flash_eqm(h, s);
+
+    let mu = mu_ph_eqm_with_crit_enhancement(p, h);
+
+    return mu;
+
 }
 /// returns lambda (thermal conductivity) given 
 /// enthalpy and entropy point
@@ -198,22 +213,58 @@ pub fn lambda_hs_eqm(h: AvailableEnergy, s: SpecificHeatCapacity,) -> ThermalCon
 pub fn tpvx_hs_flash_eqm(h: AvailableEnergy,
     s: SpecificHeatCapacity,) -> 
 (ThermodynamicTemperature, Pressure, SpecificVolume, Ratio) {
-    let region = hs_flash_region(h, s);
+    match try_tpvx_hs_flash_eqm(h, s) {
+        Ok(tpvx) => tpvx,
+        Err(error) => panic!("h,s flash failed: {}", error),
+    }
+}
+
+/// returns temperature, pressure, specific volume and quality given 
+/// enthalpy and entropy point
+///
+/// returns an error rather than panicking if the (h,s) point 
+/// is outside the validity range
+#[inline]
+pub fn try_tpvx_hs_flash_eqm(h: AvailableEnergy,
+    s: SpecificHeatCapacity,) -> 
+Result<(ThermodynamicTemperature, Pressure, SpecificVolume, Ratio), SteamTableError> {
+    let (t, p, v, x, _region, _iterations) = 
+        try_tpvx_hs_flash_eqm_with_iterations(h, s)?;
+
+    return Ok((t, p, v, x));
+}
+
+/// same as try_tpvx_hs_flash_eqm, but also returns the backward 
+/// equation subregion used and the number of iterations taken
+/// (zero if no iterations were needed), for flash diagnostics
+pub(crate) fn try_tpvx_hs_flash_eqm_with_iterations(h: AvailableEnergy,
+    s: SpecificHeatCapacity,) -> 
+Result<(ThermodynamicTemperature, Pressure, SpecificVolume, Ratio, BackwdEqnSubRegion, usize), SteamTableError> {
+    let region = try_hs_flash_region(h, s)?;
 
     match region {
         BackwdEqnSubRegion::Region1 => {
             // page 87 of Kretzchmar textbook
-            let mut pressure =  p_hs_1(h, s); 
-            // correct this once 
-            pressure = p_hs_newton_raphson_single_correction_fixed_t_estimate(h, s, pressure);
-            let temperature = t_ph_1(pressure, h);
+            //
+            // for some pressures eg. 0.1 bar
+            // the guessed pressure is inaccurate (it may even 
+            // be negative), so we use it as an initial guess and
+            // converge to the correct (T,p) using newton raphson 
+            // on the forward equations
+            let p_min = sat_pressure_4(
+                ThermodynamicTemperature::new::<kelvin>(273.15));
+            let p_guess = p_hs_1(h, s).max(p_min);
+            let t_guess = t_ph_1(p_guess, h);
+
+            let (temperature, pressure, iterations) = tp_hs_newton_raphson(
+                h, s, t_guess, p_guess, FwdEqnRegion::Region1)?;
             // in region 1, we are necessarily liquid,
             // quality is zero
             let quality = Ratio::new::<ratio>(0.0);
 
-            let specific_volume = v_ps_eqm(pressure, s);
+            let specific_volume = v_tp_1(temperature, pressure);
 
-            return (temperature, pressure, specific_volume, quality);
+            return Ok((temperature, pressure, specific_volume, quality, region, iterations));
         },
         BackwdEqnSubRegion::Region2a => {
             // page 92 to 94 of Kretzchmar textbook
@@ -223,9 +274,9 @@ pub fn tpvx_hs_flash_eqm(h: AvailableEnergy,
             // in region 2, we are necessarily vapour/gas
             // quality is 1
             let quality = Ratio::new::<ratio>(1.0);
-            let specific_volume = v_ps_eqm(pressure, s);
+            let specific_volume = try_v_ps_eqm(pressure, s)?;
 
-            return (temperature, pressure, specific_volume, quality);
+            return Ok((temperature, pressure, specific_volume, quality, region, 0));
         },
         BackwdEqnSubRegion::Region2b => {
             // page 92 to 94 of Kretzchmar textbook
@@ -235,9 +286,9 @@ pub fn tpvx_hs_flash_eqm(h: AvailableEnergy,
             // in region 2, we are necessarily vapour/gas
             // quality is 1
             let quality = Ratio::new::<ratio>(1.0);
-            let specific_volume = v_ps_eqm(pressure, s);
+            let specific_volume = try_v_ps_eqm(pressure, s)?;
 
-            return (temperature, pressure, specific_volume, quality);
+            return Ok((temperature, pressure, specific_volume, quality, region, 0));
         },
         BackwdEqnSubRegion::Region2c => {
             // page 92 to 94 of Kretzchmar textbook
@@ -247,9 +298,9 @@ pub fn tpvx_hs_flash_eqm(h: AvailableEnergy,
             // in region 2, we are necessarily vapour/gas
             // quality is 1
             let quality = Ratio::new::<ratio>(1.0);
-            let specific_volume = v_ps_eqm(pressure, s);
+            let specific_volume = try_v_ps_eqm(pressure, s)?;
 
-            return (temperature, pressure, specific_volume, quality);
+            return Ok((temperature, pressure, specific_volume, quality, region, 0));
         },
         BackwdEqnSubRegion::Region3a => {
 
@@ -261,8 +312,8 @@ pub fn tpvx_hs_flash_eqm(h: AvailableEnergy,
             let specific_volume = v_ps_3a(pressure, s);
 
             // quality now 
-            let quality = x_ph_flash(pressure, h);
-            return (temperature, pressure, specific_volume, quality.into());
+            let quality = try_x_ph_flash(pressure, h)?;
+            return Ok((temperature, pressure, specific_volume, quality.into(), region, 0));
         },
         BackwdEqnSubRegion::Region3b => {
             // page 97 onwards of Kretzchmar textbook
@@ -272,8 +323,8 @@ pub fn tpvx_hs_flash_eqm(h: AvailableEnergy,
             // then specific volume  page 99 of Kretzchmar textbook
             let specific_volume = v_ps_3b(pressure, s);
             // quality now 
-            let quality = x_ph_flash(pressure, h);
-            return (temperature, pressure, specific_volume, quality.into());
+            let quality = try_x_ph_flash(pressure, h)?;
+            return Ok((temperature, pressure, specific_volume, quality.into(), region, 0));
         },
         BackwdEqnSubRegion::Region4 => {
             // page 101
@@ -291,10 +342,11 @@ pub fn tpvx_hs_flash_eqm(h: AvailableEnergy,
                     sat_pressure_for_backward, steam_quality_bound);
 
 
-            let sat_temp = tsat_hs_4(h, s);
-
             if s >= min_entropy_for_backward_eqn {
 
+                let sat_temp = tsat_hs_4(h, s);
+                let iterations = 0;
+
                 // page 103 
                 let sat_pressure = sat_pressure_4(sat_temp);
 
@@ -303,22 +355,23 @@ pub fn tpvx_hs_flash_eqm(h: AvailableEnergy,
                 // x= (h - h_liq)/(h_vap-h_liq)
                 // this is the same algorithm I used in the x_ph_flash 
                 // calculation
-                let quality = x_ph_flash(sat_pressure, h);
+                let quality = try_x_ph_flash(sat_pressure, h)?;
                 // quality is then used to calculate specific volume...
                 // however, it is double calculation here...
                 // for x_ph,
                 // I'm not overly concerned about computational cost now 
                 // but it is an inefficiency
-                let specific_volume = v_ps_eqm(sat_pressure, s);
-                return (sat_temp, sat_pressure, specific_volume, quality.into());
+                let specific_volume = try_v_ps_eqm(sat_pressure, s)?;
+                return Ok((sat_temp, sat_pressure, specific_volume, quality.into(), region, iterations));
             } else {
 
                 // if in regime above 623.15 K, 
                 // or below the threshold entropy 
                 // we need another procedure...
-                // may include iteration...
-                // to determine temperature
+                // here we iterate on the saturation temperature
+                // such that the (h,s) point lies on the tie line
                 // page 103 
+                let (sat_temp, iterations) = tsat_hs_4_iterative(h, s)?;
                 let sat_pressure = sat_pressure_4(sat_temp);
 
                 // now, we are using the enthalpy, temperature and 
@@ -326,66 +379,74 @@ pub fn tpvx_hs_flash_eqm(h: AvailableEnergy,
                 // x= (h - h_liq)/(h_vap-h_liq)
                 // this is the same algorithm I used in the x_ph_flash 
                 // calculation
-                let quality = x_ph_flash(sat_pressure, h);
+                let quality = try_x_ph_flash(sat_pressure, h)?;
                 // quality is then used to calculate specific volume...
                 // however, it is double calculation here...
                 // for x_ph,
                 // I'm not overly concerned about computational cost now 
                 // but it is an inefficiency
-                let specific_volume = v_ps_eqm(sat_pressure, s);
-                return (sat_temp, sat_pressure, specific_volume, quality.into());
+                let specific_volume = try_v_ps_eqm(sat_pressure, s)?;
+                return Ok((sat_temp, sat_pressure, specific_volume, quality.into(), region, iterations));
             };
         },
         BackwdEqnSubRegion::Region5 => {
-            unimplemented!("Region 5 does not have (h,s) flashing");
-        },
-    }
-
-}
-
-// for some pressures eg. 0.1 bar
-// the guessed pressure is inaccurate
-//
-// this does a single manual newton raphson correction in order to get 
-// it closer to the actual pressure
-// not implemented
-#[inline]
-fn p_hs_newton_raphson_single_correction_fixed_t_estimate(
-    _h: AvailableEnergy,
-    _s: SpecificHeatCapacity, 
-    _p_guess: Pressure) -> Pressure {
+            // there are no backward equations for region 5,
+            // so we use a two dimensional newton raphson on 
+            // h_tp_5 and s_tp_5
+            let (t_guess, p_guess) = tp_hs_region_5_initial_guess(h, s)?;
 
-    // we are trying to converge to a correct pressure using (h,s)
-    //
-    // however, first p(h,s) fails for low pressure
-    //
+            let (temperature, pressure, iterations) = tp_hs_newton_raphson(
+                h, s, t_guess, p_guess, FwdEqnRegion::Region5)?;
 
+            check_if_within_hs_region_5_validity_range(temperature, pressure)?;
 
+            // region 5 is necessarily vapour/gas
+            let quality = Ratio::new::<ratio>(1.0);
+            let specific_volume = v_tp_5(temperature, pressure);
 
-    todo!();
+            return Ok((temperature, pressure, specific_volume, quality, region, iterations));
+        },
+    }
 
 }
 
-/// allows the user to check which region one is in based on a ph flash
+/// allows the user to check which region one is in based on a hs flash
 ///
-/// note that ph flash does not work in region 5
+/// region 5 is from 1073.15 K to 2273.15 K, up to 50 MPa
 ///
 /// the way to do region separation is first by entropy according to 
 /// fig 2.14
 ///
 /// once that is done, then we separate region by enthalpy.
 pub fn hs_flash_region(h: AvailableEnergy, s: SpecificHeatCapacity) -> BackwdEqnSubRegion {
+    match try_hs_flash_region(h, s) {
+        Ok(region) => region,
+        Err(error) => panic!("h,s point is outside validity range: {}", error),
+    }
+}
+
+/// allows the user to check which region one is in based on a hs flash
+/// returns an error rather than panicking if the (h,s) point 
+/// is outside the validity range
+pub fn try_hs_flash_region(h: AvailableEnergy, 
+    s: SpecificHeatCapacity) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
     // this is absolute minimum and max entropy
     // based on fig 2.14
     let s_min = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(-0.00858);
     let s_max = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(11.92);
 
+    // region 5 lies above the 1073.15 K isotherm, and 
+    // extends beyond s_max at low pressures, so check it first
+    if is_hs_point_region_5(h, s) {
+        return Ok(BackwdEqnSubRegion::Region5);
+    };
+
     if s < s_min {
-        panic!("entropy too low for hs flash");
+        return Err(SteamTableError::EntropyBelowBound { s, bound: s_min });
     };
     if s > s_max {
-        panic!("entropy too high for hs flash");
+        return Err(SteamTableError::EntropyAboveBound { s, bound: s_max });
     };
 
     let s_bound_low_entropy_region_1_and_4 = 
@@ -480,7 +541,7 @@ pub fn hs_flash_region(h: AvailableEnergy, s: SpecificHeatCapacity) -> BackwdEqn
 }
 
 fn hs_region_low_entropy_region_1_and_4(
-    h: AvailableEnergy, s: SpecificHeatCapacity,) -> BackwdEqnSubRegion {
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
     let region_1_and_4_h_boundary: AvailableEnergy = 
         h1_prime_s_boundary_enthalpy(s)
@@ -497,43 +558,48 @@ fn hs_region_low_entropy_region_1_and_4(
     // should be subtracted from the enthalpy to ensure that it is correctly 
     // assigned to single phase region (region 1)
 
-    let upper_bound_pressure = Pressure::new::<megapascal>(100.0 - 1.0e-4);
+    // in this entropy range, the upper bound is the 
+    // 100 MPa isobar in region 1 (page 73)
     let lower_bound_pressure = Pressure::new::<megapascal>(0.000_611_212_677 * 1.01);
-    let upper_bound_enthalpy = h_ps_eqm(upper_bound_pressure, s);
-    let lower_bound_enthalpy = h_ps_eqm(lower_bound_pressure, s);
+    let lower_bound_enthalpy = try_h_ps_eqm(lower_bound_pressure, s)?;
+
+    let upper_bound_enthalpy = try_h_isobar_p_100_mpa(s)?;
 
     if h > upper_bound_enthalpy {
-        panic!("enthalpy too high for hs flash");
+        return Err(SteamTableError::EnthalpyAboveBound { 
+            h, bound: upper_bound_enthalpy });
     };
 
     if h < lower_bound_enthalpy {
-        panic!("enthalpy too low for hs flash");
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
 
 
     if h >= region_1_and_4_h_boundary {
 
-        return BackwdEqnSubRegion::Region1;
+        return Ok(BackwdEqnSubRegion::Region1);
     } else {
-        return BackwdEqnSubRegion::Region4;
+        return Ok(BackwdEqnSubRegion::Region4);
     };
 
 }
 
 fn hs_region_low_entropy_region_1_3a_and_4(
-    h: AvailableEnergy, s: SpecificHeatCapacity,) -> BackwdEqnSubRegion {
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
-    let upper_bound_pressure = Pressure::new::<megapascal>(100.0 - 1.0e-4);
     let lower_bound_pressure = Pressure::new::<megapascal>(0.000_611_212_677 * 1.01);
-    let upper_bound_enthalpy = h_ps_eqm(upper_bound_pressure, s);
-    let lower_bound_enthalpy = h_ps_eqm(lower_bound_pressure, s);
+    let upper_bound_enthalpy = try_h_isobar_p_100_mpa(s)?;
+    let lower_bound_enthalpy = try_h_ps_eqm(lower_bound_pressure, s)?;
 
     if h > upper_bound_enthalpy {
-        panic!("enthalpy too high for hs flash");
+        return Err(SteamTableError::EnthalpyAboveBound { 
+            h, bound: upper_bound_enthalpy });
     };
 
     if h < lower_bound_enthalpy {
-        panic!("enthalpy too low for hs flash");
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
 
     // now add corrected h 
@@ -546,7 +612,7 @@ fn hs_region_low_entropy_region_1_3a_and_4(
         - AvailableEnergy::new::<kilojoule_per_kilogram>(0.0034);
 
     if h < region_1_and_4_h_boundary {
-        return BackwdEqnSubRegion::Region4;
+        return Ok(BackwdEqnSubRegion::Region4);
     };
 
     // corrected h for hb13 equation, to assign it to region 1 
@@ -557,9 +623,9 @@ fn hs_region_low_entropy_region_1_3a_and_4(
         + AvailableEnergy::new::<kilojoule_per_kilogram>(0.018);
 
     if h > hb13_boundary {
-        return BackwdEqnSubRegion::Region3a;
+        return Ok(BackwdEqnSubRegion::Region3a);
     } else {
-        return BackwdEqnSubRegion::Region1;
+        return Ok(BackwdEqnSubRegion::Region1);
     };
 
     
@@ -567,71 +633,74 @@ fn hs_region_low_entropy_region_1_3a_and_4(
 }
 
 fn hs_region_near_crit_entropy_region_3a_and_4(
-    h: AvailableEnergy, s: SpecificHeatCapacity,) -> BackwdEqnSubRegion {
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
-    let upper_bound_pressure = Pressure::new::<megapascal>(100.0 - 1.0e-4);
     let lower_bound_pressure = Pressure::new::<megapascal>(0.000_611_212_677 * 1.01);
-    let upper_bound_enthalpy = h_ps_eqm(upper_bound_pressure, s);
-    let lower_bound_enthalpy = h_ps_eqm(lower_bound_pressure, s);
+    let upper_bound_enthalpy = try_h_isobar_p_100_mpa(s)?;
+    let lower_bound_enthalpy = try_h_ps_eqm(lower_bound_pressure, s)?;
 
     if h > upper_bound_enthalpy {
-        panic!("enthalpy too high for hs flash");
+        return Err(SteamTableError::EnthalpyAboveBound { 
+            h, bound: upper_bound_enthalpy });
     };
 
     if h < lower_bound_enthalpy {
-        panic!("enthalpy too low for hs flash");
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
     let region_3a_and_4_h_boundary: AvailableEnergy = 
         h3a_prime_s_boundary_enthalpy(s)
         - AvailableEnergy::new::<kilojoule_per_kilogram>(0.0045);
 
     if h >= region_3a_and_4_h_boundary {
-        return BackwdEqnSubRegion::Region3a;
+        return Ok(BackwdEqnSubRegion::Region3a);
     } else {
-        return BackwdEqnSubRegion::Region4;
+        return Ok(BackwdEqnSubRegion::Region4);
     };
 
 }
 fn hs_region_crit_entropy_region_3a_and_4(
-    h: AvailableEnergy, s: SpecificHeatCapacity,) -> BackwdEqnSubRegion {
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
 
-    let upper_bound_pressure = Pressure::new::<megapascal>(100.0 - 1.0e-4);
     let lower_bound_pressure = Pressure::new::<megapascal>(0.000_611_212_677 * 1.01);
-    let upper_bound_enthalpy = h_ps_eqm(upper_bound_pressure, s);
-    let lower_bound_enthalpy = h_ps_eqm(lower_bound_pressure, s);
+    let upper_bound_enthalpy = try_h_isobar_p_100_mpa(s)?;
+    let lower_bound_enthalpy = try_h_ps_eqm(lower_bound_pressure, s)?;
 
     if h > upper_bound_enthalpy {
-        panic!("enthalpy too high for hs flash");
+        return Err(SteamTableError::EnthalpyAboveBound { 
+            h, bound: upper_bound_enthalpy });
     };
 
     if h < lower_bound_enthalpy {
-        panic!("enthalpy too low for hs flash");
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
     let region_3a_and_4_h_boundary: AvailableEnergy = h3a_prime_s_boundary_enthalpy(s)
         - AvailableEnergy::new::<kilojoule_per_kilogram>(0.0045);
 
     if h >= region_3a_and_4_h_boundary {
-        return BackwdEqnSubRegion::Region3a;
+        return Ok(BackwdEqnSubRegion::Region3a);
     } else {
-        return BackwdEqnSubRegion::Region4;
+        return Ok(BackwdEqnSubRegion::Region4);
     };
 
 }
 fn hs_region_near_crit_entropy_region_3b_and_4(
-    h: AvailableEnergy, s: SpecificHeatCapacity,) -> BackwdEqnSubRegion {
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
-    let upper_bound_pressure = Pressure::new::<megapascal>(100.0 - 1.0e-4);
     let lower_bound_pressure = Pressure::new::<megapascal>(0.000_611_212_677 * 1.01);
-    let upper_bound_enthalpy = h_ps_eqm(upper_bound_pressure, s);
-    let lower_bound_enthalpy = h_ps_eqm(lower_bound_pressure, s);
+    let upper_bound_enthalpy = try_h_isobar_p_100_mpa(s)?;
+    let lower_bound_enthalpy = try_h_ps_eqm(lower_bound_pressure, s)?;
 
     if h > upper_bound_enthalpy {
-        panic!("enthalpy too high for hs flash");
+        return Err(SteamTableError::EnthalpyAboveBound { 
+            h, bound: upper_bound_enthalpy });
     };
 
     if h < lower_bound_enthalpy {
-        panic!("enthalpy too low for hs flash");
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
     // on page 84 for critical entropy all the way up to 
     // bound of region 2c, we correct with 0.0073 kJ/(kg )
@@ -655,26 +724,27 @@ fn hs_region_near_crit_entropy_region_3b_and_4(
 
 
     if h >= h2c3b_boundary {
-        return BackwdEqnSubRegion::Region3b;
+        return Ok(BackwdEqnSubRegion::Region3b);
     } else {
-        return BackwdEqnSubRegion::Region4;
+        return Ok(BackwdEqnSubRegion::Region4);
     };
 
 }
 fn hs_region_near_crit_entropy_region_2c_3b_and_4(
-    h: AvailableEnergy, s: SpecificHeatCapacity,) -> BackwdEqnSubRegion {
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
-    let upper_bound_pressure = Pressure::new::<megapascal>(100.0 - 1.0e-4);
     let lower_bound_pressure = Pressure::new::<megapascal>(0.000_611_212_677 * 1.01);
-    let upper_bound_enthalpy = h_ps_eqm(upper_bound_pressure, s);
-    let lower_bound_enthalpy = h_ps_eqm(lower_bound_pressure, s);
+    let upper_bound_enthalpy = try_h_isobar_p_100_mpa(s)?;
+    let lower_bound_enthalpy = try_h_ps_eqm(lower_bound_pressure, s)?;
 
     if h > upper_bound_enthalpy {
-        panic!("enthalpy too high for hs flash");
+        return Err(SteamTableError::EnthalpyAboveBound { 
+            h, bound: upper_bound_enthalpy });
     };
 
     if h < lower_bound_enthalpy {
-        panic!("enthalpy too low for hs flash");
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
 
     // on page 84 for critical entropy all the way up to 
@@ -704,7 +774,7 @@ fn hs_region_near_crit_entropy_region_2c_3b_and_4(
         correction_factor;
 
     if h < h2c3b_boundary {
-        return BackwdEqnSubRegion::Region4;
+        return Ok(BackwdEqnSubRegion::Region4);
     }; 
     // now a few more guard clauses to bound maximum and minimum h 
     let hb23_min = AvailableEnergy::new::<kilojoule_per_kilogram>(
@@ -714,10 +784,10 @@ fn hs_region_near_crit_entropy_region_2c_3b_and_4(
         2.812_942_061e3
     );
     if h >= hb23_max {
-        return BackwdEqnSubRegion::Region2c;
+        return Ok(BackwdEqnSubRegion::Region2c);
     };
     if h <= hb23_min {
-        return BackwdEqnSubRegion::Region3b;
+        return Ok(BackwdEqnSubRegion::Region3b);
     };
 
 
@@ -732,26 +802,27 @@ fn hs_region_near_crit_entropy_region_2c_3b_and_4(
     let p2c_hs = p_hs_2c(h, s);
 
     if p2c_hs <= p_b23_boundary {
-        return BackwdEqnSubRegion::Region2c;
+        return Ok(BackwdEqnSubRegion::Region2c);
     } else {
-        return BackwdEqnSubRegion::Region3b;
+        return Ok(BackwdEqnSubRegion::Region3b);
     };
 
 }
 fn hs_region_high_entropy_region_2c_and_4(
-    h: AvailableEnergy, s: SpecificHeatCapacity,) -> BackwdEqnSubRegion {
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
-    let upper_bound_pressure = Pressure::new::<megapascal>(100.0 - 1.0e-4);
     let lower_bound_pressure = Pressure::new::<megapascal>(0.000_611_212_677 * 1.01);
-    let upper_bound_enthalpy = h_ps_eqm(upper_bound_pressure, s);
-    let lower_bound_enthalpy = h_ps_eqm(lower_bound_pressure, s);
+    let upper_bound_enthalpy = try_h_isobar_p_100_mpa(s)?;
+    let lower_bound_enthalpy = try_h_ps_eqm(lower_bound_pressure, s)?;
 
     if h > upper_bound_enthalpy {
-        panic!("enthalpy too high for hs flash");
+        return Err(SteamTableError::EnthalpyAboveBound { 
+            h, bound: upper_bound_enthalpy });
     };
 
     if h < lower_bound_enthalpy {
-        panic!("enthalpy too low for hs flash");
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
 
     // on page 84 for critical entropy all the way up to 
@@ -771,31 +842,32 @@ fn hs_region_high_entropy_region_2c_and_4(
         AvailableEnergy::new::<kilojoule_per_kilogram>(0.0058);
 
     if h < h2c3b_boundary {
-        return BackwdEqnSubRegion::Region4;
+        return Ok(BackwdEqnSubRegion::Region4);
     } else {
-        return BackwdEqnSubRegion::Region2c;
+        return Ok(BackwdEqnSubRegion::Region2c);
     };
 
 }
 fn hs_region_high_entropy_region_2b_and_4(
-    h: AvailableEnergy, s: SpecificHeatCapacity,) -> BackwdEqnSubRegion {
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
 
     let lower_bound_pressure = Pressure::new::<megapascal>(0.000_611_212_677 * 1.01);
-    let lower_bound_enthalpy = h_ps_eqm(lower_bound_pressure, s);
+    let lower_bound_enthalpy = try_h_ps_eqm(lower_bound_pressure, s)?;
 
     if h < lower_bound_enthalpy {
-        panic!("enthalpy too low for hs flash");
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
 
     if s < SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(6.040) {
         // upper bound enthalpy checking depends on pressure only 
         // when s < 6.040 kJ/(kg K)
-        let upper_bound_pressure = Pressure::new::<megapascal>(100.0 - 1.0e-4);
-        let upper_bound_enthalpy = h_ps_eqm(upper_bound_pressure, s);
+        let upper_bound_enthalpy = try_h_isobar_p_100_mpa(s)?;
 
         if h > upper_bound_enthalpy {
-            panic!("enthalpy too high for hs flash");
+            return Err(SteamTableError::EnthalpyAboveBound { 
+                h, bound: upper_bound_enthalpy });
         };
 
     } else {
@@ -805,9 +877,8 @@ fn hs_region_high_entropy_region_2b_and_4(
         let t_max = ThermodynamicTemperature::new::<kelvin>(1073.15);
         let upper_bound_enthalpy = h_tp_eqm_single_phase(t_max, p_sat);
         if h > upper_bound_enthalpy {
-
-            dbg!(&(h,upper_bound_enthalpy));
-            panic!("enthalpy too high for hs flash");
+            return Err(SteamTableError::EnthalpyAboveBound { 
+                h, bound: upper_bound_enthalpy });
         };
 
 
@@ -816,14 +887,14 @@ fn hs_region_high_entropy_region_2b_and_4(
     let h2ab_double_prime_boundary = h2ab_double_prime_s_boundary_enthalpy(s);
 
     if h > h2ab_double_prime_boundary {
-        return BackwdEqnSubRegion::Region2b;
+        return Ok(BackwdEqnSubRegion::Region2b);
     } else {
-        return BackwdEqnSubRegion::Region4;
+        return Ok(BackwdEqnSubRegion::Region4);
     };
 
 }
 fn hs_region_high_entropy_region_2b_2a_and_4(
-    h: AvailableEnergy, s: SpecificHeatCapacity,) -> BackwdEqnSubRegion {
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
     let lower_bound_pressure = Pressure::new::<megapascal>(0.000_611_212_677 * 1.01);
     // the upper bound enthalpy is determined by the 
@@ -831,23 +902,25 @@ fn hs_region_high_entropy_region_2b_2a_and_4(
     let p_sat = sat_pressure_4(ThermodynamicTemperature::new::<kelvin>(273.15));
     let t_max = ThermodynamicTemperature::new::<kelvin>(1073.15);
     let upper_bound_enthalpy = h_tp_eqm_single_phase(t_max, p_sat);
-    let lower_bound_enthalpy = h_ps_eqm(lower_bound_pressure, s);
+    let lower_bound_enthalpy = try_h_ps_eqm(lower_bound_pressure, s)?;
 
     if h > upper_bound_enthalpy {
-        panic!("enthalpy too high for hs flash");
+        return Err(SteamTableError::EnthalpyAboveBound { 
+            h, bound: upper_bound_enthalpy });
     };
 
     if h < lower_bound_enthalpy {
-        panic!("enthalpy too low for hs flash");
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
     // now still need to check if temperature is 
     // too high
-    additional_temperature_check_for_1073_15_k_isotherm(h, s);
+    additional_temperature_check_for_1073_15_k_isotherm(h, s)?;
 
     let h2ab_double_prime_boundary = h2ab_double_prime_s_boundary_enthalpy(s);
 
     if h <= h2ab_double_prime_boundary {
-        return BackwdEqnSubRegion::Region4;
+        return Ok(BackwdEqnSubRegion::Region4);
     }
 
     let h2ab_boundary = h_2a2b(s);
@@ -856,15 +929,15 @@ fn hs_region_high_entropy_region_2b_2a_and_4(
     // see page 91
 
     if h <= h2ab_boundary {
-        return BackwdEqnSubRegion::Region2a;
+        return Ok(BackwdEqnSubRegion::Region2a);
     } else {
-        return BackwdEqnSubRegion::Region2b;
+        return Ok(BackwdEqnSubRegion::Region2b);
     };
 
 
 }
 fn hs_region_high_entropy_region_2a_or_4_only(
-    h: AvailableEnergy, s: SpecificHeatCapacity,) -> BackwdEqnSubRegion {
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
     let lower_bound_pressure = Pressure::new::<megapascal>(0.000_611_212_677 * 1.01);
     // the upper bound enthalpy is determined by the 
@@ -872,28 +945,29 @@ fn hs_region_high_entropy_region_2a_or_4_only(
     let p_sat = sat_pressure_4(ThermodynamicTemperature::new::<kelvin>(273.15));
     let t_max = ThermodynamicTemperature::new::<kelvin>(1073.15);
     let conservative_upper_bound_enthalpy = h_tp_eqm_single_phase(t_max, p_sat);
-    let lower_bound_enthalpy = h_ps_eqm(lower_bound_pressure, s);
+    let lower_bound_enthalpy = try_h_ps_eqm(lower_bound_pressure, s)?;
 
     if h > conservative_upper_bound_enthalpy {
-        panic!("enthalpy too high for hs flash");
+        return Err(SteamTableError::EnthalpyAboveBound { 
+            h, bound: conservative_upper_bound_enthalpy });
     };
 
     if h < lower_bound_enthalpy {
-        dbg!(&(h,lower_bound_enthalpy));
-        panic!("enthalpy too low for hs flash");
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
 
     // now still need to check if temperature is 
     // too high
-    additional_temperature_check_for_1073_15_k_isotherm(h, s);
+    additional_temperature_check_for_1073_15_k_isotherm(h, s)?;
 
 
     let h2ab_double_prime_boundary = h2ab_double_prime_s_boundary_enthalpy(s);
 
     if h <= h2ab_double_prime_boundary {
-        return BackwdEqnSubRegion::Region4;
+        return Ok(BackwdEqnSubRegion::Region4);
     } else {
-        return BackwdEqnSubRegion::Region2a;
+        return Ok(BackwdEqnSubRegion::Region2a);
     };
 
 
@@ -901,7 +975,7 @@ fn hs_region_high_entropy_region_2a_or_4_only(
 }
 
 fn hs_region_high_entropy_region_2a_only(
-    h: AvailableEnergy, s: SpecificHeatCapacity,) -> BackwdEqnSubRegion {
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<BackwdEqnSubRegion, SteamTableError> {
 
     let lower_bound_pressure = Pressure::new::<megapascal>(0.000_611_212_677 * 1.01);
     // the upper bound enthalpy is determined by the 
@@ -909,30 +983,31 @@ fn hs_region_high_entropy_region_2a_only(
     let p_sat = sat_pressure_4(ThermodynamicTemperature::new::<kelvin>(273.15));
     let t_max = ThermodynamicTemperature::new::<kelvin>(1073.15);
     let conservative_upper_bound_enthalpy = h_tp_eqm_single_phase(t_max, p_sat);
-    let lower_bound_enthalpy = h_ps_eqm(lower_bound_pressure, s);
+    let lower_bound_enthalpy = try_h_ps_eqm(lower_bound_pressure, s)?;
 
     if h > conservative_upper_bound_enthalpy {
-        panic!("enthalpy too high for hs flash");
+        return Err(SteamTableError::EnthalpyAboveBound { 
+            h, bound: conservative_upper_bound_enthalpy });
     };
 
     if h < lower_bound_enthalpy {
-        dbg!(&(h,lower_bound_enthalpy));
-        panic!("enthalpy too low for hs flash");
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
 
     // now still need to check if temperature is 
     // too high
-    additional_temperature_check_for_1073_15_k_isotherm(h, s);
+    additional_temperature_check_for_1073_15_k_isotherm(h, s)?;
 
 
-    return BackwdEqnSubRegion::Region2a;
+    return Ok(BackwdEqnSubRegion::Region2a);
 
 
 
 }
 
 fn additional_temperature_check_for_1073_15_k_isotherm(
-    h: AvailableEnergy, s: SpecificHeatCapacity,){
+    h: AvailableEnergy, s: SpecificHeatCapacity,) -> Result<(), SteamTableError> {
 
     let t_bound = ThermodynamicTemperature::new::<kelvin>(1073.15);
     let low_bound_entropy_pressure = 
@@ -949,33 +1024,214 @@ fn additional_temperature_check_for_1073_15_k_isotherm(
         s_tp_eqm_single_phase(t_bound, high_bound_entropy_pressure);
 
     if s < low_bound_entropy {
-        panic!("entropy too low for 1073.15K isotherm additional check");
+        return Err(SteamTableError::EntropyBelowBound { 
+            s, bound: low_bound_entropy });
     };
     if s > high_bound_entropy {
-        panic!("entropy too high for 1073.15K isotherm additional check");
+        return Err(SteamTableError::EntropyAboveBound { 
+            s, bound: high_bound_entropy });
     };
 
     if s < mid_bound_entropy {
         let p = p_hs_2b(h, s);
-        let t = t_ph_eqm(p, h);
+        let t = t_above_1073_15_k_estimate(p, h)?;
         
         if t > t_bound {
-            dbg!(&(t,t_bound));
-            dbg!(&(h,s));
-            panic!("temperature too high for (h,s) point (2b regime)");
+            return Err(SteamTableError::TemperatureAboveBound { 
+                t, bound: t_bound });
         };
     } else {
         let p = p_hs_2a(h, s);
-        let t = t_ph_eqm(p, h);
+        let t = t_above_1073_15_k_estimate(p, h)?;
         
         if t > t_bound {
-            dbg!(&(t,t_bound));
-            dbg!(&(h,s));
-            panic!("temperature too high for (h,s) point (2a regime)");
+            return Err(SteamTableError::TemperatureAboveBound { 
+                t, bound: t_bound });
         };
 
     };
 
+    return Ok(());
+
+}
+
+// the (p,h) flash covers region 5 up to 50 MPa, but points above the 
+// 1073.15 K isotherm at higher pressures are outside its validity 
+// range and come back as an EnthalpyAboveBound error
+//
+// that error is not the one we want to report here, so we estimate the 
+// temperature using the region 2 backward eqn instead (only used 
+// to report a TemperatureAboveBound error)
+fn t_above_1073_15_k_estimate(p: Pressure, 
+    h: AvailableEnergy) -> Result<ThermodynamicTemperature, SteamTableError> {
+    match try_t_ph_eqm(p, h) {
+        Ok(t) => Ok(t),
+        Err(SteamTableError::EnthalpyAboveBound { .. }) => Ok(t_ph_2(p, h)),
+        Err(error) => Err(error),
+    }
+}
+
+// checks if the (h,s) point lies above the 1073.15 K isotherm 
+// at pressures up to 50 MPa, ie in region 5
+//
+// the region 5 values on the isotherm are used as the boundary
+// to be consistent with the (p,h) and (p,s) flashes
+fn is_hs_point_region_5(h: AvailableEnergy, s: SpecificHeatCapacity) -> bool {
+
+    let t_boundary = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let p_max_region_5 = Pressure::new::<megapascal>(50.0);
+
+    // below this entropy, points above the 1073.15 K isotherm 
+    // are above 50 MPa and are not in region 5
+    if s < s_tp_5(t_boundary, p_max_region_5) {
+        return false;
+    };
+
+    // otherwise, compare against the enthalpy on the isotherm 
+    // at the same entropy
+    //
+    // at very high entropy, the boundary pressure is below the 
+    // minimum pressure, points below the isotherm are then outside 
+    // the validity range altogether, and are left to the 
+    // entropy bounds checks
+    let p_boundary = p_s_region_2_5_boundary(s);
+    let h_boundary = h_tp_5(t_boundary, p_boundary);
+
+    return h > h_boundary;
+}
+
+// after the newton raphson iteration in region 5, we check 
+// if the (T,p) point is still within region 5 
+fn check_if_within_hs_region_5_validity_range(
+    t: ThermodynamicTemperature, p: Pressure) -> Result<(), SteamTableError> {
+
+    let p_min = sat_pressure_4(ThermodynamicTemperature::new::<kelvin>(273.15));
+    let p_max = Pressure::new::<megapascal>(50.0);
+    let t_min = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let t_max = ThermodynamicTemperature::new::<kelvin>(2273.15);
+
+    if p < p_min {
+        return Err(SteamTableError::PressureBelowBound { p, bound: p_min });
+    };
+    if p > p_max {
+        return Err(SteamTableError::PressureAboveBound { p, bound: p_max });
+    };
+    if t > t_max {
+        return Err(SteamTableError::TemperatureAboveBound { t, bound: t_max });
+    };
+    if t < t_min {
+        return Err(SteamTableError::TemperatureBelowBound { t, bound: t_min });
+    };
+
+    return Ok(());
+}
+
+/// checks if the (h,s) point was assigned to its region only 
+/// because of the enthalpy (or pressure) corrections applied to 
+/// the region boundaries on pages 80 to 85, 
+/// ie. the point lies between the corrected and uncorrected boundary
+///
+/// these corrections push points close to the saturation line into 
+/// the single phase regions, and points close to the B13 and B23 
+/// boundaries into regions 1 and 2c respectively
+pub(crate) fn hs_boundary_correction_applied(h: AvailableEnergy,
+    s: SpecificHeatCapacity,
+    region: &BackwdEqnSubRegion) -> bool {
+
+    let s_bound_low_entropy_region_1_3a_and_4 = 
+        SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(3.778);
+    let s_b23_min = 
+        SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(
+            5.048_096_828
+        );
+    let s_b23_max = 
+        SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(
+            5.260_578_707
+        );
+    let s_2bc = 
+        SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(5.85);
+
+    // true if h lies within [boundary - correction, boundary)
+    let within_correction = |boundary: AvailableEnergy, correction_kj_per_kg: f64| -> bool {
+        let correction = AvailableEnergy::new::<kilojoule_per_kilogram>(correction_kj_per_kg);
+        return h >= boundary - correction && h < boundary;
+    };
+
+    match region {
+        BackwdEqnSubRegion::Region1 => {
+            if s > s_bound_low_entropy_region_1_3a_and_4 {
+                return false;
+            };
+
+            // saturated liquid line correction (page 82)
+            if within_correction(h1_prime_s_boundary_enthalpy(s), 0.0034) {
+                return true;
+            };
+
+            // B13 boundary correction (page 85), only applies 
+            // where region 3a is beside region 1
+            let s_bound_low_entropy_region_1_and_4 = 
+                SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(3.398);
+            if s < s_bound_low_entropy_region_1_and_4 {
+                return false;
+            };
+            let hb13_boundary = hb13_s_boundary_enthalpy(s);
+            let hb13_correction = AvailableEnergy::new::<kilojoule_per_kilogram>(0.018);
+
+            return h > hb13_boundary && h <= hb13_boundary + hb13_correction;
+        },
+        BackwdEqnSubRegion::Region3a => {
+            if s <= s_bound_low_entropy_region_1_3a_and_4 || s > s_crit() {
+                return false;
+            };
+
+            return within_correction(h3a_prime_s_boundary_enthalpy(s), 0.0045);
+        },
+        BackwdEqnSubRegion::Region3b | BackwdEqnSubRegion::Region2c => {
+            if s <= s_crit() || s > s_2bc {
+                return false;
+            };
+
+            // saturated vapour line correction (page 84)
+            let correction_factor_threshold_entropy = 
+                SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(5.211);
+            let correction_kj_per_kg = if s < correction_factor_threshold_entropy {
+                0.0073
+            } else {
+                0.0058
+            };
+
+            if within_correction(h2c3b_prime_s_boundary_enthalpy(s), correction_kj_per_kg) {
+                return true;
+            };
+
+            // B23 pressure correction (page 80), points are assigned 
+            // to region 2c if p2c(h,s) is within the corrected 
+            // B23 pressure
+            if *region != BackwdEqnSubRegion::Region2c 
+                || s < s_b23_min || s > s_b23_max {
+                return false;
+            };
+            let hb23_min = AvailableEnergy::new::<kilojoule_per_kilogram>(
+                2.563_592_004e3
+            );
+            let hb23_max = AvailableEnergy::new::<kilojoule_per_kilogram>(
+                2.812_942_061e3
+            );
+            if h <= hb23_min || h >= hb23_max {
+                return false;
+            };
+
+            let p_b23_boundary = p_boundary_2_3(tb23_s_boundary_enthalpy(s, h));
+            let p2c_hs = p_hs_2c(h, s);
+
+            return p2c_hs > p_b23_boundary 
+                && p2c_hs <= p_b23_boundary * (1.0 + 4.5e-5);
+        },
+        _ => {
+            return false;
+        },
+    }
 }
 
 /// note:
@@ -983,3 +1239,34 @@ fn additional_temperature_check_for_1073_15_k_isotherm(
 /// for simplicity to avoid iterations
 pub mod validity_range;
 
+/// iterative solvers for the (h,s) flash where backward 
+/// equations are not available or not accurate enough
+pub(crate) mod newton_raphson;
+use newton_raphson::*;
+
+/// fallible versions of the (h,s) flash functions 
+/// which return a SteamTableError rather than panicking
+pub mod try_flash;
+pub use try_flash::*;
+
+/// (h,s) flash with a configurable out of range policy
+/// (error, clamp to boundary or extrapolate)
+pub mod out_of_range;
+pub use out_of_range::*;
+
+/// (h,s) flash which also reports the region, subregion,
+/// iterations and residuals (see flash_diagnostics)
+pub mod diagnostics;
+pub use diagnostics::*;
+
+/// (h,s) flash returning all intensive properties at once
+/// (see steam_state)
+pub mod steam_state_flash;
+pub use steam_state_flash::*;
+
+/// (h,s) viscosity and thermal conductivity with a
+/// selectable two phase mixing model in region 4
+/// (see two_phase_mixing_model)
+pub mod two_phase_mixing;
+pub use two_phase_mixing::*;
+
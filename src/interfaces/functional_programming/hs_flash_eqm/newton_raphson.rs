@@ -0,0 +1,290 @@
+use uom::si::available_energy::joule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::pressure::{megapascal, pascal};
+use uom::si::specific_heat_capacity::joule_per_kilogram_kelvin;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::temperature_coefficient::per_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::constants::T_C_KELVIN;
+use crate::errors::SteamTableError;
+use crate::region_1_subcooled_liquid::{alpha_v_tp_1, cp_tp_1, h_tp_1, s_tp_1, v_tp_1};
+use crate::region_5_steam_at_800_plus_degc::{alpha_v_tp_5, cp_tp_5, h_tp_5, s_tp_5, try_t_ph_5, v_tp_5};
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+use crate::interfaces::functional_programming::trho_flash_eqm::{h_trho_single_phase, s_trho_single_phase, sat_liq_vap_regions_trho, sat_p_rho_liq_vap_trho};
+use super::validity_range::s_crit;
+
+/// the (T,p) newton raphson stops when the temperature correction
+/// is less than this (in K) and the relative pressure correction
+/// is less than HS_NEWTON_RAPHSON_P_REL_TOLERANCE
+const HS_NEWTON_RAPHSON_T_TOLERANCE_KELVIN: f64 = 1.0e-9;
+const HS_NEWTON_RAPHSON_P_REL_TOLERANCE: f64 = 1.0e-9;
+const HS_NEWTON_RAPHSON_MAX_ITERATIONS: usize = 100;
+
+/// two dimensional newton raphson on the forward equations
+/// h(T,p) and s(T,p) in regions 1 and 5
+///
+/// the jacobian is written in terms of cp, v and alpha_v:
+///
+/// (dh/dT)_p = cp
+/// (dh/dp)_T = v (1 - T alpha_v)
+/// (ds/dT)_p = cp/T
+/// (ds/dp)_T = - v alpha_v
+///
+/// returns temperature, pressure and the number of iterations taken
+pub(crate) fn tp_hs_newton_raphson(
+    h: AvailableEnergy,
+    s: SpecificHeatCapacity,
+    t_guess: ThermodynamicTemperature,
+    p_guess: Pressure,
+    region: FwdEqnRegion) -> Result<(ThermodynamicTemperature, Pressure, usize), SteamTableError> {
+
+    // these are the forward equations used,
+    // (h, s, cp, v, alpha_v) as functions of (T,p)
+    let forward_eqns = |t: ThermodynamicTemperature, p: Pressure|
+        -> Result<(f64, f64, f64, f64, f64), SteamTableError> {
+        let (h, s, cp, v, alpha_v) = match region {
+            FwdEqnRegion::Region1 => (h_tp_1(t, p), s_tp_1(t, p),
+                cp_tp_1(t, p), v_tp_1(t, p), alpha_v_tp_1(t, p)),
+            FwdEqnRegion::Region5 => (h_tp_5(t, p), s_tp_5(t, p),
+                cp_tp_5(t, p), v_tp_5(t, p), alpha_v_tp_5(t, p)),
+            _ => return Err(SteamTableError::RegionNotImplemented {
+                flash: "(h,s) newton raphson", region }),
+        };
+        Ok((h.get::<joule_per_kilogram>(),
+            s.get::<joule_per_kilogram_kelvin>(),
+            cp.get::<joule_per_kilogram_kelvin>(),
+            v.get::<cubic_meter_per_kilogram>(),
+            alpha_v.get::<per_kelvin>()))
+    };
+
+    let h_target = h.get::<joule_per_kilogram>();
+    let s_target = s.get::<joule_per_kilogram_kelvin>();
+
+    let mut t_kelvin = t_guess.get::<kelvin>();
+    let mut p_pascal = p_guess.get::<pascal>();
+
+    for iteration in 0..HS_NEWTON_RAPHSON_MAX_ITERATIONS {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<pascal>(p_pascal);
+
+        let (h_test, s_test, cp, v, alpha_v) = forward_eqns(t, p)?;
+
+        let residual_h = h_target - h_test;
+        let residual_s = s_target - s_test;
+
+        let dh_dt = cp;
+        let dh_dp = v * (1.0 - t_kelvin * alpha_v);
+        let ds_dt = cp / t_kelvin;
+        let ds_dp = -v * alpha_v;
+
+        let determinant = dh_dt * ds_dp - dh_dp * ds_dt;
+
+        let delta_t = (residual_h * ds_dp - dh_dp * residual_s)/determinant;
+        let delta_p = (dh_dt * residual_s - ds_dt * residual_h)/determinant;
+
+        t_kelvin += delta_t;
+
+        // pressure must remain positive, so if we overshoot,
+        // just halve the pressure
+        if p_pascal + delta_p <= 0.0 {
+            p_pascal *= 0.5;
+        } else {
+            p_pascal += delta_p;
+        };
+
+        if delta_t.abs() < HS_NEWTON_RAPHSON_T_TOLERANCE_KELVIN
+            && delta_p.abs() < HS_NEWTON_RAPHSON_P_REL_TOLERANCE * p_pascal {
+            return Ok((ThermodynamicTemperature::new::<kelvin>(t_kelvin),
+                    Pressure::new::<pascal>(p_pascal),
+                    iteration + 1));
+        };
+    }
+
+    return Err(SteamTableError::FlashDidNotConverge {
+        flash: "(h,s)", iterations: HS_NEWTON_RAPHSON_MAX_ITERATIONS });
+}
+
+/// initial guess for region 5 (h,s) flash
+///
+/// enthalpy hardly depends on pressure in region 5, so we take
+/// the temperature at 1 MPa, then estimate pressure
+/// treating steam as an ideal gas:
+///
+/// s - s_ref = - R ln (p/p_ref) at const temperature
+pub(crate) fn tp_hs_region_5_initial_guess(
+    h: AvailableEnergy,
+    s: SpecificHeatCapacity) -> Result<(ThermodynamicTemperature, Pressure), SteamTableError> {
+
+    let p_ref = Pressure::new::<megapascal>(1.0);
+    let t_guess = try_t_ph_5(p_ref, h)?;
+    let s_ref = s_tp_5(t_guess, p_ref);
+
+    let r = crate::constants::specific_gas_constant_of_water();
+    let ln_p_ratio: f64 = -((s - s_ref)/r).get::<uom::si::ratio::ratio>();
+
+    let p_guess = p_ref * ln_p_ratio.exp();
+
+    return Ok((t_guess, p_guess));
+}
+
+/// pressure on the 1073.15 K isotherm (region 5 side)
+/// for a given entropy,
+/// this is the lower enthalpy boundary of region 5 in the
+/// h-s diagram
+///
+/// newton raphson on s_tp_5(1073.15 K, p) in ln(p)
+/// (ds/d ln p)_T = - p v alpha_v
+pub(crate) fn p_s_region_2_5_boundary(s: SpecificHeatCapacity) -> Pressure {
+    let t = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let s_target = s.get::<joule_per_kilogram_kelvin>();
+
+    // ideal gas initial guess
+    let p_ref = Pressure::new::<megapascal>(1.0);
+    let s_ref = s_tp_5(t, p_ref).get::<joule_per_kilogram_kelvin>();
+    let r = crate::constants::specific_gas_constant_of_water()
+        .get::<joule_per_kilogram_kelvin>();
+
+    let mut ln_p = p_ref.get::<pascal>().ln() - (s_target - s_ref)/r;
+
+    for _ in 0..HS_NEWTON_RAPHSON_MAX_ITERATIONS {
+        let p = Pressure::new::<pascal>(ln_p.exp());
+        let residual = s_tp_5(t, p).get::<joule_per_kilogram_kelvin>() - s_target;
+        let ds_dlnp = - p.get::<pascal>()
+            * v_tp_5(t, p).get::<cubic_meter_per_kilogram>()
+            * alpha_v_tp_5(t, p).get::<per_kelvin>();
+
+        let delta_ln_p = residual/ds_dlnp;
+        ln_p -= delta_ln_p;
+
+        if delta_ln_p.abs() < HS_NEWTON_RAPHSON_P_REL_TOLERANCE {
+            break;
+        };
+    }
+
+    return Pressure::new::<pascal>(ln_p.exp());
+}
+
+/// saturated liquid and vapour enthalpy and entropy 
+/// ((h', s'), (h'', s''))
+///
+/// the saturated densities and regions are the same as 
+/// in the (T,rho) flash (see sat_p_rho_liq_vap_trho)
+fn sat_liq_vap_h_s(t: ThermodynamicTemperature) -> 
+((AvailableEnergy, SpecificHeatCapacity), (AvailableEnergy, SpecificHeatCapacity)) {
+
+    let (p, rho_liq, rho_vap) = sat_p_rho_liq_vap_trho(t);
+    let (region_liq, region_vap) = sat_liq_vap_regions_trho(t);
+
+    return ((h_trho_single_phase(region_liq, t, p, rho_liq), 
+            s_trho_single_phase(region_liq, t, p, rho_liq)),
+        (h_trho_single_phase(region_vap, t, p, rho_vap), 
+            s_trho_single_phase(region_vap, t, p, rho_vap)));
+}
+
+/// the backward equation tsat_hs_4 only works for
+/// s >= s''(623.15 K),
+/// for the rest of region 4 (including near the critical point
+/// where region 3 borders region 4), we iterate on the saturation
+/// temperature
+///
+/// the (h,s) point lies on the tie line between the saturated 
+/// liquid (s',h') and saturated vapour (s'',h'') when
+///
+/// g(T) = (h - h'(T)) (s''(T) - s'(T)) - (h''(T) - h'(T)) (s - s'(T)) = 0
+///
+/// (the tie line slope is close to, but not exactly, T in IF97, 
+/// because regions 1, 2 and 3 are not perfectly consistent 
+/// at saturation)
+///
+/// we use bisection between 273.15 K and the critical temperature
+/// (or the temperature where the tie line ends at s, whichever is lower)
+///
+/// returns the saturation temperature and the number of bisection
+/// iterations taken on the tie line
+pub(crate) fn tsat_hs_4_iterative(h: AvailableEnergy,
+    s: SpecificHeatCapacity) -> Result<(ThermodynamicTemperature, usize), SteamTableError> {
+
+    let tie_line_residual = |t_kelvin: f64| -> f64 {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let ((h_liq, s_liq), (h_vap, s_vap)) = sat_liq_vap_h_s(t);
+
+        (h - h_liq).get::<joule_per_kilogram>()
+            * (s_vap - s_liq).get::<joule_per_kilogram_kelvin>()
+            - (h_vap - h_liq).get::<joule_per_kilogram>()
+            * (s - s_liq).get::<joule_per_kilogram_kelvin>()
+    };
+
+    let mut t_low = 273.15;
+    // just slightly below critical temperature, because the
+    // saturated liquid and vapour meet at the critical point
+    let mut t_high = T_C_KELVIN - 1.0e-6;
+
+    // the tie line is only valid between s'(T) and s''(T),
+    // if extended past its ends, it may cross other tie lines, 
+    // so we cap the upper temperature where s'(T) = s 
+    // (below critical entropy) or s''(T) = s (above critical entropy)
+    //
+    // this residual decreases with temperature in both cases, 
+    // so bisection works here too
+    let below_crit_entropy = s < s_crit();
+    let tie_line_end_residual = |t_kelvin: f64| -> f64 {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let ((_h_liq, s_liq), (_h_vap, s_vap)) = sat_liq_vap_h_s(t);
+
+        if below_crit_entropy {
+            (s - s_liq).get::<joule_per_kilogram_kelvin>()
+        } else {
+            (s_vap - s).get::<joule_per_kilogram_kelvin>()
+        }
+    };
+
+    if tie_line_end_residual(t_high) < 0.0 {
+        let mut t_end_low = t_low;
+        let mut t_end_high = t_high;
+
+        for _ in 0..HS_NEWTON_RAPHSON_MAX_ITERATIONS {
+            let t_mid = 0.5 * (t_end_low + t_end_high);
+
+            if tie_line_end_residual(t_mid) < 0.0 {
+                t_end_high = t_mid;
+            } else {
+                t_end_low = t_mid;
+            };
+
+            if t_end_high - t_end_low < HS_NEWTON_RAPHSON_T_TOLERANCE_KELVIN {
+                break;
+            };
+        }
+
+        t_high = t_end_low;
+    };
+
+    let mut residual_low = tie_line_residual(t_low);
+    let residual_high = tie_line_residual(t_high);
+
+    if residual_low.signum() == residual_high.signum() {
+        return Err(SteamTableError::FlashDidNotConverge {
+            flash: "(h,s) saturation temperature", iterations: 0 });
+    };
+
+    for iteration in 0..HS_NEWTON_RAPHSON_MAX_ITERATIONS {
+        let t_mid = 0.5 * (t_low + t_high);
+
+        if t_high - t_low < HS_NEWTON_RAPHSON_T_TOLERANCE_KELVIN {
+            return Ok((ThermodynamicTemperature::new::<kelvin>(t_mid), iteration));
+        };
+
+        let residual_mid = tie_line_residual(t_mid);
+
+        if residual_mid.signum() == residual_low.signum() {
+            t_low = t_mid;
+            residual_low = residual_mid;
+        } else {
+            t_high = t_mid;
+        };
+    }
+
+    return Ok((ThermodynamicTemperature::new::<kelvin>(0.5 * (t_low + t_high)),
+        HS_NEWTON_RAPHSON_MAX_ITERATIONS));
+}
@@ -0,0 +1,80 @@
+use uom::si::available_energy::joule_per_kilogram;
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::newton_refinement::RefinementEquations;
+use crate::interfaces::functional_programming::out_of_range_policy::*;
+use crate::interfaces::functional_programming::ph_flash_eqm::PH_REFINEMENT_EQUATIONS;
+
+use super::validity_range::try_check_if_within_hs_validity_region_with_policy;
+use super::*;
+
+/// (h,s) flash which applies an out of range policy
+/// if the (h,s) point is outside the validity range
+/// (see try_check_if_within_hs_validity_region_with_policy)
+///
+/// for ClampToBoundary, the point is moved onto the nearest
+/// valid point before flashing,
+/// for ExtrapolateForwardEquation, the backward equation p(h,s)
+/// of the region of the nearest valid point is used at the input
+/// point, and then the forward equations of that region are
+/// extrapolated as in the (p,h) flash (regions 1, 2 and 3 only)
+///
+/// what was done is reported in PolicyFlashState::report
+pub fn try_hs_flash_with_policy(h: AvailableEnergy,
+    s: SpecificHeatCapacity,
+    policy: OutOfRangePolicy) -> Result<PolicyFlashState, SteamTableError> {
+
+    let checked = try_check_if_within_hs_validity_region_with_policy(h, s, policy)?;
+    let (h, s) = checked.point;
+    let report = checked.report;
+
+    if !report.was_extrapolated() {
+        return Ok(PolicyFlashState { state: try_flash(h, s)?, report });
+    };
+
+    let (h_valid, s_valid) = checked.nearest_valid_point;
+    let subregion = hs_flash_region(h_valid, s_valid);
+    let flash = "extrapolated (h,s)";
+
+    let p = match subregion {
+        BackwdEqnSubRegion::Region1 => p_hs_1(h, s),
+        BackwdEqnSubRegion::Region2a => p_hs_2a(h, s),
+        BackwdEqnSubRegion::Region2b => p_hs_2b(h, s),
+        BackwdEqnSubRegion::Region2c => p_hs_2c(h, s),
+        BackwdEqnSubRegion::Region3a => p_hs_3a(h, s),
+        BackwdEqnSubRegion::Region3b => p_hs_3b(h, s),
+        BackwdEqnSubRegion::Region4 | BackwdEqnSubRegion::Region5 => {
+            return Err(SteamTableError::RegionNotImplemented {
+                flash, region: subregion.into() });
+        },
+    };
+
+    let valid_state = try_flash(h_valid, s_valid)?;
+    let equations = RefinementEquations {
+        flash,
+        ..PH_REFINEMENT_EQUATIONS
+    };
+
+    let mut state = try_extrapolate_p_steam_state(p,
+        h.get::<joule_per_kilogram>(),
+        EXTRAPOLATION_H_TOLERANCE_JOULE_PER_KG,
+        &valid_state,
+        &equations)?;
+    state.h = h;
+    state.s = s;
+
+    return Ok(PolicyFlashState { state, report });
+}
+
+/// (h,s) flash which applies an out of range policy,
+/// panics if the policy is Error and the point is outside the
+/// validity range
+pub fn hs_flash_with_policy(h: AvailableEnergy,
+    s: SpecificHeatCapacity,
+    policy: OutOfRangePolicy) -> PolicyFlashState {
+    match try_hs_flash_with_policy(h, s, policy) {
+        Ok(state) => state,
+        Err(error) => panic!("h,s flash failed: {}", error),
+    }
+}
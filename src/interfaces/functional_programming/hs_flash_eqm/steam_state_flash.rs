@@ -0,0 +1,51 @@
+use uom::si::f64::*;
+use uom::si::ratio::ratio;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+
+use super::*;
+
+/// (h,s) flash returning all intensive properties at once
+///
+/// the (h,s) flash itself is only done once, whereas
+/// cp_hs_eqm, w_hs_eqm, mu_hs_eqm, lambda_hs_eqm etc.
+/// each do the (h,s) flash and then a (p,h) flash
+///
+/// the enthalpy and entropy of the returned state are the
+/// input enthalpy and entropy, rather than the forward equations
+/// evaluated at the flashed state
+pub fn try_flash(h: AvailableEnergy,
+    s: SpecificHeatCapacity) -> Result<SteamState, SteamTableError> {
+
+    let (t, p, v, x, subregion, _iterations) =
+        try_tpvx_hs_flash_eqm_with_iterations(h, s)?;
+
+    let region: FwdEqnRegion = subregion.into();
+    let x: f64 = x.get::<ratio>();
+
+    let mut state = match region {
+        FwdEqnRegion::Region4 => {
+            let (liquid, vapour) = SteamState::new_saturated_liquid_and_vapour(t);
+
+            SteamState::new_two_phase(&liquid, &vapour, x)
+        },
+        _ => SteamState::new_single_phase(region, t, p, v.recip(), x),
+    };
+
+    state.p = p;
+    state.h = h;
+    state.s = s;
+
+    return Ok(state);
+}
+
+/// (h,s) flash returning all intensive properties at once,
+/// panics if the (h,s) point is outside the validity range
+pub fn flash(h: AvailableEnergy,
+    s: SpecificHeatCapacity) -> SteamState {
+    match try_flash(h, s) {
+        Ok(state) => state,
+        Err(error) => panic!("h,s flash failed: {}", error),
+    }
+}
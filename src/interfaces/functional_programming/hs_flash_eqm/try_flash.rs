@@ -0,0 +1,92 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::ph_flash_eqm::{try_cp_ph_eqm, try_kappa_ph_eqm, try_lambda_ph_eqm, try_mu_ph_eqm, try_w_ph_eqm};
+
+use super::try_tpvx_hs_flash_eqm;
+
+/// returns temperature given
+/// enthalpy and entropy point
+pub fn try_t_hs_eqm(h: AvailableEnergy, 
+    s: SpecificHeatCapacity,) -> Result<ThermodynamicTemperature, SteamTableError> {
+    let (t,_p,_v,_x) = try_tpvx_hs_flash_eqm(h, s)?;
+
+    return Ok(t);
+}
+
+/// returns pressure given
+/// enthalpy and entropy point
+pub fn try_p_hs_eqm(h: AvailableEnergy, 
+    s: SpecificHeatCapacity,) -> Result<Pressure, SteamTableError> {
+    let (_t,p,_v,_x) = try_tpvx_hs_flash_eqm(h, s)?;
+
+    return Ok(p);
+}
+
+/// returns specific volume given
+/// enthalpy and entropy point
+pub fn try_v_hs_eqm(h: AvailableEnergy, 
+    s: SpecificHeatCapacity,) -> Result<SpecificVolume, SteamTableError> {
+    let (_t,_p,v,_x) = try_tpvx_hs_flash_eqm(h, s)?;
+
+    return Ok(v);
+}
+
+/// returns quality given
+/// enthalpy and entropy point
+pub fn try_x_hs_eqm(h: AvailableEnergy, 
+    s: SpecificHeatCapacity,) -> Result<Ratio, SteamTableError> {
+    let (_t,_p,_v,x) = try_tpvx_hs_flash_eqm(h, s)?;
+
+    return Ok(x);
+}
+
+/// returns cp given 
+/// enthalpy and entropy point
+/// uses ph flash
+pub fn try_cp_hs_eqm(h: AvailableEnergy, 
+    s: SpecificHeatCapacity,) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let (_t,p,_v,_x) = try_tpvx_hs_flash_eqm(h, s)?;
+
+    return try_cp_ph_eqm(p, h);
+}
+
+/// returns w (speed of sound) given 
+/// enthalpy and entropy point
+/// uses ph flash
+pub fn try_w_hs_eqm(h: AvailableEnergy, 
+    s: SpecificHeatCapacity,) -> Result<Velocity, SteamTableError> {
+    let (_t,p,_v,_x) = try_tpvx_hs_flash_eqm(h, s)?;
+
+    return try_w_ph_eqm(p, h);
+}
+
+/// returns kappa (isentropic exponent) given 
+/// enthalpy and entropy point
+/// uses ph flash
+pub fn try_kappa_hs_eqm(h: AvailableEnergy, 
+    s: SpecificHeatCapacity,) -> Result<Ratio, SteamTableError> {
+    let (_t,p,_v,_x) = try_tpvx_hs_flash_eqm(h, s)?;
+
+    return try_kappa_ph_eqm(p, h);
+}
+
+/// returns mu, or sometimes eta (dynamic viscosity) given 
+/// enthalpy and entropy point
+/// uses ph flash
+pub fn try_mu_hs_eqm(h: AvailableEnergy, 
+    s: SpecificHeatCapacity,) -> Result<DynamicViscosity, SteamTableError> {
+    let (_t,p,_v,_x) = try_tpvx_hs_flash_eqm(h, s)?;
+
+    return try_mu_ph_eqm(p, h);
+}
+
+/// returns lambda (thermal conductivity) given 
+/// enthalpy and entropy point
+/// uses ph flash
+pub fn try_lambda_hs_eqm(h: AvailableEnergy, 
+    s: SpecificHeatCapacity,) -> Result<ThermalConductivity, SteamTableError> {
+    let (_t,p,_v,_x) = try_tpvx_hs_flash_eqm(h, s)?;
+
+    return try_lambda_ph_eqm(p, h);
+}
@@ -0,0 +1,28 @@
+use uom::si::f64::*;
+
+use crate::interfaces::functional_programming::ph_flash_eqm::{lambda_ph_eqm_with_mixing_model, mu_ph_eqm_with_mixing_model};
+use crate::interfaces::functional_programming::two_phase_mixing_model::TwoPhaseMixingModel;
+
+use super::*;
+
+/// returns mu (dynamic viscosity) given enthalpy and entropy,
+/// using the mixing model in region 4
+/// uses ph flash, as in mu_hs_eqm
+pub fn mu_hs_eqm_with_mixing_model(h: AvailableEnergy,
+    s: SpecificHeatCapacity,
+    model: TwoPhaseMixingModel) -> DynamicViscosity {
+    let (_t,p,_v,_x) = tpvx_hs_flash_eqm(h, s);
+
+    return mu_ph_eqm_with_mixing_model(p, h, model);
+}
+
+/// returns lambda (thermal conductivity) given enthalpy and entropy,
+/// using the mixing model in region 4
+/// uses ph flash, as in lambda_hs_eqm
+pub fn lambda_hs_eqm_with_mixing_model(h: AvailableEnergy,
+    s: SpecificHeatCapacity,
+    model: TwoPhaseMixingModel) -> ThermalConductivity {
+    let (_t,p,_v,_x) = tpvx_hs_flash_eqm(h, s);
+
+    return lambda_ph_eqm_with_mixing_model(p, h, model);
+}
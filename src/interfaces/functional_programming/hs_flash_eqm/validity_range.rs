@@ -3,6 +3,12 @@ use uom::si::{f64::*, specific_heat_capacity::kilojoule_per_kilogram_kelvin};
 use uom::si::thermodynamic_temperature::kelvin;
 use uom::si::temperature_interval::millikelvin;
 
+use uom::si::pressure::megapascal;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::out_of_range_policy::{try_apply_out_of_range_policy, OutOfRangePolicy, PolicyPoint};
+use crate::interfaces::functional_programming::validity_domain::validity_domain_hs;
+use crate::interfaces::functional_programming::ps_flash_eqm::try_h_ps_eqm;
 use crate::region_1_subcooled_liquid::{p_hs_1, t_ph_1};
 
 /// based on page 72 boundary, we use this
@@ -47,7 +53,23 @@ pub fn hs_is_below_isobar_p_100_mpa_in_region1(
         // in this case we are outside region 1
         return false;
     };
-    todo!();
+
+    // otherwise, the point is below the isobar if its enthalpy 
+    // is lower than that on the isobar at the same entropy
+    match try_h_isobar_p_100_mpa(s) {
+        Ok(h_isobar) => return h <= h_isobar,
+        Err(_) => return false,
+    };
+}
+
+/// enthalpy on the 100 MPa isobar with the same entropy, 
+/// this is the upper enthalpy bound of the (h,s) flash 
+/// in regions 1, 2 and 3 (page 73)
+pub fn try_h_isobar_p_100_mpa(s: SpecificHeatCapacity) 
+    -> Result<AvailableEnergy, SteamTableError> {
+    let p_max = Pressure::new::<megapascal>(100.0);
+
+    return try_h_ps_eqm(p_max, s);
 }
 
 
@@ -58,3 +80,16 @@ pub(crate) fn s_crit() -> SpecificHeatCapacity {
     )
 
 }
+
+/// checks if the (h,s) point is within the validity range,
+/// applying the out of range policy if it is not
+///
+/// returns the (h,s) point to flash (moved onto the nearest valid
+/// point for ClampToBoundary), the nearest valid point
+/// and what the policy did
+pub(crate) fn try_check_if_within_hs_validity_region_with_policy(
+    h: AvailableEnergy, s: SpecificHeatCapacity,
+    policy: OutOfRangePolicy) -> Result<PolicyPoint<AvailableEnergy, SpecificHeatCapacity>, SteamTableError> {
+
+    return try_apply_out_of_range_policy(validity_domain_hs(h, s), policy);
+}
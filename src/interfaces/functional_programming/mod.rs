@@ -22,6 +22,55 @@ pub mod pt_flash_metastable;
 /// allows for pressure entropy flash 
 pub mod ps_flash_eqm;
 
+/// newton raphson refinement of backward equation estimates
+/// using the forward equations, used by the refined
+/// (p,h) and (p,s) flashes
+pub(crate) mod newton_refinement;
+
 /// allows for enthalpy entropy flash; 
 /// Note: this only works for part of the steam table
 pub mod hs_flash_eqm;
+
+/// allows for pressure specific volume flash
+pub mod pv_flash_eqm;
+
+/// allows for temperature density flash, 
+/// including two phase states within the vapour dome
+pub mod trho_flash_eqm;
+
+/// allows for internal energy specific volume flash,
+/// useful for rigid control volumes where mass and internal
+/// energy are conserved
+pub mod uv_flash_eqm;
+
+/// saturated liquid and vapour properties given saturation
+/// pressure or temperature, (p,x) and (T,x) flashes
+/// are done through the mixture properties
+pub mod saturation_eqm;
+
+/// checks whether points are within the validity range of
+/// each flash pair, and if not, which bound was violated
+/// and the nearest valid point
+pub mod validity_domain;
+
+/// what the *_flash_with_policy functions do when given
+/// points outside the validity range
+pub mod out_of_range_policy;
+
+/// reports the region, subregion, iterations and residuals 
+/// of the *_with_diagnostics flash functions
+pub mod flash_diagnostics;
+
+/// all intensive properties at a given state in one struct,
+/// returned by the single call flash functions
+pub mod steam_state;
+
+/// two phase viscosity and thermal conductivity models
+/// (McAdams, Cicchitti, Dukler, Beattie-Whalley, Lin,
+/// mass and volume weighted) for region 4
+pub mod two_phase_mixing_model;
+
+/// cv, speed of sound and isentropic exponent of a vapour liquid
+/// mixture in homogeneous equilibrium (region 4), from the
+/// saturation line derivatives rather than quality weighted averages
+pub mod two_phase_equilibrium_derivatives;
@@ -0,0 +1,346 @@
+use uom::si::f64::*;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+use uom::si::pressure::pascal;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::errors::SteamTableError;
+use crate::region_1_subcooled_liquid::v_tp_1;
+use crate::region_2_vapour::v_tp_2;
+use crate::region_3_single_phase_plus_supercritical_steam::{alpha_v_rho_t_3, cv_rho_t_3, kappa_t_rho_t_3, p_rho_t_3};
+use crate::region_5_steam_at_800_plus_degc::v_tp_5;
+
+use super::pt_flash_eqm::{try_region_fwd_eqn_single_phase, FwdEqnRegion};
+
+/// the region 3 iteration also requires the pressure from
+/// the forward equation to match the input pressure
+/// to within this relative tolerance
+const REFINEMENT_P_REL_TOLERANCE: f64 = 1.0e-10;
+const REFINEMENT_MAX_ITERATIONS: usize = 50;
+/// refined temperatures within this relative tolerance of a
+/// region boundary count as being on either side of it
+const REFINEMENT_REGION_T_REL_TOLERANCE: f64 = 1.0e-9;
+
+/// partial derivatives of a property (h or s) with respect
+/// to temperature and specific volume in region 3
+///
+/// the closure gets (rho, T, cv, alpha_v, kappa_t) in SI units
+/// and returns (d/dT at constant v, d/dv at constant T)
+pub(crate) type Region3Derivatives = fn(f64, f64, f64, f64, f64) -> (f64, f64);
+
+/// the backward and forward equations for the property (h or s)
+/// which a refined flash iterates on, all property values in SI units
+pub(crate) struct RefinementEquations {
+    /// name of the flash, for the non convergence error
+    pub flash: &'static str,
+    /// backward equation T(p, property) in regions 1, 2, 3 and 5
+    pub t_backward: fn(FwdEqnRegion, Pressure, f64) -> Result<ThermodynamicTemperature, SteamTableError>,
+    /// backward equation v(p, property) in region 3
+    pub v_backward_region_3: fn(Pressure, f64) -> SpecificVolume,
+    /// equilibrium specific volume in region 4
+    pub v_region_4: fn(Pressure, f64) -> SpecificVolume,
+    /// property and its derivative with respect to temperature
+    /// at constant pressure in regions 1, 2 and 5
+    pub property_tp: fn(FwdEqnRegion, ThermodynamicTemperature, Pressure) -> (f64, f64),
+    /// property in region 3
+    pub property_rho_t_3: fn(MassDensity, ThermodynamicTemperature) -> f64,
+    /// derivatives of the property in region 3
+    pub derivatives_region_3: Region3Derivatives,
+}
+
+/// refines the temperature and density of a (p,h) or (p,s) flash
+/// in a given region, starting from the backward equations
+///
+/// regions 1, 2 and 5: newton raphson on T
+/// region 3: newton raphson on (v,T)
+/// region 4: T is the saturation temperature, nothing to refine
+///
+/// the refinement moves T slightly away from the backward equation
+/// estimate, so near a region boundary the refined point may land in
+/// the neighbouring region of the (T,p) chart. In that case, the
+/// refined point is not a valid solution of the forward equations
+/// for the region we picked, so we fall back to the backward
+/// equation estimate only if it already meets the tolerance,
+/// otherwise a FlashDidNotConverge error is returned
+/// (no point within the region was found in
+/// REFINEMENT_MAX_ITERATIONS)
+pub(crate) fn try_refine_t_rho(p: Pressure,
+    region: FwdEqnRegion,
+    target: f64,
+    tolerance: f64,
+    equations: &RefinementEquations) -> Result<(ThermodynamicTemperature, MassDensity), SteamTableError> {
+
+    let guess = try_backward_t_rho(p, region, target, equations)?;
+    let (t, rho) = try_refine_t_rho_from_guess(p, region, guess,
+        target, tolerance, equations)?;
+
+    if region != FwdEqnRegion::Region4 && !is_same_region(region, t, p) {
+
+        let guess_residual = property_single_phase(region, p, guess, equations) - target;
+
+        if guess_residual.abs() < tolerance {
+            return Ok(guess);
+        };
+
+        return Err(SteamTableError::FlashDidNotConverge {
+            flash: equations.flash, iterations: REFINEMENT_MAX_ITERATIONS });
+    };
+
+    return Ok((t, rho));
+}
+
+/// the property (h or s, in SI units) from the forward equations
+/// at a given temperature and density in regions 1, 2, 3 and 5
+fn property_single_phase(region: FwdEqnRegion,
+    p: Pressure,
+    t_rho: (ThermodynamicTemperature, MassDensity),
+    equations: &RefinementEquations) -> f64 {
+
+    let (t, rho) = t_rho;
+
+    match region {
+        FwdEqnRegion::Region3 => return (equations.property_rho_t_3)(rho, t),
+        _ => return (equations.property_tp)(region, t, p).0,
+    }
+}
+
+/// temperature and density from the backward equations,
+/// used as the starting guess for the refinement
+pub(crate) fn try_backward_t_rho(p: Pressure,
+    region: FwdEqnRegion,
+    target: f64,
+    equations: &RefinementEquations) -> Result<(ThermodynamicTemperature, MassDensity), SteamTableError> {
+
+    let t = (equations.t_backward)(region, p, target)?;
+
+    match region {
+        FwdEqnRegion::Region3 => {
+            return Ok((t, (equations.v_backward_region_3)(p, target).recip()));
+        },
+        FwdEqnRegion::Region4 => {
+            return Ok((t, (equations.v_region_4)(p, target).recip()));
+        },
+        _ => return Ok((t, v_tp_single_phase(region, t, p).recip())),
+    }
+}
+
+/// refines the temperature and density of a (p,h) or (p,s) flash
+/// in a given region, starting from a given guess
+///
+/// there are no range or region checks here, so this is also used
+/// to extrapolate the forward equations outside their validity range
+/// (see out_of_range_policy)
+pub(crate) fn try_refine_t_rho_from_guess(p: Pressure,
+    region: FwdEqnRegion,
+    guess: (ThermodynamicTemperature, MassDensity),
+    target: f64,
+    tolerance: f64,
+    equations: &RefinementEquations) -> Result<(ThermodynamicTemperature, MassDensity), SteamTableError> {
+
+    match region {
+        FwdEqnRegion::Region1 | FwdEqnRegion::Region2 | FwdEqnRegion::Region5 => {
+            let t = try_refine_t_single_phase(p, guess.0, target, tolerance,
+                equations.flash,
+                |t, p| (equations.property_tp)(region, t, p))?;
+
+            return Ok((t, v_tp_single_phase(region, t, p).recip()));
+        },
+        FwdEqnRegion::Region3 => {
+            return try_refine_t_rho_region_3(p, guess, target, tolerance, equations);
+        },
+        FwdEqnRegion::Region4 => return Ok(guess),
+    }
+}
+
+/// refines the density in region 3 given temperature and pressure,
+/// using newton raphson on p(rho,T) = p starting from rho_guess
+///
+/// (dp/drho)_T = 1/(rho kappa_t)
+///
+/// there are no range checks here, so this is also used to
+/// extrapolate region 3 above 100 MPa (see out_of_range_policy)
+pub(crate) fn try_refine_rho_region_3(t: ThermodynamicTemperature,
+    p: Pressure,
+    rho_guess: MassDensity,
+    flash: &'static str) -> Result<MassDensity, SteamTableError> {
+
+    let p_pascal = p.get::<pascal>();
+    let mut rho_kg_per_m3 = rho_guess.get::<kilogram_per_cubic_meter>();
+
+    for _ in 0..REFINEMENT_MAX_ITERATIONS {
+        let rho = MassDensity::new::<kilogram_per_cubic_meter>(rho_kg_per_m3);
+        let residual_p = (p_rho_t_3(rho, t).get::<pascal>() - p_pascal)/p_pascal;
+
+        if residual_p.abs() < REFINEMENT_P_REL_TOLERANCE {
+            return Ok(rho);
+        };
+
+        let kappa_t = kappa_t_rho_t_3(rho, t).value;
+        let dp_drho = 1.0/(rho_kg_per_m3 * kappa_t)/p_pascal;
+        let delta_rho = residual_p/dp_drho;
+
+        // prevent the density from going negative
+        // by halving the step
+        let mut step = 1.0;
+        while rho_kg_per_m3 - step * delta_rho <= 0.0 {
+            step *= 0.5;
+        };
+
+        rho_kg_per_m3 -= step * delta_rho;
+    }
+
+    return Err(SteamTableError::FlashDidNotConverge {
+        flash, iterations: REFINEMENT_MAX_ITERATIONS });
+}
+
+/// specific volume in regions 1, 2 and 5, which are explicit in (T,p)
+fn v_tp_single_phase(region: FwdEqnRegion,
+    t: ThermodynamicTemperature,
+    p: Pressure) -> SpecificVolume {
+    match region {
+        FwdEqnRegion::Region1 => v_tp_1(t, p),
+        FwdEqnRegion::Region2 => v_tp_2(t, p),
+        _ => v_tp_5(t, p),
+    }
+}
+
+/// checks that a refined (T,p) point still lies in the region
+/// the backward equations picked
+///
+/// a refined point sitting on a region boundary (eg. the 623.15 K
+/// isotherm) belongs to the regions on both sides of it, whereas
+/// the (T,p) chart only reports one of them, so we also check
+/// just either side of the refined temperature
+///
+/// likewise, a refined region 1 or 2 point may sit on the
+/// saturation line, which the (T,p) chart reports as region 4
+fn is_same_region(region: FwdEqnRegion,
+    t: ThermodynamicTemperature,
+    p: Pressure) -> bool {
+
+    let t_kelvin = t.get::<kelvin>();
+    let t_candidates_kelvin = [
+        t_kelvin,
+        t_kelvin * (1.0 - REFINEMENT_REGION_T_REL_TOLERANCE),
+        t_kelvin * (1.0 + REFINEMENT_REGION_T_REL_TOLERANCE),
+    ];
+
+    return t_candidates_kelvin.iter().any(|t_kelvin| {
+        let t = ThermodynamicTemperature::new::<kelvin>(*t_kelvin);
+
+        match try_region_fwd_eqn_single_phase(t, p) {
+            Ok(FwdEqnRegion::Region4) => {
+                region == FwdEqnRegion::Region1 || region == FwdEqnRegion::Region2
+            },
+            Ok(region_tp) => region_tp == region,
+            Err(_) => false,
+        }
+    });
+}
+
+/// refines the temperature in regions 1, 2 and 5 given pressure
+/// and a target value of a property (eg. h or s, in SI units)
+///
+/// the property closure returns the property value and its
+/// derivative with respect to temperature at constant pressure
+/// (eg. cp for enthalpy, or cp/T for entropy),
+/// we then do newton raphson on temperature starting from the
+/// backward equation estimate
+pub(crate) fn try_refine_t_single_phase<F>(p: Pressure,
+    t_guess: ThermodynamicTemperature,
+    target: f64,
+    tolerance: f64,
+    flash: &'static str,
+    property: F) -> Result<ThermodynamicTemperature, SteamTableError>
+where
+    F: Fn(ThermodynamicTemperature, Pressure) -> (f64, f64) {
+
+    let mut t_kelvin = t_guess.get::<kelvin>();
+
+    for _ in 0..REFINEMENT_MAX_ITERATIONS {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let (value, d_value_dt) = property(t, p);
+        let residual = value - target;
+
+        if residual.abs() < tolerance {
+            return Ok(t);
+        };
+
+        t_kelvin -= residual/d_value_dt;
+    }
+
+    return Err(SteamTableError::FlashDidNotConverge {
+        flash, iterations: REFINEMENT_MAX_ITERATIONS });
+}
+
+/// refines the temperature and density in region 3 given pressure
+/// and a target value of a property (eg. h or s, in SI units)
+///
+/// region 3 is explicit in density and temperature, so we need
+/// to solve two equations simultaneously,
+///
+/// p(v,T) = p
+/// property(v,T) = target
+///
+/// using a two dimensional newton raphson in (v,T), starting from
+/// the backward equation estimates.
+///
+/// the pressure derivatives are
+///
+/// (dp/dv)_T = -1/(v kappa_t)
+/// (dp/dT)_v = alpha_v/kappa_t
+pub(crate) fn try_refine_t_rho_region_3(p: Pressure,
+    guess: (ThermodynamicTemperature, MassDensity),
+    target: f64,
+    tolerance: f64,
+    equations: &RefinementEquations) -> Result<(ThermodynamicTemperature, MassDensity), SteamTableError> {
+
+    let (t_guess, rho_guess) = guess;
+    let p_pascal = p.get::<pascal>();
+    let mut t_kelvin = t_guess.get::<kelvin>();
+    let mut v_m3_per_kg = rho_guess.recip().get::<cubic_meter_per_kilogram>();
+
+    for _ in 0..REFINEMENT_MAX_ITERATIONS {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let rho = MassDensity::new::<kilogram_per_cubic_meter>(v_m3_per_kg.recip());
+
+        // residuals, pressure residual is relative
+        let residual_p = (p_rho_t_3(rho, t).get::<pascal>() - p_pascal)/p_pascal;
+        let residual_property = (equations.property_rho_t_3)(rho, t) - target;
+
+        if residual_p.abs() < REFINEMENT_P_REL_TOLERANCE
+            && residual_property.abs() < tolerance {
+            return Ok((t, rho));
+        };
+
+        let cv = cv_rho_t_3(rho, t).value;
+        let alpha_v = alpha_v_rho_t_3(rho, t).value;
+        let kappa_t = kappa_t_rho_t_3(rho, t).value;
+
+        // jacobian
+        // [ dp/dv /p        dp/dT /p       ]
+        // [ dproperty/dv    dproperty/dT   ]
+        let dp_dv = -1.0/(v_m3_per_kg * kappa_t)/p_pascal;
+        let dp_dt = alpha_v/kappa_t/p_pascal;
+        let (dproperty_dt, dproperty_dv) =
+            (equations.derivatives_region_3)(rho.value, t_kelvin, cv, alpha_v, kappa_t);
+
+        let determinant = dp_dv * dproperty_dt - dp_dt * dproperty_dv;
+
+        let delta_v = (residual_p * dproperty_dt - dp_dt * residual_property)/determinant;
+        let delta_t = (dp_dv * residual_property - dproperty_dv * residual_p)/determinant;
+
+        // prevent the specific volume from going negative
+        // by halving the step
+        let mut step = 1.0;
+        while v_m3_per_kg - step * delta_v <= 0.0 {
+            step *= 0.5;
+        };
+
+        v_m3_per_kg -= step * delta_v;
+        t_kelvin -= step * delta_t;
+    }
+
+    return Err(SteamTableError::FlashDidNotConverge {
+        flash: equations.flash, iterations: REFINEMENT_MAX_ITERATIONS });
+}
@@ -0,0 +1,217 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::region_1_subcooled_liquid::v_tp_1;
+use crate::region_2_vapour::v_tp_2;
+use crate::region_5_steam_at_800_plus_degc::v_tp_5;
+
+use super::newton_refinement::{try_backward_t_rho, try_refine_rho_region_3, try_refine_t_rho_from_guess, RefinementEquations};
+use super::pt_flash_eqm::{try_region_fwd_eqn_single_phase, FwdEqnRegion};
+use super::steam_state::SteamState;
+use super::validity_domain::ValidityDomain;
+
+/// the extrapolated (p,h) and (p,s) flashes refine the backward
+/// equation temperature using the forward equations
+/// (see newton_refinement), since the backward equations
+/// lose their consistency with the forward equations quickly
+/// outside their validity range.
+/// These are the residual tolerances
+pub(crate) const EXTRAPOLATION_H_TOLERANCE_JOULE_PER_KG: f64 = 1.0e-3;
+pub(crate) const EXTRAPOLATION_S_TOLERANCE_JOULE_PER_KG_K: f64 = 1.0e-6;
+
+/// what to do when a flash is given a point outside the
+/// validity range, eg. when a plant transient briefly pushes
+/// the pressure above 100 MPa or the temperature below 273.15 K
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutOfRangePolicy {
+    /// return the SteamTableError for the violated bound
+    /// (this is what the try_* flash functions do)
+    #[default]
+    Error,
+    /// move the point onto the nearest valid point
+    /// (see validity_domain) and flash that point instead
+    ClampToBoundary,
+    /// flash the point using the equations of the region
+    /// which the nearest valid point is in, even though the point
+    /// itself is outside the validity range of those equations
+    ///
+    /// Note: region 4 cannot be extrapolated, nor can region 5
+    /// in the (h,s) flash, these return a RegionNotImplemented error
+    ExtrapolateForwardEquation,
+}
+
+/// reports what the out of range policy did to the input point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutOfRangeReport {
+    /// the policy which was used
+    pub policy: OutOfRangePolicy,
+    /// the bound violated by the input point,
+    /// None if the input point was within the validity range
+    pub violated_bound: Option<SteamTableError>,
+}
+
+impl OutOfRangeReport {
+
+    /// returns true if the input point was within the validity range
+    pub fn is_within_range(&self) -> bool {
+        return self.violated_bound.is_none();
+    }
+
+    /// returns true if the input point was moved onto the boundary
+    pub fn was_clamped(&self) -> bool {
+        return !self.is_within_range()
+            && self.policy == OutOfRangePolicy::ClampToBoundary;
+    }
+
+    /// returns true if the input point was flashed
+    /// using extrapolated equations
+    pub fn was_extrapolated(&self) -> bool {
+        return !self.is_within_range()
+            && self.policy == OutOfRangePolicy::ExtrapolateForwardEquation;
+    }
+}
+
+/// all intensive properties returned by the *_flash_with_policy
+/// functions, together with what the out of range policy did
+///
+/// if the point was clamped, these are the properties at the
+/// clamped point (so the input property may differ from the
+/// one here)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolicyFlashState {
+    /// the flashed state
+    pub state: SteamState,
+    /// what the out of range policy did
+    pub report: OutOfRangeReport,
+}
+
+/// the point to flash after applying the out of range policy
+/// in the validity checks (eg. try_check_if_within_ph_validity_region_with_policy)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PolicyPoint<A, B> {
+    /// the point to flash, this is the clamped point for
+    /// ClampToBoundary and the input point otherwise
+    pub point: (A, B),
+    /// the nearest valid point, for ExtrapolateForwardEquation
+    /// the region of this point is the one extrapolated
+    pub nearest_valid_point: (A, B),
+    /// what the out of range policy did
+    pub report: OutOfRangeReport,
+}
+
+/// applies the out of range policy given the validity domain
+/// of the input point
+///
+/// returns the point to flash, the nearest valid point and the report
+pub(crate) fn try_apply_out_of_range_policy<A: Copy, B: Copy>(
+    domain: ValidityDomain<A, B>,
+    policy: OutOfRangePolicy) -> Result<PolicyPoint<A, B>, SteamTableError> {
+
+    let report = OutOfRangeReport {
+        policy,
+        violated_bound: domain.violated_bound,
+    };
+
+    let violated_bound = match domain.violated_bound {
+        None => {
+            return Ok(PolicyPoint {
+                point: domain.point,
+                nearest_valid_point: domain.point,
+                report,
+            });
+        },
+        Some(error) => error,
+    };
+
+    if policy == OutOfRangePolicy::Error {
+        return Err(violated_bound);
+    };
+
+    // if there is no nearest valid point, we can neither
+    // clamp nor find a region to extrapolate from
+    let nearest_valid_point = match domain.nearest_valid_point {
+        Some(point) => point,
+        None => return Err(violated_bound),
+    };
+
+    let point = if policy == OutOfRangePolicy::ClampToBoundary {
+        nearest_valid_point
+    } else {
+        domain.point
+    };
+
+    return Ok(PolicyPoint { point, nearest_valid_point, report });
+}
+
+/// extrapolates the forward equations of the region of the nearest
+/// valid point to a (T,p) point outside the validity range
+///
+/// regions 1, 2 and 5 are explicit in (T,p), so their
+/// forward equations are simply evaluated outside their
+/// validity range, region 3 is explicit in (T,rho), so the density
+/// is found by newton raphson on p(rho,T) starting from the density
+/// of the nearest valid point,
+/// region 4 cannot be extrapolated this way
+///
+/// the steam quality is that of the nearest valid point
+pub(crate) fn try_extrapolate_tp_steam_state(t: ThermodynamicTemperature,
+    p: Pressure,
+    valid_state: &SteamState,
+    flash: &'static str) -> Result<SteamState, SteamTableError> {
+
+    let region = try_valid_state_region(valid_state)?;
+
+    let rho = match region {
+        FwdEqnRegion::Region1 => v_tp_1(t, p).recip(),
+        FwdEqnRegion::Region2 => v_tp_2(t, p).recip(),
+        FwdEqnRegion::Region3 => try_refine_rho_region_3(t, p, valid_state.rho(), flash)?,
+        FwdEqnRegion::Region4 => {
+            return Err(SteamTableError::RegionNotImplemented { flash, region });
+        },
+        FwdEqnRegion::Region5 => v_tp_5(t, p).recip(),
+    };
+
+    return Ok(SteamState::new_single_phase(region, t, p, rho, valid_state.x));
+}
+
+/// the IF97 flashes always return the region, but in case the
+/// region is missing, it is that of the (T,p) point
+fn try_valid_state_region(valid_state: &SteamState) -> Result<FwdEqnRegion, SteamTableError> {
+    return match valid_state.region {
+        Some(region) => Ok(region),
+        None => try_region_fwd_eqn_single_phase(valid_state.t, valid_state.p),
+    };
+}
+
+/// extrapolates the forward equations of the region of the nearest
+/// valid point to a (p,h) or (p,s) point outside the validity range
+///
+/// the temperature (and in region 3, density) is refined so that
+/// the forward equations return the input property,
+/// in regions 1, 2 and 5 starting from the backward equations at the
+/// input point, and in region 3 starting from the nearest valid point,
+/// region 4 cannot be extrapolated this way
+///
+/// the steam quality is that of the nearest valid point
+pub(crate) fn try_extrapolate_p_steam_state(p: Pressure,
+    target: f64,
+    tolerance: f64,
+    valid_state: &SteamState,
+    equations: &RefinementEquations) -> Result<SteamState, SteamTableError> {
+
+    let region = try_valid_state_region(valid_state)?;
+
+    let guess = match region {
+        FwdEqnRegion::Region3 => (valid_state.t, valid_state.rho()),
+        FwdEqnRegion::Region4 => {
+            return Err(SteamTableError::RegionNotImplemented {
+                flash: equations.flash, region });
+        },
+        _ => try_backward_t_rho(p, region, target, equations)?,
+    };
+
+    let (t, rho) = try_refine_t_rho_from_guess(p, region, guess,
+        target, tolerance, equations)?;
+
+    return Ok(SteamState::new_single_phase(region, t, p, rho, valid_state.x));
+}
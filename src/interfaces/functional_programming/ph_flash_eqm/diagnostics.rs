@@ -0,0 +1,54 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::flash_diagnostics::*;
+
+use super::*;
+
+/// (p,h) flash which also reports the region and backward 
+/// equation subregion used, the number of iterations and 
+/// the enthalpy residual of the forward equations at the 
+/// flashed state
+///
+/// only region 5 is iterative, since it has no backward 
+/// equation in terms of (p,h)
+pub fn try_ph_flash_with_diagnostics(p: Pressure,
+    h: AvailableEnergy) -> Result<FlashReport, SteamTableError> {
+
+    let region = try_ph_flash_region(p, h)?;
+    let subregion = ph_backward_subregion(p, h, region);
+
+    let iterations = match region {
+        FwdEqnRegion::Region5 => try_t_ph_5_with_iterations(p, h)?.1,
+        _ => 0,
+    };
+
+    let t = try_t_ph_eqm(p, h)?;
+    let v = try_v_ph_eqm(p, h)?;
+    let x = try_x_ph_flash(p, h)?;
+
+    let residuals = try_forward_equation_residuals(region, t, p, v,
+        Some(h), None, None)?;
+
+    return Ok(FlashReport {
+        t,
+        p,
+        v,
+        x,
+        region,
+        subregion: Some(subregion),
+        iterations,
+        residuals,
+        boundary_correction_applied: false,
+    });
+}
+
+/// (p,h) flash which also reports how the result was obtained,
+/// panics if the (p,h) point is outside the validity range
+pub fn ph_flash_with_diagnostics(p: Pressure,
+    h: AvailableEnergy) -> FlashReport {
+    match try_ph_flash_with_diagnostics(p, h) {
+        Ok(report) => report,
+        Err(error) => panic!("p,h flash failed: {}", error),
+    }
+}
@@ -0,0 +1,177 @@
+use uom::si::f64::*;
+use uom::si::available_energy::joule_per_kilogram;
+use uom::si::ratio::ratio;
+use uom::si::temperature_interval;
+use uom::si::temperature_coefficient::per_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::{Quantity, ISQ, SI};
+use uom::typenum::{N2, N5, P1, P2, Z0};
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::saturation_eqm::SaturationDerivatives;
+use crate::interfaces::functional_programming::trho_flash_eqm::{alpha_v_trho_single_phase, cp_trho_single_phase, h_trho_single_phase, kappa_t_trho_single_phase, sat_liq_vap_regions_trho, sat_p_rho_liq_vap_trho};
+
+use super::*;
+
+/// units of (drho/dp)_h, (kg/m3)/Pa = s2/m2
+pub type MassDensityPerPressure = Quantity<ISQ<N2, Z0, P2, Z0, Z0, Z0, Z0>, SI<f64>, f64>;
+
+/// units of (drho/dh)_p, (kg/m3)/(J/kg)
+pub type MassDensityPerSpecificEnergy = Quantity<ISQ<N5, P1, P2, Z0, Z0, Z0, Z0>, SI<f64>, f64>;
+
+/// units of (dT/dh)_p, K/(J/kg)
+pub type TemperaturePerSpecificEnergy = Quantity<ISQ<N2, Z0, P2, Z0, P1, Z0, Z0>, SI<f64>, f64>;
+
+/// partial derivatives of density and temperature with respect
+/// to pressure and enthalpy, as needed by pressure based solvers
+///
+/// in the single phase regions (1, 2, 3 and 5), these come from
+/// cp, alpha_v and kappa_t of the forward equations at the
+/// flashed (T,rho):
+///
+/// (dT/dh)_p = 1/cp
+/// (dT/dp)_h = v (T alpha_v - 1)/cp
+/// (drho/dh)_p = - rho alpha_v/cp
+/// (drho/dp)_h = rho kappa_t - rho alpha_v (dT/dp)_h
+///
+/// in region 4 (homogeneous equilibrium), temperature is the
+/// saturation temperature, and v = v' + x (v'' - v') with
+/// x = (h - h')/(h'' - h'), so:
+///
+/// (dT/dh)_p = 0
+/// (dT/dp)_h = dT_sat/dp
+/// (dv/dh)_p = (v'' - v')/(h'' - h')
+/// (dv/dp)_h = dv'/dp + x d(v''-v')/dp - (v''-v') (dh'/dp + x d(h''-h')/dp)/(h''-h')
+///
+/// with the saturation line derivatives from SaturationDerivatives
+///
+/// note that the derivatives jump across the saturation lines,
+/// and the region 4 derivatives grow without bound
+/// towards the critical point
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhJacobian {
+    /// (drho/dp) at constant enthalpy
+    pub drho_dp_h: MassDensityPerPressure,
+    /// (drho/dh) at constant pressure
+    pub drho_dh_p: MassDensityPerSpecificEnergy,
+    /// (dT/dp) at constant enthalpy
+    pub dt_dp_h: TemperaturePerPressure,
+    /// (dT/dh) at constant pressure
+    pub dt_dh_p: TemperaturePerSpecificEnergy,
+}
+
+/// partial derivatives of density and temperature
+/// with respect to pressure and enthalpy (see PhJacobian)
+pub fn try_jacobian(p: Pressure,
+    h: AvailableEnergy) -> Result<PhJacobian, SteamTableError> {
+
+    let region = try_ph_flash_region(p, h)?;
+
+    let (t, rho) = match region {
+        FwdEqnRegion::Region1 => {
+            let t = t_ph_1(p, h);
+            (t, v_tp_1(t, p).recip())
+        },
+        FwdEqnRegion::Region2 => {
+            let t = t_ph_2(p, h);
+            (t, v_tp_2(t, p).recip())
+        },
+        FwdEqnRegion::Region3 => {
+            (t_ph_3(p, h), v_ph_3(p, h).recip())
+        },
+        FwdEqnRegion::Region4 => {
+            return Ok(jacobian_two_phase(p, h));
+        },
+        FwdEqnRegion::Region5 => {
+            let t = try_t_ph_5(p, h)?;
+            (t, v_tp_5(t, p).recip())
+        },
+    };
+
+    let cp = cp_trho_single_phase(region, t, p, rho);
+    let alpha_v = alpha_v_trho_single_phase(region, t, p, rho);
+    let kappa_t = kappa_t_trho_single_phase(region, t, p, rho);
+
+    // T alpha_v
+    // (uom does not multiply absolute temperatures)
+    let t_alpha_v = Ratio::new::<ratio>(
+        t.get::<kelvin>() * alpha_v.get::<per_kelvin>());
+    let v = rho.recip();
+
+    let dt_dh_p: TemperaturePerSpecificEnergy = cp.recip();
+    let dt_dp_h: TemperaturePerPressure = v * (t_alpha_v - Ratio::new::<ratio>(1.0)) / cp;
+    let drho_dh_p: MassDensityPerSpecificEnergy = -rho * alpha_v / cp;
+    let drho_dp_h: MassDensityPerPressure = rho * kappa_t - rho * alpha_v * dt_dp_h;
+
+    return Ok(PhJacobian {
+        drho_dp_h,
+        drho_dh_p,
+        dt_dp_h,
+        dt_dh_p,
+    });
+}
+
+/// partial derivatives of density and temperature
+/// with respect to pressure and enthalpy (see PhJacobian),
+/// panics if the (p,h) point is outside the validity range
+pub fn jacobian(p: Pressure,
+    h: AvailableEnergy) -> PhJacobian {
+    match try_jacobian(p, h) {
+        Ok(jacobian) => jacobian,
+        Err(error) => panic!("p,h jacobian failed: {}", error),
+    }
+}
+
+/// partial derivatives in region 4, given pressure and enthalpy
+/// (already known to be within region 4)
+fn jacobian_two_phase(p: Pressure,
+    h: AvailableEnergy) -> PhJacobian {
+
+    let t_sat = sat_temp_4(p);
+    let (p_sat, rho_liq, rho_vap) = sat_p_rho_liq_vap_trho(t_sat);
+    let (liq_region, vap_region) = sat_liq_vap_regions_trho(t_sat);
+    let saturation_derivatives = SaturationDerivatives::new_from_t(t_sat);
+
+    let h_liq = h_trho_single_phase(liq_region, t_sat, p_sat, rho_liq);
+    let h_vap = h_trho_single_phase(vap_region, t_sat, p_sat, rho_vap);
+    let v_liq = rho_liq.recip();
+    let v_vap = rho_vap.recip();
+
+    let delta_h = h_vap - h_liq;
+    let delta_v = v_vap - v_liq;
+
+    // x = (h-hliq)/(hvap - hliq)
+    let x: f64 = ((h - h_liq)/delta_h).get::<ratio>();
+    let rho = (v_liq + delta_v * x).recip();
+
+    // at constant pressure, only the steam quality changes
+    let dv_dh_p: InversePressure = delta_v/delta_h;
+
+    // at constant enthalpy, the quality changes with pressure as
+    // dx/dp = - (dh'/dp + x d(h''-h')/dp)/(h''-h')
+    let dh_liq_dp = saturation_derivatives.dh_liq_dp;
+    let d_delta_h_dp = saturation_derivatives.d_latent_heat_dp();
+    let dv_liq_dp = saturation_derivatives.dv_liq_dp;
+    let d_delta_v_dp = saturation_derivatives.dv_vap_dp - dv_liq_dp;
+
+    let dx_dp_h: InversePressure = -(dh_liq_dp + d_delta_h_dp * x)/delta_h;
+    let dv_dp_h = dv_liq_dp + d_delta_v_dp * x + delta_v * dx_dp_h;
+
+    // drho = - rho^2 dv
+    let drho_dp_h: MassDensityPerPressure = -rho * rho * dv_dp_h;
+    let drho_dh_p: MassDensityPerSpecificEnergy = -rho * rho * dv_dh_p;
+
+    // temperature is the saturation temperature, 
+    // so it does not change with enthalpy
+    let dt_dh_p: TemperaturePerSpecificEnergy = 
+        TemperatureInterval::new::<temperature_interval::kelvin>(0.0)
+        / AvailableEnergy::new::<joule_per_kilogram>(1.0);
+
+    return PhJacobian {
+        drho_dp_h,
+        drho_dh_p,
+        dt_dp_h: saturation_derivatives.dt_sat_dp,
+        dt_dh_p,
+    };
+}
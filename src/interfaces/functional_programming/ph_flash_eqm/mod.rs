@@ -9,9 +9,11 @@ use crate::region_1_subcooled_liquid::*;
 use crate::region_1_subcooled_liquid::InversePressure;
 use crate::thermal_conductivity::lambda_0;
 use crate::thermal_conductivity::lambda_1;
-use crate::thermal_conductivity::lambda_2_crit_enhancement_term_tp_two_phase_estimate;
+use crate::thermal_conductivity::lambda_2_crit_enhancement_term_two_phase;
+use super::trho_flash_eqm::lambda_2_trho_single_phase;
 
 use super::pt_flash_eqm::FwdEqnRegion;
+use super::two_phase_equilibrium_derivatives::TwoPhaseEquilibriumDerivatives;
 
 /// obtains temperature given pressure and enthalpy
 pub fn t_ph_eqm(p: Pressure, h: AvailableEnergy,) -> ThermodynamicTemperature {
@@ -26,11 +28,11 @@ pub fn t_ph_eqm(p: Pressure, h: AvailableEnergy,) -> ThermodynamicTemperature {
             // determine sat liq/vap temperature 
             sat_temp_4(p)
         },
-        FwdEqnRegion::Region5 => todo!("region 5 ph flash not implemented"),
+        FwdEqnRegion::Region5 => t_ph_5(p, h),
     }
 }
 
-/// obtains volume given pressure and enthalpy (except for region 5)
+/// obtains volume given pressure and enthalpy
 pub fn v_ph_eqm(p: Pressure, h: AvailableEnergy) -> SpecificVolume {
     let region = ph_flash_region(p, h);
 
@@ -119,7 +121,10 @@ pub fn v_ph_eqm(p: Pressure, h: AvailableEnergy) -> SpecificVolume {
 
             }
         },
-        FwdEqnRegion::Region5 => todo!("ph flash not implemented for region 5"),
+        FwdEqnRegion::Region5 => {
+            let t = t_ph_5(p, h);
+            v_tp_5(t, p)
+        },
     }
 }
 /// returns the internal energy given temperature and pressure
@@ -317,6 +322,9 @@ pub fn s_ph_eqm(p: Pressure, h: AvailableEnergy) -> SpecificHeatCapacity {
 }
 
 /// returns the isobaric (const pressure) heat capacitygiven temperature and pressure
+///
+/// in region 4, cp is infinite (see two_phase_equilibrium_derivatives),
+/// except within SATURATED_PHASE_QUALITY_TOLERANCE of the saturation lines
 pub fn cp_ph_eqm(p: Pressure, h: AvailableEnergy) -> SpecificHeatCapacity {
     let t = t_ph_eqm(p, h);
     let region = ph_flash_region(p, h);
@@ -339,17 +347,12 @@ pub fn cp_ph_eqm(p: Pressure, h: AvailableEnergy) -> SpecificHeatCapacity {
             cp_rho_t_3(rho, t)
         },
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ph_flash(p, h);
             let t_sat = sat_temp_4(p);
 
-            let cp_liq = cp_tp_1(t_sat, p);
-            let cp_vap = cp_tp_2(t_sat, p);
-
-            let cp = steam_quality * cp_vap + (1.0 - steam_quality) * cp_liq;
-
-            cp
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).cp
         },
         FwdEqnRegion::Region5 => cp_tp_5(t, p),
     }
@@ -366,17 +369,12 @@ pub fn cv_ph_eqm(p: Pressure, h: AvailableEnergy) -> SpecificHeatCapacity {
         FwdEqnRegion::Region2 => cv_tp_2(t, p),
         FwdEqnRegion::Region3 => cv_tp_3(t, p),
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ph_flash(p, h);
             let t_sat = sat_temp_4(p);
 
-            let cv_liq = cv_tp_1(t_sat, p);
-            let cv_vap = cv_tp_2(t_sat, p);
-
-            let cv = steam_quality * cv_vap + (1.0 - steam_quality) * cv_liq;
-
-            cv
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).cv
         },
         FwdEqnRegion::Region5 => cv_tp_5(t, p),
     }
@@ -407,17 +405,12 @@ pub fn w_ph_eqm(p: Pressure, h: AvailableEnergy) -> Velocity {
             w_rho_t_3(rho, t)
         },
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ph_flash(p, h);
             let t_sat = sat_temp_4(p);
 
-            let w_liq = w_tp_1(t_sat, p);
-            let w_vap = w_tp_2(t_sat, p);
-
-            let w = steam_quality * w_vap + (1.0 - steam_quality) * w_liq;
-
-            w
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).w
         },
         FwdEqnRegion::Region5 => w_tp_5(t, p),
     }
@@ -434,17 +427,12 @@ pub fn kappa_ph_eqm(p: Pressure, h: AvailableEnergy) -> Ratio {
         FwdEqnRegion::Region2 => kappa_tp_2(t, p),
         FwdEqnRegion::Region3 => kappa_tp_3(t, p),
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ph_flash(p, h);
             let t_sat = sat_temp_4(p);
 
-            let kappa_liq = kappa_tp_1(t_sat, p);
-            let kappa_vap = kappa_tp_2(t_sat, p);
-
-            let kappa = steam_quality * kappa_vap + (1.0 - steam_quality) * kappa_liq;
-
-            kappa
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).kappa
         },
         FwdEqnRegion::Region5 => kappa_tp_5(t, p),
     }
@@ -472,17 +460,12 @@ pub fn alpha_v_ph_eqm(p: Pressure, h: AvailableEnergy) -> TemperatureCoefficient
             alpha_v_rho_t_3(rho, t)
         },
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ph_flash(p, h);
             let t_sat = sat_temp_4(p);
 
-            let alpha_v_liq = alpha_v_tp_1(t_sat, p);
-            let alpha_v_vap = alpha_v_tp_2(t_sat, p);
-
-            let alpha_v = steam_quality * alpha_v_vap + (1.0 - steam_quality) * alpha_v_liq;
-
-            alpha_v
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).alpha_v
         },
         FwdEqnRegion::Region5 => alpha_v_tp_5(t, p),
     }
@@ -499,17 +482,12 @@ pub fn kappa_t_ph_eqm(p: Pressure, h: AvailableEnergy) -> InversePressure {
         FwdEqnRegion::Region2 => kappa_t_tp_2(t, p),
         FwdEqnRegion::Region3 => kappa_t_tp_3(t, p),
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ph_flash(p, h);
             let t_sat = sat_temp_4(p);
 
-            let kappa_t_liq = kappa_t_tp_1(t_sat, p);
-            let kappa_t_vap = kappa_t_tp_2(t_sat, p);
-
-            let kappa_t = steam_quality * kappa_t_vap + (1.0 - steam_quality) * kappa_t_liq;
-
-            kappa_t
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).kappa_t
         },
         FwdEqnRegion::Region5 => kappa_t_tp_5(t, p),
     }
@@ -638,11 +616,27 @@ pub fn x_ph_flash(p: Pressure, h: AvailableEnergy,) -> f64 {
 
 // allows the user to check which region one is in based on a ph flash
 //
-// note that ph flash does not work in region 5
+// region 5 is from 1073.15 K to 2273.15 K, up to 50 MPa
 pub fn ph_flash_region(p: Pressure, h: AvailableEnergy) -> FwdEqnRegion {
 
     check_if_within_ph_validity_region(p, h);
 
+    // region 5 check, anything on or above the 1073.15 K isotherm 
+    // (up to 50 MPa) is region 5 
+    //
+    // the isotherm itself belongs to region 5, same as in the 
+    // (T,p) flash (see region_fwd_eqn_single_phase), so we use 
+    // the region 5 enthalpy on the isotherm as the boundary
+    let p_max_region_5 = Pressure::new::<megapascal>(50.0);
+    if p <= p_max_region_5 {
+        let h_region_2_5_boundary = h_tp_5(
+            ThermodynamicTemperature::new::<kelvin>(1073.15), p);
+
+        if h >= h_region_2_5_boundary {
+            return FwdEqnRegion::Region5;
+        };
+    };
+
     // if inside validity range, then we will start partitioning
     // first, we check if pressure is smaller or greater than 16.529 MPa
     // this is saturation pressure at 623.15K 
@@ -734,15 +728,8 @@ pub fn ph_flash_region(p: Pressure, h: AvailableEnergy) -> FwdEqnRegion {
 /// see page 38 top left
 ///
 fn check_if_within_ph_validity_region(p: Pressure, h: AvailableEnergy,){
-    if is_outside_pressure_range(p) {
-        panic!("p,h point is outside pressure range");
-    };
-
-    if is_below_isotherm_t_273_15(p, h) {
-        panic!("p,h point below 273.15K");
-    };
-    if is_above_isotherm_t_1073_15(p, h) {
-        panic!("p,h point above 1073.15K");
+    if let Err(error) = try_check_if_within_ph_validity_region(p, h) {
+        panic!("p,h point is outside validity range: {}", error);
     };
 }
 
@@ -761,17 +748,63 @@ pub(crate) use boundaries_from_single_phase_regions_to_region_4_multiphase::*;
 pub(crate) mod boundaries_between_single_phase_regions;
 pub(crate) use boundaries_between_single_phase_regions::*;
 
+/// fallible versions of the (p,h) flash functions 
+/// which return a SteamTableError rather than panicking
+pub mod try_flash;
+pub use try_flash::*;
+
+/// (p,h) flash with newton refinement on the forward equations,
+/// so that the flashed state returns the input enthalpy
+/// to within a user supplied tolerance
+pub mod refined;
+pub use refined::*;
+
+/// (p,h) flash with a configurable out of range policy
+/// (error, clamp to boundary or extrapolate)
+pub mod out_of_range;
+pub use out_of_range::*;
+
+/// (p,h) flash which also reports the region, subregion,
+/// iterations and residuals (see flash_diagnostics)
+pub mod diagnostics;
+pub use diagnostics::*;
+
+/// (p,h) flash returning all intensive properties at once
+/// (see steam_state)
+pub mod steam_state_flash;
+pub use steam_state_flash::*;
+
+/// (p,h) viscosity and thermal conductivity with a
+/// selectable two phase mixing model in region 4
+/// (see two_phase_mixing_model)
+pub mod two_phase_mixing;
+pub use two_phase_mixing::*;
+
+/// analytic partial derivatives of density and temperature
+/// with respect to pressure and enthalpy (see PhJacobian)
+pub mod jacobian;
+pub use jacobian::*;
+
 /// viscosity 
 pub use crate::dynamic_viscosity::mu_ph_eqm as mu_ph_eqm;
 
+/// returns lambda (thermal conductivity) given pressure and enthalpy
+///
+/// in region 4, this is the two phase thermal conductivity 
+/// as defined in thermal_conductivity::lambda_tp_eqm_two_phase, 
+/// use lambda_sat_liq_vap_p for the saturated liquid 
+/// and vapour thermal conductivities separately
 pub fn lambda_ph_eqm(p: Pressure, h: AvailableEnergy) -> ThermalConductivity {
     let t = t_ph_eqm(p, h);
-    let x = x_ph_flash(p, h);
     let rho = v_ph_eqm(p, h).recip();
 
     let lambda_0 = lambda_0(t);
     let lambda_1 = lambda_1(rho, t);
-    let lambda_2 = lambda_2_crit_enhancement_term_tp_two_phase_estimate(t, p, x);
+    let lambda_2 = match ph_flash_region(p, h) {
+        FwdEqnRegion::Region4 => 
+            lambda_2_crit_enhancement_term_two_phase(t, x_ph_flash(p, h)),
+        region => lambda_2_trho_single_phase(region, t, p, rho),
+    };
     let lambda_star = ThermalConductivity::new::<watt_per_meter_kelvin>(1.0e-3);
 
     let dimensionless_lambda = lambda_0 * lambda_1 + lambda_2;
@@ -0,0 +1,61 @@
+use uom::si::available_energy::joule_per_kilogram;
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::newton_refinement::RefinementEquations;
+use crate::interfaces::functional_programming::out_of_range_policy::*;
+
+use super::*;
+
+/// (p,h) flash which applies an out of range policy
+/// if the (p,h) point is outside the validity range
+/// (see try_check_if_within_ph_validity_region_with_policy)
+///
+/// for ClampToBoundary, the point is moved onto the nearest
+/// valid point before flashing,
+/// for ExtrapolateForwardEquation, the forward equations of the
+/// region of the nearest valid point are used at the input point,
+/// with the temperature (and in region 3, density) refined so that
+/// the forward equations return the input enthalpy
+///
+/// what was done is reported in PolicyFlashState::report
+pub fn try_ph_flash_with_policy(p: Pressure,
+    h: AvailableEnergy,
+    policy: OutOfRangePolicy) -> Result<PolicyFlashState, SteamTableError> {
+
+    let checked = try_check_if_within_ph_validity_region_with_policy(p, h, policy)?;
+    let (p, h) = checked.point;
+    let report = checked.report;
+
+    if !report.was_extrapolated() {
+        return Ok(PolicyFlashState { state: try_flash_within_range(p, h)?, report });
+    };
+
+    let (p_valid, h_valid) = checked.nearest_valid_point;
+    let valid_state = try_flash_within_range(p_valid, h_valid)?;
+    let equations = RefinementEquations {
+        flash: "extrapolated (p,h)",
+        ..PH_REFINEMENT_EQUATIONS
+    };
+
+    let mut state = try_extrapolate_p_steam_state(p,
+        h.get::<joule_per_kilogram>(),
+        EXTRAPOLATION_H_TOLERANCE_JOULE_PER_KG,
+        &valid_state,
+        &equations)?;
+    state.h = h;
+
+    return Ok(PolicyFlashState { state, report });
+}
+
+/// (p,h) flash which applies an out of range policy,
+/// panics if the policy is Error and the point is outside the
+/// validity range
+pub fn ph_flash_with_policy(p: Pressure,
+    h: AvailableEnergy,
+    policy: OutOfRangePolicy) -> PolicyFlashState {
+    match try_ph_flash_with_policy(p, h, policy) {
+        Ok(state) => state,
+        Err(error) => panic!("p,h flash failed: {}", error),
+    }
+}
@@ -0,0 +1,262 @@
+use uom::si::available_energy::joule_per_kilogram;
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::newton_refinement::{try_refine_t_rho, RefinementEquations};
+use crate::interfaces::functional_programming::trho_flash_eqm::{cp_trho_single_phase, cv_trho_single_phase, s_trho_single_phase, u_trho_single_phase, w_trho_single_phase};
+
+use super::*;
+
+/// (p,h) flash with newton refinement
+///
+/// the backward equations T(p,h) and v(p,h) are not exactly
+/// consistent with the forward equations, so h(T(p,h),p) does not
+/// exactly return the input enthalpy.
+///
+/// Here, we use the backward equations as a starting guess,
+/// and iterate on the forward equations until the enthalpy residual
+/// is below h_tolerance
+///
+/// regions 1, 2 and 5: newton raphson on T, using cp = (dh/dT)_p
+/// region 3: newton raphson on (v,T) so that p(v,T) = p and h(v,T) = h
+/// region 4: T is the saturation temperature, nothing to refine
+///
+/// returns region, temperature and density
+pub(crate) fn try_region_t_rho_ph_eqm_refined(p: Pressure,
+    h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> Result<(FwdEqnRegion, ThermodynamicTemperature, MassDensity), SteamTableError> {
+
+    let region = try_ph_flash_region(p, h)?;
+    let (t, rho) = try_refine_t_rho(p, region,
+        h.get::<joule_per_kilogram>(),
+        h_tolerance.get::<joule_per_kilogram>(),
+        &PH_REFINEMENT_EQUATIONS)?;
+
+    return Ok((region, t, rho));
+}
+
+/// enthalpy equations for the refined (p,h) flash
+///
+/// (dh/dT)_v = cv + v alpha_v/kappa_t
+/// (dh/dv)_T = (T alpha_v - 1)/kappa_t
+pub(crate) const PH_REFINEMENT_EQUATIONS: RefinementEquations = RefinementEquations {
+    flash: "refined (p,h)",
+    t_backward: t_ph_backward,
+    v_backward_region_3: |p, h| v_ph_3(p, AvailableEnergy::new::<joule_per_kilogram>(h)),
+    v_region_4: |p, h| v_ph_eqm(p, AvailableEnergy::new::<joule_per_kilogram>(h)),
+    property_tp: h_cp_tp,
+    property_rho_t_3: |rho, t| h_rho_t_3(rho, t).get::<joule_per_kilogram>(),
+    derivatives_region_3: |rho, t, cv, alpha_v, kappa_t| {
+        (cv + alpha_v/(rho * kappa_t), (t * alpha_v - 1.0)/kappa_t)
+    },
+};
+
+/// backward equation T(p,h) for the single phase regions
+fn t_ph_backward(region: FwdEqnRegion,
+    p: Pressure,
+    h: f64) -> Result<ThermodynamicTemperature, SteamTableError> {
+    let h = AvailableEnergy::new::<joule_per_kilogram>(h);
+
+    match region {
+        FwdEqnRegion::Region1 => Ok(t_ph_1(p, h)),
+        FwdEqnRegion::Region2 => Ok(t_ph_2(p, h)),
+        FwdEqnRegion::Region3 => Ok(t_ph_3(p, h)),
+        FwdEqnRegion::Region4 => Ok(sat_temp_4(p)),
+        FwdEqnRegion::Region5 => try_t_ph_5(p, h),
+    }
+}
+
+/// enthalpy and cp = (dh/dT)_p in regions 1, 2 and 5
+fn h_cp_tp(region: FwdEqnRegion,
+    t: ThermodynamicTemperature,
+    p: Pressure) -> (f64, f64) {
+    match region {
+        FwdEqnRegion::Region1 => (h_tp_1(t, p).get::<joule_per_kilogram>(), cp_tp_1(t, p).value),
+        FwdEqnRegion::Region2 => (h_tp_2(t, p).get::<joule_per_kilogram>(), cp_tp_2(t, p).value),
+        _ => (h_tp_5(t, p).get::<joule_per_kilogram>(), cp_tp_5(t, p).value),
+    }
+}
+
+/// obtains temperature given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+pub fn try_t_ph_eqm_refined(p: Pressure,
+    h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> Result<ThermodynamicTemperature, SteamTableError> {
+    let (_region, t, _rho) = try_region_t_rho_ph_eqm_refined(p, h, h_tolerance)?;
+
+    return Ok(t);
+}
+
+/// obtains specific volume given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+pub fn try_v_ph_eqm_refined(p: Pressure,
+    h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> Result<SpecificVolume, SteamTableError> {
+    let (_region, _t, rho) = try_region_t_rho_ph_eqm_refined(p, h, h_tolerance)?;
+
+    return Ok(rho.recip());
+}
+
+/// obtains internal energy given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+///
+/// in region 4, this is the same as try_u_ph_eqm
+pub fn try_u_ph_eqm_refined(p: Pressure,
+    h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> Result<AvailableEnergy, SteamTableError> {
+    let (region, t, rho) = try_region_t_rho_ph_eqm_refined(p, h, h_tolerance)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return try_u_ph_eqm(p, h);
+    };
+
+    return Ok(u_trho_single_phase(region, t, p, rho));
+}
+
+/// obtains entropy given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+///
+/// in region 4, this is the same as try_s_ph_eqm
+pub fn try_s_ph_eqm_refined(p: Pressure,
+    h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let (region, t, rho) = try_region_t_rho_ph_eqm_refined(p, h, h_tolerance)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return try_s_ph_eqm(p, h);
+    };
+
+    return Ok(s_trho_single_phase(region, t, p, rho));
+}
+
+/// obtains cp given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+///
+/// in region 4, this is the same as try_cp_ph_eqm
+pub fn try_cp_ph_eqm_refined(p: Pressure,
+    h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let (region, t, rho) = try_region_t_rho_ph_eqm_refined(p, h, h_tolerance)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return try_cp_ph_eqm(p, h);
+    };
+
+    return Ok(cp_trho_single_phase(region, t, p, rho));
+}
+
+/// obtains cv given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+///
+/// in region 4, this is the same as try_cv_ph_eqm
+pub fn try_cv_ph_eqm_refined(p: Pressure,
+    h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let (region, t, rho) = try_region_t_rho_ph_eqm_refined(p, h, h_tolerance)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return try_cv_ph_eqm(p, h);
+    };
+
+    return Ok(cv_trho_single_phase(region, t, p, rho));
+}
+
+/// obtains speed of sound given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+///
+/// in region 4, this is the same as try_w_ph_eqm
+pub fn try_w_ph_eqm_refined(p: Pressure,
+    h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> Result<Velocity, SteamTableError> {
+    let (region, t, rho) = try_region_t_rho_ph_eqm_refined(p, h, h_tolerance)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return try_w_ph_eqm(p, h);
+    };
+
+    return Ok(w_trho_single_phase(region, t, p, rho));
+}
+
+/// obtains temperature given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+pub fn t_ph_eqm_refined(p: Pressure, h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> ThermodynamicTemperature {
+    match try_t_ph_eqm_refined(p, h, h_tolerance) {
+        Ok(t) => t,
+        Err(error) => panic!("refined p,h flash failed: {}", error),
+    }
+}
+
+/// obtains specific volume given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+pub fn v_ph_eqm_refined(p: Pressure, h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> SpecificVolume {
+    match try_v_ph_eqm_refined(p, h, h_tolerance) {
+        Ok(v) => v,
+        Err(error) => panic!("refined p,h flash failed: {}", error),
+    }
+}
+
+/// obtains internal energy given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+pub fn u_ph_eqm_refined(p: Pressure, h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> AvailableEnergy {
+    match try_u_ph_eqm_refined(p, h, h_tolerance) {
+        Ok(u) => u,
+        Err(error) => panic!("refined p,h flash failed: {}", error),
+    }
+}
+
+/// obtains entropy given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+pub fn s_ph_eqm_refined(p: Pressure, h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> SpecificHeatCapacity {
+    match try_s_ph_eqm_refined(p, h, h_tolerance) {
+        Ok(s) => s,
+        Err(error) => panic!("refined p,h flash failed: {}", error),
+    }
+}
+
+/// obtains cp given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+pub fn cp_ph_eqm_refined(p: Pressure, h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> SpecificHeatCapacity {
+    match try_cp_ph_eqm_refined(p, h, h_tolerance) {
+        Ok(cp) => cp,
+        Err(error) => panic!("refined p,h flash failed: {}", error),
+    }
+}
+
+/// obtains cv given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+pub fn cv_ph_eqm_refined(p: Pressure, h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> SpecificHeatCapacity {
+    match try_cv_ph_eqm_refined(p, h, h_tolerance) {
+        Ok(cv) => cv,
+        Err(error) => panic!("refined p,h flash failed: {}", error),
+    }
+}
+
+/// obtains speed of sound given pressure and enthalpy,
+/// refined using the forward equations until the
+/// enthalpy residual is below h_tolerance
+pub fn w_ph_eqm_refined(p: Pressure, h: AvailableEnergy,
+    h_tolerance: AvailableEnergy) -> Velocity {
+    match try_w_ph_eqm_refined(p, h, h_tolerance) {
+        Ok(w) => w,
+        Err(error) => panic!("refined p,h flash failed: {}", error),
+    }
+}
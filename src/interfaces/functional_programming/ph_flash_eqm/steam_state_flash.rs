@@ -0,0 +1,88 @@
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::out_of_range_policy::OutOfRangePolicy;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+
+use super::*;
+
+/// (p,h) flash returning all intensive properties at once
+///
+/// the region and backward equations are only evaluated once,
+/// unlike calling t_ph_eqm, v_ph_eqm, cp_ph_eqm, lambda_ph_eqm etc.
+/// one by one
+///
+/// the pressure and enthalpy of the returned state are the
+/// input pressure and enthalpy, rather than the forward equations
+/// evaluated at the flashed state (the two differ by the
+/// consistency of the backward equations)
+///
+/// points outside the validity range return the violated bound,
+/// this is the same as try_ph_flash_with_policy with
+/// OutOfRangePolicy::Error
+pub fn try_flash(p: Pressure,
+    h: AvailableEnergy) -> Result<SteamState, SteamTableError> {
+    let flashed = try_ph_flash_with_policy(p, h, OutOfRangePolicy::Error)?;
+
+    return Ok(flashed.state);
+}
+
+/// (p,h) flash for a point within the validity range
+/// (or rather, a point which the policy has already dealt with)
+pub(crate) fn try_flash_within_range(p: Pressure,
+    h: AvailableEnergy) -> Result<SteamState, SteamTableError> {
+
+    let region = try_ph_flash_region(p, h)?;
+
+    let mut state = match region {
+        FwdEqnRegion::Region1 => {
+            let t = t_ph_1(p, h);
+            SteamState::new_single_phase(region, t, p, v_tp_1(t, p).recip(), 0.0)
+        },
+        FwdEqnRegion::Region2 => {
+            let t = t_ph_2(p, h);
+            SteamState::new_single_phase(region, t, p, v_tp_2(t, p).recip(), 1.0)
+        },
+        FwdEqnRegion::Region3 => {
+            // same steam quality as x_ph_flash,
+            // at or above crit pressure, we just consider it vapour
+            let x = if p >= Pressure::new::<megapascal>(22.064) {
+                1.0
+            } else if h < h_3a3b_backwards_ph_boundary(p) {
+                0.0
+            } else {
+                1.0
+            };
+            SteamState::new_single_phase(region, t_ph_3(p, h), p,
+                v_ph_3(p, h).recip(), x)
+        },
+        FwdEqnRegion::Region4 => {
+            // x = (h-hliq)/(hvap - hliq)
+            let t_sat = sat_temp_4(p);
+            let (liquid, vapour) = SteamState::new_saturated_liquid_and_vapour(t_sat);
+            let x: f64 = ((h - liquid.h)/(vapour.h - liquid.h)).get::<ratio>();
+
+            SteamState::new_two_phase(&liquid, &vapour, x)
+        },
+        FwdEqnRegion::Region5 => {
+            let t = try_t_ph_5(p, h)?;
+            SteamState::new_single_phase(region, t, p, v_tp_5(t, p).recip(), 1.0)
+        },
+    };
+
+    state.p = p;
+    state.h = h;
+
+    return Ok(state);
+}
+
+/// (p,h) flash returning all intensive properties at once,
+/// panics if the (p,h) point is outside the validity range
+pub fn flash(p: Pressure,
+    h: AvailableEnergy) -> SteamState {
+    match try_flash(p, h) {
+        Ok(state) => state,
+        Err(error) => panic!("p,h flash failed: {}", error),
+    }
+}
@@ -0,0 +1,122 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::region_1_subcooled_liquid::InversePressure;
+
+use super::*;
+
+/// allows the user to check which region one is in based on a ph flash
+/// returns an error rather than panicking if the (p,h) point 
+/// is outside the validity range
+pub fn try_ph_flash_region(p: Pressure, 
+    h: AvailableEnergy) -> Result<FwdEqnRegion, SteamTableError> {
+
+    try_check_if_within_ph_validity_region(p, h)?;
+
+    return Ok(ph_flash_region(p, h));
+}
+
+// region 5 has no backward equation in terms of (p,h), 
+// so the temperature is found by newton raphson iteration, 
+// which may not converge
+fn try_converged_ph_flash_region(p: Pressure, 
+    h: AvailableEnergy) -> Result<FwdEqnRegion, SteamTableError> {
+    let region = try_ph_flash_region(p, h)?;
+
+    if region == FwdEqnRegion::Region5 {
+        try_t_ph_5(p, h)?;
+    };
+
+    return Ok(region);
+}
+
+/// obtains temperature given pressure and enthalpy
+pub fn try_t_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<ThermodynamicTemperature, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(t_ph_eqm(p, h));
+}
+
+/// obtains volume given pressure and enthalpy
+pub fn try_v_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<SpecificVolume, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(v_ph_eqm(p, h));
+}
+
+/// obtains internal energy given pressure and enthalpy
+pub fn try_u_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<AvailableEnergy, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(u_ph_eqm(p, h));
+}
+
+/// obtains entropy given pressure and enthalpy
+pub fn try_s_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<SpecificHeatCapacity, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(s_ph_eqm(p, h));
+}
+
+/// obtains cp given pressure and enthalpy
+pub fn try_cp_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<SpecificHeatCapacity, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(cp_ph_eqm(p, h));
+}
+
+/// obtains cv given pressure and enthalpy
+pub fn try_cv_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<SpecificHeatCapacity, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(cv_ph_eqm(p, h));
+}
+
+/// obtains speed of sound given pressure and enthalpy
+pub fn try_w_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<Velocity, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(w_ph_eqm(p, h));
+}
+
+/// obtains isentropic exponent given pressure and enthalpy
+pub fn try_kappa_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<Ratio, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(kappa_ph_eqm(p, h));
+}
+
+/// obtains isobaric cubic expansion coefficient given pressure and enthalpy
+pub fn try_alpha_v_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<TemperatureCoefficient, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(alpha_v_ph_eqm(p, h));
+}
+
+/// obtains isothermal compressibility given pressure and enthalpy
+pub fn try_kappa_t_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<InversePressure, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(kappa_t_ph_eqm(p, h));
+}
+
+/// obtains steam quality given pressure and enthalpy
+pub fn try_x_ph_flash(p: Pressure, 
+    h: AvailableEnergy) -> Result<f64, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(x_ph_flash(p, h));
+}
+
+/// obtains dynamic viscosity given pressure and enthalpy
+pub fn try_mu_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<DynamicViscosity, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(mu_ph_eqm(p, h));
+}
+
+/// obtains thermal conductivity given pressure and enthalpy
+pub fn try_lambda_ph_eqm(p: Pressure, 
+    h: AvailableEnergy) -> Result<ThermalConductivity, SteamTableError> {
+    try_converged_ph_flash_region(p, h)?;
+    return Ok(lambda_ph_eqm(p, h));
+}
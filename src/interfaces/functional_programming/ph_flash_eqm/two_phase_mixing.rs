@@ -0,0 +1,40 @@
+use uom::si::f64::*;
+
+use crate::interfaces::functional_programming::saturation_eqm::SaturationState;
+use crate::interfaces::functional_programming::two_phase_mixing_model::TwoPhaseMixingModel;
+
+use super::*;
+
+/// returns mu (dynamic viscosity) given pressure and enthalpy,
+/// using the mixing model in region 4
+///
+/// outside region 4, this is the same as mu_ph_eqm
+pub fn mu_ph_eqm_with_mixing_model(p: Pressure,
+    h: AvailableEnergy,
+    model: TwoPhaseMixingModel) -> DynamicViscosity {
+
+    match ph_flash_region(p, h) {
+        FwdEqnRegion::Region4 => {
+            let saturation_state = SaturationState::new_from_p(p);
+            model.mu_two_phase(&saturation_state, x_ph_flash(p, h))
+        },
+        _ => mu_ph_eqm(p, h),
+    }
+}
+
+/// returns lambda (thermal conductivity) given pressure and enthalpy,
+/// using the mixing model in region 4
+///
+/// outside region 4, this is the same as lambda_ph_eqm
+pub fn lambda_ph_eqm_with_mixing_model(p: Pressure,
+    h: AvailableEnergy,
+    model: TwoPhaseMixingModel) -> ThermalConductivity {
+
+    match ph_flash_region(p, h) {
+        FwdEqnRegion::Region4 => {
+            let saturation_state = SaturationState::new_from_p(p);
+            model.lambda_two_phase(&saturation_state, x_ph_flash(p, h))
+        },
+        _ => lambda_ph_eqm(p, h),
+    }
+}
@@ -1,14 +1,21 @@
 use uom::si::{f64::*, pressure::megapascal, thermodynamic_temperature::kelvin};
 
 use crate::{interfaces::functional_programming::pt_flash_eqm::h_tp_eqm_single_phase, region_4_vap_liq_equilibrium::sat_pressure_4};
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::out_of_range_policy::{try_apply_out_of_range_policy, OutOfRangePolicy, PolicyPoint};
+use crate::interfaces::functional_programming::validity_domain::validity_domain_ph;
 
-// checks if pressure is 
+// checks if the (p,h) point is 
 // lower than saturation pressure at 273.15K or higher than 100 MPa
+// or if it lies outside the 273.15K and 1073.15K isotherms
+// (2273.15K isotherm for pressures up to 50 MPa, ie region 5)
 //
-// if so, it falls outside the ph boundary
-pub(crate) fn is_outside_pressure_range(p: Pressure) -> bool {
+// if so, it falls outside the ph boundary and we return 
+// the bound which the (p,h) point violated
+// see page 38 top left
+pub(crate) fn try_check_if_within_ph_validity_region(
+    p: Pressure, h: AvailableEnergy) -> Result<(), SteamTableError> {
 
-    // first determine if p,h point is outside pressure range
     let lower_pressure_limit: Pressure = 
         sat_pressure_4(
             ThermodynamicTemperature::new::<kelvin>(273.15)
@@ -17,58 +24,50 @@ pub(crate) fn is_outside_pressure_range(p: Pressure) -> bool {
     let upper_pressure_limit: Pressure = Pressure::new::<megapascal>(100.0);
 
     if p < lower_pressure_limit {
-        return true;
+        return Err(SteamTableError::PressureBelowBound { 
+            p, bound: lower_pressure_limit });
     };
 
     if p > upper_pressure_limit {
-        return true;
+        return Err(SteamTableError::PressureAboveBound { 
+            p, bound: upper_pressure_limit });
     };
 
-    return false;
-
-}
-
-// making a function to check if a p,h value is below the isotherm at 
-// 273.15K
-//
-pub(crate) fn is_below_isotherm_t_273_15(p: Pressure, h: AvailableEnergy) -> bool{
-
-    // first check if outside pressure range 
-    if is_outside_pressure_range(p) {
-        panic!("outside pressure range");
-    };
-
-    // let's have the lower enthalpy range 
-    let lower_temp_bound = ThermodynamicTemperature::new::<kelvin>(273.15);
-
-    let lower_bound_enthalpy = h_tp_eqm_single_phase(lower_temp_bound, p);
+    let lower_bound_enthalpy = h_tp_eqm_single_phase(
+        ThermodynamicTemperature::new::<kelvin>(273.15), p);
 
     if h < lower_bound_enthalpy {
-        return true;
+        return Err(SteamTableError::EnthalpyBelowBound { 
+            h, bound: lower_bound_enthalpy });
     };
 
-    return false;
-
-}
-
-// making a function to check if p,h value is above the isotherm T = 1073.15K
-pub(crate) fn is_above_isotherm_t_1073_15(p: Pressure,h: AvailableEnergy) -> bool {
-    // first check if outside pressure range 
-    if is_outside_pressure_range(p) {
-        panic!("outside pressure range");
+    // region 5 extends the upper temperature to 2273.15 K 
+    // but only up to 50 MPa
+    let upper_temp_bound = if p <= Pressure::new::<megapascal>(50.0) {
+        ThermodynamicTemperature::new::<kelvin>(2273.15)
+    } else {
+        ThermodynamicTemperature::new::<kelvin>(1073.15)
     };
 
-    let upper_temp_bound = ThermodynamicTemperature::new::<kelvin>(1073.15);
-
     let upper_bound_enthalpy = h_tp_eqm_single_phase(upper_temp_bound, p);
 
     if h > upper_bound_enthalpy {
-        return true;
+        return Err(SteamTableError::EnthalpyAboveBound { 
+            h, bound: upper_bound_enthalpy });
     };
 
-    return false;
-
+    return Ok(());
 }
 
-
-
+/// checks if the (p,h) point is within the validity range,
+/// applying the out of range policy if it is not
+///
+/// returns the (p,h) point to flash (moved onto the nearest valid
+/// point for ClampToBoundary), the nearest valid point
+/// and what the policy did
+pub(crate) fn try_check_if_within_ph_validity_region_with_policy(
+    p: Pressure, h: AvailableEnergy,
+    policy: OutOfRangePolicy) -> Result<PolicyPoint<Pressure, AvailableEnergy>, SteamTableError> {
+
+    return try_apply_out_of_range_policy(validity_domain_ph(p, h), policy);
+}
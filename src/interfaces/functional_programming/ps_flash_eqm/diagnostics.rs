@@ -0,0 +1,55 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::flash_diagnostics::*;
+use crate::region_5_steam_at_800_plus_degc::try_t_ps_5_with_iterations;
+
+use super::*;
+
+/// (p,s) flash which also reports the region and backward 
+/// equation subregion used, the number of iterations and 
+/// the entropy residual of the forward equations at the 
+/// flashed state
+///
+/// only region 5 is iterative, since it has no backward 
+/// equation in terms of (p,s)
+pub fn try_ps_flash_with_diagnostics(p: Pressure,
+    s: SpecificHeatCapacity) -> Result<FlashReport, SteamTableError> {
+
+    let region = try_ps_flash_region(p, s)?;
+    let subregion = ps_backward_subregion(p, s, region);
+
+    let iterations = match region {
+        FwdEqnRegion::Region5 => try_t_ps_5_with_iterations(p, s)?.1,
+        _ => 0,
+    };
+
+    let t = try_t_ps_eqm(p, s)?;
+    let v = try_v_ps_eqm(p, s)?;
+    let x = try_x_ps_flash(p, s)?;
+
+    let residuals = try_forward_equation_residuals(region, t, p, v,
+        None, Some(s), None)?;
+
+    return Ok(FlashReport {
+        t,
+        p,
+        v,
+        x,
+        region,
+        subregion: Some(subregion),
+        iterations,
+        residuals,
+        boundary_correction_applied: false,
+    });
+}
+
+/// (p,s) flash which also reports how the result was obtained,
+/// panics if the (p,s) point is outside the validity range
+pub fn ps_flash_with_diagnostics(p: Pressure,
+    s: SpecificHeatCapacity) -> FlashReport {
+    match try_ps_flash_with_diagnostics(p, s) {
+        Ok(report) => report,
+        Err(error) => panic!("p,s flash failed: {}", error),
+    }
+}
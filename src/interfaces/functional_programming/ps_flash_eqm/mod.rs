@@ -11,11 +11,44 @@ pub(crate) use boundaries_from_single_phase_regions_to_region_4_multiphase::*;
 /// this checks for boundary in between single phase regions (1 to 3) 
 pub(crate) mod boundaries_between_single_phase_regions;
 pub(crate) use boundaries_between_single_phase_regions::*;
+
+/// fallible versions of the (p,s) flash functions 
+/// which return a SteamTableError rather than panicking
+pub mod try_flash;
+pub use try_flash::*;
+
+/// (p,s) flash with newton refinement on the forward equations,
+/// so that the flashed state returns the input entropy
+/// to within a user supplied tolerance
+pub mod refined;
+pub use refined::*;
+
+/// (p,s) flash with a configurable out of range policy
+/// (error, clamp to boundary or extrapolate)
+pub mod out_of_range;
+pub use out_of_range::*;
+
+/// (p,s) flash which also reports the region, subregion,
+/// iterations and residuals (see flash_diagnostics)
+pub mod diagnostics;
+pub use diagnostics::*;
+
+/// (p,s) flash returning all intensive properties at once
+/// (see steam_state)
+pub mod steam_state_flash;
+pub use steam_state_flash::*;
+
+/// (p,s) viscosity and thermal conductivity with a
+/// selectable two phase mixing model in region 4
+/// (see two_phase_mixing_model)
+pub mod two_phase_mixing;
+pub use two_phase_mixing::*;
 use uom::si::{f64::*, pressure::megapascal, ratio::ratio, thermodynamic_temperature::kelvin};
 
-use crate::{region_1_subcooled_liquid::{alpha_v_tp_1, cp_tp_1, cv_tp_1, h_tp_1, kappa_t_tp_1, kappa_tp_1, s_tp_1, t_ps_1, u_tp_1, v_tp_1, w_tp_1, InversePressure}, region_2_vapour::{alpha_v_tp_2, cp_tp_2, cv_tp_2, h_tp_2, kappa_t_tp_2, kappa_tp_2, s_tp_2, t_ps_2, u_tp_2, v_tp_2, w_tp_2}, region_3_single_phase_plus_supercritical_steam::{alpha_v_rho_t_3, cp_rho_t_3, cv_tp_3, h_rho_t_3, kappa_t_tp_3, kappa_tp_3, s_3a3b_backwards_ps_boundary, s_rho_t_3, t_ps_3, u_rho_t_3, v_ps_3, v_tp_3c, v_tp_3r, v_tp_3s, v_tp_3t, v_tp_3u, v_tp_3x, v_tp_3y, v_tp_3z, w_rho_t_3}, region_4_vap_liq_equilibrium::{sat_pressure_4, sat_temp_4}, region_5_steam_at_800_plus_degc::{alpha_v_tp_5, cp_tp_5, cv_tp_5, h_tp_5, kappa_t_tp_5, kappa_tp_5, u_tp_5, w_tp_5}};
+use crate::{region_1_subcooled_liquid::{alpha_v_tp_1, cp_tp_1, cv_tp_1, h_tp_1, kappa_t_tp_1, kappa_tp_1, s_tp_1, t_ps_1, u_tp_1, v_tp_1, w_tp_1, InversePressure}, region_2_vapour::{alpha_v_tp_2, cp_tp_2, cv_tp_2, h_tp_2, kappa_t_tp_2, kappa_tp_2, s_tp_2, t_ps_2, u_tp_2, v_tp_2, w_tp_2}, region_3_single_phase_plus_supercritical_steam::{alpha_v_rho_t_3, cp_rho_t_3, cv_tp_3, h_rho_t_3, kappa_t_tp_3, kappa_tp_3, s_3a3b_backwards_ps_boundary, s_rho_t_3, t_ps_3, u_rho_t_3, v_ps_3, v_tp_3c, v_tp_3r, v_tp_3s, v_tp_3t, v_tp_3u, v_tp_3x, v_tp_3y, v_tp_3z, w_rho_t_3}, region_4_vap_liq_equilibrium::{sat_pressure_4, sat_temp_4}, region_5_steam_at_800_plus_degc::{alpha_v_tp_5, cp_tp_5, cv_tp_5, h_tp_5, kappa_t_tp_5, kappa_tp_5, s_tp_5, t_ps_5, try_t_ps_5, u_tp_5, v_tp_5, w_tp_5}};
 
 use super::pt_flash_eqm::FwdEqnRegion;
+use super::two_phase_equilibrium_derivatives::TwoPhaseEquilibriumDerivatives;
 /// obtains temperature given pressure and entropy
 pub fn t_ps_eqm(p: Pressure, s: SpecificHeatCapacity,) -> ThermodynamicTemperature {
     let region = ps_flash_region(p, s);
@@ -29,7 +62,7 @@ pub fn t_ps_eqm(p: Pressure, s: SpecificHeatCapacity,) -> ThermodynamicTemperatu
             // determine sat liq/vap temperature 
             sat_temp_4(p)
         },
-        FwdEqnRegion::Region5 => todo!("region 5 ps flash not implemented"),
+        FwdEqnRegion::Region5 => t_ps_5(p, s),
     }
 }
 
@@ -122,7 +155,10 @@ pub fn v_ps_eqm(p: Pressure, s: SpecificHeatCapacity) -> SpecificVolume {
 
             }
         },
-        FwdEqnRegion::Region5 => todo!("ps flash not implemented for region 5"),
+        FwdEqnRegion::Region5 => {
+            let t = t_ps_5(p, s);
+            v_tp_5(t, p)
+        },
     }
 }
 /// obtains steam quality (vap fraction) given 
@@ -440,11 +476,27 @@ pub fn h_ps_eqm(p: Pressure, s: SpecificHeatCapacity) -> AvailableEnergy {
 
 // allows the user to check which region one is in based on a ps flash
 //
-// note that ps flash does not work in region 5
+// region 5 is from 1073.15 K to 2273.15 K, up to 50 MPa
 pub fn ps_flash_region(p: Pressure, s: SpecificHeatCapacity) -> FwdEqnRegion {
 
     check_if_within_ps_validity_region(p, s);
 
+    // region 5 check, anything on or above the 1073.15 K isotherm 
+    // (up to 50 MPa) is region 5 
+    //
+    // the isotherm itself belongs to region 5, same as in the 
+    // (T,p) flash (see region_fwd_eqn_single_phase), so we use 
+    // the region 5 entropy on the isotherm as the boundary
+    let p_max_region_5 = Pressure::new::<megapascal>(50.0);
+    if p <= p_max_region_5 {
+        let s_region_2_5_boundary = s_tp_5(
+            ThermodynamicTemperature::new::<kelvin>(1073.15), p);
+
+        if s >= s_region_2_5_boundary {
+            return FwdEqnRegion::Region5;
+        };
+    };
+
     // if inside validity range, then we will start partitioning
     // first, we check if pressure is smaller or greater than 16.529 MPa
     // this is saturation pressure at 623.15K 
@@ -533,6 +585,9 @@ pub fn ps_flash_region(p: Pressure, s: SpecificHeatCapacity) -> FwdEqnRegion {
 
 
 /// returns the isobaric (const pressure) heat capacitygiven temperature and pressure
+///
+/// in region 4, cp is infinite (see two_phase_equilibrium_derivatives),
+/// except within SATURATED_PHASE_QUALITY_TOLERANCE of the saturation lines
 pub fn cp_ps_eqm(p: Pressure, s: SpecificHeatCapacity) -> SpecificHeatCapacity {
     let t = t_ps_eqm(p, s);
     let region = ps_flash_region(p, s);
@@ -555,17 +610,12 @@ pub fn cp_ps_eqm(p: Pressure, s: SpecificHeatCapacity) -> SpecificHeatCapacity {
             cp_rho_t_3(rho, t)
         },
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ps_flash(p, s);
             let t_sat = sat_temp_4(p);
 
-            let cp_liq = cp_tp_1(t_sat, p);
-            let cp_vap = cp_tp_2(t_sat, p);
-
-            let cp = steam_quality * cp_vap + (1.0 - steam_quality) * cp_liq;
-
-            cp
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).cp
         },
         FwdEqnRegion::Region5 => cp_tp_5(t, p),
     }
@@ -582,17 +632,12 @@ pub fn cv_ps_eqm(p: Pressure, s: SpecificHeatCapacity) -> SpecificHeatCapacity {
         FwdEqnRegion::Region2 => cv_tp_2(t, p),
         FwdEqnRegion::Region3 => cv_tp_3(t, p),
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ps_flash(p, s);
             let t_sat = sat_temp_4(p);
 
-            let cv_liq = cv_tp_1(t_sat, p);
-            let cv_vap = cv_tp_2(t_sat, p);
-
-            let cv = steam_quality * cv_vap + (1.0 - steam_quality) * cv_liq;
-
-            cv
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).cv
         },
         FwdEqnRegion::Region5 => cv_tp_5(t, p),
     }
@@ -623,17 +668,12 @@ pub fn w_ps_eqm(p: Pressure, s: SpecificHeatCapacity) -> Velocity {
             w_rho_t_3(rho, t)
         },
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ps_flash(p, s);
             let t_sat = sat_temp_4(p);
 
-            let w_liq = w_tp_1(t_sat, p);
-            let w_vap = w_tp_2(t_sat, p);
-
-            let w = steam_quality * w_vap + (1.0 - steam_quality) * w_liq;
-
-            w
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).w
         },
         FwdEqnRegion::Region5 => w_tp_5(t, p),
     }
@@ -650,17 +690,12 @@ pub fn kappa_ps_eqm(p: Pressure, s: SpecificHeatCapacity) -> Ratio {
         FwdEqnRegion::Region2 => kappa_tp_2(t, p),
         FwdEqnRegion::Region3 => kappa_tp_3(t, p),
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ps_flash(p, s);
             let t_sat = sat_temp_4(p);
 
-            let kappa_liq = kappa_tp_1(t_sat, p);
-            let kappa_vap = kappa_tp_2(t_sat, p);
-
-            let kappa = steam_quality * kappa_vap + (1.0 - steam_quality) * kappa_liq;
-
-            kappa
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).kappa
         },
         FwdEqnRegion::Region5 => kappa_tp_5(t, p),
     }
@@ -688,17 +723,12 @@ pub fn alpsa_v_ps_eqm(p: Pressure, s: SpecificHeatCapacity) -> TemperatureCoeffi
             alpha_v_rho_t_3(rho, t)
         },
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ps_flash(p, s);
             let t_sat = sat_temp_4(p);
 
-            let alpha_v_liq = alpha_v_tp_1(t_sat, p);
-            let alpha_v_vap = alpha_v_tp_2(t_sat, p);
-
-            let alpha_v = steam_quality * alpha_v_vap + (1.0 - steam_quality) * alpha_v_liq;
-
-            alpha_v
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).alpha_v
         },
         FwdEqnRegion::Region5 => alpha_v_tp_5(t, p),
     }
@@ -715,33 +745,19 @@ pub fn kappa_t_ps_eqm(p: Pressure, s: SpecificHeatCapacity) -> InversePressure {
         FwdEqnRegion::Region2 => kappa_t_tp_2(t, p),
         FwdEqnRegion::Region3 => kappa_t_tp_3(t, p),
         FwdEqnRegion::Region4 => {
-            // I'm just using quality to interpolate here 
-            // not sure if 100% correct
+            // homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
             let steam_quality = x_ps_flash(p, s);
             let t_sat = sat_temp_4(p);
 
-            let kappa_t_liq = kappa_t_tp_1(t_sat, p);
-            let kappa_t_vap = kappa_t_tp_2(t_sat, p);
-
-            let kappa_t = steam_quality * kappa_t_vap + (1.0 - steam_quality) * kappa_t_liq;
-
-            kappa_t
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t_sat, steam_quality).kappa_t
         },
         FwdEqnRegion::Region5 => kappa_t_tp_5(t, p),
     }
 }
 
 fn check_if_within_ps_validity_region(p: Pressure, s: SpecificHeatCapacity,){
-    if is_outside_pressure_range(p) {
-        panic!("p,s point is outside pressure range");
-    };
-
-    if is_below_isotherm_t_273_15(p, s) {
-        dbg!(&(p,s));
-        panic!("p,s point below 273.15K");
-    };
-    if is_above_isotherm_t_1073_15(p, s) {
-        dbg!(&(p,s));
-        panic!("p,s point above 1073.15K");
+    if let Err(error) = try_check_if_within_ps_validity_region(p, s) {
+        panic!("p,s point is outside validity range: {}", error);
     };
 }
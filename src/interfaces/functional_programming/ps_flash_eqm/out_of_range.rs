@@ -0,0 +1,61 @@
+use uom::si::f64::*;
+use uom::si::specific_heat_capacity::joule_per_kilogram_kelvin;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::newton_refinement::RefinementEquations;
+use crate::interfaces::functional_programming::out_of_range_policy::*;
+
+use super::*;
+
+/// (p,s) flash which applies an out of range policy
+/// if the (p,s) point is outside the validity range
+/// (see try_check_if_within_ps_validity_region_with_policy)
+///
+/// for ClampToBoundary, the point is moved onto the nearest
+/// valid point before flashing,
+/// for ExtrapolateForwardEquation, the forward equations of the
+/// region of the nearest valid point are used at the input point,
+/// with the temperature (and in region 3, density) refined so that
+/// the forward equations return the input entropy
+///
+/// what was done is reported in PolicyFlashState::report
+pub fn try_ps_flash_with_policy(p: Pressure,
+    s: SpecificHeatCapacity,
+    policy: OutOfRangePolicy) -> Result<PolicyFlashState, SteamTableError> {
+
+    let checked = try_check_if_within_ps_validity_region_with_policy(p, s, policy)?;
+    let (p, s) = checked.point;
+    let report = checked.report;
+
+    if !report.was_extrapolated() {
+        return Ok(PolicyFlashState { state: try_flash_within_range(p, s)?, report });
+    };
+
+    let (p_valid, s_valid) = checked.nearest_valid_point;
+    let valid_state = try_flash_within_range(p_valid, s_valid)?;
+    let equations = RefinementEquations {
+        flash: "extrapolated (p,s)",
+        ..PS_REFINEMENT_EQUATIONS
+    };
+
+    let mut state = try_extrapolate_p_steam_state(p,
+        s.get::<joule_per_kilogram_kelvin>(),
+        EXTRAPOLATION_S_TOLERANCE_JOULE_PER_KG_K,
+        &valid_state,
+        &equations)?;
+    state.s = s;
+
+    return Ok(PolicyFlashState { state, report });
+}
+
+/// (p,s) flash which applies an out of range policy,
+/// panics if the policy is Error and the point is outside the
+/// validity range
+pub fn ps_flash_with_policy(p: Pressure,
+    s: SpecificHeatCapacity,
+    policy: OutOfRangePolicy) -> PolicyFlashState {
+    match try_ps_flash_with_policy(p, s, policy) {
+        Ok(state) => state,
+        Err(error) => panic!("p,s flash failed: {}", error),
+    }
+}
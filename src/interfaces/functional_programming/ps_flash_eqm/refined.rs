@@ -0,0 +1,260 @@
+use uom::si::specific_heat_capacity::joule_per_kilogram_kelvin;
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::newton_refinement::{try_refine_t_rho, RefinementEquations};
+use crate::interfaces::functional_programming::trho_flash_eqm::{cp_trho_single_phase, cv_trho_single_phase, h_trho_single_phase, u_trho_single_phase, w_trho_single_phase};
+
+use super::*;
+
+/// (p,s) flash with newton refinement
+///
+/// the backward equations T(p,s) and v(p,s) are not exactly
+/// consistent with the forward equations, so s(T(p,s),p) does not
+/// exactly return the input entropy.
+///
+/// Here, we use the backward equations as a starting guess,
+/// and iterate on the forward equations until the entropy residual
+/// is below s_tolerance
+///
+/// regions 1, 2 and 5: newton raphson on T, using cp/T = (ds/dT)_p
+/// region 3: newton raphson on (v,T) so that p(v,T) = p and s(v,T) = s
+/// region 4: T is the saturation temperature, nothing to refine
+///
+/// returns region, temperature and density
+pub(crate) fn try_region_t_rho_ps_eqm_refined(p: Pressure,
+    s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> Result<(FwdEqnRegion, ThermodynamicTemperature, MassDensity), SteamTableError> {
+
+    let region = try_ps_flash_region(p, s)?;
+    let (t, rho) = try_refine_t_rho(p, region,
+        s.get::<joule_per_kilogram_kelvin>(),
+        s_tolerance.get::<joule_per_kilogram_kelvin>(),
+        &PS_REFINEMENT_EQUATIONS)?;
+
+    return Ok((region, t, rho));
+}
+
+/// entropy equations for the refined (p,s) flash
+///
+/// (ds/dT)_v = cv/T
+/// (ds/dv)_T = alpha_v/kappa_t
+pub(crate) const PS_REFINEMENT_EQUATIONS: RefinementEquations = RefinementEquations {
+    flash: "refined (p,s)",
+    t_backward: t_ps_backward,
+    v_backward_region_3: |p, s| v_ps_3(p, SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(s)),
+    v_region_4: |p, s| v_ps_eqm(p, SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(s)),
+    property_tp: s_cp_over_t_tp,
+    property_rho_t_3: |rho, t| s_rho_t_3(rho, t).get::<joule_per_kilogram_kelvin>(),
+    derivatives_region_3: |_rho, t, cv, alpha_v, kappa_t| (cv/t, alpha_v/kappa_t),
+};
+
+/// backward equation T(p,s) for the single phase regions
+fn t_ps_backward(region: FwdEqnRegion,
+    p: Pressure,
+    s: f64) -> Result<ThermodynamicTemperature, SteamTableError> {
+    let s = SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(s);
+
+    match region {
+        FwdEqnRegion::Region1 => Ok(t_ps_1(p, s)),
+        FwdEqnRegion::Region2 => Ok(t_ps_2(p, s)),
+        FwdEqnRegion::Region3 => Ok(t_ps_3(p, s)),
+        FwdEqnRegion::Region4 => Ok(sat_temp_4(p)),
+        FwdEqnRegion::Region5 => try_t_ps_5(p, s),
+    }
+}
+
+/// entropy and cp/T = (ds/dT)_p in regions 1, 2 and 5
+fn s_cp_over_t_tp(region: FwdEqnRegion,
+    t: ThermodynamicTemperature,
+    p: Pressure) -> (f64, f64) {
+    match region {
+        FwdEqnRegion::Region1 => (s_tp_1(t, p).get::<joule_per_kilogram_kelvin>(), cp_tp_1(t, p).value/t.value),
+        FwdEqnRegion::Region2 => (s_tp_2(t, p).get::<joule_per_kilogram_kelvin>(), cp_tp_2(t, p).value/t.value),
+        _ => (s_tp_5(t, p).get::<joule_per_kilogram_kelvin>(), cp_tp_5(t, p).value/t.value),
+    }
+}
+
+/// obtains temperature given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+pub fn try_t_ps_eqm_refined(p: Pressure,
+    s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> Result<ThermodynamicTemperature, SteamTableError> {
+    let (_region, t, _rho) = try_region_t_rho_ps_eqm_refined(p, s, s_tolerance)?;
+
+    return Ok(t);
+}
+
+/// obtains specific volume given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+pub fn try_v_ps_eqm_refined(p: Pressure,
+    s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> Result<SpecificVolume, SteamTableError> {
+    let (_region, _t, rho) = try_region_t_rho_ps_eqm_refined(p, s, s_tolerance)?;
+
+    return Ok(rho.recip());
+}
+
+/// obtains internal energy given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+///
+/// in region 4, this is the same as try_u_ps_eqm
+pub fn try_u_ps_eqm_refined(p: Pressure,
+    s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> Result<AvailableEnergy, SteamTableError> {
+    let (region, t, rho) = try_region_t_rho_ps_eqm_refined(p, s, s_tolerance)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return try_u_ps_eqm(p, s);
+    };
+
+    return Ok(u_trho_single_phase(region, t, p, rho));
+}
+
+/// obtains enthalpy given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+///
+/// in region 4, this is the same as try_h_ps_eqm
+pub fn try_h_ps_eqm_refined(p: Pressure,
+    s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> Result<AvailableEnergy, SteamTableError> {
+    let (region, t, rho) = try_region_t_rho_ps_eqm_refined(p, s, s_tolerance)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return try_h_ps_eqm(p, s);
+    };
+
+    return Ok(h_trho_single_phase(region, t, p, rho));
+}
+
+/// obtains cp given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+///
+/// in region 4, this is the same as try_cp_ps_eqm
+pub fn try_cp_ps_eqm_refined(p: Pressure,
+    s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let (region, t, rho) = try_region_t_rho_ps_eqm_refined(p, s, s_tolerance)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return try_cp_ps_eqm(p, s);
+    };
+
+    return Ok(cp_trho_single_phase(region, t, p, rho));
+}
+
+/// obtains cv given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+///
+/// in region 4, this is the same as try_cv_ps_eqm
+pub fn try_cv_ps_eqm_refined(p: Pressure,
+    s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let (region, t, rho) = try_region_t_rho_ps_eqm_refined(p, s, s_tolerance)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return try_cv_ps_eqm(p, s);
+    };
+
+    return Ok(cv_trho_single_phase(region, t, p, rho));
+}
+
+/// obtains speed of sound given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+///
+/// in region 4, this is the same as try_w_ps_eqm
+pub fn try_w_ps_eqm_refined(p: Pressure,
+    s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> Result<Velocity, SteamTableError> {
+    let (region, t, rho) = try_region_t_rho_ps_eqm_refined(p, s, s_tolerance)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return try_w_ps_eqm(p, s);
+    };
+
+    return Ok(w_trho_single_phase(region, t, p, rho));
+}
+
+/// obtains temperature given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+pub fn t_ps_eqm_refined(p: Pressure, s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> ThermodynamicTemperature {
+    match try_t_ps_eqm_refined(p, s, s_tolerance) {
+        Ok(t) => t,
+        Err(error) => panic!("refined p,s flash failed: {}", error),
+    }
+}
+
+/// obtains specific volume given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+pub fn v_ps_eqm_refined(p: Pressure, s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> SpecificVolume {
+    match try_v_ps_eqm_refined(p, s, s_tolerance) {
+        Ok(v) => v,
+        Err(error) => panic!("refined p,s flash failed: {}", error),
+    }
+}
+
+/// obtains internal energy given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+pub fn u_ps_eqm_refined(p: Pressure, s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> AvailableEnergy {
+    match try_u_ps_eqm_refined(p, s, s_tolerance) {
+        Ok(u) => u,
+        Err(error) => panic!("refined p,s flash failed: {}", error),
+    }
+}
+
+/// obtains enthalpy given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+pub fn h_ps_eqm_refined(p: Pressure, s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> AvailableEnergy {
+    match try_h_ps_eqm_refined(p, s, s_tolerance) {
+        Ok(h) => h,
+        Err(error) => panic!("refined p,s flash failed: {}", error),
+    }
+}
+
+/// obtains cp given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+pub fn cp_ps_eqm_refined(p: Pressure, s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> SpecificHeatCapacity {
+    match try_cp_ps_eqm_refined(p, s, s_tolerance) {
+        Ok(cp) => cp,
+        Err(error) => panic!("refined p,s flash failed: {}", error),
+    }
+}
+
+/// obtains cv given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+pub fn cv_ps_eqm_refined(p: Pressure, s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> SpecificHeatCapacity {
+    match try_cv_ps_eqm_refined(p, s, s_tolerance) {
+        Ok(cv) => cv,
+        Err(error) => panic!("refined p,s flash failed: {}", error),
+    }
+}
+
+/// obtains speed of sound given pressure and entropy,
+/// refined using the forward equations until the
+/// entropy residual is below s_tolerance
+pub fn w_ps_eqm_refined(p: Pressure, s: SpecificHeatCapacity,
+    s_tolerance: SpecificHeatCapacity) -> Velocity {
+    match try_w_ps_eqm_refined(p, s, s_tolerance) {
+        Ok(w) => w,
+        Err(error) => panic!("refined p,s flash failed: {}", error),
+    }
+}
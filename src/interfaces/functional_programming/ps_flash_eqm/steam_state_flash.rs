@@ -0,0 +1,88 @@
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::out_of_range_policy::OutOfRangePolicy;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+
+use super::*;
+
+/// (p,s) flash returning all intensive properties at once
+///
+/// the region and backward equations are only evaluated once,
+/// unlike calling t_ps_eqm, v_ps_eqm, cp_ps_eqm, lambda_ps_eqm etc.
+/// one by one
+///
+/// the pressure and entropy of the returned state are the
+/// input pressure and entropy, rather than the forward equations
+/// evaluated at the flashed state (the two differ by the
+/// consistency of the backward equations)
+///
+/// points outside the validity range return the violated bound,
+/// this is the same as try_ps_flash_with_policy with
+/// OutOfRangePolicy::Error
+pub fn try_flash(p: Pressure,
+    s: SpecificHeatCapacity) -> Result<SteamState, SteamTableError> {
+    let flashed = try_ps_flash_with_policy(p, s, OutOfRangePolicy::Error)?;
+
+    return Ok(flashed.state);
+}
+
+/// (p,s) flash for a point within the validity range
+/// (or rather, a point which the policy has already dealt with)
+pub(crate) fn try_flash_within_range(p: Pressure,
+    s: SpecificHeatCapacity) -> Result<SteamState, SteamTableError> {
+
+    let region = try_ps_flash_region(p, s)?;
+
+    let mut state = match region {
+        FwdEqnRegion::Region1 => {
+            let t = t_ps_1(p, s);
+            SteamState::new_single_phase(region, t, p, v_tp_1(t, p).recip(), 0.0)
+        },
+        FwdEqnRegion::Region2 => {
+            let t = t_ps_2(p, s);
+            SteamState::new_single_phase(region, t, p, v_tp_2(t, p).recip(), 1.0)
+        },
+        FwdEqnRegion::Region3 => {
+            // same steam quality as x_ps_flash,
+            // at or above crit pressure, we just consider it vapour
+            let x = if p >= Pressure::new::<megapascal>(22.064) {
+                1.0
+            } else if s < s_3a3b_backwards_ps_boundary() {
+                0.0
+            } else {
+                1.0
+            };
+            SteamState::new_single_phase(region, t_ps_3(p, s), p,
+                v_ps_3(p, s).recip(), x)
+        },
+        FwdEqnRegion::Region4 => {
+            // x = (s-sliq)/(svap - sliq)
+            let t_sat = sat_temp_4(p);
+            let (liquid, vapour) = SteamState::new_saturated_liquid_and_vapour(t_sat);
+            let x: f64 = ((s - liquid.s)/(vapour.s - liquid.s)).get::<ratio>();
+
+            SteamState::new_two_phase(&liquid, &vapour, x)
+        },
+        FwdEqnRegion::Region5 => {
+            let t = try_t_ps_5(p, s)?;
+            SteamState::new_single_phase(region, t, p, v_tp_5(t, p).recip(), 1.0)
+        },
+    };
+
+    state.p = p;
+    state.s = s;
+
+    return Ok(state);
+}
+
+/// (p,s) flash returning all intensive properties at once,
+/// panics if the (p,s) point is outside the validity range
+pub fn flash(p: Pressure,
+    s: SpecificHeatCapacity) -> SteamState {
+    match try_flash(p, s) {
+        Ok(state) => state,
+        Err(error) => panic!("p,s flash failed: {}", error),
+    }
+}
@@ -0,0 +1,108 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::region_1_subcooled_liquid::InversePressure;
+
+use super::*;
+
+/// allows the user to check which region one is in based on a ps flash
+/// returns an error rather than panicking if the (p,s) point 
+/// is outside the validity range
+pub fn try_ps_flash_region(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<FwdEqnRegion, SteamTableError> {
+
+    try_check_if_within_ps_validity_region(p, s)?;
+
+    return Ok(ps_flash_region(p, s));
+}
+
+// region 5 has no backward equation in terms of (p,s), 
+// so the temperature is found by newton raphson iteration, 
+// which may not converge
+fn try_converged_ps_flash_region(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<FwdEqnRegion, SteamTableError> {
+    let region = try_ps_flash_region(p, s)?;
+
+    if region == FwdEqnRegion::Region5 {
+        try_t_ps_5(p, s)?;
+    };
+
+    return Ok(region);
+}
+
+/// obtains temperature given pressure and entropy
+pub fn try_t_ps_eqm(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<ThermodynamicTemperature, SteamTableError> {
+    try_converged_ps_flash_region(p, s)?;
+    return Ok(t_ps_eqm(p, s));
+}
+
+/// obtains volume given pressure and entropy
+pub fn try_v_ps_eqm(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<SpecificVolume, SteamTableError> {
+    try_converged_ps_flash_region(p, s)?;
+    return Ok(v_ps_eqm(p, s));
+}
+
+/// obtains steam quality given pressure and entropy
+pub fn try_x_ps_flash(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<f64, SteamTableError> {
+    try_converged_ps_flash_region(p, s)?;
+    return Ok(x_ps_flash(p, s));
+}
+
+/// obtains internal energy given pressure and entropy
+pub fn try_u_ps_eqm(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<AvailableEnergy, SteamTableError> {
+    try_converged_ps_flash_region(p, s)?;
+    return Ok(u_ps_eqm(p, s));
+}
+
+/// obtains enthalpy given pressure and entropy
+pub fn try_h_ps_eqm(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<AvailableEnergy, SteamTableError> {
+    try_converged_ps_flash_region(p, s)?;
+    return Ok(h_ps_eqm(p, s));
+}
+
+/// obtains cp given pressure and entropy
+pub fn try_cp_ps_eqm(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<SpecificHeatCapacity, SteamTableError> {
+    try_converged_ps_flash_region(p, s)?;
+    return Ok(cp_ps_eqm(p, s));
+}
+
+/// obtains cv given pressure and entropy
+pub fn try_cv_ps_eqm(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<SpecificHeatCapacity, SteamTableError> {
+    try_converged_ps_flash_region(p, s)?;
+    return Ok(cv_ps_eqm(p, s));
+}
+
+/// obtains speed of sound given pressure and entropy
+pub fn try_w_ps_eqm(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<Velocity, SteamTableError> {
+    try_converged_ps_flash_region(p, s)?;
+    return Ok(w_ps_eqm(p, s));
+}
+
+/// obtains isentropic exponent given pressure and entropy
+pub fn try_kappa_ps_eqm(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<Ratio, SteamTableError> {
+    try_converged_ps_flash_region(p, s)?;
+    return Ok(kappa_ps_eqm(p, s));
+}
+
+/// obtains isobaric cubic expansion coefficient given pressure and entropy
+pub fn try_alpha_v_ps_eqm(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<TemperatureCoefficient, SteamTableError> {
+    try_converged_ps_flash_region(p, s)?;
+    return Ok(alpsa_v_ps_eqm(p, s));
+}
+
+/// obtains isothermal compressibility given pressure and entropy
+pub fn try_kappa_t_ps_eqm(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<InversePressure, SteamTableError> {
+    try_converged_ps_flash_region(p, s)?;
+    return Ok(kappa_t_ps_eqm(p, s));
+}
@@ -0,0 +1,43 @@
+use uom::si::f64::*;
+
+use crate::interfaces::functional_programming::ph_flash_eqm::{lambda_ph_eqm, mu_ph_eqm};
+use crate::interfaces::functional_programming::saturation_eqm::SaturationState;
+use crate::interfaces::functional_programming::two_phase_mixing_model::TwoPhaseMixingModel;
+
+use super::*;
+
+/// returns mu (dynamic viscosity) given pressure and entropy,
+/// using the mixing model in region 4
+///
+/// outside region 4, this uses the (p,h) flash 
+/// (mu_ph_eqm) at the enthalpy of the (p,s) point
+pub fn mu_ps_eqm_with_mixing_model(p: Pressure,
+    s: SpecificHeatCapacity,
+    model: TwoPhaseMixingModel) -> DynamicViscosity {
+
+    match ps_flash_region(p, s) {
+        FwdEqnRegion::Region4 => {
+            let saturation_state = SaturationState::new_from_p(p);
+            model.mu_two_phase(&saturation_state, x_ps_flash(p, s))
+        },
+        _ => mu_ph_eqm(p, h_ps_eqm(p, s)),
+    }
+}
+
+/// returns lambda (thermal conductivity) given pressure and entropy,
+/// using the mixing model in region 4
+///
+/// outside region 4, this uses the (p,h) flash 
+/// (lambda_ph_eqm) at the enthalpy of the (p,s) point
+pub fn lambda_ps_eqm_with_mixing_model(p: Pressure,
+    s: SpecificHeatCapacity,
+    model: TwoPhaseMixingModel) -> ThermalConductivity {
+
+    match ps_flash_region(p, s) {
+        FwdEqnRegion::Region4 => {
+            let saturation_state = SaturationState::new_from_p(p);
+            model.lambda_two_phase(&saturation_state, x_ps_flash(p, s))
+        },
+        _ => lambda_ph_eqm(p, h_ps_eqm(p, s)),
+    }
+}
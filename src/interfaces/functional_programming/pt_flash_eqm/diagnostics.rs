@@ -0,0 +1,63 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::flash_diagnostics::*;
+use crate::interfaces::functional_programming::ph_flash_eqm::try_x_ph_flash;
+use crate::region_3_single_phase_plus_supercritical_steam::p_rho_t_3;
+
+use super::*;
+
+/// (T,p) flash which also reports the region used and, 
+/// in region 3, the pressure residual of the forward equation
+///
+/// as with try_tp_flash_with_policy, the steam quality x
+/// is only used if the (T,p) point lies on the saturation line
+///
+/// regions 1, 2 and 5 are explicit in (T,p), so there are 
+/// no residuals, region 3 is explicit in (T,rho) so the 
+/// specific volume comes from the v(T,p) backward equations,
+/// and the residual is p(rho,T) - p
+pub fn try_tp_flash_with_diagnostics(t: ThermodynamicTemperature,
+    p: Pressure,
+    x: f64) -> Result<FlashReport, SteamTableError> {
+
+    let region = try_region_fwd_eqn_single_phase(t, p)?;
+
+    // steam quality, as in try_tp_flash_with_policy
+    let x = match region {
+        FwdEqnRegion::Region1 => 0.0,
+        FwdEqnRegion::Region2 | FwdEqnRegion::Region5 => 1.0,
+        FwdEqnRegion::Region3 => try_x_ph_flash(p, h_tp_eqm_two_phase(t, p, x))?,
+        FwdEqnRegion::Region4 => x.clamp(0.0, 1.0),
+    };
+
+    let v = v_tp_eqm_two_phase(t, p, x);
+
+    let mut residuals = FlashResiduals::default();
+    if region == FwdEqnRegion::Region3 {
+        residuals.p = Some(p_rho_t_3(v.recip(), t) - p);
+    };
+
+    return Ok(FlashReport {
+        t,
+        p,
+        v,
+        x,
+        region,
+        subregion: None,
+        iterations: 0,
+        residuals,
+        boundary_correction_applied: false,
+    });
+}
+
+/// (T,p) flash which also reports how the result was obtained,
+/// panics if the (T,p) point is outside the validity range
+pub fn tp_flash_with_diagnostics(t: ThermodynamicTemperature,
+    p: Pressure,
+    x: f64) -> FlashReport {
+    match try_tp_flash_with_diagnostics(t, p, x) {
+        Ok(report) => report,
+        Err(error) => panic!("t,p flash failed: {}", error),
+    }
+}
@@ -1,8 +1,9 @@
-use uom::si::{f64::*, pressure::pascal, thermodynamic_temperature::kelvin};
+use uom::si::{f64::*, thermodynamic_temperature::kelvin};
 
-use crate::{region_1_subcooled_liquid::{alpha_v_tp_1, cp_tp_1, cv_tp_1, h_tp_1, kappa_t_tp_1, kappa_tp_1, s_tp_1, u_tp_1, v_tp_1, w_tp_1, InversePressure}, region_2_vapour::{alpha_v_tp_2, cp_tp_2, cv_tp_2, h_tp_2, kappa_t_tp_2, kappa_tp_2, s_tp_2, u_tp_2, v_tp_2, w_tp_2}, region_3_single_phase_plus_supercritical_steam::{alpha_v_tp_3, cp_tp_3, cv_tp_3, h_tp_3, kappa_t_tp_3, kappa_tp_3, p_boundary_2_3, s_tp_3, u_tp_3, v_tp_3, w_tp_3}, region_4_vap_liq_equilibrium::sat_pressure_4, region_5_steam_at_800_plus_degc::{alpha_v_tp_5, cp_tp_5, cv_tp_5, h_tp_5, kappa_t_tp_5, kappa_tp_5, s_tp_5, u_tp_5, v_tp_5, w_tp_5}};
+use crate::{region_1_subcooled_liquid::{alpha_v_tp_1, cp_tp_1, cv_tp_1, h_tp_1, kappa_t_tp_1, kappa_tp_1, s_tp_1, u_tp_1, v_tp_1, w_tp_1, InversePressure}, region_2_vapour::{alpha_v_tp_2, cp_tp_2, cv_tp_2, h_tp_2, kappa_t_tp_2, kappa_tp_2, s_tp_2, u_tp_2, v_tp_2, w_tp_2}, region_3_single_phase_plus_supercritical_steam::{alpha_v_tp_3, cp_tp_3, cv_tp_3, h_tp_3, kappa_t_tp_3, kappa_tp_3, s_tp_3, u_tp_3, v_tp_3, w_tp_3}, region_4_vap_liq_equilibrium::sat_pressure_4, region_5_steam_at_800_plus_degc::{alpha_v_tp_5, cp_tp_5, cv_tp_5, h_tp_5, kappa_t_tp_5, kappa_tp_5, s_tp_5, u_tp_5, v_tp_5, w_tp_5}};
 
-#[derive(Debug,PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug,PartialEq, Eq, PartialOrd, Ord,Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// an enum to help represent the appropriate 
 /// regions in the forward equations
 pub enum FwdEqnRegion {
@@ -30,6 +31,32 @@ pub enum FwdEqnRegion {
 pub mod multiphase_flashing;
 pub use multiphase_flashing::*;
 
+/// fallible versions of the single phase (T,p) flash functions 
+/// which return a SteamTableError rather than panicking
+pub mod try_flash;
+pub use try_flash::*;
+
+/// (T,p) flash with a configurable out of range policy
+/// (error, clamp to boundary or extrapolate)
+pub mod out_of_range;
+pub use out_of_range::*;
+
+/// (T,p) flash which also reports the region, subregion,
+/// iterations and residuals (see flash_diagnostics)
+pub mod diagnostics;
+pub use diagnostics::*;
+
+/// (T,p) single phase flash returning all intensive properties at once
+/// (see steam_state)
+pub mod steam_state_flash;
+pub use steam_state_flash::*;
+
+/// (T,p) viscosity and thermal conductivity with a
+/// selectable two phase mixing model in region 4
+/// (see two_phase_mixing_model)
+pub mod two_phase_mixing;
+pub use two_phase_mixing::*;
+
 
 /// Determines which region of the pT chart
 /// a point belongs to.
@@ -41,46 +68,9 @@ pub use multiphase_flashing::*;
 /// Pressure is assumed to be in Pa
 ///
 pub fn region_fwd_eqn_single_phase(t: ThermodynamicTemperature, p: Pressure) -> FwdEqnRegion {
-    let p_sat_reg4 = sat_pressure_4(t);
-
-    let p_boundary_23 = p_boundary_2_3(t);
-
-    let t_kelvin = t.get::<kelvin>();
-    let p_pascal = p.get::<pascal>();
-    let p_boundary_23_pascal = p_boundary_23.get::<pascal>();
-    let p_sat_reg4_pascal = p_sat_reg4.get::<pascal>();
-
-    match (t_kelvin, p_pascal) {
-        (temp, pres)
-            if (1073.15..=2273.15).contains(&temp) && (0.0..=50.0e6).contains(&pres) =>
-            {
-                FwdEqnRegion::Region5
-            }
-        (temp, pres) if (273.15..647.096).contains(&temp) && pres == p_sat_reg4_pascal => {
-            FwdEqnRegion::Region4
-        }
-        (temp, pres)
-            if (623.15..=863.15).contains(&temp) && (p_boundary_23_pascal..100e6).contains(&pres) =>
-            {
-                FwdEqnRegion::Region3
-            }
-        (temp, pres)
-            if ((273.15..=623.15).contains(&temp) && (0.0..=p_sat_reg4_pascal).contains(&pres))
-                || ((623.15..=863.15).contains(&temp)
-                    && (0.0..=p_boundary_23_pascal).contains(&pres))
-                    || ((863.15..=1073.15).contains(&temp) && (0.0..100e6).contains(&pres)) =>
-            {
-                FwdEqnRegion::Region2
-            }
-        (temp, pres)
-            if (273.15..=623.15).contains(&temp) && (p_sat_reg4_pascal..=100e6).contains(&pres) =>
-            {
-                FwdEqnRegion::Region1
-            }
-        _ => {
-            dbg!(&(t,p));
-            panic!("t,p flashing at eqm out of bounds!")
-        },
+    match try_region_fwd_eqn_single_phase(t, p) {
+        Ok(region) => region,
+        Err(error) => panic!("t,p flashing at eqm out of bounds: {}", error),
     }
 }
 
@@ -4,6 +4,7 @@ use crate::region_3_single_phase_plus_supercritical_steam::{alpha_v_rho_t_3, cp_
 use crate::constants::{p_crit_water, t_crit_water, T_C_KELVIN};
 
 use super::*;
+use crate::interfaces::functional_programming::two_phase_equilibrium_derivatives::TwoPhaseEquilibriumDerivatives;
 
 pub fn region_fwd_eqn_two_phase(
     t: ThermodynamicTemperature,
@@ -100,7 +101,6 @@ pub fn h_tp_eqm_two_phase(
     t: ThermodynamicTemperature, p: Pressure,
     x: f64) -> AvailableEnergy {
     let region = region_fwd_eqn_two_phase(t, p, x);
-    dbg!(&region);
 
 
 
@@ -170,10 +170,7 @@ pub fn h_tp_eqm_two_phase(
             let near_critical_point: bool = (t_kelvin - 647.096).abs() < 0.025;
             let near_saturation_line: bool = (p_mpa - sat_pressure_4(t).get::<megapascal>()).abs() < 5e-4 ;
             // if we are ON the saturated line, we must be mindful
-            //dbg!(&(h_liq,h_vap,h_saturated,steam_quality));
 
-            dbg!(&near_critical_point);
-            dbg!(&(near_saturation_line,h_liq,h_vap,h_saturated));
             if near_critical_point && near_saturation_line {
                 // this was intended to get the enthalpy of vapourisation 
                 // to zero near critical point
@@ -566,6 +563,9 @@ pub fn s_tp_eqm_two_phase(t: ThermodynamicTemperature, p: Pressure,
 }
 
 /// returns the isobaric (const pressure) heat capacitygiven temperature and pressure
+///
+/// in region 4, cp is infinite (see two_phase_equilibrium_derivatives),
+/// except within SATURATED_PHASE_QUALITY_TOLERANCE of the saturation lines
 pub fn cp_tp_eqm_two_phase(t: ThermodynamicTemperature, 
     p: Pressure, x: f64) -> SpecificHeatCapacity {
     let region = region_fwd_eqn_two_phase(t, p, x);
@@ -638,75 +638,10 @@ pub fn cp_tp_eqm_two_phase(t: ThermodynamicTemperature,
             cp_tp_3(t, p)
         },
         FwdEqnRegion::Region4 => {
-            // the only time we get region 4 is in multiphase steam
-            // but I'm having this here anyway cause I kiasu
-            let mut steam_quality = x;
-            if steam_quality < 0.0 {
-                steam_quality = 0.0;
-            } else if steam_quality > 1.0 {
-                steam_quality = 1.0;
-            }; 
-
-            let t_sat_kelvin = t.get::<kelvin>();
-            if t_sat_kelvin <= 623.15 {
-                let cp_liq = cp_tp_1(t, p);
-                let cp_vap = cp_tp_2(t, p);
-
-                let cp = steam_quality * cp_vap + (1.0 - steam_quality) * cp_liq;
-
-                cp
-            } else {
-                // in the case we hit region 3 and region 4 boundary, the algorithm must differ
-
-                // looks like all I need to do is use the backward equations 
-                // v(t,p)
-                // let's do the liquid one first 
-
-                // this is with reference to the pt equations on 
-                // fig 2.24 page 109
-                //
-                // alternatively, this is a cheat way... 
-                // I look at t_sat, and force it to find liquid volume 
-                // using a slightly colder temperature than tsat 
-                // and for vapour I get it slightly higher than  
-                // the tsat
-                let p_mpa = p.get::<megapascal>();
-                let v_vap: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 640.691 {
-                        v_tp_3t(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3r(t, p)
-                    } else // this covers pressure from 21.0434 Mpa to crit point 
-                    if p_mpa <= 21.9010 {
-                        v_tp_3x(t, p)
-                    } else {
-                        v_tp_3z(t, p)
-                    }
-                };
-
-                let v_liq: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 634.659 {
-                        v_tp_3c(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3s(t, p)
-                    } else if p_mpa <= 21.9316 {
-                        v_tp_3u(t, p)
-                    } else {
-                        v_tp_3y(t, p)
-                    }
-
-
-                };
-
-                let cp_liq = cp_rho_t_3(v_liq.recip(), t);
-                let cp_vap = cp_rho_t_3(v_vap.recip(), t);
-                let cp = steam_quality * cp_vap + (1.0 - steam_quality) * cp_liq;
-
-                cp
-            }
-
+            // the only time we get region 4 is in multiphase steam,
+            // which is a homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t, x).cp
         },
         FwdEqnRegion::Region5 => cp_tp_5(t, p),
     }
@@ -786,75 +721,10 @@ pub fn cv_tp_eqm_two_phase(t: ThermodynamicTemperature, p: Pressure,
             cv_tp_3(t, p)
         },
         FwdEqnRegion::Region4 => {
-            // the only time we get region 4 is in multiphase steam
-            // but I'm having this here anyway cause I kiasu
-            let mut steam_quality = x;
-            if steam_quality < 0.0 {
-                steam_quality = 0.0;
-            } else if steam_quality > 1.0 {
-                steam_quality = 1.0;
-            }; 
-
-            let t_sat_kelvin = t.get::<kelvin>();
-            if t_sat_kelvin <= 623.15 {
-                let cv_liq = cv_tp_1(t, p);
-                let cv_vap = cv_tp_2(t, p);
-
-                let cv = steam_quality * cv_vap + (1.0 - steam_quality) * cv_liq;
-
-                cv
-            } else {
-                // in the case we hit region 3 and region 4 boundary, the algorithm must differ
-
-                // looks like all I need to do is use the backward equations 
-                // v(t,p)
-                // let's do the liquid one first 
-
-                // this is with reference to the pt equations on 
-                // fig 2.24 page 109
-                //
-                // alternatively, this is a cheat way... 
-                // I look at t_sat, and force it to find liquid volume 
-                // using a slightly colder temperature than tsat 
-                // and for vapour I get it slightly higher than  
-                // the tsat
-                let p_mpa = p.get::<megapascal>();
-                let v_vap: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 640.691 {
-                        v_tp_3t(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3r(t, p)
-                    } else // this covers pressure from 21.0434 Mpa to crit point 
-                    if p_mpa <= 21.9010 {
-                        v_tp_3x(t, p)
-                    } else {
-                        v_tp_3z(t, p)
-                    }
-                };
-
-                let v_liq: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 634.659 {
-                        v_tp_3c(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3s(t, p)
-                    } else if p_mpa <= 21.9316 {
-                        v_tp_3u(t, p)
-                    } else {
-                        v_tp_3y(t, p)
-                    }
-
-
-                };
-
-                let cv_liq = cv_rho_t_3(v_liq.recip(), t);
-                let cv_vap = cv_rho_t_3(v_vap.recip(), t);
-                let cv = steam_quality * cv_vap + (1.0 - steam_quality) * cv_liq;
-
-                cv
-            }
-
+            // the only time we get region 4 is in multiphase steam,
+            // which is a homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t, x).cv
         },
         FwdEqnRegion::Region5 => cv_tp_5(t, p),
     }
@@ -1090,75 +960,10 @@ pub fn w_tp_eqm_two_phase(t: ThermodynamicTemperature, p: Pressure,
             w_tp_3(t, p)
         },
         FwdEqnRegion::Region4 => {
-            // the only time we get region 4 is in multiphase steam
-            // but I'm having this here anyway cause I kiasu
-            let mut steam_quality = x;
-            if steam_quality < 0.0 {
-                steam_quality = 0.0;
-            } else if steam_quality > 1.0 {
-                steam_quality = 1.0;
-            }; 
-
-            let t_sat_kelvin = t.get::<kelvin>();
-            if t_sat_kelvin <= 623.15 {
-                let w_liq = w_tp_1(t, p);
-                let w_vap = w_tp_2(t, p);
-
-                let h = steam_quality * w_vap + (1.0 - steam_quality) * w_liq;
-
-                h
-            } else {
-                // in the case we hit region 3 and region 4 boundary, the algorithm must differ
-
-                // looks like all I need to do is use the backward equations 
-                // v(t,p)
-                // let's do the liquid one first 
-
-                // this is with reference to the pt equations on 
-                // fig 2.24 page 109
-                //
-                // alternatively, this is a cheat way... 
-                // I look at t_sat, and force it to find liquid volume 
-                // using a slightly colder temperature than tsat 
-                // and for vapour I get it slightly higher than  
-                // the tsat
-                let p_mpa = p.get::<megapascal>();
-                let v_vap: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 640.691 {
-                        v_tp_3t(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3r(t, p)
-                    } else // this covers pressure from 21.0434 Mpa to crit point 
-                    if p_mpa <= 21.9010 {
-                        v_tp_3x(t, p)
-                    } else {
-                        v_tp_3z(t, p)
-                    }
-                };
-
-                let v_liq: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 634.659 {
-                        v_tp_3c(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3s(t, p)
-                    } else if p_mpa <= 21.9316 {
-                        v_tp_3u(t, p)
-                    } else {
-                        v_tp_3y(t, p)
-                    }
-
-
-                };
-
-                let w_liq = w_rho_t_3(v_liq.recip(), t);
-                let w_vap = w_rho_t_3(v_vap.recip(), t);
-                let w = steam_quality * w_vap + (1.0 - steam_quality) * w_liq;
-
-                w
-            }
-
+            // the only time we get region 4 is in multiphase steam,
+            // which is a homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t, x).w
         },
         FwdEqnRegion::Region5 => w_tp_5(t, p),
     }
@@ -1239,75 +1044,10 @@ pub fn kappa_tp_eqm_two_phase(t: ThermodynamicTemperature,
 
         },
         FwdEqnRegion::Region4 => {
-            // the only time we get region 4 is in multiphase steam
-            // but I'm having this here anyway cause I kiasu
-            let mut steam_quality = x;
-            if steam_quality < 0.0 {
-                steam_quality = 0.0;
-            } else if steam_quality > 1.0 {
-                steam_quality = 1.0;
-            }; 
-
-            let t_sat_kelvin = t.get::<kelvin>();
-            if t_sat_kelvin <= 623.15 {
-                let kappa_liq = kappa_tp_1(t, p);
-                let kappa_vap = kappa_tp_2(t, p);
-
-                let kappa = steam_quality * kappa_vap + (1.0 - steam_quality) * kappa_liq;
-
-                kappa
-            } else {
-                // in the case we hit region 3 and region 4 boundary, the algorithm must differ
-
-                // looks like all I need to do is use the backward equations 
-                // v(t,p)
-                // let's do the liquid one first 
-
-                // this is with reference to the pt equations on 
-                // fig 2.24 page 109
-                //
-                // alternatively, this is a cheat way... 
-                // I look at t_sat, and force it to find liquid volume 
-                // using a slightly colder temperature than tsat 
-                // and for vapour I get it slightly higher than  
-                // the tsat
-                let p_mpa = p.get::<megapascal>();
-                let v_vap: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 640.691 {
-                        v_tp_3t(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3r(t, p)
-                    } else // this covers pressure from 21.0434 Mpa to crit point 
-                    if p_mpa <= 21.9010 {
-                        v_tp_3x(t, p)
-                    } else {
-                        v_tp_3z(t, p)
-                    }
-                };
-
-                let v_liq: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 634.659 {
-                        v_tp_3c(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3s(t, p)
-                    } else if p_mpa <= 21.9316 {
-                        v_tp_3u(t, p)
-                    } else {
-                        v_tp_3y(t, p)
-                    }
-
-
-                };
-
-                let kappa_liq = kappa_rho_t_3(v_liq.recip(), t);
-                let kappa_vap = kappa_rho_t_3(v_vap.recip(), t);
-                let kappa = steam_quality * kappa_vap + (1.0 - steam_quality) * kappa_liq;
-
-                Ratio::new::<ratio>(kappa)
-            }
-
+            // the only time we get region 4 is in multiphase steam,
+            // which is a homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t, x).kappa
         },
         FwdEqnRegion::Region5 => kappa_tp_5(t, p),
     }
@@ -1386,75 +1126,10 @@ pub fn alpha_v_tp_eqm_two_phase(t: ThermodynamicTemperature,
             alpha_v_tp_3(t, p)
         },
         FwdEqnRegion::Region4 => {
-            // the only time we get region 4 is in multiphase steam
-            // but I'm having this here anyway cause I kiasu
-            let mut steam_quality = x;
-            if steam_quality < 0.0 {
-                steam_quality = 0.0;
-            } else if steam_quality > 1.0 {
-                steam_quality = 1.0;
-            }; 
-
-            let t_sat_kelvin = t.get::<kelvin>();
-            if t_sat_kelvin <= 623.15 {
-                let alpha_v_liq = alpha_v_tp_1(t, p);
-                let alpha_v_vap = alpha_v_tp_2(t, p);
-
-                let alpha_v = steam_quality * alpha_v_vap + (1.0 - steam_quality) * alpha_v_liq;
-
-                alpha_v
-            } else {
-                // in the case we hit region 3 and region 4 boundary, the algorithm must differ
-
-                // looks like all I need to do is use the backward equations 
-                // v(t,p)
-                // let's do the liquid one first 
-
-                // this is with reference to the pt equations on 
-                // fig 2.24 page 109
-                //
-                // alternatively, this is a cheat way... 
-                // I look at t_sat, and force it to find liquid volume 
-                // using a slightly colder temperature than tsat 
-                // and for vapour I get it slightly higher than  
-                // the tsat
-                let p_mpa = p.get::<megapascal>();
-                let v_vap: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 640.691 {
-                        v_tp_3t(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3r(t, p)
-                    } else // this covers pressure from 21.0434 Mpa to crit point 
-                    if p_mpa <= 21.9010 {
-                        v_tp_3x(t, p)
-                    } else {
-                        v_tp_3z(t, p)
-                    }
-                };
-
-                let v_liq: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 634.659 {
-                        v_tp_3c(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3s(t, p)
-                    } else if p_mpa <= 21.9316 {
-                        v_tp_3u(t, p)
-                    } else {
-                        v_tp_3y(t, p)
-                    }
-
-
-                };
-
-                let alpha_v_liq = alpha_v_rho_t_3(v_liq.recip(), t);
-                let alpha_v_vap = alpha_v_rho_t_3(v_vap.recip(), t);
-                let alpha_v = steam_quality * alpha_v_vap + (1.0 - steam_quality) * alpha_v_liq;
-
-                alpha_v
-            }
-
+            // the only time we get region 4 is in multiphase steam,
+            // which is a homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t, x).alpha_v
         },
         FwdEqnRegion::Region5 => alpha_v_tp_5(t, p),
     }
@@ -1534,75 +1209,10 @@ pub fn kappa_t_tp_eqm(t: ThermodynamicTemperature, p: Pressure, x: f64) -> Inver
 
         },
         FwdEqnRegion::Region4 => {
-            // the only time we get region 4 is in multiphase steam
-            // but I'm having this here anyway cause I kiasu
-            let mut steam_quality = x;
-            if steam_quality < 0.0 {
-                steam_quality = 0.0;
-            } else if steam_quality > 1.0 {
-                steam_quality = 1.0;
-            }; 
-
-            let t_sat_kelvin = t.get::<kelvin>();
-            if t_sat_kelvin <= 623.15 {
-                let kappa_t_liq = kappa_t_tp_1(t, p);
-                let kappa_t_vap = kappa_t_tp_2(t, p);
-
-                let kappa_t = steam_quality * kappa_t_vap + (1.0 - steam_quality) * kappa_t_liq;
-
-                kappa_t
-            } else {
-                // in the case we hit region 3 and region 4 boundary, the algorithm must differ
-
-                // looks like all I need to do is use the backward equations 
-                // v(t,p)
-                // let's do the liquid one first 
-
-                // this is with reference to the pt equations on 
-                // fig 2.24 page 109
-                //
-                // alternatively, this is a cheat way... 
-                // I look at t_sat, and force it to find liquid volume 
-                // using a slightly colder temperature than tsat 
-                // and for vapour I get it slightly higher than  
-                // the tsat
-                let p_mpa = p.get::<megapascal>();
-                let v_vap: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 640.691 {
-                        v_tp_3t(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3r(t, p)
-                    } else // this covers pressure from 21.0434 Mpa to crit point 
-                    if p_mpa <= 21.9010 {
-                        v_tp_3x(t, p)
-                    } else {
-                        v_tp_3z(t, p)
-                    }
-                };
-
-                let v_liq: SpecificVolume = {
-                    // this covers up to tsat at 643.15 K
-                    if t_sat_kelvin <= 634.659 {
-                        v_tp_3c(t, p)
-                    } else if t_sat_kelvin <= 643.15 {
-                        v_tp_3s(t, p)
-                    } else if p_mpa <= 21.9316 {
-                        v_tp_3u(t, p)
-                    } else {
-                        v_tp_3y(t, p)
-                    }
-
-
-                };
-
-                let kappa_t_liq = kappa_t_rho_t_3(v_liq.recip(), t);
-                let kappa_t_vap = kappa_t_rho_t_3(v_vap.recip(), t);
-                let kappa_t = steam_quality * kappa_t_vap + (1.0 - steam_quality) * kappa_t_liq;
-
-                kappa_t
-            }
-
+            // the only time we get region 4 is in multiphase steam,
+            // which is a homogeneous equilibrium mixture,
+            // see two_phase_equilibrium_derivatives
+            TwoPhaseEquilibriumDerivatives::new_from_flash(t, x).kappa_t
         },
         FwdEqnRegion::Region5 => kappa_t_tp_5(t, p),
     }
@@ -0,0 +1,83 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::out_of_range_policy::*;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+use crate::interfaces::functional_programming::validity_domain::validity_domain_pt;
+
+use super::*;
+
+/// checks if the (T,p) point is within the validity range,
+/// applying the out of range policy if it is not
+///
+/// returns the (T,p) point to flash (moved onto the nearest valid
+/// point for ClampToBoundary), the nearest valid point
+/// and what the policy did
+pub(crate) fn try_check_if_within_pt_validity_region_with_policy(
+    t: ThermodynamicTemperature, p: Pressure,
+    policy: OutOfRangePolicy) -> Result<PolicyPoint<ThermodynamicTemperature, Pressure>, SteamTableError> {
+
+    return try_apply_out_of_range_policy(validity_domain_pt(t, p), policy);
+}
+
+/// (T,p) flash which applies an out of range policy
+/// if the (T,p) point is outside the validity range
+///
+/// as with the control volume constructors, the steam quality x
+/// is only used if the (T,p) point lies on the saturation line
+///
+/// for ClampToBoundary, the point is moved onto the nearest
+/// valid point before flashing,
+/// for ExtrapolateForwardEquation, the forward equations
+/// of the region of the nearest valid point are used
+/// at the input point
+///
+/// what was done is reported in PolicyFlashState::report
+pub fn try_tp_flash_with_policy(t: ThermodynamicTemperature,
+    p: Pressure,
+    x: f64,
+    policy: OutOfRangePolicy) -> Result<PolicyFlashState, SteamTableError> {
+
+    let checked = try_check_if_within_pt_validity_region_with_policy(t, p, policy)?;
+    let (t, p) = checked.point;
+    let report = checked.report;
+
+    if !report.was_extrapolated() {
+        return Ok(PolicyFlashState { state: try_flash_two_phase(t, p, x)?, report });
+    };
+
+    let (t_valid, p_valid) = checked.nearest_valid_point;
+    let valid_state = try_flash_two_phase(t_valid, p_valid, x)?;
+    let state = try_extrapolate_tp_steam_state(t, p, &valid_state,
+        "extrapolated (T,p)")?;
+
+    return Ok(PolicyFlashState { state, report });
+}
+
+/// (T,p) flash which applies an out of range policy,
+/// panics if the policy is Error and the point is outside the
+/// validity range
+pub fn tp_flash_with_policy(t: ThermodynamicTemperature,
+    p: Pressure,
+    x: f64,
+    policy: OutOfRangePolicy) -> PolicyFlashState {
+    match try_tp_flash_with_policy(t, p, x, policy) {
+        Ok(state) => state,
+        Err(error) => panic!("t,p flash failed: {}", error),
+    }
+}
+
+/// (T,p) flash which uses the steam quality x
+/// on the saturation line, and the single phase flash otherwise
+fn try_flash_two_phase(t: ThermodynamicTemperature,
+    p: Pressure,
+    x: f64) -> Result<SteamState, SteamTableError> {
+
+    if try_region_fwd_eqn_single_phase(t, p)? == FwdEqnRegion::Region4 {
+        let (liquid, vapour) = SteamState::new_saturated_liquid_and_vapour(t);
+
+        return Ok(SteamState::new_two_phase(&liquid, &vapour, x.clamp(0.0, 1.0)));
+    };
+
+    return try_flash(t, p);
+}
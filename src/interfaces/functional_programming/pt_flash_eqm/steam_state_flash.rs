@@ -0,0 +1,64 @@
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+use crate::region_3_single_phase_plus_supercritical_steam::h_3a3b_backwards_ph_boundary;
+
+use super::*;
+
+/// single phase (T,p) flash returning all intensive properties at once
+///
+/// the region is only determined once, unlike calling
+/// v_tp_eqm_single_phase, cp_tp_eqm_single_phase etc. one by one
+///
+/// region 4 (ie. a (T,p) point exactly on the saturation line)
+/// needs steam quality, so it returns a RegionNotImplemented error
+pub fn try_flash(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<SteamState, SteamTableError> {
+
+    let region = try_region_fwd_eqn_single_phase(t, p)?;
+
+    match region {
+        FwdEqnRegion::Region1 => {
+            return Ok(SteamState::new_single_phase(region, t, p,
+                v_tp_1(t, p).recip(), 0.0));
+        },
+        FwdEqnRegion::Region2 => {
+            return Ok(SteamState::new_single_phase(region, t, p,
+                v_tp_2(t, p).recip(), 1.0));
+        },
+        FwdEqnRegion::Region3 => {
+            let mut state = SteamState::new_single_phase(region, t, p,
+                v_tp_3(t, p).recip(), 1.0);
+
+            // same steam quality as x_ph_flash,
+            // at or above crit pressure, we just consider it vapour
+            if p < Pressure::new::<megapascal>(22.064)
+                && state.h < h_3a3b_backwards_ph_boundary(p) {
+                state.x = 0.0;
+            };
+
+            return Ok(state);
+        },
+        FwdEqnRegion::Region4 => {
+            return Err(SteamTableError::RegionNotImplemented {
+                flash: "(T,p) single phase", region });
+        },
+        FwdEqnRegion::Region5 => {
+            return Ok(SteamState::new_single_phase(region, t, p,
+                v_tp_5(t, p).recip(), 1.0));
+        },
+    }
+}
+
+/// single phase (T,p) flash returning all intensive properties at once,
+/// panics if the (T,p) point is outside the validity range
+/// or on the saturation line
+pub fn flash(t: ThermodynamicTemperature,
+    p: Pressure) -> SteamState {
+    match try_flash(t, p) {
+        Ok(state) => state,
+        Err(error) => panic!("t,p flash failed: {}", error),
+    }
+}
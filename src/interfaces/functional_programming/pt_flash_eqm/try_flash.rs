@@ -0,0 +1,175 @@
+use uom::si::{f64::*, pressure::{megapascal, pascal}, thermodynamic_temperature::kelvin};
+
+use crate::errors::SteamTableError;
+use crate::region_1_subcooled_liquid::InversePressure;
+use crate::region_3_single_phase_plus_supercritical_steam::p_boundary_2_3;
+use crate::region_4_vap_liq_equilibrium::sat_pressure_4;
+
+use super::*;
+
+/// relative tolerance within which a (T,p) point is taken to be
+/// on the saturation line (region 4)
+const SATURATION_PRESSURE_REL_TOLERANCE: f64 = 1e-9;
+
+/// Determines which region of the pT chart
+/// a point belongs to, returning an error instead 
+/// of panicking if the point is out of bounds
+pub fn try_region_fwd_eqn_single_phase(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<FwdEqnRegion, SteamTableError> {
+
+    let t_min = ThermodynamicTemperature::new::<kelvin>(273.15);
+    let t_max = ThermodynamicTemperature::new::<kelvin>(2273.15);
+    // region 5 starts at 1073.15 K and only goes up to 50 MPa
+    let t_region_5 = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let p_min = Pressure::new::<megapascal>(0.0);
+    let p_max = Pressure::new::<megapascal>(100.0);
+    let p_max_region_5 = Pressure::new::<megapascal>(50.0);
+
+    if t < t_min {
+        return Err(SteamTableError::TemperatureBelowBound { t, bound: t_min });
+    };
+    if t > t_max {
+        return Err(SteamTableError::TemperatureAboveBound { t, bound: t_max });
+    };
+    if p < p_min {
+        return Err(SteamTableError::PressureBelowBound { p, bound: p_min });
+    };
+    if p > p_max {
+        return Err(SteamTableError::PressureAboveBound { p, bound: p_max });
+    };
+    if t > t_region_5 && p > p_max_region_5 {
+        return Err(SteamTableError::PressureAboveBound { p, bound: p_max_region_5 });
+    };
+
+    let p_sat_reg4 = sat_pressure_4(t);
+
+    let p_boundary_23 = p_boundary_2_3(t);
+
+    let t_kelvin = t.get::<kelvin>();
+    let p_pascal = p.get::<pascal>();
+    let p_boundary_23_pascal = p_boundary_23.get::<pascal>();
+    let p_sat_reg4_pascal = p_sat_reg4.get::<pascal>();
+
+    match (t_kelvin, p_pascal) {
+        (temp, pres)
+            if (1073.15..=2273.15).contains(&temp) && (0.0..=50.0e6).contains(&pres) =>
+            {
+                Ok(FwdEqnRegion::Region5)
+            }
+        // the saturation pressure is compared with a relative
+        // tolerance, exact float equality depends on how the
+        // compiler orders the operations in sat_pressure_4
+        // (eg. debug vs release builds)
+        (temp, pres) if (273.15..647.096).contains(&temp)
+            && (pres - p_sat_reg4_pascal).abs() <= SATURATION_PRESSURE_REL_TOLERANCE * p_sat_reg4_pascal => {
+            Ok(FwdEqnRegion::Region4)
+        }
+        (temp, pres)
+            if (623.15..=863.15).contains(&temp) && (p_boundary_23_pascal..=100e6).contains(&pres) =>
+            {
+                Ok(FwdEqnRegion::Region3)
+            }
+        (temp, pres)
+            if ((273.15..=623.15).contains(&temp) && (0.0..=p_sat_reg4_pascal).contains(&pres))
+                || ((623.15..=863.15).contains(&temp)
+                    && (0.0..=p_boundary_23_pascal).contains(&pres))
+                    || ((863.15..=1073.15).contains(&temp) && (0.0..=100e6).contains(&pres)) =>
+            {
+                Ok(FwdEqnRegion::Region2)
+            }
+        (temp, pres)
+            if (273.15..=623.15).contains(&temp) && (p_sat_reg4_pascal..=100e6).contains(&pres) =>
+            {
+                Ok(FwdEqnRegion::Region1)
+            }
+        // every (T,p) point within the bounds above falls into 
+        // one of the regions, so this is only reached by 
+        // non finite (NaN) inputs which slip past the bounds checks
+        _ => Err(SteamTableError::TemperatureAboveBound { t, bound: t_max }),
+    }
+}
+
+/// single phase (T,p) flash cannot handle region 4 
+/// because steam quality is needed
+fn try_single_phase_region(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<FwdEqnRegion, SteamTableError> {
+    let region = try_region_fwd_eqn_single_phase(t, p)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return Err(SteamTableError::RegionNotImplemented { 
+            flash: "(T,p) single phase", region });
+    };
+
+    return Ok(region);
+}
+
+/// returns the enthalpy given temperature and pressure
+/// single phase only!
+pub fn try_h_tp_eqm_single_phase(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<AvailableEnergy, SteamTableError> {
+    try_single_phase_region(t, p)?;
+    return Ok(h_tp_eqm_single_phase(t, p));
+}
+
+/// returns the internal energy given temperature and pressure
+pub fn try_u_tp_eqm_single_phase(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<AvailableEnergy, SteamTableError> {
+    try_single_phase_region(t, p)?;
+    return Ok(u_tp_eqm_single_phase(t, p));
+}
+
+/// returns the specific entropy given temperature and pressure
+pub fn try_s_tp_eqm_single_phase(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<SpecificHeatCapacity, SteamTableError> {
+    try_single_phase_region(t, p)?;
+    return Ok(s_tp_eqm_single_phase(t, p));
+}
+
+/// returns the isobaric (const pressure) heat capacity given temperature and pressure
+pub fn try_cp_tp_eqm_single_phase(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<SpecificHeatCapacity, SteamTableError> {
+    try_single_phase_region(t, p)?;
+    return Ok(cp_tp_eqm_single_phase(t, p));
+}
+
+/// returns the isochoric (const vol) heat capacity given temperature and pressure
+pub fn try_cv_tp_eqm_single_phase(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<SpecificHeatCapacity, SteamTableError> {
+    try_single_phase_region(t, p)?;
+    return Ok(cv_tp_eqm_single_phase(t, p));
+}
+
+/// returns the specific volume given temperature and pressure
+pub fn try_v_tp_eqm_single_phase(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<SpecificVolume, SteamTableError> {
+    try_single_phase_region(t, p)?;
+    return Ok(v_tp_eqm_single_phase(t, p));
+}
+
+/// returns the speed of sound given temperature and pressure
+pub fn try_w_tp_eqm_single_phase(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<Velocity, SteamTableError> {
+    try_single_phase_region(t, p)?;
+    return Ok(w_tp_eqm_single_phase(t, p));
+}
+
+/// returns the isentropic exponent 
+pub fn try_kappa_tp_eqm_single_phase(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<Ratio, SteamTableError> {
+    try_single_phase_region(t, p)?;
+    return Ok(kappa_tp_eqm_single_phase(t, p));
+}
+
+/// returns the isobaric cubic expansion coefficient
+pub fn try_alpha_v_tp_eqm_single_phase(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<TemperatureCoefficient, SteamTableError> {
+    try_single_phase_region(t, p)?;
+    return Ok(alpha_v_tp_eqm_single_phase(t, p));
+}
+
+/// returns the isothermal compressibility
+pub fn try_kappa_t_tp_eqm(t: ThermodynamicTemperature, 
+    p: Pressure) -> Result<InversePressure, SteamTableError> {
+    try_single_phase_region(t, p)?;
+    return Ok(kappa_t_tp_eqm(t, p));
+}
@@ -0,0 +1,42 @@
+use uom::si::f64::*;
+
+use crate::interfaces::functional_programming::saturation_eqm::SaturationState;
+use crate::interfaces::functional_programming::two_phase_mixing_model::TwoPhaseMixingModel;
+
+use super::*;
+
+/// returns mu (dynamic viscosity) given temperature, pressure
+/// and steam quality, using the mixing model in region 4
+///
+/// outside region 4, this is the same as mu_tp_eqm_two_phase
+pub fn mu_tp_eqm_two_phase_with_mixing_model(t: ThermodynamicTemperature,
+    p: Pressure,
+    x: f64,
+    model: TwoPhaseMixingModel) -> DynamicViscosity {
+
+    match region_fwd_eqn_two_phase(t, p, x) {
+        FwdEqnRegion::Region4 => {
+            let saturation_state = SaturationState::new_from_t(t);
+            model.mu_two_phase(&saturation_state, x)
+        },
+        _ => mu_tp_eqm_two_phase(t, p, x),
+    }
+}
+
+/// returns lambda (thermal conductivity) given temperature, 
+/// pressure and steam quality, using the mixing model in region 4
+///
+/// outside region 4, this is the same as lambda_tp_eqm_two_phase
+pub fn lambda_tp_eqm_two_phase_with_mixing_model(t: ThermodynamicTemperature,
+    p: Pressure,
+    x: f64,
+    model: TwoPhaseMixingModel) -> ThermalConductivity {
+
+    match region_fwd_eqn_two_phase(t, p, x) {
+        FwdEqnRegion::Region4 => {
+            let saturation_state = SaturationState::new_from_t(t);
+            model.lambda_two_phase(&saturation_state, x)
+        },
+        _ => lambda_tp_eqm_two_phase(t, p, x),
+    }
+}
@@ -0,0 +1,260 @@
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::thermal_conductivity::watt_per_meter_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::dynamic_viscosity::mu_rho_t_eqm;
+use crate::errors::SteamTableError;
+use crate::region_1_subcooled_liquid::h_tp_1;
+use crate::region_2_vapour::{cp_tp_2_metastable, cv_tp_2_metastable, h_tp_2, h_tp_2_metastable, kappa_t_tp_2_metastable, v_tp_2_metastable, InversePressure};
+use crate::region_4_vap_liq_equilibrium::{sat_pressure_4, sat_temp_4};
+use crate::thermal_conductivity::{lambda_0, lambda_1, lambda_2_crit_enhancement_term_rho_t};
+
+/// fallible versions of the metastable (T,p) flash functions
+/// which return a SteamTableError rather than panicking
+pub mod try_flash;
+pub use try_flash::*;
+
+/// steam quality of the equilibrium moisture line which
+/// forms the lower bound of the metastable vapour region
+/// (5% moisture, ie x = 0.95)
+const METASTABLE_VAPOUR_MIN_EQUILIBRIUM_QUALITY: f64 = 0.95;
+const METASTABLE_BOUND_T_TOLERANCE_KELVIN: f64 = 1.0e-9;
+const METASTABLE_BOUND_MAX_ITERATIONS: usize = 200;
+
+/// checks if a (T,p) point is within the metastable
+/// vapour region
+///
+/// that is, from the saturated vapour line down to the
+/// 5% equilibrium moisture line, for pressures from
+/// the saturation pressure at 273.15 K up to 10 MPa
+pub fn is_metastable_vapour(t: ThermodynamicTemperature, p: Pressure) -> bool {
+    return try_check_metastable_vapour_range(t, p).is_ok();
+}
+
+/// checks if a (T,p) point is within the metastable
+/// vapour region, returning an error describing which
+/// bound is exceeded if not
+///
+/// the metastable vapour region is bounded by:
+///
+/// 1. the saturation pressure at 273.15 K and 10 MPa
+/// 2. the saturated vapour line (T <= T_sat(p)),
+///    above which the vapour is superheated and
+///    pt_flash_eqm should be used instead
+/// 3. the 5% equilibrium moisture line, ie where the
+///    metastable vapour enthalpy equals the enthalpy of
+///    equilibrium wet steam with x = 0.95 at the same pressure
+pub fn try_check_metastable_vapour_range(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<(), SteamTableError> {
+
+    let p_min = sat_pressure_4(ThermodynamicTemperature::new::<kelvin>(273.15));
+    let p_max = Pressure::new::<megapascal>(10.0);
+
+    if p < p_min {
+        return Err(SteamTableError::PressureBelowBound { p, bound: p_min });
+    };
+    if p > p_max {
+        return Err(SteamTableError::PressureAboveBound { p, bound: p_max });
+    };
+
+    let t_sat = sat_temp_4(p);
+
+    if t > t_sat {
+        return Err(SteamTableError::TemperatureAboveBound { t, bound: t_sat });
+    };
+
+    let h_moisture_line = h_moisture_line_metastable(t_sat, p);
+
+    if h_tp_2_metastable(t, p) < h_moisture_line {
+        let bound = t_moisture_line_metastable(p, t, t_sat, h_moisture_line);
+        return Err(SteamTableError::TemperatureBelowBound { t, bound });
+    };
+
+    return Ok(());
+}
+
+/// enthalpy of equilibrium wet steam with x = 0.95 at
+/// a given saturation temperature and pressure
+///
+/// below 10 MPa, the saturated liquid and vapour lie in
+/// regions 1 and 2, so h' and h'' come straight from the
+/// region 1 and 2 equations at T_sat rather than building
+/// a whole SaturationState for every property call
+fn h_moisture_line_metastable(t_sat: ThermodynamicTemperature,
+    p: Pressure) -> AvailableEnergy {
+    let h_liq = h_tp_1(t_sat, p);
+    let h_vap = h_tp_2(t_sat, p);
+    let x = METASTABLE_VAPOUR_MIN_EQUILIBRIUM_QUALITY;
+
+    return h_vap * x + h_liq * (1.0 - x);
+}
+
+/// temperature of the 5% equilibrium moisture line at a
+/// given pressure, found by bisection on the metastable
+/// vapour enthalpy (which increases with temperature)
+/// between t_low and t_high
+fn t_moisture_line_metastable(p: Pressure,
+    t_low: ThermodynamicTemperature,
+    t_high: ThermodynamicTemperature,
+    h_moisture_line: AvailableEnergy) -> ThermodynamicTemperature {
+
+    let mut t_low_kelvin = t_low.get::<kelvin>();
+    let mut t_high_kelvin = t_high.get::<kelvin>();
+
+    for _ in 0..METASTABLE_BOUND_MAX_ITERATIONS {
+        let t_mid_kelvin = 0.5 * (t_low_kelvin + t_high_kelvin);
+        let t_mid = ThermodynamicTemperature::new::<kelvin>(t_mid_kelvin);
+
+        if h_tp_2_metastable(t_mid, p) < h_moisture_line {
+            t_low_kelvin = t_mid_kelvin;
+        } else {
+            t_high_kelvin = t_mid_kelvin;
+        };
+
+        if t_high_kelvin - t_low_kelvin < METASTABLE_BOUND_T_TOLERANCE_KELVIN {
+            break;
+        };
+    }
+
+    return ThermodynamicTemperature::new::<kelvin>(t_high_kelvin);
+}
+
+/// returns the specific volume of metastable vapour
+/// given temperature and pressure
+pub fn v_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> SpecificVolume {
+    match try_v_tp_metastable(t, p) {
+        Ok(v) => v,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// returns the enthalpy of metastable vapour
+/// given temperature and pressure
+pub fn h_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> AvailableEnergy {
+    match try_h_tp_metastable(t, p) {
+        Ok(h) => h,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// returns the internal energy of metastable vapour
+/// given temperature and pressure
+pub fn u_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> AvailableEnergy {
+    match try_u_tp_metastable(t, p) {
+        Ok(u) => u,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// returns the specific entropy of metastable vapour
+/// given temperature and pressure
+pub fn s_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> SpecificHeatCapacity {
+    match try_s_tp_metastable(t, p) {
+        Ok(s) => s,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// returns the isobaric heat capacity of metastable vapour
+/// given temperature and pressure
+pub fn cp_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> SpecificHeatCapacity {
+    match try_cp_tp_metastable(t, p) {
+        Ok(cp) => cp,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// returns the isochoric heat capacity of metastable vapour
+/// given temperature and pressure
+pub fn cv_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> SpecificHeatCapacity {
+    match try_cv_tp_metastable(t, p) {
+        Ok(cv) => cv,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// returns the speed of sound of metastable vapour
+/// given temperature and pressure
+pub fn w_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> Velocity {
+    match try_w_tp_metastable(t, p) {
+        Ok(w) => w,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// returns the isentropic exponent of metastable vapour
+/// given temperature and pressure
+pub fn kappa_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> Ratio {
+    match try_kappa_tp_metastable(t, p) {
+        Ok(kappa) => kappa,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// returns the isobaric cubic expansion coefficient
+/// of metastable vapour given temperature and pressure
+pub fn alpha_v_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> TemperatureCoefficient {
+    match try_alpha_v_tp_metastable(t, p) {
+        Ok(alpha_v) => alpha_v,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// returns the isothermal compressibility of metastable vapour
+/// given temperature and pressure
+pub fn kappa_t_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> InversePressure {
+    match try_kappa_t_tp_metastable(t, p) {
+        Ok(kappa_t) => kappa_t,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// returns mu, or sometimes eta (dynamic viscosity) of
+/// metastable vapour given temperature and pressure
+pub fn mu_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> DynamicViscosity {
+    match try_mu_tp_metastable(t, p) {
+        Ok(mu) => mu,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// returns lambda (thermal conductivity) of
+/// metastable vapour given temperature and pressure
+pub fn lambda_tp_metastable(t: ThermodynamicTemperature, p: Pressure) -> ThermalConductivity {
+    match try_lambda_tp_metastable(t, p) {
+        Ok(lambda) => lambda,
+        Err(error) => panic!("metastable t,p flash failed: {}", error),
+    }
+}
+
+/// viscosity of metastable vapour, using the density
+/// from the metastable region 2 equation
+///
+/// no range checks here
+pub(crate) fn mu_tp_2_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> DynamicViscosity {
+    let rho = v_tp_2_metastable(t, p).recip();
+
+    return mu_rho_t_eqm(t, rho);
+}
+
+/// thermal conductivity of metastable vapour, using the density,
+/// cp, cv and kappa_t from the metastable region 2 equation
+///
+/// no range checks here
+pub(crate) fn lambda_tp_2_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> ThermalConductivity {
+    let rho = v_tp_2_metastable(t, p).recip();
+    let cp = cp_tp_2_metastable(t, p);
+    let cv = cv_tp_2_metastable(t, p);
+    let kappa_t = kappa_t_tp_2_metastable(t, p);
+
+    let lambda_0 = lambda_0(t);
+    let lambda_1 = lambda_1(rho, t);
+    let lambda_2 = lambda_2_crit_enhancement_term_rho_t(rho, t, cp, cv, kappa_t);
+    let lambda_star = ThermalConductivity::new::<watt_per_meter_kelvin>(1.0e-3);
+
+    return lambda_star * (lambda_0 * lambda_1 + lambda_2);
+}
+
@@ -0,0 +1,102 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::region_2_vapour::{alpha_v_tp_2_metastable, cp_tp_2_metastable, cv_tp_2_metastable, h_tp_2_metastable, kappa_t_tp_2_metastable, kappa_tp_2_metastable, s_tp_2_metastable, u_tp_2_metastable, v_tp_2_metastable, w_tp_2_metastable, InversePressure};
+
+use super::{lambda_tp_2_metastable, mu_tp_2_metastable, try_check_metastable_vapour_range};
+
+/// returns the specific volume of metastable vapour
+/// given temperature and pressure
+pub fn try_v_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<SpecificVolume, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(v_tp_2_metastable(t, p));
+}
+
+/// returns the enthalpy of metastable vapour
+/// given temperature and pressure
+pub fn try_h_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<AvailableEnergy, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(h_tp_2_metastable(t, p));
+}
+
+/// returns the internal energy of metastable vapour
+/// given temperature and pressure
+pub fn try_u_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<AvailableEnergy, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(u_tp_2_metastable(t, p));
+}
+
+/// returns the specific entropy of metastable vapour
+/// given temperature and pressure
+pub fn try_s_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<SpecificHeatCapacity, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(s_tp_2_metastable(t, p));
+}
+
+/// returns the isobaric heat capacity of metastable vapour
+/// given temperature and pressure
+pub fn try_cp_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<SpecificHeatCapacity, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(cp_tp_2_metastable(t, p));
+}
+
+/// returns the isochoric heat capacity of metastable vapour
+/// given temperature and pressure
+pub fn try_cv_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<SpecificHeatCapacity, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(cv_tp_2_metastable(t, p));
+}
+
+/// returns the speed of sound of metastable vapour
+/// given temperature and pressure
+pub fn try_w_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<Velocity, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(w_tp_2_metastable(t, p));
+}
+
+/// returns the isentropic exponent of metastable vapour
+/// given temperature and pressure
+pub fn try_kappa_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<Ratio, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(kappa_tp_2_metastable(t, p));
+}
+
+/// returns the isobaric cubic expansion coefficient
+/// of metastable vapour given temperature and pressure
+pub fn try_alpha_v_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<TemperatureCoefficient, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(alpha_v_tp_2_metastable(t, p));
+}
+
+/// returns the isothermal compressibility of metastable vapour
+/// given temperature and pressure
+pub fn try_kappa_t_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<InversePressure, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(kappa_t_tp_2_metastable(t, p));
+}
+
+/// returns mu, or sometimes eta (dynamic viscosity) of
+/// metastable vapour given temperature and pressure
+pub fn try_mu_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<DynamicViscosity, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(mu_tp_2_metastable(t, p));
+}
+
+/// returns lambda (thermal conductivity) of
+/// metastable vapour given temperature and pressure
+pub fn try_lambda_tp_metastable(t: ThermodynamicTemperature,
+    p: Pressure) -> Result<ThermalConductivity, SteamTableError> {
+    try_check_metastable_vapour_range(t, p)?;
+    return Ok(lambda_tp_2_metastable(t, p));
+}
@@ -0,0 +1,54 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::flash_diagnostics::*;
+use crate::interfaces::functional_programming::ph_flash_eqm::{try_ph_flash_region, try_t_ph_eqm, try_v_ph_eqm, try_x_ph_flash};
+
+use super::*;
+
+/// (p,v) flash which also reports the region and (p,h) backward 
+/// equation subregion used, the number of illinois iterations 
+/// on enthalpy, and the specific volume residual at the 
+/// flashed state
+///
+/// the reported specific volume is that of the flashed state, 
+/// ie. the input specific volume plus the residual
+pub fn try_pv_flash_with_diagnostics(p: Pressure,
+    v: SpecificVolume) -> Result<FlashReport, SteamTableError> {
+
+    let (h, iterations) = try_h_pv_eqm_with_iterations(p, v)?;
+
+    let region = try_ph_flash_region(p, h)?;
+    let subregion = ph_backward_subregion(p, h, region);
+
+    let t = try_t_ph_eqm(p, h)?;
+    let v_flashed = try_v_ph_eqm(p, h)?;
+    let x = try_x_ph_flash(p, h)?;
+
+    let residuals = FlashResiduals {
+        v: Some(v_flashed - v),
+        ..Default::default()
+    };
+
+    return Ok(FlashReport {
+        t,
+        p,
+        v: v_flashed,
+        x,
+        region,
+        subregion: Some(subregion),
+        iterations,
+        residuals,
+        boundary_correction_applied: false,
+    });
+}
+
+/// (p,v) flash which also reports how the result was obtained,
+/// panics if the (p,v) point is outside the validity range
+pub fn pv_flash_with_diagnostics(p: Pressure,
+    v: SpecificVolume) -> FlashReport {
+    match try_pv_flash_with_diagnostics(p, v) {
+        Ok(report) => report,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
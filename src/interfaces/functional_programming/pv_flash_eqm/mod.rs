@@ -0,0 +1,369 @@
+use uom::si::available_energy::joule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::temperature_coefficient::per_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::errors::SteamTableError;
+use crate::region_1_subcooled_liquid::{alpha_v_tp_1, h_tp_1};
+use crate::region_2_vapour::h_tp_2;
+use crate::region_3_single_phase_plus_supercritical_steam::{h_3a3b_backwards_ph_boundary, t_boundary_2_3, v_ph_3a, v_ph_3b};
+use crate::region_4_vap_liq_equilibrium::sat_pressure_4;
+
+use super::ph_flash_eqm::{ph_flash_region, try_v_ph_eqm};
+use super::pt_flash_eqm::{h_tp_eqm_single_phase, FwdEqnRegion};
+
+/// fallible versions of the (p,v) flash functions
+/// which return a SteamTableError rather than panicking
+pub mod try_flash;
+pub use try_flash::*;
+
+/// (p,v) flash which also reports the region, subregion,
+/// iterations and residuals (see flash_diagnostics)
+pub mod diagnostics;
+pub use diagnostics::*;
+
+/// (p,v) flash returning all intensive properties at once
+/// (see steam_state)
+pub mod steam_state_flash;
+pub use steam_state_flash::*;
+
+/// the enthalpy iteration stops when the relative difference
+/// in specific volume is less than this,
+/// or when the enthalpy bracket is less than
+/// PV_FLASH_H_TOLERANCE_JOULE_PER_KG
+const PV_FLASH_V_REL_TOLERANCE: f64 = 1.0e-12;
+const PV_FLASH_H_TOLERANCE_JOULE_PER_KG: f64 = 1.0e-6;
+const PV_FLASH_MAX_ITERATIONS: usize = 200;
+
+/// allows the user to check which region one is in based on a
+/// (p,v) flash
+///
+/// panics if the (p,v) point is outside the validity range
+pub fn pv_flash_region(p: Pressure, v: SpecificVolume) -> FwdEqnRegion {
+    match try_pv_flash_region(p, v) {
+        Ok(region) => region,
+        Err(error) => panic!("p,v point is outside validity range: {}", error),
+    }
+}
+
+/// returns enthalpy given pressure and specific volume
+///
+/// panics if the (p,v) point is outside the validity range
+#[inline]
+pub fn h_pv_eqm(p: Pressure, v: SpecificVolume) -> AvailableEnergy {
+    match try_h_pv_eqm(p, v) {
+        Ok(h) => h,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
+
+/// returns enthalpy given pressure and specific volume
+///
+/// at constant pressure, specific volume increases with
+/// enthalpy (through the liquid, two phase and vapour regions)
+/// so we iterate on enthalpy using v_ph_eqm with a
+/// bracketed secant (illinois) method,
+///
+/// the bracket is from the enthalpy of liquid at maximum density
+/// to the enthalpy at the upper temperature bound
+/// (1073.15 K, or 2273.15 K up to 50 MPa)
+///
+/// Note: liquid water is densest at about 277 K (at atmospheric
+/// pressure), so specific volumes between that of maximum
+/// density and that at 273.15 K have two solutions,
+/// we return the one above the temperature of maximum density
+pub fn try_h_pv_eqm(p: Pressure,
+    v: SpecificVolume) -> Result<AvailableEnergy, SteamTableError> {
+    let (h, _iterations) = try_h_pv_eqm_with_iterations(p, v)?;
+
+    return Ok(h);
+}
+
+/// same as try_h_pv_eqm, but also returns the number of 
+/// illinois iterations taken, for flash diagnostics
+pub(crate) fn try_h_pv_eqm_with_iterations(p: Pressure,
+    v: SpecificVolume) -> Result<(AvailableEnergy, usize), SteamTableError> {
+
+    let lower_pressure_limit: Pressure =
+        sat_pressure_4(ThermodynamicTemperature::new::<kelvin>(273.15));
+    let upper_pressure_limit: Pressure = Pressure::new::<megapascal>(100.0);
+
+    if p < lower_pressure_limit {
+        return Err(SteamTableError::PressureBelowBound {
+            p, bound: lower_pressure_limit });
+    };
+
+    if p > upper_pressure_limit {
+        return Err(SteamTableError::PressureAboveBound {
+            p, bound: upper_pressure_limit });
+    };
+
+    // region 5 extends the upper temperature to 2273.15 K
+    // but only up to 50 MPa
+    let upper_temp_bound = if p <= Pressure::new::<megapascal>(50.0) {
+        ThermodynamicTemperature::new::<kelvin>(2273.15)
+    } else {
+        ThermodynamicTemperature::new::<kelvin>(1073.15)
+    };
+
+    let mut h_low = h_tp_1(t_max_density_region_1(p), p);
+    let mut h_high = h_tp_eqm_single_phase(upper_temp_bound, p);
+
+    let mut v_low = try_v_ph_eqm(p, h_low)?;
+    let mut v_high = try_v_ph_eqm(p, h_high)?;
+
+    if v < v_low {
+        return Err(SteamTableError::SpecificVolumeBelowBound { v, bound: v_low });
+    };
+
+    if v > v_high {
+        return Err(SteamTableError::SpecificVolumeAboveBound { v, bound: v_high });
+    };
+
+    // in region 3, we start from the subregion bracket given by 
+    // the backward equations v_ph_3a and v_ph_3b rather than 
+    // the whole enthalpy range 
+    //
+    // the bracket is only used if v_ph_eqm (which uses the same 
+    // backward equations within region 3) agrees that v lies 
+    // within it
+    if let Some((h_low_3, h_high_3)) = region_3_starting_bracket(p, v) {
+        let v_low_3 = try_v_ph_eqm(p, h_low_3)?;
+        let v_high_3 = try_v_ph_eqm(p, h_high_3)?;
+
+        if v_low_3 <= v && v <= v_high_3 {
+            h_low = h_low_3;
+            h_high = h_high_3;
+            v_low = v_low_3;
+            v_high = v_high_3;
+        };
+    };
+
+    // residuals are relative to the target specific volume
+    let mut residual_low: f64 = ((v_low - v)/v).value;
+    let mut residual_high: f64 = ((v_high - v)/v).value;
+
+    // for the illinois method, we keep track of which end
+    // of the bracket was retained in the last iteration
+    let mut last_retained_low: Option<bool> = None;
+
+    for iteration in 0..PV_FLASH_MAX_ITERATIONS {
+
+        let h_low_joule_per_kg = h_low.get::<joule_per_kilogram>();
+        let h_high_joule_per_kg = h_high.get::<joule_per_kilogram>();
+
+        // regula falsi estimate, falling back to bisection
+        // if the residuals are equal
+        let h_new_joule_per_kg = if residual_high != residual_low {
+            h_low_joule_per_kg - residual_low
+                * (h_high_joule_per_kg - h_low_joule_per_kg)
+                /(residual_high - residual_low)
+        } else {
+            0.5 * (h_low_joule_per_kg + h_high_joule_per_kg)
+        };
+
+        let h_new = AvailableEnergy::new::<joule_per_kilogram>(h_new_joule_per_kg);
+        let v_new = try_v_ph_eqm(p, h_new)?;
+        let residual_new: f64 = ((v_new - v)/v).value;
+
+        if residual_new.abs() < PV_FLASH_V_REL_TOLERANCE {
+            return Ok((h_new, iteration + 1));
+        };
+
+        if residual_new.signum() == residual_low.signum() {
+            h_low = h_new;
+            residual_low = residual_new;
+
+            // the high end was retained twice in a row,
+            // so halve its residual (illinois modification)
+            if last_retained_low == Some(false) {
+                residual_high *= 0.5;
+            };
+            last_retained_low = Some(false);
+        } else {
+            h_high = h_new;
+            residual_high = residual_new;
+
+            if last_retained_low == Some(true) {
+                residual_low *= 0.5;
+            };
+            last_retained_low = Some(true);
+        };
+
+        let bracket = (h_high - h_low).get::<joule_per_kilogram>().abs();
+        if bracket < PV_FLASH_H_TOLERANCE_JOULE_PER_KG {
+            return Ok((h_new, iteration + 1));
+        };
+    }
+
+    return Err(SteamTableError::FlashDidNotConverge {
+        flash: "(p,v)", iterations: PV_FLASH_MAX_ITERATIONS });
+}
+
+/// above the critical pressure, region 3 lies between the 
+/// 623.15 K isotherm (region 1 and 3 boundary) and the B23 line 
+/// (region 2 and 3 boundary), and is split into subregions 3a and 3b 
+/// at h_3a3b_backwards_ph_boundary
+///
+/// the backward equations v_ph_3a and v_ph_3b evaluated at these 
+/// enthalpies bound the specific volume of each subregion, 
+/// so if v lies within region 3, this returns the enthalpy 
+/// bracket of the subregion containing v
+///
+/// below the critical pressure, the 3a and 3b boundary may lie 
+/// within the vapour dome, so no bracket is returned
+fn region_3_starting_bracket(p: Pressure,
+    v: SpecificVolume) -> Option<(AvailableEnergy, AvailableEnergy)> {
+
+    if p <= Pressure::new::<megapascal>(22.064) {
+        return None;
+    };
+
+    let h_13 = h_tp_1(ThermodynamicTemperature::new::<kelvin>(623.15), p);
+    let h_3ab = h_3a3b_backwards_ph_boundary(p);
+    let h_23 = h_tp_2(t_boundary_2_3(p), p);
+
+    if v_ph_3a(p, h_13) <= v && v <= v_ph_3a(p, h_3ab) {
+        return Some((h_13, h_3ab));
+    };
+
+    if v_ph_3b(p, h_3ab) <= v && v <= v_ph_3b(p, h_23) {
+        return Some((h_3ab, h_23));
+    };
+
+    return None;
+}
+
+/// allows the user to check which region one is in based on a
+/// (p,v) flash
+///
+/// returns an error rather than panicking if the (p,v) point
+/// is outside the validity range
+pub fn try_pv_flash_region(p: Pressure,
+    v: SpecificVolume) -> Result<FwdEqnRegion, SteamTableError> {
+    let h = try_h_pv_eqm(p, v)?;
+
+    return Ok(ph_flash_region(p, h));
+}
+
+/// temperature of maximum density for liquid water at a
+/// given pressure (where alpha_v = 0)
+///
+/// at high pressures, or pressures where water at the
+/// temperature of maximum density is vapour, this is just 273.15 K
+fn t_max_density_region_1(p: Pressure) -> ThermodynamicTemperature {
+
+    let alpha_v = |t_kelvin: f64| -> f64 {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        alpha_v_tp_1(t, p).get::<per_kelvin>()
+    };
+
+    let mut t_low = 273.15;
+    let mut t_high = 300.0;
+
+    if alpha_v(t_low) >= 0.0 {
+        return ThermodynamicTemperature::new::<kelvin>(t_low);
+    };
+
+    // bisection on alpha_v, which is negative below the
+    // temperature of maximum density
+    for _ in 0..PV_FLASH_MAX_ITERATIONS {
+        let t_mid = 0.5 * (t_low + t_high);
+
+        if alpha_v(t_mid) < 0.0 {
+            t_low = t_mid;
+        } else {
+            t_high = t_mid;
+        };
+
+        if t_high - t_low < 1.0e-9 {
+            break;
+        };
+    }
+
+    let t_max_density = ThermodynamicTemperature::new::<kelvin>(t_low);
+
+    // if the water is vapour at this temperature,
+    // (pressures just above the triple point)
+    // then just use 273.15 K
+    if p < sat_pressure_4(t_max_density) {
+        return ThermodynamicTemperature::new::<kelvin>(273.15);
+    };
+
+    return t_max_density;
+}
+
+/// returns temperature given pressure and specific volume
+pub fn t_pv_eqm(p: Pressure, v: SpecificVolume) -> ThermodynamicTemperature {
+    match try_t_pv_eqm(p, v) {
+        Ok(t) => t,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
+
+/// returns internal energy given pressure and specific volume
+pub fn u_pv_eqm(p: Pressure, v: SpecificVolume) -> AvailableEnergy {
+    match try_u_pv_eqm(p, v) {
+        Ok(u) => u,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
+
+/// returns entropy given pressure and specific volume
+pub fn s_pv_eqm(p: Pressure, v: SpecificVolume) -> SpecificHeatCapacity {
+    match try_s_pv_eqm(p, v) {
+        Ok(s) => s,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
+
+/// returns steam quality given pressure and specific volume
+pub fn x_pv_flash(p: Pressure, v: SpecificVolume) -> f64 {
+    match try_x_pv_flash(p, v) {
+        Ok(x) => x,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
+
+/// returns cp given pressure and specific volume
+pub fn cp_pv_eqm(p: Pressure, v: SpecificVolume) -> SpecificHeatCapacity {
+    match try_cp_pv_eqm(p, v) {
+        Ok(cp) => cp,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
+
+/// returns cv given pressure and specific volume
+pub fn cv_pv_eqm(p: Pressure, v: SpecificVolume) -> SpecificHeatCapacity {
+    match try_cv_pv_eqm(p, v) {
+        Ok(cv) => cv,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
+
+/// returns w (speed of sound) given pressure and specific volume
+pub fn w_pv_eqm(p: Pressure, v: SpecificVolume) -> Velocity {
+    match try_w_pv_eqm(p, v) {
+        Ok(w) => w,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
+
+/// returns mu, or sometimes eta (dynamic viscosity) given
+/// pressure and specific volume
+pub fn mu_pv_eqm(p: Pressure, v: SpecificVolume) -> DynamicViscosity {
+    match try_mu_pv_eqm(p, v) {
+        Ok(mu) => mu,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
+
+/// returns lambda (thermal conductivity) given
+/// pressure and specific volume
+pub fn lambda_pv_eqm(p: Pressure, v: SpecificVolume) -> ThermalConductivity {
+    match try_lambda_pv_eqm(p, v) {
+        Ok(lambda) => lambda,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
@@ -0,0 +1,35 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::ph_flash_eqm;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+
+use super::*;
+
+/// (p,v) flash returning all intensive properties at once
+///
+/// enthalpy is iterated once (see try_h_pv_eqm), and then 
+/// the (p,h) flash is used for the rest
+///
+/// the pressure and specific volume of the returned state are 
+/// the input pressure and specific volume
+pub fn try_flash(p: Pressure,
+    v: SpecificVolume) -> Result<SteamState, SteamTableError> {
+
+    let h = try_h_pv_eqm(p, v)?;
+    let mut state = ph_flash_eqm::try_flash(p, h)?;
+
+    state.v = v;
+
+    return Ok(state);
+}
+
+/// (p,v) flash returning all intensive properties at once,
+/// panics if the (p,v) point is outside the validity range
+pub fn flash(p: Pressure,
+    v: SpecificVolume) -> SteamState {
+    match try_flash(p, v) {
+        Ok(state) => state,
+        Err(error) => panic!("p,v flash failed: {}", error),
+    }
+}
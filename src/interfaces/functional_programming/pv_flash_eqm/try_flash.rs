@@ -0,0 +1,89 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::ph_flash_eqm::{try_cp_ph_eqm, try_cv_ph_eqm, try_lambda_ph_eqm, try_mu_ph_eqm, try_s_ph_eqm, try_t_ph_eqm, try_u_ph_eqm, try_w_ph_eqm, try_x_ph_flash};
+
+use super::try_h_pv_eqm;
+
+/// returns temperature given pressure and specific volume
+/// uses ph flash
+pub fn try_t_pv_eqm(p: Pressure,
+    v: SpecificVolume) -> Result<ThermodynamicTemperature, SteamTableError> {
+    let h = try_h_pv_eqm(p, v)?;
+
+    return try_t_ph_eqm(p, h);
+}
+
+/// returns internal energy given pressure and specific volume
+/// uses ph flash
+pub fn try_u_pv_eqm(p: Pressure,
+    v: SpecificVolume) -> Result<AvailableEnergy, SteamTableError> {
+    let h = try_h_pv_eqm(p, v)?;
+
+    return try_u_ph_eqm(p, h);
+}
+
+/// returns entropy given pressure and specific volume
+/// uses ph flash
+pub fn try_s_pv_eqm(p: Pressure,
+    v: SpecificVolume) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let h = try_h_pv_eqm(p, v)?;
+
+    return try_s_ph_eqm(p, h);
+}
+
+/// returns steam quality given pressure and specific volume
+/// uses ph flash
+pub fn try_x_pv_flash(p: Pressure,
+    v: SpecificVolume) -> Result<f64, SteamTableError> {
+    let h = try_h_pv_eqm(p, v)?;
+
+    return try_x_ph_flash(p, h);
+}
+
+/// returns cp given pressure and specific volume
+/// uses ph flash
+pub fn try_cp_pv_eqm(p: Pressure,
+    v: SpecificVolume) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let h = try_h_pv_eqm(p, v)?;
+
+    return try_cp_ph_eqm(p, h);
+}
+
+/// returns cv given pressure and specific volume
+/// uses ph flash
+pub fn try_cv_pv_eqm(p: Pressure,
+    v: SpecificVolume) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let h = try_h_pv_eqm(p, v)?;
+
+    return try_cv_ph_eqm(p, h);
+}
+
+/// returns w (speed of sound) given pressure and specific volume
+/// uses ph flash
+pub fn try_w_pv_eqm(p: Pressure,
+    v: SpecificVolume) -> Result<Velocity, SteamTableError> {
+    let h = try_h_pv_eqm(p, v)?;
+
+    return try_w_ph_eqm(p, h);
+}
+
+/// returns mu, or sometimes eta (dynamic viscosity) given
+/// pressure and specific volume
+/// uses ph flash
+pub fn try_mu_pv_eqm(p: Pressure,
+    v: SpecificVolume) -> Result<DynamicViscosity, SteamTableError> {
+    let h = try_h_pv_eqm(p, v)?;
+
+    return try_mu_ph_eqm(p, h);
+}
+
+/// returns lambda (thermal conductivity) given
+/// pressure and specific volume
+/// uses ph flash
+pub fn try_lambda_pv_eqm(p: Pressure,
+    v: SpecificVolume) -> Result<ThermalConductivity, SteamTableError> {
+    let h = try_h_pv_eqm(p, v)?;
+
+    return try_lambda_ph_eqm(p, h);
+}
@@ -0,0 +1,226 @@
+use uom::si::f64::*;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::constants::{p_crit_water, t_crit_water};
+use crate::dynamic_viscosity::mu_rho_t_eqm;
+use crate::errors::SteamTableError;
+use crate::region_4_vap_liq_equilibrium::{sat_pressure_4, sat_temp_4};
+use crate::surface_tension::water_surf_tension;
+
+use super::pt_flash_eqm::FwdEqnRegion;
+use super::trho_flash_eqm::{cp_trho_single_phase, cv_trho_single_phase, h_trho_single_phase, lambda_trho_single_phase, s_trho_single_phase, sat_liq_vap_regions_trho, sat_p_rho_liq_vap_trho, u_trho_single_phase, w_trho_single_phase};
+
+/// properties of a saturated phase (either saturated liquid
+/// or saturated vapour) at a given saturation temperature
+/// and pressure
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaturatedPhaseProperties {
+    /// specific enthalpy
+    pub h: AvailableEnergy,
+    /// specific entropy
+    pub s: SpecificHeatCapacity,
+    /// specific volume
+    pub v: SpecificVolume,
+    /// specific internal energy
+    pub u: AvailableEnergy,
+    /// isobaric heat capacity
+    pub cp: SpecificHeatCapacity,
+    /// isochoric heat capacity
+    pub cv: SpecificHeatCapacity,
+    /// speed of sound
+    pub w: Velocity,
+    /// dynamic viscosity
+    pub mu: DynamicViscosity,
+    /// thermal conductivity
+    pub lambda: ThermalConductivity,
+}
+
+/// saturated liquid and vapour properties at a given
+/// saturation temperature or pressure,
+/// from the triple point (273.15 K) up to the critical point
+///
+/// below 623.15 K, the saturated liquid and vapour
+/// use regions 1 and 2,
+/// otherwise the region 3 v(T,p) subregion equations
+/// (3c, 3s, 3u, 3y for liquid and 3t, 3r, 3x, 3z for vapour)
+/// are used to get the saturated densities,
+/// and the region 3 equations are used from there
+///
+/// mixture properties at a given steam quality can be
+/// obtained via the *_mixture methods, so this doubles as
+/// a (p,x) or (T,x) flash
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaturationState {
+    /// saturation pressure
+    pub p_sat: Pressure,
+    /// saturation temperature
+    pub t_sat: ThermodynamicTemperature,
+    /// saturated liquid properties (denoted with ')
+    pub liquid: SaturatedPhaseProperties,
+    /// saturated vapour properties (denoted with '')
+    pub vapour: SaturatedPhaseProperties,
+    /// surface tension, units are N/m
+    /// (RadiantExposure in uom, see water_surf_tension)
+    pub surface_tension: RadiantExposure,
+}
+
+impl SaturationState {
+
+    /// saturation state given saturation temperature
+    ///
+    /// panics if temperature is outside 273.15 K to the
+    /// critical temperature
+    pub fn new_from_t(t: ThermodynamicTemperature) -> Self {
+        match Self::try_new_from_t(t) {
+            Ok(sat_state) => sat_state,
+            Err(error) => panic!("t,x flash failed: {}", error),
+        }
+    }
+
+    /// saturation state given saturation pressure
+    ///
+    /// panics if pressure is outside the saturation pressure
+    /// at 273.15 K to the critical pressure
+    pub fn new_from_p(p: Pressure) -> Self {
+        match Self::try_new_from_p(p) {
+            Ok(sat_state) => sat_state,
+            Err(error) => panic!("p,x flash failed: {}", error),
+        }
+    }
+
+    /// saturation state given saturation temperature
+    ///
+    /// returns an error if temperature is outside 273.15 K to the
+    /// critical temperature
+    pub fn try_new_from_t(t: ThermodynamicTemperature) -> Result<Self, SteamTableError> {
+
+        let t_min = ThermodynamicTemperature::new::<kelvin>(273.15);
+        let t_max = t_crit_water();
+
+        if t < t_min {
+            return Err(SteamTableError::TemperatureBelowBound { t, bound: t_min });
+        };
+        if t > t_max {
+            return Err(SteamTableError::TemperatureAboveBound { t, bound: t_max });
+        };
+
+        return Ok(Self::new_from_valid_t(t));
+    }
+
+    /// saturation state given saturation pressure
+    ///
+    /// returns an error if pressure is outside the saturation pressure
+    /// at 273.15 K to the critical pressure
+    pub fn try_new_from_p(p: Pressure) -> Result<Self, SteamTableError> {
+
+        let p_min = sat_pressure_4(ThermodynamicTemperature::new::<kelvin>(273.15));
+        let p_max = p_crit_water();
+
+        if p < p_min {
+            return Err(SteamTableError::PressureBelowBound { p, bound: p_min });
+        };
+        if p > p_max {
+            return Err(SteamTableError::PressureAboveBound { p, bound: p_max });
+        };
+
+        // the saturation temperature from the region 4 equation
+        // may be a tiny bit above the critical temperature
+        // at the critical pressure, so we cap it here
+        let t = sat_temp_4(p).min(t_crit_water());
+
+        return Ok(Self::new_from_valid_t(t));
+    }
+
+    /// assembles the saturation state given a saturation temperature
+    /// which is already within the validity range
+    fn new_from_valid_t(t: ThermodynamicTemperature) -> Self {
+
+        let (p_sat, rho_liq, rho_vap) = sat_p_rho_liq_vap_trho(t);
+        let (liq_region, vap_region) = sat_liq_vap_regions_trho(t);
+
+        let liquid = saturated_phase_properties(liq_region, t, p_sat, rho_liq);
+        let vapour = saturated_phase_properties(vap_region, t, p_sat, rho_vap);
+
+        return Self {
+            p_sat,
+            t_sat: t,
+            liquid,
+            vapour,
+            surface_tension: water_surf_tension(t),
+        };
+    }
+
+    /// latent heat of vaporisation, h'' - h'
+    pub fn latent_heat(&self) -> AvailableEnergy {
+        return self.vapour.h - self.liquid.h;
+    }
+
+    /// specific enthalpy of a vapour liquid mixture at
+    /// steam quality x
+    ///
+    /// x is clamped between 0 and 1, as in the two phase pt flash
+    pub fn h_mixture(&self, x: f64) -> AvailableEnergy {
+        let x = x.clamp(0.0, 1.0);
+        return self.vapour.h * x + self.liquid.h * (1.0 - x);
+    }
+
+    /// specific entropy of a vapour liquid mixture at
+    /// steam quality x
+    ///
+    /// x is clamped between 0 and 1, as in the two phase pt flash
+    pub fn s_mixture(&self, x: f64) -> SpecificHeatCapacity {
+        let x = x.clamp(0.0, 1.0);
+        return self.vapour.s * x + self.liquid.s * (1.0 - x);
+    }
+
+    /// specific volume of a vapour liquid mixture at
+    /// steam quality x
+    ///
+    /// x is clamped between 0 and 1, as in the two phase pt flash
+    pub fn v_mixture(&self, x: f64) -> SpecificVolume {
+        let x = x.clamp(0.0, 1.0);
+        return self.vapour.v * x + self.liquid.v * (1.0 - x);
+    }
+
+    /// specific internal energy of a vapour liquid mixture at
+    /// steam quality x
+    ///
+    /// x is clamped between 0 and 1, as in the two phase pt flash
+    pub fn u_mixture(&self, x: f64) -> AvailableEnergy {
+        let x = x.clamp(0.0, 1.0);
+        return self.vapour.u * x + self.liquid.u * (1.0 - x);
+    }
+
+    /// density of a vapour liquid mixture at steam quality x
+    pub fn rho_mixture(&self, x: f64) -> MassDensity {
+        return self.v_mixture(x).recip();
+    }
+}
+
+/// properties of a saturated phase given the region,
+/// saturation temperature, saturation pressure and density
+/// of that phase
+fn saturated_phase_properties(region: FwdEqnRegion,
+    t: ThermodynamicTemperature,
+    p_sat: Pressure,
+    rho: MassDensity) -> SaturatedPhaseProperties {
+
+    return SaturatedPhaseProperties {
+        h: h_trho_single_phase(region, t, p_sat, rho),
+        s: s_trho_single_phase(region, t, p_sat, rho),
+        v: rho.recip(),
+        u: u_trho_single_phase(region, t, p_sat, rho),
+        cp: cp_trho_single_phase(region, t, p_sat, rho),
+        cv: cv_trho_single_phase(region, t, p_sat, rho),
+        w: w_trho_single_phase(region, t, p_sat, rho),
+        mu: mu_rho_t_eqm(t, rho),
+        lambda: lambda_trho_single_phase(region, t, p_sat, rho),
+    };
+}
+
+/// slopes of the saturation line (dp_sat/dT, dT_sat/dp) and of
+/// the saturated liquid and vapour properties along it
+pub mod saturation_derivatives;
+pub use saturation_derivatives::*;
@@ -0,0 +1,190 @@
+use uom::si::f64::*;
+use uom::si::ratio::ratio;
+use uom::si::temperature_coefficient::per_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::{Quantity, ISQ, SI};
+use uom::typenum::{N1, N2, P2, P3, P4, Z0};
+
+use crate::constants::{p_crit_water, t_crit_water};
+use crate::errors::SteamTableError;
+use crate::region_4_vap_liq_equilibrium::{dp_sat_dt_4, sat_pressure_4, sat_temp_4, PressurePerTemperature, TemperaturePerPressure};
+
+use super::super::pt_flash_eqm::FwdEqnRegion;
+use super::super::trho_flash_eqm::{alpha_v_trho_single_phase, cp_trho_single_phase, kappa_t_trho_single_phase, sat_liq_vap_regions_trho, sat_p_rho_liq_vap_trho};
+
+/// units of dv/dp, (m3/kg)/Pa
+pub type SpecificVolumePerPressure = Quantity<ISQ<P4, N2, P2, Z0, Z0, Z0, Z0>, SI<f64>, f64>;
+
+/// units of ds/dp, (J/(kg K))/Pa = m3/(kg K)
+pub type SpecificEntropyPerPressure = Quantity<ISQ<P3, N1, Z0, Z0, N1, Z0, Z0>, SI<f64>, f64>;
+
+/// slopes of the saturation line and of the saturated liquid 
+/// and vapour properties along it, with respect to saturation 
+/// pressure, as needed by drift flux and two fluid codes
+///
+/// dp_sat/dT and dT_sat/dp are from the region 4 saturation 
+/// equation (see region_4_vap_liq_equilibrium::dp_sat_dt_4), 
+/// and the properties of each phase change along the 
+/// saturation line as:
+///
+/// dv/dp = v (alpha_v dT_sat/dp - kappa_t)
+/// ds/dp = cp/T dT_sat/dp - v alpha_v
+/// dh/dp = cp dT_sat/dp + v (1 - T alpha_v)
+///
+/// where cp, alpha_v and kappa_t are from the region 1, 2 or 3
+/// forward equations of each phase (see SaturationState)
+///
+/// the phase derivatives grow without bound towards the critical
+/// point, where the liquid and vapour become the same
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaturationDerivatives {
+    /// saturation pressure
+    pub p_sat: Pressure,
+    /// saturation temperature
+    pub t_sat: ThermodynamicTemperature,
+    /// slope of the saturation pressure curve
+    pub dp_sat_dt: PressurePerTemperature,
+    /// slope of the saturation temperature curve
+    pub dt_sat_dp: TemperaturePerPressure,
+    /// dh'/dp, units are (J/kg)/Pa, which is m3/kg
+    pub dh_liq_dp: SpecificVolume,
+    /// dh''/dp, units are (J/kg)/Pa, which is m3/kg
+    pub dh_vap_dp: SpecificVolume,
+    /// dv'/dp
+    pub dv_liq_dp: SpecificVolumePerPressure,
+    /// dv''/dp
+    pub dv_vap_dp: SpecificVolumePerPressure,
+    /// ds'/dp
+    pub ds_liq_dp: SpecificEntropyPerPressure,
+    /// ds''/dp
+    pub ds_vap_dp: SpecificEntropyPerPressure,
+}
+
+impl SaturationDerivatives {
+
+    /// saturation line derivatives given saturation temperature
+    ///
+    /// panics if temperature is outside 273.15 K to the
+    /// critical temperature
+    pub fn new_from_t(t: ThermodynamicTemperature) -> Self {
+        match Self::try_new_from_t(t) {
+            Ok(derivatives) => derivatives,
+            Err(error) => panic!("saturation derivatives failed: {}", error),
+        }
+    }
+
+    /// saturation line derivatives given saturation pressure
+    ///
+    /// panics if pressure is outside the saturation pressure
+    /// at 273.15 K to the critical pressure
+    pub fn new_from_p(p: Pressure) -> Self {
+        match Self::try_new_from_p(p) {
+            Ok(derivatives) => derivatives,
+            Err(error) => panic!("saturation derivatives failed: {}", error),
+        }
+    }
+
+    /// saturation line derivatives given saturation temperature
+    ///
+    /// returns an error if temperature is outside 273.15 K to the
+    /// critical temperature
+    pub fn try_new_from_t(t: ThermodynamicTemperature) -> Result<Self, SteamTableError> {
+
+        let t_min = ThermodynamicTemperature::new::<kelvin>(273.15);
+        let t_max = t_crit_water();
+
+        if t < t_min {
+            return Err(SteamTableError::TemperatureBelowBound { t, bound: t_min });
+        };
+        if t > t_max {
+            return Err(SteamTableError::TemperatureAboveBound { t, bound: t_max });
+        };
+
+        return Ok(Self::new_from_valid_t(t));
+    }
+
+    /// saturation line derivatives given saturation pressure
+    ///
+    /// returns an error if pressure is outside the saturation pressure
+    /// at 273.15 K to the critical pressure
+    pub fn try_new_from_p(p: Pressure) -> Result<Self, SteamTableError> {
+
+        let p_min = sat_pressure_4(ThermodynamicTemperature::new::<kelvin>(273.15));
+        let p_max = p_crit_water();
+
+        if p < p_min {
+            return Err(SteamTableError::PressureBelowBound { p, bound: p_min });
+        };
+        if p > p_max {
+            return Err(SteamTableError::PressureAboveBound { p, bound: p_max });
+        };
+
+        // capped at the critical temperature, as in SaturationState
+        let t = sat_temp_4(p).min(t_crit_water());
+
+        return Ok(Self::new_from_valid_t(t));
+    }
+
+    /// assembles the saturation line derivatives given a 
+    /// saturation temperature which is already within the validity range
+    fn new_from_valid_t(t: ThermodynamicTemperature) -> Self {
+
+        let (p_sat, rho_liq, rho_vap) = sat_p_rho_liq_vap_trho(t);
+        let (liq_region, vap_region) = sat_liq_vap_regions_trho(t);
+
+        let dp_sat_dt = dp_sat_dt_4(t);
+        let dt_sat_dp = dp_sat_dt.recip();
+
+        let (dh_liq_dp, dv_liq_dp, ds_liq_dp) = saturated_phase_derivatives(
+            liq_region, t, p_sat, rho_liq, dt_sat_dp);
+        let (dh_vap_dp, dv_vap_dp, ds_vap_dp) = saturated_phase_derivatives(
+            vap_region, t, p_sat, rho_vap, dt_sat_dp);
+
+        return Self {
+            p_sat,
+            t_sat: t,
+            dp_sat_dt,
+            dt_sat_dp,
+            dh_liq_dp,
+            dh_vap_dp,
+            dv_liq_dp,
+            dv_vap_dp,
+            ds_liq_dp,
+            ds_vap_dp,
+        };
+    }
+
+    /// d(h''-h')/dp, slope of the latent heat of vaporisation
+    pub fn d_latent_heat_dp(&self) -> SpecificVolume {
+        return self.dh_vap_dp - self.dh_liq_dp;
+    }
+}
+
+/// dh/dp, dv/dp and ds/dp of a saturated phase along the 
+/// saturation line given the region, saturation temperature, 
+/// saturation pressure, density of that phase and dT_sat/dp
+fn saturated_phase_derivatives(region: FwdEqnRegion,
+    t: ThermodynamicTemperature,
+    p_sat: Pressure,
+    rho: MassDensity,
+    dt_sat_dp: TemperaturePerPressure) 
+    -> (SpecificVolume, SpecificVolumePerPressure, SpecificEntropyPerPressure) {
+
+    let v = rho.recip();
+    let cp = cp_trho_single_phase(region, t, p_sat, rho);
+    let alpha_v = alpha_v_trho_single_phase(region, t, p_sat, rho);
+    let kappa_t = kappa_t_trho_single_phase(region, t, p_sat, rho);
+
+    // 1/T and T alpha_v, 
+    // (uom does not divide by absolute temperatures)
+    let t_kelvin = t.get::<kelvin>();
+    let inverse_t = TemperatureCoefficient::new::<per_kelvin>(t_kelvin.recip());
+    let t_alpha_v = Ratio::new::<ratio>(t_kelvin * alpha_v.get::<per_kelvin>());
+
+    let dv_dp: SpecificVolumePerPressure = v * (alpha_v * dt_sat_dp - kappa_t);
+    let ds_dp: SpecificEntropyPerPressure = cp * inverse_t * dt_sat_dp - v * alpha_v;
+    let dh_dp: SpecificVolume = cp * dt_sat_dp + v * (Ratio::new::<ratio>(1.0) - t_alpha_v);
+
+    return (dh_dp, dv_dp, ds_dp);
+}
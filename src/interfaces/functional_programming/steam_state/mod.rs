@@ -0,0 +1,237 @@
+use uom::si::f64::*;
+use uom::si::ratio::ratio;
+use uom::si::thermal_conductivity::watt_per_meter_kelvin;
+
+use crate::constants::t_crit_water;
+use crate::dynamic_viscosity::mu_rho_t_eqm;
+use crate::region_1_subcooled_liquid::{alpha_v_tp_1, cp_tp_1, cv_tp_1, h_tp_1, kappa_t_tp_1, kappa_tp_1, s_tp_1, u_tp_1, w_tp_1, InversePressure};
+use crate::region_2_vapour::{alpha_v_tp_2, cp_tp_2, cv_tp_2, h_tp_2, kappa_t_tp_2, kappa_tp_2, s_tp_2, u_tp_2, w_tp_2};
+use crate::region_3_single_phase_plus_supercritical_steam::{alpha_v_rho_t_3, cp_rho_t_3, cv_rho_t_3, h_rho_t_3, kappa_rho_t_3, kappa_t_rho_t_3, s_rho_t_3, u_rho_t_3, w_rho_t_3};
+use crate::region_5_steam_at_800_plus_degc::{alpha_v_tp_5, cp_tp_5, cv_tp_5, h_tp_5, kappa_t_tp_5, kappa_tp_5, s_tp_5, u_tp_5, w_tp_5};
+use crate::surface_tension::water_surf_tension;
+use crate::thermal_conductivity::{lambda_0, lambda_1, lambda_2_crit_enhancement_term_rho_t};
+
+use super::pt_flash_eqm::FwdEqnRegion;
+use super::two_phase_equilibrium_derivatives::TwoPhaseEquilibriumDerivatives;
+use super::trho_flash_eqm::{sat_liq_vap_regions_trho, sat_p_rho_liq_vap_trho};
+
+/// all intensive properties of water/steam at a given state,
+/// returned by the flash and try_flash functions in the
+/// (p,h), (p,s), (T,p) and (h,s) flash modules
+///
+/// the region and backward equations are evaluated once,
+/// and all properties are obtained from the forward equations
+/// at the flashed temperature and density, which is much cheaper
+/// than calling the *_ph_eqm (or similar) functions one by one
+///
+/// in region 4, properties are evaluated for the saturated
+/// liquid and vapour (as in the (T,rho) flash) and interpolated
+/// using steam quality
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SteamState {
+    /// the IF97 region of the forward equations used,
+    /// None for states from property backends which do not
+    /// use the IF97 regions (see SteamPropertyProvider)
+    pub region: Option<FwdEqnRegion>,
+    /// temperature
+    pub t: ThermodynamicTemperature,
+    /// pressure
+    pub p: Pressure,
+    /// specific volume
+    pub v: SpecificVolume,
+    /// specific enthalpy
+    pub h: AvailableEnergy,
+    /// specific internal energy
+    pub u: AvailableEnergy,
+    /// specific entropy
+    pub s: SpecificHeatCapacity,
+    /// steam quality
+    pub x: f64,
+    /// isobaric heat capacity
+    pub cp: SpecificHeatCapacity,
+    /// isochoric heat capacity
+    pub cv: SpecificHeatCapacity,
+    /// speed of sound
+    pub w: Velocity,
+    /// isentropic exponent
+    pub kappa: Ratio,
+    /// isobaric cubic expansion coefficient
+    pub alpha_v: TemperatureCoefficient,
+    /// isothermal compressibility
+    pub kappa_t: InversePressure,
+    /// dynamic viscosity
+    pub mu: DynamicViscosity,
+    /// thermal conductivity
+    pub lambda: ThermalConductivity,
+    /// surface tension, units are N/m
+    /// (RadiantExposure in uom, see water_surf_tension),
+    /// None above the critical temperature
+    pub sigma: Option<RadiantExposure>,
+    /// prandtl number, mu cp/lambda
+    pub prandtl: Ratio,
+}
+
+impl SteamState {
+
+    /// density
+    pub fn rho(&self) -> MassDensity {
+        return self.v.recip();
+    }
+
+    /// evaluates all properties for a single phase region
+    /// (1, 2, 3 or 5) given temperature, pressure and density
+    ///
+    /// regions 1, 2 and 5 use (T,p), region 3 uses (T,rho)
+    pub(crate) fn new_single_phase(region: FwdEqnRegion,
+        t: ThermodynamicTemperature,
+        p: Pressure,
+        rho: MassDensity,
+        x: f64) -> Self {
+
+        let (h, u, s, cp, cv, w, kappa, alpha_v, kappa_t) = match region {
+            FwdEqnRegion::Region1 => (h_tp_1(t, p), u_tp_1(t, p), s_tp_1(t, p),
+                cp_tp_1(t, p), cv_tp_1(t, p), w_tp_1(t, p), kappa_tp_1(t, p),
+                alpha_v_tp_1(t, p), kappa_t_tp_1(t, p)),
+            FwdEqnRegion::Region2 => (h_tp_2(t, p), u_tp_2(t, p), s_tp_2(t, p),
+                cp_tp_2(t, p), cv_tp_2(t, p), w_tp_2(t, p), kappa_tp_2(t, p),
+                alpha_v_tp_2(t, p), kappa_t_tp_2(t, p)),
+            FwdEqnRegion::Region3 => (h_rho_t_3(rho, t), u_rho_t_3(rho, t), s_rho_t_3(rho, t),
+                cp_rho_t_3(rho, t), cv_rho_t_3(rho, t), w_rho_t_3(rho, t),
+                Ratio::new::<ratio>(kappa_rho_t_3(rho, t)),
+                alpha_v_rho_t_3(rho, t), kappa_t_rho_t_3(rho, t)),
+            FwdEqnRegion::Region4 => unreachable!("region 4 is split into liquid and vapour"),
+            FwdEqnRegion::Region5 => (h_tp_5(t, p), u_tp_5(t, p), s_tp_5(t, p),
+                cp_tp_5(t, p), cv_tp_5(t, p), w_tp_5(t, p), kappa_tp_5(t, p),
+                alpha_v_tp_5(t, p), kappa_t_tp_5(t, p)),
+        };
+
+        // the critical enhancement term needs cp, cv and kappa_t,
+        // which we already have
+        let lambda_2 = lambda_2_crit_enhancement_term_rho_t(rho, t, cp, cv, kappa_t);
+        let lambda = lambda_from_lambda_2(t, rho, lambda_2);
+        let mu = mu_rho_t_eqm(t, rho);
+
+        return Self {
+            region: Some(region),
+            t,
+            p,
+            v: rho.recip(),
+            h,
+            u,
+            s,
+            x,
+            cp,
+            cv,
+            w,
+            kappa,
+            alpha_v,
+            kappa_t,
+            mu,
+            lambda,
+            sigma: surface_tension(t),
+            prandtl: mu * cp / lambda,
+        };
+    }
+
+    /// evaluates all properties for the saturated liquid and
+    /// saturated vapour at a given saturation temperature
+    ///
+    /// the flashes use these to get the steam quality in region 4
+    /// before calling new_two_phase
+    pub(crate) fn new_saturated_liquid_and_vapour(
+        t: ThermodynamicTemperature) -> (Self, Self) {
+
+        let (p_sat, rho_liq, rho_vap) = sat_p_rho_liq_vap_trho(t);
+        let (liq_region, vap_region) = sat_liq_vap_regions_trho(t);
+
+        let liquid = Self::new_single_phase(liq_region, t, p_sat, rho_liq, 0.0);
+        let vapour = Self::new_single_phase(vap_region, t, p_sat, rho_vap, 1.0);
+
+        return (liquid, vapour);
+    }
+
+    /// interpolates the saturated liquid and vapour properties
+    /// using steam quality x (clamped between 0 and 1)
+    ///
+    /// cp, cv, w, kappa, alpha_v and kappa_t are those of the 
+    /// homogeneous equilibrium mixture rather than interpolated
+    /// (see two_phase_equilibrium_derivatives), 
+    /// so cp, alpha_v, kappa_t and the prandtl number are infinite
+    ///
+    /// as in the (T,rho) flash, viscosity and the lambda_0 lambda_1
+    /// terms of thermal conductivity are evaluated at the
+    /// mixture density, while the critical enhancement term is
+    /// interpolated, and the viscosity has no critical enhancement
+    /// (the default TwoPhaseMixingModel, see two_phase_mixing_model)
+    pub(crate) fn new_two_phase(liquid: &Self,
+        vapour: &Self,
+        x: f64) -> Self {
+
+        let x_clamped = x.clamp(0.0, 1.0);
+        let one_minus_x = 1.0 - x_clamped;
+
+        let t = liquid.t;
+        let v = vapour.v * x_clamped + liquid.v * one_minus_x;
+        let rho = v.recip();
+        let derivatives = TwoPhaseEquilibriumDerivatives::new_from_saturated_states(
+            liquid, vapour, x_clamped);
+        let cp = derivatives.cp;
+
+        let lambda_2 = lambda_2_from_lambda(vapour) * x_clamped 
+            + lambda_2_from_lambda(liquid) * one_minus_x;
+        let lambda = lambda_from_lambda_2(t, rho, lambda_2);
+        let mu = mu_rho_t_eqm(t, rho);
+
+        return Self {
+            region: Some(FwdEqnRegion::Region4),
+            t,
+            p: liquid.p,
+            v,
+            h: vapour.h * x_clamped + liquid.h * one_minus_x,
+            u: vapour.u * x_clamped + liquid.u * one_minus_x,
+            s: vapour.s * x_clamped + liquid.s * one_minus_x,
+            x,
+            cp,
+            cv: derivatives.cv,
+            w: derivatives.w,
+            kappa: derivatives.kappa,
+            alpha_v: derivatives.alpha_v,
+            kappa_t: derivatives.kappa_t,
+            mu,
+            lambda,
+            sigma: surface_tension(t),
+            prandtl: mu * cp / lambda,
+        };
+    }
+}
+
+/// surface tension is only defined up to the critical temperature
+fn surface_tension(t: ThermodynamicTemperature) -> Option<RadiantExposure> {
+    if t > t_crit_water() {
+        return None;
+    };
+
+    return Some(water_surf_tension(t));
+}
+
+/// thermal conductivity given the critical enhancement term,
+/// lambda = lambda_star (lambda_0 lambda_1 + lambda_2)
+fn lambda_from_lambda_2(t: ThermodynamicTemperature,
+    rho: MassDensity,
+    lambda_2: f64) -> ThermalConductivity {
+
+    let lambda_star = ThermalConductivity::new::<watt_per_meter_kelvin>(1.0e-3);
+
+    return lambda_star * (lambda_0(t) * lambda_1(rho, t) + lambda_2);
+}
+
+/// recovers the dimensionless critical enhancement term of
+/// a single phase state
+fn lambda_2_from_lambda(state: &SteamState) -> f64 {
+
+    let lambda_star = ThermalConductivity::new::<watt_per_meter_kelvin>(1.0e-3);
+    let lambda: f64 = (state.lambda/lambda_star).get::<ratio>();
+
+    return lambda - lambda_0(state.t) * lambda_1(state.rho(), state.t);
+}
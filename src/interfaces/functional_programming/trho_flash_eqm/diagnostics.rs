@@ -0,0 +1,53 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::flash_diagnostics::*;
+use crate::interfaces::functional_programming::pt_flash_eqm::v_tp_eqm_single_phase;
+
+use super::*;
+
+/// (T,rho) flash which also reports the region used, the number
+/// of pressure newton raphson iterations (regions 1, 2 and 5)
+/// and the specific volume residual v(T,p) - 1/rho of the 
+/// forward equation at the flashed pressure
+///
+/// regions 3 and 4 are explicit in (T,rho), so there are no
+/// iterations or residuals
+pub fn try_trho_flash_with_diagnostics(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<FlashReport, SteamTableError> {
+
+    let (region, p, x, iterations) = 
+        try_region_px_trho_flash_eqm_with_iterations(t, rho)?;
+
+    let v: SpecificVolume = rho.recip();
+
+    let mut residuals = FlashResiduals::default();
+    match region {
+        FwdEqnRegion::Region1 | FwdEqnRegion::Region2 | FwdEqnRegion::Region5 => {
+            residuals.v = Some(v_tp_eqm_single_phase(t, p) - v);
+        },
+        FwdEqnRegion::Region3 | FwdEqnRegion::Region4 => (),
+    };
+
+    return Ok(FlashReport {
+        t,
+        p,
+        v,
+        x,
+        region,
+        subregion: None,
+        iterations,
+        residuals,
+        boundary_correction_applied: false,
+    });
+}
+
+/// (T,rho) flash which also reports how the result was obtained,
+/// panics if the (T,rho) point is outside the validity range
+pub fn trho_flash_with_diagnostics(t: ThermodynamicTemperature,
+    rho: MassDensity) -> FlashReport {
+    match try_trho_flash_with_diagnostics(t, rho) {
+        Ok(report) => report,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
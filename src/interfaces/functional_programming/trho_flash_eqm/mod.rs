@@ -0,0 +1,403 @@
+use uom::si::f64::*;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+use uom::si::pressure::megapascal;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::constants::{specific_gas_constant_of_water, T_C_KELVIN};
+use crate::errors::SteamTableError;
+use crate::region_1_subcooled_liquid::v_tp_1;
+use crate::region_2_vapour::v_tp_2;
+use crate::region_3_single_phase_plus_supercritical_steam::{p_boundary_2_3, p_rho_t_3, v_tp_3c, v_tp_3r, v_tp_3s, v_tp_3t, v_tp_3u, v_tp_3x, v_tp_3y, v_tp_3z};
+use crate::region_4_vap_liq_equilibrium::sat_pressure_4;
+use crate::region_5_steam_at_800_plus_degc::v_tp_5;
+
+use super::pt_flash_eqm::FwdEqnRegion;
+
+/// fallible versions of the (T,rho) flash functions
+/// which return a SteamTableError rather than panicking
+pub mod try_flash;
+pub use try_flash::*;
+
+/// newton raphson for pressure given temperature and density
+/// in regions 1, 2 and 5
+pub(crate) mod newton_raphson;
+use newton_raphson::*;
+
+/// (T,rho) flash which also reports the region, subregion,
+/// iterations and residuals (see flash_diagnostics)
+pub mod diagnostics;
+pub use diagnostics::*;
+
+/// (T,rho) flash returning all intensive properties at once
+/// (see steam_state)
+pub mod steam_state_flash;
+pub use steam_state_flash::*;
+
+/// allows the user to check which region one is in based on a
+/// (T,rho) flash
+///
+/// panics if the (T,rho) point is outside the validity range
+pub fn trho_flash_region(t: ThermodynamicTemperature,
+    rho: MassDensity) -> FwdEqnRegion {
+    match try_trho_flash_region(t, rho) {
+        Ok(region) => region,
+        Err(error) => panic!("t,rho point is outside validity range: {}", error),
+    }
+}
+
+/// allows the user to check which region one is in based on a
+/// (T,rho) flash
+///
+/// returns an error rather than panicking if the temperature
+/// or density is outside the validity range.
+/// Note that pressure bounds are only checked when pressure is
+/// calculated (see try_px_trho_flash_eqm)
+pub fn try_trho_flash_region(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<FwdEqnRegion, SteamTableError> {
+
+    let t_min = ThermodynamicTemperature::new::<kelvin>(273.15);
+    let t_max = ThermodynamicTemperature::new::<kelvin>(2273.15);
+    let rho_min = MassDensity::new::<kilogram_per_cubic_meter>(0.0);
+
+    if t < t_min {
+        return Err(SteamTableError::TemperatureBelowBound { t, bound: t_min });
+    };
+    if t > t_max {
+        return Err(SteamTableError::TemperatureAboveBound { t, bound: t_max });
+    };
+    if rho <= rho_min {
+        return Err(SteamTableError::DensityBelowBound { rho, bound: rho_min });
+    };
+
+    let t_kelvin = t.get::<kelvin>();
+
+    // from 1073.15 K, only region 5 is possible
+    // (up to 50 MPa)
+    if t_kelvin >= 1073.15 {
+        // on the 1073.15 K isotherm itself, points above 50 MPa 
+        // are in region 2 (as in the (T,p) flash)
+        let p_max_region_5 = Pressure::new::<megapascal>(50.0);
+        if t_kelvin == 1073.15 && rho > v_tp_5(t, p_max_region_5).recip() {
+            return Ok(FwdEqnRegion::Region2);
+        };
+
+        return Ok(FwdEqnRegion::Region5);
+    };
+
+    // from 863.15 K to 1073.15 K, only region 2 is possible
+    // (up to 100 MPa)
+    if t_kelvin > 863.15 {
+        return Ok(FwdEqnRegion::Region2);
+    };
+
+    // below 623.15 K, we have regions 1, 2 and 4
+    // these are separated by the saturated liquid and vapour
+    // densities
+    if t_kelvin <= 623.15 {
+        let (_p_sat, rho_liq, rho_vap) = sat_p_rho_liq_vap_trho(t);
+
+        if rho >= rho_liq {
+            return Ok(FwdEqnRegion::Region1);
+        };
+        if rho <= rho_vap {
+            return Ok(FwdEqnRegion::Region2);
+        };
+        return Ok(FwdEqnRegion::Region4);
+    };
+
+    // from 623.15 K to 863.15 K, region 2 lies below the
+    // B23 line,
+    // the density along the B23 line is obtained from region 2
+    let p_boundary_23 = p_boundary_2_3(t);
+    let rho_boundary_23 = v_tp_2(t, p_boundary_23).recip();
+
+    if rho <= rho_boundary_23 {
+        return Ok(FwdEqnRegion::Region2);
+    };
+
+    // below the critical temperature, region 4 cuts into
+    // region 3
+    if t_kelvin < T_C_KELVIN {
+        let (_p_sat, rho_liq, rho_vap) = sat_p_rho_liq_vap_trho(t);
+
+        if rho > rho_vap && rho < rho_liq {
+            return Ok(FwdEqnRegion::Region4);
+        };
+    };
+
+    return Ok(FwdEqnRegion::Region3);
+}
+
+/// returns pressure and steam quality given temperature and
+/// density
+///
+/// panics if the (T,rho) point is outside the validity range
+#[inline]
+pub fn px_trho_flash_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> (Pressure, f64) {
+    match try_px_trho_flash_eqm(t, rho) {
+        Ok(px) => px,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
+
+/// returns pressure and steam quality given temperature and
+/// density
+///
+/// returns an error rather than panicking if the (T,rho) point
+/// is outside the validity range
+#[inline]
+pub fn try_px_trho_flash_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<(Pressure, f64), SteamTableError> {
+
+    let (_region, p, x) = try_region_px_trho_flash_eqm(t, rho)?;
+
+    return Ok((p, x));
+}
+
+/// returns region, pressure and steam quality given
+/// temperature and density
+///
+/// I'm doing this combined function to prevent double calculation
+/// in the property functions
+pub(crate) fn try_region_px_trho_flash_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<(FwdEqnRegion, Pressure, f64), SteamTableError> {
+
+    let (region, p, x, _iterations) = 
+        try_region_px_trho_flash_eqm_with_iterations(t, rho)?;
+
+    return Ok((region, p, x));
+}
+
+/// same as try_region_px_trho_flash_eqm, but also returns the 
+/// number of pressure newton raphson iterations taken
+/// (zero for regions 3 and 4), for flash diagnostics
+pub(crate) fn try_region_px_trho_flash_eqm_with_iterations(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<(FwdEqnRegion, Pressure, f64, usize), SteamTableError> {
+
+    let region = try_trho_flash_region(t, rho)?;
+    let p_max = Pressure::new::<megapascal>(100.0);
+    let p_max_region_5 = Pressure::new::<megapascal>(50.0);
+
+    // ideal gas estimate for pressure, used as an initial
+    // guess for the vapour regions
+    let p_ideal_gas: Pressure = rho * specific_gas_constant_of_water() * t;
+
+    match region {
+        FwdEqnRegion::Region1 => {
+            // liquid is hardly compressible, so we start from
+            // saturation pressure
+            let p_guess = sat_pressure_4(t);
+            let (p, iterations) = p_trho_newton_raphson(t, rho, p_guess, region)?;
+
+            if p > p_max {
+                return Err(SteamTableError::PressureAboveBound { p, bound: p_max });
+            };
+
+            return Ok((region, p, 0.0, iterations));
+        },
+        FwdEqnRegion::Region2 => {
+            let (p, iterations) = p_trho_newton_raphson(t, rho, p_ideal_gas, region)?;
+
+            if p > p_max {
+                return Err(SteamTableError::PressureAboveBound { p, bound: p_max });
+            };
+
+            return Ok((region, p, 1.0, iterations));
+        },
+        FwdEqnRegion::Region3 => {
+            // region 3 is explicit in density and temperature
+            let p = p_rho_t_3(rho, t);
+
+            if p > p_max {
+                return Err(SteamTableError::PressureAboveBound { p, bound: p_max });
+            };
+
+            // region 3 is special, if it is equal or above
+            // crit temperature, then just consider it vapour,
+            // doesn't really matter
+            //
+            // otherwise it is liquid if denser than
+            // the saturated liquid
+            if t.get::<kelvin>() >= T_C_KELVIN {
+                return Ok((region, p, 1.0, 0));
+            };
+
+            let (_p_sat, rho_liq, _rho_vap) = sat_p_rho_liq_vap_trho(t);
+
+            if rho >= rho_liq {
+                return Ok((region, p, 0.0, 0));
+            } else {
+                return Ok((region, p, 1.0, 0));
+            };
+        },
+        FwdEqnRegion::Region4 => {
+            // for this we consider vapour liquid equilibrium
+            //
+            // v = v_vap (x) + v_liq (1-x)
+            // v - v_liq = x (v_vap - v_liq)
+            // x = (v - v_liq)/(v_vap - v_liq)
+            let (p_sat, rho_liq, rho_vap) = sat_p_rho_liq_vap_trho(t);
+
+            let v = rho.recip();
+            let v_liq = rho_liq.recip();
+            let v_vap = rho_vap.recip();
+
+            let x: Ratio = (v - v_liq)/(v_vap - v_liq);
+
+            return Ok((region, p_sat, x.get::<uom::si::ratio::ratio>(), 0));
+        },
+        FwdEqnRegion::Region5 => {
+            let (p, iterations) = p_trho_newton_raphson(t, rho, p_ideal_gas, region)?;
+
+            if p > p_max_region_5 {
+                return Err(SteamTableError::PressureAboveBound { p, bound: p_max_region_5 });
+            };
+
+            return Ok((region, p, 1.0, iterations));
+        },
+    }
+}
+
+/// returns saturation pressure, saturated liquid density and
+/// saturated vapour density for a given temperature
+///
+/// below 623.15 K, regions 1 and 2 are used,
+/// otherwise the region 3 v(T,p) subregion equations are used
+/// (fig 2.24 page 109), as in the two phase pt flash
+pub(crate) fn sat_p_rho_liq_vap_trho(t: ThermodynamicTemperature)
+    -> (Pressure, MassDensity, MassDensity) {
+
+    let p_sat = sat_pressure_4(t);
+    let t_kelvin = t.get::<kelvin>();
+
+    if t_kelvin <= 623.15 {
+        return (p_sat, v_tp_1(t, p_sat).recip(), v_tp_2(t, p_sat).recip());
+    };
+
+    let v_vap: SpecificVolume = {
+        // this covers up to tsat at 643.15 K
+        if t_kelvin <= 640.691 {
+            v_tp_3t(t, p_sat)
+        } else if t_kelvin <= 643.15 {
+            v_tp_3r(t, p_sat)
+        } else if t_kelvin <= 646.483 {
+            v_tp_3x(t, p_sat)
+        } else {
+            v_tp_3z(t, p_sat)
+        }
+    };
+
+    let v_liq: SpecificVolume = {
+        // this covers up to tsat at 643.15 K
+        if t_kelvin <= 634.659 {
+            v_tp_3c(t, p_sat)
+        } else if t_kelvin <= 643.15 {
+            v_tp_3s(t, p_sat)
+        } else if t_kelvin <= 646.599 {
+            v_tp_3u(t, p_sat)
+        } else {
+            v_tp_3y(t, p_sat)
+        }
+    };
+
+    return (p_sat, v_liq.recip(), v_vap.recip());
+}
+
+/// returns the regions used for the saturated liquid and
+/// saturated vapour at a given temperature
+///
+/// below 623.15 K, the saturated liquid and vapour are
+/// in regions 1 and 2,
+/// otherwise both of them are in region 3
+pub(crate) fn sat_liq_vap_regions_trho(t: ThermodynamicTemperature)
+    -> (FwdEqnRegion, FwdEqnRegion) {
+
+    if t.get::<kelvin>() <= 623.15 {
+        return (FwdEqnRegion::Region1, FwdEqnRegion::Region2);
+    };
+
+    return (FwdEqnRegion::Region3, FwdEqnRegion::Region3);
+}
+
+/// returns pressure given temperature and density
+pub fn p_trho_eqm(t: ThermodynamicTemperature, rho: MassDensity) -> Pressure {
+    match try_p_trho_eqm(t, rho) {
+        Ok(p) => p,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
+
+/// returns steam quality given temperature and density
+pub fn x_trho_flash(t: ThermodynamicTemperature, rho: MassDensity) -> f64 {
+    match try_x_trho_flash(t, rho) {
+        Ok(x) => x,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
+
+/// returns specific enthalpy given temperature and density
+pub fn h_trho_eqm(t: ThermodynamicTemperature, rho: MassDensity) -> AvailableEnergy {
+    match try_h_trho_eqm(t, rho) {
+        Ok(h) => h,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
+
+/// returns specific entropy given temperature and density
+pub fn s_trho_eqm(t: ThermodynamicTemperature, rho: MassDensity) -> SpecificHeatCapacity {
+    match try_s_trho_eqm(t, rho) {
+        Ok(s) => s,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
+
+/// returns specific internal energy given temperature and density
+pub fn u_trho_eqm(t: ThermodynamicTemperature, rho: MassDensity) -> AvailableEnergy {
+    match try_u_trho_eqm(t, rho) {
+        Ok(u) => u,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
+
+/// returns isobaric heat capacity given temperature and density
+pub fn cp_trho_eqm(t: ThermodynamicTemperature, rho: MassDensity) -> SpecificHeatCapacity {
+    match try_cp_trho_eqm(t, rho) {
+        Ok(cp) => cp,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
+
+/// returns isochoric heat capacity given temperature and density
+pub fn cv_trho_eqm(t: ThermodynamicTemperature, rho: MassDensity) -> SpecificHeatCapacity {
+    match try_cv_trho_eqm(t, rho) {
+        Ok(cv) => cv,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
+
+/// returns speed of sound given temperature and density
+pub fn w_trho_eqm(t: ThermodynamicTemperature, rho: MassDensity) -> Velocity {
+    match try_w_trho_eqm(t, rho) {
+        Ok(w) => w,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
+
+/// returns mu, or sometimes eta (dynamic viscosity) given
+/// temperature and density
+pub fn mu_trho_eqm(t: ThermodynamicTemperature, rho: MassDensity) -> DynamicViscosity {
+    match try_mu_trho_eqm(t, rho) {
+        Ok(mu) => mu,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
+
+/// returns lambda (thermal conductivity) given
+/// temperature and density
+pub fn lambda_trho_eqm(t: ThermodynamicTemperature, rho: MassDensity) -> ThermalConductivity {
+    match try_lambda_trho_eqm(t, rho) {
+        Ok(lambda) => lambda,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
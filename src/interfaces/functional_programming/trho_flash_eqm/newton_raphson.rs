@@ -0,0 +1,69 @@
+use uom::si::f64::*;
+use uom::si::pressure::pascal;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+use crate::region_1_subcooled_liquid::{kappa_t_tp_1, v_tp_1};
+use crate::region_2_vapour::{kappa_t_tp_2, v_tp_2};
+use crate::region_5_steam_at_800_plus_degc::{kappa_t_tp_5, v_tp_5};
+
+/// the pressure newton raphson stops when the relative
+/// pressure correction is less than this
+const TRHO_NEWTON_RAPHSON_P_REL_TOLERANCE: f64 = 1.0e-10;
+const TRHO_NEWTON_RAPHSON_MAX_ITERATIONS: usize = 100;
+
+/// newton raphson on the forward equation v(T,p) for pressure
+/// given temperature and density in regions 1, 2 and 5
+///
+/// the derivative is written in terms of the isothermal
+/// compressibility:
+///
+/// (dv/dp)_T = - v kappa_t
+///
+/// region 3 does not need this since the helmholtz free energy
+/// equation gives p(rho, T) explicitly
+///
+/// returns pressure and the number of iterations taken
+pub(crate) fn p_trho_newton_raphson(
+    t: ThermodynamicTemperature,
+    rho: MassDensity,
+    p_guess: Pressure,
+    region: FwdEqnRegion) -> Result<(Pressure, usize), SteamTableError> {
+
+    let v_target = rho.recip().get::<cubic_meter_per_kilogram>();
+    let mut p_pascal = p_guess.get::<pascal>();
+
+    for iteration in 0..TRHO_NEWTON_RAPHSON_MAX_ITERATIONS {
+        let p = Pressure::new::<pascal>(p_pascal);
+
+        let (v, kappa_t) = match region {
+            FwdEqnRegion::Region1 => (v_tp_1(t, p), kappa_t_tp_1(t, p)),
+            FwdEqnRegion::Region2 => (v_tp_2(t, p), kappa_t_tp_2(t, p)),
+            FwdEqnRegion::Region5 => (v_tp_5(t, p), kappa_t_tp_5(t, p)),
+            _ => return Err(SteamTableError::RegionNotImplemented {
+                flash: "(T,rho) newton raphson", region }),
+        };
+
+        let v = v.get::<cubic_meter_per_kilogram>();
+        // kappa_t is stored in SI units (1/Pa)
+        let dv_dp = - v * kappa_t.value;
+
+        let delta_p = (v_target - v)/dv_dp;
+
+        // pressure must remain positive, so if we overshoot,
+        // just halve the pressure
+        if p_pascal + delta_p <= 0.0 {
+            p_pascal *= 0.5;
+        } else {
+            p_pascal += delta_p;
+        };
+
+        if delta_p.abs() < TRHO_NEWTON_RAPHSON_P_REL_TOLERANCE * p_pascal {
+            return Ok((Pressure::new::<pascal>(p_pascal), iteration + 1));
+        };
+    }
+
+    return Err(SteamTableError::FlashDidNotConverge {
+        flash: "(T,rho)", iterations: TRHO_NEWTON_RAPHSON_MAX_ITERATIONS });
+}
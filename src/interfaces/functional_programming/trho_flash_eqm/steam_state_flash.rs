@@ -0,0 +1,38 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+
+use super::*;
+
+/// (T,rho) flash returning all intensive properties at once
+///
+/// the region, pressure and steam quality are only 
+/// determined once, unlike calling p_trho_eqm, h_trho_eqm,
+/// cp_trho_eqm etc. one by one
+pub fn try_flash(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<SteamState, SteamTableError> {
+
+    let (region, p, x) = try_region_px_trho_flash_eqm(t, rho)?;
+
+    match region {
+        FwdEqnRegion::Region4 => {
+            let (liquid, vapour) = SteamState::new_saturated_liquid_and_vapour(t);
+
+            return Ok(SteamState::new_two_phase(&liquid, &vapour, x));
+        },
+        _ => {
+            return Ok(SteamState::new_single_phase(region, t, p, rho, x));
+        },
+    }
+}
+
+/// (T,rho) flash returning all intensive properties at once,
+/// panics if the (T,rho) point is outside the validity range
+pub fn flash(t: ThermodynamicTemperature,
+    rho: MassDensity) -> SteamState {
+    match try_flash(t, rho) {
+        Ok(state) => state,
+        Err(error) => panic!("t,rho flash failed: {}", error),
+    }
+}
@@ -0,0 +1,275 @@
+use std::ops::{Add, Mul};
+
+use uom::si::f64::*;
+use uom::si::thermal_conductivity::watt_per_meter_kelvin;
+
+use crate::dynamic_viscosity::mu_rho_t_eqm;
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+use crate::interfaces::functional_programming::two_phase_equilibrium_derivatives::TwoPhaseEquilibriumDerivatives;
+use crate::region_1_subcooled_liquid::{alpha_v_tp_1, cp_tp_1, cv_tp_1, h_tp_1, kappa_t_tp_1, s_tp_1, u_tp_1, w_tp_1, InversePressure};
+use crate::region_2_vapour::{alpha_v_tp_2, cp_tp_2, cv_tp_2, h_tp_2, kappa_t_tp_2, s_tp_2, u_tp_2, w_tp_2};
+use crate::region_3_single_phase_plus_supercritical_steam::{alpha_v_rho_t_3, cp_rho_t_3, cv_rho_t_3, h_rho_t_3, kappa_t_rho_t_3, s_rho_t_3, u_rho_t_3, w_rho_t_3};
+use crate::region_5_steam_at_800_plus_degc::{alpha_v_tp_5, cp_tp_5, cv_tp_5, h_tp_5, kappa_t_tp_5, s_tp_5, u_tp_5, w_tp_5};
+use crate::thermal_conductivity::{lambda_0, lambda_1, lambda_2_crit_enhancement_term_rho_t};
+
+use super::{sat_liq_vap_regions_trho, sat_p_rho_liq_vap_trho, try_region_px_trho_flash_eqm};
+
+/// returns pressure given temperature and density
+pub fn try_p_trho_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<Pressure, SteamTableError> {
+    let (_region, p, _x) = try_region_px_trho_flash_eqm(t, rho)?;
+
+    return Ok(p);
+}
+
+/// returns steam quality given temperature and density
+pub fn try_x_trho_flash(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<f64, SteamTableError> {
+    let (_region, _p, x) = try_region_px_trho_flash_eqm(t, rho)?;
+
+    return Ok(x);
+}
+
+/// returns specific enthalpy given temperature and density
+pub fn try_h_trho_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<AvailableEnergy, SteamTableError> {
+    return trho_property(t, rho, h_trho_single_phase);
+}
+
+/// returns specific entropy given temperature and density
+pub fn try_s_trho_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<SpecificHeatCapacity, SteamTableError> {
+    return trho_property(t, rho, s_trho_single_phase);
+}
+
+/// returns specific internal energy given temperature and density
+pub fn try_u_trho_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<AvailableEnergy, SteamTableError> {
+    return trho_property(t, rho, u_trho_single_phase);
+}
+
+/// returns isobaric heat capacity given temperature and density
+///
+/// in region 4, cp is infinite
+/// (see two_phase_equilibrium_derivatives)
+pub fn try_cp_trho_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let (region, _p, x) = try_region_px_trho_flash_eqm(t, rho)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return Ok(TwoPhaseEquilibriumDerivatives::new(t, x).cp);
+    };
+
+    return trho_property(t, rho, cp_trho_single_phase);
+}
+
+/// returns isochoric heat capacity given temperature and density
+///
+/// in region 4, this is the homogeneous equilibrium value
+/// (see two_phase_equilibrium_derivatives)
+pub fn try_cv_trho_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let (region, _p, x) = try_region_px_trho_flash_eqm(t, rho)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return Ok(TwoPhaseEquilibriumDerivatives::new(t, x).cv);
+    };
+
+    return trho_property(t, rho, cv_trho_single_phase);
+}
+
+/// returns speed of sound given temperature and density
+///
+/// in region 4, this is the homogeneous equilibrium value
+/// (see two_phase_equilibrium_derivatives)
+pub fn try_w_trho_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<Velocity, SteamTableError> {
+    let (region, _p, x) = try_region_px_trho_flash_eqm(t, rho)?;
+
+    if region == FwdEqnRegion::Region4 {
+        return Ok(TwoPhaseEquilibriumDerivatives::new(t, x).w);
+    };
+
+    return trho_property(t, rho, w_trho_single_phase);
+}
+
+/// returns mu, or sometimes eta (dynamic viscosity) given
+/// temperature and density
+///
+/// the viscosity correlation is already in terms of
+/// temperature and density, so we only need to check
+/// if the point is within the validity range
+pub fn try_mu_trho_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<DynamicViscosity, SteamTableError> {
+    try_region_px_trho_flash_eqm(t, rho)?;
+
+    return Ok(mu_rho_t_eqm(t, rho));
+}
+
+/// returns lambda (thermal conductivity) given
+/// temperature and density
+///
+/// lambda_0 and lambda_1 are in terms of temperature and density,
+/// the critical enhancement term lambda_2 needs cp, cv and
+/// kappa_t, which are obtained from the (T,rho) flash
+///
+/// in region 4, lambda_2 is interpolated using steam quality
+pub fn try_lambda_trho_eqm(t: ThermodynamicTemperature,
+    rho: MassDensity) -> Result<ThermalConductivity, SteamTableError> {
+
+    let lambda_2 = trho_property(t, rho, lambda_2_trho_single_phase)?;
+
+    let lambda_0 = lambda_0(t);
+    let lambda_1 = lambda_1(rho, t);
+    let lambda_star = ThermalConductivity::new::<watt_per_meter_kelvin>(1.0e-3);
+
+    let dimensionless_lambda = lambda_0 * lambda_1 + lambda_2;
+
+    return Ok(lambda_star * dimensionless_lambda);
+}
+
+/// evaluates a property given temperature and density
+///
+/// the single_phase_property closure evaluates the property
+/// given region, temperature, pressure and density
+/// for single phase regions 1, 2, 3 and 5.
+///
+/// in region 4, the property is evaluated for the saturated
+/// liquid and saturated vapour, and interpolated using
+/// steam quality
+fn trho_property<Q, F>(t: ThermodynamicTemperature,
+    rho: MassDensity,
+    single_phase_property: F) -> Result<Q, SteamTableError>
+where
+    Q: Mul<f64, Output = Q> + Add<Output = Q>,
+    F: Fn(FwdEqnRegion, ThermodynamicTemperature, Pressure, MassDensity) -> Q {
+
+    let (region, p, x) = try_region_px_trho_flash_eqm(t, rho)?;
+
+    if region != FwdEqnRegion::Region4 {
+        return Ok(single_phase_property(region, t, p, rho));
+    };
+
+    let (liq_region, vap_region) = sat_liq_vap_regions_trho(t);
+
+    let (p_sat, rho_liq, rho_vap) = sat_p_rho_liq_vap_trho(t);
+
+    let property_liq = single_phase_property(liq_region, t, p_sat, rho_liq);
+    let property_vap = single_phase_property(vap_region, t, p_sat, rho_vap);
+
+    return Ok(property_vap * x + property_liq * (1.0 - x));
+}
+
+pub(crate) fn cp_trho_single_phase(region: FwdEqnRegion, t: ThermodynamicTemperature,
+    p: Pressure, rho: MassDensity) -> SpecificHeatCapacity {
+    match region {
+        FwdEqnRegion::Region1 => cp_tp_1(t, p),
+        FwdEqnRegion::Region2 => cp_tp_2(t, p),
+        FwdEqnRegion::Region3 => cp_rho_t_3(rho, t),
+        FwdEqnRegion::Region4 => unreachable!("region 4 is split into liquid and vapour"),
+        FwdEqnRegion::Region5 => cp_tp_5(t, p),
+    }
+}
+
+pub(crate) fn cv_trho_single_phase(region: FwdEqnRegion, t: ThermodynamicTemperature,
+    p: Pressure, rho: MassDensity) -> SpecificHeatCapacity {
+    match region {
+        FwdEqnRegion::Region1 => cv_tp_1(t, p),
+        FwdEqnRegion::Region2 => cv_tp_2(t, p),
+        FwdEqnRegion::Region3 => cv_rho_t_3(rho, t),
+        FwdEqnRegion::Region4 => unreachable!("region 4 is split into liquid and vapour"),
+        FwdEqnRegion::Region5 => cv_tp_5(t, p),
+    }
+}
+
+pub(crate) fn alpha_v_trho_single_phase(region: FwdEqnRegion, t: ThermodynamicTemperature,
+    p: Pressure, rho: MassDensity) -> TemperatureCoefficient {
+    match region {
+        FwdEqnRegion::Region1 => alpha_v_tp_1(t, p),
+        FwdEqnRegion::Region2 => alpha_v_tp_2(t, p),
+        FwdEqnRegion::Region3 => alpha_v_rho_t_3(rho, t),
+        FwdEqnRegion::Region4 => unreachable!("region 4 is split into liquid and vapour"),
+        FwdEqnRegion::Region5 => alpha_v_tp_5(t, p),
+    }
+}
+
+pub(crate) fn kappa_t_trho_single_phase(region: FwdEqnRegion, t: ThermodynamicTemperature,
+    p: Pressure, rho: MassDensity) -> InversePressure {
+    match region {
+        FwdEqnRegion::Region1 => kappa_t_tp_1(t, p),
+        FwdEqnRegion::Region2 => kappa_t_tp_2(t, p),
+        FwdEqnRegion::Region3 => kappa_t_rho_t_3(rho, t),
+        FwdEqnRegion::Region4 => unreachable!("region 4 is split into liquid and vapour"),
+        FwdEqnRegion::Region5 => kappa_t_tp_5(t, p),
+    }
+}
+
+pub(crate) fn h_trho_single_phase(region: FwdEqnRegion, t: ThermodynamicTemperature,
+    p: Pressure, rho: MassDensity) -> AvailableEnergy {
+    match region {
+        FwdEqnRegion::Region1 => h_tp_1(t, p),
+        FwdEqnRegion::Region2 => h_tp_2(t, p),
+        FwdEqnRegion::Region3 => h_rho_t_3(rho, t),
+        FwdEqnRegion::Region4 => unreachable!("region 4 is split into liquid and vapour"),
+        FwdEqnRegion::Region5 => h_tp_5(t, p),
+    }
+}
+
+pub(crate) fn s_trho_single_phase(region: FwdEqnRegion, t: ThermodynamicTemperature,
+    p: Pressure, rho: MassDensity) -> SpecificHeatCapacity {
+    match region {
+        FwdEqnRegion::Region1 => s_tp_1(t, p),
+        FwdEqnRegion::Region2 => s_tp_2(t, p),
+        FwdEqnRegion::Region3 => s_rho_t_3(rho, t),
+        FwdEqnRegion::Region4 => unreachable!("region 4 is split into liquid and vapour"),
+        FwdEqnRegion::Region5 => s_tp_5(t, p),
+    }
+}
+
+pub(crate) fn u_trho_single_phase(region: FwdEqnRegion, t: ThermodynamicTemperature,
+    p: Pressure, rho: MassDensity) -> AvailableEnergy {
+    match region {
+        FwdEqnRegion::Region1 => u_tp_1(t, p),
+        FwdEqnRegion::Region2 => u_tp_2(t, p),
+        FwdEqnRegion::Region3 => u_rho_t_3(rho, t),
+        FwdEqnRegion::Region4 => unreachable!("region 4 is split into liquid and vapour"),
+        FwdEqnRegion::Region5 => u_tp_5(t, p),
+    }
+}
+
+pub(crate) fn w_trho_single_phase(region: FwdEqnRegion, t: ThermodynamicTemperature,
+    p: Pressure, rho: MassDensity) -> Velocity {
+    match region {
+        FwdEqnRegion::Region1 => w_tp_1(t, p),
+        FwdEqnRegion::Region2 => w_tp_2(t, p),
+        FwdEqnRegion::Region3 => w_rho_t_3(rho, t),
+        FwdEqnRegion::Region4 => unreachable!("region 4 is split into liquid and vapour"),
+        FwdEqnRegion::Region5 => w_tp_5(t, p),
+    }
+}
+
+/// thermal conductivity for a single phase given
+/// region, temperature, pressure and density
+pub(crate) fn lambda_trho_single_phase(region: FwdEqnRegion, t: ThermodynamicTemperature,
+    p: Pressure, rho: MassDensity) -> ThermalConductivity {
+
+    let lambda_0 = lambda_0(t);
+    let lambda_1 = lambda_1(rho, t);
+    let lambda_2 = lambda_2_trho_single_phase(region, t, p, rho);
+    let lambda_star = ThermalConductivity::new::<watt_per_meter_kelvin>(1.0e-3);
+
+    return lambda_star * (lambda_0 * lambda_1 + lambda_2);
+}
+
+/// critical enhancement term (lambda_2) for a single phase
+/// given region, temperature, pressure and density
+pub(crate) fn lambda_2_trho_single_phase(region: FwdEqnRegion, t: ThermodynamicTemperature,
+    p: Pressure, rho: MassDensity) -> f64 {
+
+    let cp = cp_trho_single_phase(region, t, p, rho);
+    let cv = cv_trho_single_phase(region, t, p, rho);
+    let kappa_t = kappa_t_trho_single_phase(region, t, p, rho);
+
+    return lambda_2_crit_enhancement_term_rho_t(rho, t, cp, cv, kappa_t);
+}
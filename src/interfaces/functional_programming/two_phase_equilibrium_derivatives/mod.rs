@@ -0,0 +1,218 @@
+use uom::si::f64::*;
+use uom::si::ratio::ratio;
+use uom::si::specific_heat_capacity::joule_per_kilogram_kelvin;
+use uom::si::temperature_coefficient::per_kelvin;
+use uom::si::velocity::meter_per_second;
+
+use crate::constants::t_crit_water;
+use crate::region_1_subcooled_liquid::InversePressure;
+use crate::region_4_vap_liq_equilibrium::dp_sat_dt_4;
+
+use super::saturation_eqm::SaturationDerivatives;
+use super::steam_state::SteamState;
+
+/// units of dp/dT, Pa/K
+pub use crate::region_4_vap_liq_equilibrium::PressurePerTemperature;
+
+/// steam quality within which a region 4 point from a (p,h), 
+/// (p,s) or (T,p) flash is taken to be the saturated liquid 
+/// (or vapour) itself rather than a mixture 
+/// (see TwoPhaseEquilibriumDerivatives::new_from_flash)
+///
+/// the (p,h) and (p,s) backward equations are only consistent with
+/// the forward equations to about 25 mK, which is roughly
+/// 1e-4 of the latent heat (or entropy of vaporisation), 
+/// so a saturated liquid or vapour point from steam tables 
+/// may land just inside region 4, where cp, alpha_v and kappa_t 
+/// are infinite and the speed of sound is the (much lower) 
+/// mixture value
+pub const SATURATED_PHASE_QUALITY_TOLERANCE: f64 = 1.0e-4;
+
+/// derivative properties of a vapour liquid mixture in
+/// homogeneous equilibrium, ie. both phases are at the saturation
+/// temperature and pressure and there is no slip between phases
+///
+/// unlike the single phase properties, these are not quality
+/// weighted averages of the saturated liquid and vapour values,
+/// pressure is only a function of temperature along the dome,
+/// so the mixture behaves very differently:
+///
+/// at constant pressure, temperature is fixed, so adding heat
+/// only changes steam quality, hence cp, alpha_v and kappa_t
+/// are infinite (f64::INFINITY),
+///
+/// cv and the speed of sound are finite, these are obtained
+/// from the slope of the saturation line and the saturation line
+/// derivatives of each phase (see SaturationDerivatives)
+///
+/// on the saturation lines, ie. steam quality of exactly 0 or 1,
+/// the saturated liquid or vapour values are returned 
+/// (any other quality is a mixture), as are the single phase
+/// values at the critical point (where the liquid and vapour 
+/// are the same)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TwoPhaseEquilibriumDerivatives {
+    /// slope of the saturation line from the region 4 
+    /// saturation equation (see dp_sat_dt_4)
+    pub dp_sat_dt: PressurePerTemperature,
+    /// isobaric heat capacity (infinite within the dome)
+    pub cp: SpecificHeatCapacity,
+    /// isochoric heat capacity of the mixture, (du/dT)_v
+    pub cv: SpecificHeatCapacity,
+    /// homogeneous equilibrium speed of sound,
+    /// w^2 = -v^2 (dp/dv)_s
+    pub w: Velocity,
+    /// isentropic exponent, -(v/p) (dp/dv)_s = w^2/(pv)
+    pub kappa: Ratio,
+    /// isobaric cubic expansion coefficient (infinite within the dome)
+    pub alpha_v: TemperatureCoefficient,
+    /// isothermal compressibility (infinite within the dome)
+    pub kappa_t: InversePressure,
+}
+
+impl TwoPhaseEquilibriumDerivatives {
+
+    /// derivative properties given saturation temperature
+    /// and steam quality x (clamped between 0 and 1)
+    ///
+    /// panics if temperature is outside 273.15 K to the
+    /// critical temperature
+    pub fn new(t: ThermodynamicTemperature, x: f64) -> Self {
+        let (liquid, vapour) = SteamState::new_saturated_liquid_and_vapour(
+            t.min(t_crit_water()));
+
+        return Self::new_from_saturated_states(&liquid, &vapour, x);
+    }
+
+    /// derivative properties given saturation temperature
+    /// and the steam quality from a flash, 
+    /// unlike new, a steam quality within 
+    /// SATURATED_PHASE_QUALITY_TOLERANCE of 0 or 1 is taken to be 
+    /// the saturated liquid or vapour
+    ///
+    /// so there is a step in the derivative properties at this 
+    /// quality, rather than at the saturation lines, which the 
+    /// backward equations cannot resolve anyway
+    pub fn new_from_flash(t: ThermodynamicTemperature, x: f64) -> Self {
+        let x = if x <= SATURATED_PHASE_QUALITY_TOLERANCE {
+            0.0
+        } else if x >= 1.0 - SATURATED_PHASE_QUALITY_TOLERANCE {
+            1.0
+        } else {
+            x
+        };
+
+        return Self::new(t, x);
+    }
+
+    /// derivative properties given the saturated liquid
+    /// and vapour states (see SteamState::new_saturated_liquid_and_vapour)
+    pub(crate) fn new_from_saturated_states(liquid: &SteamState,
+        vapour: &SteamState,
+        x: f64) -> Self {
+
+        let x = x.clamp(0.0, 1.0);
+
+        let p = liquid.p.value;
+
+        let delta_v = vapour.v.value - liquid.v.value;
+        let delta_s = vapour.s.value - liquid.s.value;
+        let delta_u = vapour.u.value - liquid.u.value;
+
+        let dp_sat_dt = dp_sat_dt_4(liquid.t);
+
+        // at the critical point, there is only one phase
+        // (and the saturation line derivatives of each phase 
+        // grow without bound)
+        if delta_v.abs() < 1.0e-9 || delta_s.abs() < 1.0e-9 {
+            return Self::new_saturated_phase(liquid, dp_sat_dt);
+        };
+
+        // on the saturation lines
+        if x == 0.0 {
+            return Self::new_saturated_phase(liquid, dp_sat_dt);
+        };
+        if x == 1.0 {
+            return Self::new_saturated_phase(vapour, dp_sat_dt);
+        };
+
+        // derivatives of each phase along the saturation line,
+        // d/dT = dp_sat/dT d/dp, and since u = h - pv,
+        // du/dp = dh/dp - v - p dv/dp
+        let saturation = SaturationDerivatives::new_from_t(liquid.t);
+        let dp_dt = dp_sat_dt.value;
+
+        let dv_liq_dt = saturation.dv_liq_dp.value * dp_dt;
+        let ds_liq_dt = saturation.ds_liq_dp.value * dp_dt;
+        let du_liq_dt = (saturation.dh_liq_dp.value - liquid.v.value 
+            - p * saturation.dv_liq_dp.value) * dp_dt;
+
+        let dv_vap_dt = saturation.dv_vap_dp.value * dp_dt;
+        let ds_vap_dt = saturation.ds_vap_dp.value * dp_dt;
+        let du_vap_dt = (saturation.dh_vap_dp.value - vapour.v.value 
+            - p * saturation.dv_vap_dp.value) * dp_dt;
+
+        let d_delta_v_dt = dv_vap_dt - dv_liq_dt;
+        let d_delta_s_dt = ds_vap_dt - ds_liq_dt;
+        let d_delta_u_dt = du_vap_dt - du_liq_dt;
+
+        // mixture volume v = v' + x (v'' - v'),
+        // at constant v, the quality changes with temperature as
+        // dx/dT = - (dv'/dT + x d(v''-v')/dT)/(v''-v')
+        // and cv = du/dT at constant v
+        let dx_dt_const_v = -(dv_liq_dt + x * d_delta_v_dt)/delta_v;
+        let cv = du_liq_dt + x * d_delta_u_dt + delta_u * dx_dt_const_v;
+
+        // likewise at constant entropy,
+        // dx/dT = - (ds'/dT + x d(s''-s')/dT)/(s''-s')
+        // and w^2 = -v^2 (dp/dv)_s = -v^2 (dp/dT) / (dv/dT)_s
+        let v = liquid.v.value + x * delta_v;
+        let dx_dt_const_s = -(ds_liq_dt + x * d_delta_s_dt)/delta_s;
+        let dv_dt_const_s = dv_liq_dt + x * d_delta_v_dt + delta_v * dx_dt_const_s;
+        let w_squared = -v * v * dp_dt/dv_dt_const_s;
+
+        let kappa = w_squared/(p * v);
+
+        return Self {
+            dp_sat_dt,
+            cp: SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(f64::INFINITY),
+            cv: SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(cv),
+            w: Velocity::new::<meter_per_second>(w_squared.sqrt()),
+            kappa: Ratio::new::<ratio>(kappa),
+            alpha_v: TemperatureCoefficient::new::<per_kelvin>(f64::INFINITY),
+            kappa_t: Ratio::new::<ratio>(f64::INFINITY)/liquid.p,
+        };
+    }
+
+    /// the single phase derivative properties of a saturated phase
+    fn new_saturated_phase(state: &SteamState,
+        dp_sat_dt: PressurePerTemperature) -> Self {
+        return Self {
+            dp_sat_dt,
+            cp: state.cp,
+            cv: state.cv,
+            w: state.w,
+            kappa: state.kappa,
+            alpha_v: state.alpha_v,
+            kappa_t: state.kappa_t,
+        };
+    }
+}
+
+/// isochoric heat capacity of a two phase mixture
+/// given saturation temperature and steam quality
+pub fn cv_two_phase_eqm(t: ThermodynamicTemperature, x: f64) -> SpecificHeatCapacity {
+    return TwoPhaseEquilibriumDerivatives::new(t, x).cv;
+}
+
+/// homogeneous equilibrium speed of sound of a two phase mixture
+/// given saturation temperature and steam quality
+pub fn w_two_phase_eqm(t: ThermodynamicTemperature, x: f64) -> Velocity {
+    return TwoPhaseEquilibriumDerivatives::new(t, x).w;
+}
+
+/// isentropic exponent of a two phase mixture
+/// given saturation temperature and steam quality
+pub fn kappa_two_phase_eqm(t: ThermodynamicTemperature, x: f64) -> Ratio {
+    return TwoPhaseEquilibriumDerivatives::new(t, x).kappa;
+}
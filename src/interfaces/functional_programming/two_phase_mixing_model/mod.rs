@@ -0,0 +1,191 @@
+use uom::si::f64::*;
+use uom::si::ratio::ratio;
+use uom::si::thermal_conductivity::watt_per_meter_kelvin;
+
+use crate::dynamic_viscosity::mu_rho_t_eqm;
+use crate::thermal_conductivity::{lambda_0, lambda_1};
+
+use super::saturation_eqm::SaturationState;
+
+/// how the viscosity of a vapour liquid mixture in region 4
+/// is obtained from steam quality and the saturated liquid
+/// and vapour properties
+///
+/// these are the usual homogeneous (two phase) viscosity models
+/// used in two phase pressure drop correlations,
+/// where x is steam quality and beta is the void fraction
+/// of the homogeneous model (no slip)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TwoPhaseViscosityModel {
+    /// the IAPWS 2008 correlation (without critical enhancement)
+    /// evaluated at the mixture density,
+    /// this is what mu_tp_eqm_two_phase has always done
+    #[default]
+    MixtureDensity,
+    /// McAdams et al. (1942),
+    /// 1/mu = x/mu_vap + (1-x)/mu_liq
+    McAdams,
+    /// Cicchitti et al. (1960),
+    /// mu = x mu_vap + (1-x) mu_liq
+    Cicchitti,
+    /// Dukler et al. (1964),
+    /// mu = rho_mix (x mu_vap/rho_vap + (1-x) mu_liq/rho_liq)
+    Dukler,
+    /// Beattie and Whalley (1982),
+    /// mu = beta mu_vap + (1-beta)(1 + 2.5 beta) mu_liq
+    BeattieWhalley,
+    /// Lin et al. (1991),
+    /// mu = mu_liq mu_vap/(mu_vap + x^1.4 (mu_liq - mu_vap))
+    Lin,
+}
+
+/// how the thermal conductivity of a vapour liquid mixture in
+/// region 4 is obtained from steam quality and the saturated
+/// liquid and vapour properties
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TwoPhaseConductivityModel {
+    /// lambda_0 lambda_1 evaluated at the mixture density plus
+    /// the quality weighted critical enhancement term of
+    /// each phase (see thermal_conductivity::lambda_tp_eqm_two_phase),
+    /// this is what lambda_tp_eqm_two_phase has always done
+    #[default]
+    MixtureDensity,
+    /// lambda = x lambda_vap + (1-x) lambda_liq
+    MassWeighted,
+    /// lambda = beta lambda_vap + (1-beta) lambda_liq
+    /// where beta is the homogeneous void fraction
+    VolumeWeighted,
+}
+
+/// the two phase viscosity and thermal conductivity models
+/// used by the *_with_mixing_model functions
+/// of the (T,p), (p,h), (p,s) and (h,s) flashes
+///
+/// the default is what the *_eqm functions return in region 4
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoPhaseMixingModel {
+    /// viscosity model
+    pub viscosity: TwoPhaseViscosityModel,
+    /// thermal conductivity model
+    pub thermal_conductivity: TwoPhaseConductivityModel,
+}
+
+impl TwoPhaseMixingModel {
+
+    /// mixing model given the viscosity and thermal conductivity models
+    pub fn new(viscosity: TwoPhaseViscosityModel,
+        thermal_conductivity: TwoPhaseConductivityModel) -> Self {
+        return Self { viscosity, thermal_conductivity };
+    }
+
+    /// two phase viscosity given the saturation state
+    /// and steam quality x (clamped between 0 and 1)
+    pub fn mu_two_phase(&self,
+        saturation_state: &SaturationState,
+        x: f64) -> DynamicViscosity {
+        return self.viscosity.mu_two_phase(saturation_state, x);
+    }
+
+    /// two phase thermal conductivity given the saturation state
+    /// and steam quality x (clamped between 0 and 1)
+    pub fn lambda_two_phase(&self,
+        saturation_state: &SaturationState,
+        x: f64) -> ThermalConductivity {
+        return self.thermal_conductivity.lambda_two_phase(saturation_state, x);
+    }
+}
+
+impl TwoPhaseViscosityModel {
+
+    /// two phase viscosity given the saturation state
+    /// and steam quality x (clamped between 0 and 1)
+    pub fn mu_two_phase(&self,
+        saturation_state: &SaturationState,
+        x: f64) -> DynamicViscosity {
+
+        let x = x.clamp(0.0, 1.0);
+        let t = saturation_state.t_sat;
+        let rho_liq = saturation_state.liquid.v.recip();
+        let rho_vap = saturation_state.vapour.v.recip();
+        let mu_liq = saturation_state.liquid.mu;
+        let mu_vap = saturation_state.vapour.mu;
+
+        match self {
+            Self::MixtureDensity => {
+                mu_rho_t_eqm(t, saturation_state.rho_mixture(x))
+            },
+            Self::McAdams => {
+                (mu_vap.recip() * x + mu_liq.recip() * (1.0 - x)).recip()
+            },
+            Self::Cicchitti => {
+                mu_vap * x + mu_liq * (1.0 - x)
+            },
+            Self::Dukler => {
+                let rho_mix = saturation_state.rho_mixture(x);
+                rho_mix * (mu_vap/rho_vap * x + mu_liq/rho_liq * (1.0 - x))
+            },
+            Self::BeattieWhalley => {
+                let beta = homogeneous_void_fraction(x, rho_liq, rho_vap);
+                mu_vap * beta + mu_liq * (1.0 - beta) * (1.0 + 2.5 * beta)
+            },
+            Self::Lin => {
+                mu_liq * mu_vap / (mu_vap + (mu_liq - mu_vap) * x.powf(1.4))
+            },
+        }
+    }
+}
+
+impl TwoPhaseConductivityModel {
+
+    /// two phase thermal conductivity given the saturation state
+    /// and steam quality x (clamped between 0 and 1)
+    pub fn lambda_two_phase(&self,
+        saturation_state: &SaturationState,
+        x: f64) -> ThermalConductivity {
+
+        let x = x.clamp(0.0, 1.0);
+        let t = saturation_state.t_sat;
+        let rho_liq = saturation_state.liquid.v.recip();
+        let rho_vap = saturation_state.vapour.v.recip();
+        let lambda_liq = saturation_state.liquid.lambda;
+        let lambda_vap = saturation_state.vapour.lambda;
+
+        match self {
+            Self::MixtureDensity => {
+                let lambda_star = ThermalConductivity::new::<watt_per_meter_kelvin>(1.0e-3);
+                let rho_mix = saturation_state.rho_mixture(x);
+
+                // critical enhancement term of each phase
+                let lambda_2_liq = (lambda_liq/lambda_star).get::<ratio>()
+                    - lambda_0(t) * lambda_1(rho_liq, t);
+                let lambda_2_vap = (lambda_vap/lambda_star).get::<ratio>()
+                    - lambda_0(t) * lambda_1(rho_vap, t);
+                let lambda_2 = x * lambda_2_vap + (1.0 - x) * lambda_2_liq;
+
+                lambda_star * (lambda_0(t) * lambda_1(rho_mix, t) + lambda_2)
+            },
+            Self::MassWeighted => {
+                lambda_vap * x + lambda_liq * (1.0 - x)
+            },
+            Self::VolumeWeighted => {
+                let beta = homogeneous_void_fraction(x, rho_liq, rho_vap);
+                lambda_vap * beta + lambda_liq * (1.0 - beta)
+            },
+        }
+    }
+}
+
+/// void fraction of the homogeneous (no slip) model,
+/// beta = x rho_liq/(x rho_liq + (1-x) rho_vap)
+pub fn homogeneous_void_fraction(x: f64,
+    rho_liq: MassDensity,
+    rho_vap: MassDensity) -> f64 {
+
+    let x = x.clamp(0.0, 1.0);
+    let beta: Ratio = rho_liq * x / (rho_liq * x + rho_vap * (1.0 - x));
+
+    return beta.get::<ratio>();
+}
@@ -0,0 +1,43 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::flash_diagnostics::*;
+use crate::interfaces::functional_programming::trho_flash_eqm::try_region_px_trho_flash_eqm;
+
+use super::*;
+
+/// (u,v) flash which also reports the region used, the number 
+/// of illinois iterations on temperature, and the internal 
+/// energy residual at the flashed state
+pub fn try_uv_flash_with_diagnostics(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<FlashReport, SteamTableError> {
+
+    let (t, iterations) = try_t_uv_eqm_with_iterations(u, v)?;
+
+    let (region, p, x) = try_region_px_trho_flash_eqm(t, v.recip())?;
+
+    let residuals = try_forward_equation_residuals(region, t, p, v,
+        None, None, Some(u))?;
+
+    return Ok(FlashReport {
+        t,
+        p,
+        v,
+        x,
+        region,
+        subregion: None,
+        iterations,
+        residuals,
+        boundary_correction_applied: false,
+    });
+}
+
+/// (u,v) flash which also reports how the result was obtained,
+/// panics if the (u,v) point is outside the validity range
+pub fn uv_flash_with_diagnostics(u: AvailableEnergy,
+    v: SpecificVolume) -> FlashReport {
+    match try_uv_flash_with_diagnostics(u, v) {
+        Ok(report) => report,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
@@ -0,0 +1,290 @@
+use uom::si::available_energy::joule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::errors::SteamTableError;
+
+use super::pt_flash_eqm::FwdEqnRegion;
+use super::trho_flash_eqm::{try_trho_flash_region, try_u_trho_eqm};
+
+/// fallible versions of the (u,v) flash functions
+/// which return a SteamTableError rather than panicking
+pub mod try_flash;
+pub use try_flash::*;
+
+/// (u,v) flash which also reports the region, subregion,
+/// iterations and residuals (see flash_diagnostics)
+pub mod diagnostics;
+pub use diagnostics::*;
+
+/// (u,v) flash returning all intensive properties at once
+/// (see steam_state)
+pub mod steam_state_flash;
+pub use steam_state_flash::*;
+
+/// the temperature iteration stops when the internal energy
+/// is within UV_FLASH_U_TOLERANCE_JOULE_PER_KG of the target,
+/// or when the temperature bracket is less than
+/// UV_FLASH_T_TOLERANCE_KELVIN
+const UV_FLASH_U_TOLERANCE_JOULE_PER_KG: f64 = 1.0e-6;
+const UV_FLASH_T_TOLERANCE_KELVIN: f64 = 1.0e-10;
+const UV_FLASH_MAX_ITERATIONS: usize = 200;
+
+/// allows the user to check which region one is in based on a
+/// (u,v) flash
+///
+/// panics if the (u,v) point is outside the validity range
+pub fn uv_flash_region(u: AvailableEnergy, v: SpecificVolume) -> FwdEqnRegion {
+    match try_uv_flash_region(u, v) {
+        Ok(region) => region,
+        Err(error) => panic!("u,v point is outside validity range: {}", error),
+    }
+}
+
+/// allows the user to check which region one is in based on a
+/// (u,v) flash
+///
+/// returns an error rather than panicking if the (u,v) point
+/// is outside the validity range
+pub fn try_uv_flash_region(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<FwdEqnRegion, SteamTableError> {
+    let t = try_t_uv_eqm(u, v)?;
+
+    return try_trho_flash_region(t, v.recip());
+}
+
+/// returns temperature given internal energy and specific volume
+///
+/// panics if the (u,v) point is outside the validity range
+#[inline]
+pub fn t_uv_eqm(u: AvailableEnergy, v: SpecificVolume) -> ThermodynamicTemperature {
+    match try_t_uv_eqm(u, v) {
+        Ok(t) => t,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
+
+/// returns temperature given internal energy and specific volume
+///
+/// at constant specific volume (density), internal energy
+/// increases with temperature since cv is positive.
+/// This is also true within the vapour dome, where heating
+/// a rigid vessel changes the steam quality.
+/// So we iterate on temperature using the (T,rho) flash
+/// with a bracketed secant (illinois) method,
+///
+/// the bracket is from 273.15 K to the highest temperature
+/// where the isochore is still within the validity range
+/// (2273.15 K, or lower if pressure exceeds 100 MPa, or 50 MPa
+/// in region 5)
+///
+/// Note: internal energy is continuous along the isochore,
+/// including where it crosses the saturation line,
+/// so (u,v) points on the saturation line do not need
+/// special treatment, they are returned either as region 4
+/// with steam quality 0 or 1, or as the adjoining single phase
+/// region at saturation pressure
+pub fn try_t_uv_eqm(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<ThermodynamicTemperature, SteamTableError> {
+    let (t, _iterations) = try_t_uv_eqm_with_iterations(u, v)?;
+
+    return Ok(t);
+}
+
+/// same as try_t_uv_eqm, but also returns the number of 
+/// illinois iterations taken, for flash diagnostics
+pub(crate) fn try_t_uv_eqm_with_iterations(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<(ThermodynamicTemperature, usize), SteamTableError> {
+
+    let rho: MassDensity = v.recip();
+
+    let u_residual = |t_kelvin: f64| -> Result<f64, SteamTableError> {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let u_at_t = try_u_trho_eqm(t, rho)?;
+        Ok((u_at_t - u).get::<joule_per_kilogram>())
+    };
+
+    let mut t_low: f64 = 273.15;
+    let mut t_high: f64 = t_max_isochore(rho)?;
+
+    // if the lower end of the bracket is outside the
+    // validity range (eg. density too high), the error is
+    // returned here
+    let mut residual_low: f64 = u_residual(t_low)?;
+    let mut residual_high: f64 = u_residual(t_high)?;
+
+    if residual_low > 0.0 {
+        let bound = u + AvailableEnergy::new::<joule_per_kilogram>(residual_low);
+        return Err(SteamTableError::InternalEnergyBelowBound { u, bound });
+    };
+
+    if residual_high < 0.0 {
+        let bound = u + AvailableEnergy::new::<joule_per_kilogram>(residual_high);
+        return Err(SteamTableError::InternalEnergyAboveBound { u, bound });
+    };
+
+    // for the illinois method, we keep track of which end
+    // of the bracket was retained in the last iteration
+    let mut last_retained_low: Option<bool> = None;
+
+    for iteration in 0..UV_FLASH_MAX_ITERATIONS {
+
+        // regula falsi estimate, falling back to bisection
+        // if the residuals are equal
+        let t_new = if residual_high != residual_low {
+            t_low - residual_low * (t_high - t_low)/(residual_high - residual_low)
+        } else {
+            0.5 * (t_low + t_high)
+        };
+
+        let residual_new = u_residual(t_new)?;
+
+        if residual_new.abs() < UV_FLASH_U_TOLERANCE_JOULE_PER_KG {
+            return Ok((ThermodynamicTemperature::new::<kelvin>(t_new), iteration + 1));
+        };
+
+        if residual_new.signum() == residual_low.signum() {
+            t_low = t_new;
+            residual_low = residual_new;
+
+            // the high end was retained twice in a row,
+            // so halve its residual (illinois modification)
+            if last_retained_low == Some(false) {
+                residual_high *= 0.5;
+            };
+            last_retained_low = Some(false);
+        } else {
+            t_high = t_new;
+            residual_high = residual_new;
+
+            if last_retained_low == Some(true) {
+                residual_low *= 0.5;
+            };
+            last_retained_low = Some(true);
+        };
+
+        if (t_high - t_low).abs() < UV_FLASH_T_TOLERANCE_KELVIN {
+            return Ok((ThermodynamicTemperature::new::<kelvin>(t_new), iteration + 1));
+        };
+    }
+
+    return Err(SteamTableError::FlashDidNotConverge {
+        flash: "(u,v)", iterations: UV_FLASH_MAX_ITERATIONS });
+}
+
+/// highest temperature (in kelvin) along an isochore for which
+/// the (T,rho) flash is within the validity range
+///
+/// pressure increases with temperature along an isochore,
+/// so for dense fluids, the upper bound is where pressure
+/// reaches 100 MPa (or 50 MPa in region 5) rather than 2273.15 K
+///
+/// this is found by bisection between 273.15 K and 2273.15 K
+fn t_max_isochore(rho: MassDensity) -> Result<f64, SteamTableError> {
+
+    let is_valid = |t_kelvin: f64| -> bool {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        try_u_trho_eqm(t, rho).is_ok()
+    };
+
+    let mut t_low: f64 = 273.15;
+    let mut t_high: f64 = 2273.15;
+
+    if is_valid(t_high) {
+        return Ok(t_high);
+    };
+
+    // if even the lowest temperature is invalid, return
+    // that error to the user
+    try_u_trho_eqm(ThermodynamicTemperature::new::<kelvin>(t_low), rho)?;
+
+    for _ in 0..UV_FLASH_MAX_ITERATIONS {
+        let t_mid = 0.5 * (t_low + t_high);
+
+        if is_valid(t_mid) {
+            t_low = t_mid;
+        } else {
+            t_high = t_mid;
+        };
+
+        if t_high - t_low < UV_FLASH_T_TOLERANCE_KELVIN {
+            break;
+        };
+    }
+
+    return Ok(t_low);
+}
+
+/// returns pressure given internal energy and specific volume
+pub fn p_uv_eqm(u: AvailableEnergy, v: SpecificVolume) -> Pressure {
+    match try_p_uv_eqm(u, v) {
+        Ok(p) => p,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
+
+/// returns steam quality given internal energy and specific volume
+pub fn x_uv_flash(u: AvailableEnergy, v: SpecificVolume) -> f64 {
+    match try_x_uv_flash(u, v) {
+        Ok(x) => x,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
+
+/// returns enthalpy given internal energy and specific volume
+pub fn h_uv_eqm(u: AvailableEnergy, v: SpecificVolume) -> AvailableEnergy {
+    match try_h_uv_eqm(u, v) {
+        Ok(h) => h,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
+
+/// returns entropy given internal energy and specific volume
+pub fn s_uv_eqm(u: AvailableEnergy, v: SpecificVolume) -> SpecificHeatCapacity {
+    match try_s_uv_eqm(u, v) {
+        Ok(s) => s,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
+
+/// returns cp given internal energy and specific volume
+pub fn cp_uv_eqm(u: AvailableEnergy, v: SpecificVolume) -> SpecificHeatCapacity {
+    match try_cp_uv_eqm(u, v) {
+        Ok(cp) => cp,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
+
+/// returns cv given internal energy and specific volume
+pub fn cv_uv_eqm(u: AvailableEnergy, v: SpecificVolume) -> SpecificHeatCapacity {
+    match try_cv_uv_eqm(u, v) {
+        Ok(cv) => cv,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
+
+/// returns w (speed of sound) given internal energy and specific volume
+pub fn w_uv_eqm(u: AvailableEnergy, v: SpecificVolume) -> Velocity {
+    match try_w_uv_eqm(u, v) {
+        Ok(w) => w,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
+
+/// returns mu, or sometimes eta (dynamic viscosity) given
+/// internal energy and specific volume
+pub fn mu_uv_eqm(u: AvailableEnergy, v: SpecificVolume) -> DynamicViscosity {
+    match try_mu_uv_eqm(u, v) {
+        Ok(mu) => mu,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
+
+/// returns lambda (thermal conductivity) given
+/// internal energy and specific volume
+pub fn lambda_uv_eqm(u: AvailableEnergy, v: SpecificVolume) -> ThermalConductivity {
+    match try_lambda_uv_eqm(u, v) {
+        Ok(lambda) => lambda,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
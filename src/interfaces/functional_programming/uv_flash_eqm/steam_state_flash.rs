@@ -0,0 +1,38 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+use crate::interfaces::functional_programming::trho_flash_eqm;
+
+use super::*;
+
+/// (u,v) flash returning all intensive properties at once
+///
+/// temperature is iterated once (see try_t_uv_eqm), and then 
+/// the (T,rho) flash is used for the rest
+///
+/// the internal energy and specific volume of the returned 
+/// state are the input internal energy and specific volume,
+/// so that mass and energy balances on a rigid control volume
+/// are conserved exactly
+pub fn try_flash(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<SteamState, SteamTableError> {
+
+    let t = try_t_uv_eqm(u, v)?;
+    let mut state = trho_flash_eqm::try_flash(t, v.recip())?;
+
+    state.u = u;
+    state.v = v;
+
+    return Ok(state);
+}
+
+/// (u,v) flash returning all intensive properties at once,
+/// panics if the (u,v) point is outside the validity range
+pub fn flash(u: AvailableEnergy,
+    v: SpecificVolume) -> SteamState {
+    match try_flash(u, v) {
+        Ok(state) => state,
+        Err(error) => panic!("u,v flash failed: {}", error),
+    }
+}
@@ -0,0 +1,89 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::trho_flash_eqm::{try_cp_trho_eqm, try_cv_trho_eqm, try_h_trho_eqm, try_lambda_trho_eqm, try_mu_trho_eqm, try_p_trho_eqm, try_s_trho_eqm, try_w_trho_eqm, try_x_trho_flash};
+
+use super::try_t_uv_eqm;
+
+/// returns pressure given internal energy and specific volume
+/// uses (T,rho) flash
+pub fn try_p_uv_eqm(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<Pressure, SteamTableError> {
+    let t = try_t_uv_eqm(u, v)?;
+
+    return try_p_trho_eqm(t, v.recip());
+}
+
+/// returns steam quality given internal energy and specific volume
+/// uses (T,rho) flash
+pub fn try_x_uv_flash(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<f64, SteamTableError> {
+    let t = try_t_uv_eqm(u, v)?;
+
+    return try_x_trho_flash(t, v.recip());
+}
+
+/// returns enthalpy given internal energy and specific volume
+/// uses (T,rho) flash
+pub fn try_h_uv_eqm(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<AvailableEnergy, SteamTableError> {
+    let t = try_t_uv_eqm(u, v)?;
+
+    return try_h_trho_eqm(t, v.recip());
+}
+
+/// returns entropy given internal energy and specific volume
+/// uses (T,rho) flash
+pub fn try_s_uv_eqm(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let t = try_t_uv_eqm(u, v)?;
+
+    return try_s_trho_eqm(t, v.recip());
+}
+
+/// returns cp given internal energy and specific volume
+/// uses (T,rho) flash
+pub fn try_cp_uv_eqm(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let t = try_t_uv_eqm(u, v)?;
+
+    return try_cp_trho_eqm(t, v.recip());
+}
+
+/// returns cv given internal energy and specific volume
+/// uses (T,rho) flash
+pub fn try_cv_uv_eqm(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<SpecificHeatCapacity, SteamTableError> {
+    let t = try_t_uv_eqm(u, v)?;
+
+    return try_cv_trho_eqm(t, v.recip());
+}
+
+/// returns w (speed of sound) given internal energy and specific volume
+/// uses (T,rho) flash
+pub fn try_w_uv_eqm(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<Velocity, SteamTableError> {
+    let t = try_t_uv_eqm(u, v)?;
+
+    return try_w_trho_eqm(t, v.recip());
+}
+
+/// returns mu, or sometimes eta (dynamic viscosity) given
+/// internal energy and specific volume
+/// uses (T,rho) flash
+pub fn try_mu_uv_eqm(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<DynamicViscosity, SteamTableError> {
+    let t = try_t_uv_eqm(u, v)?;
+
+    return try_mu_trho_eqm(t, v.recip());
+}
+
+/// returns lambda (thermal conductivity) given
+/// internal energy and specific volume
+/// uses (T,rho) flash
+pub fn try_lambda_uv_eqm(u: AvailableEnergy,
+    v: SpecificVolume) -> Result<ThermalConductivity, SteamTableError> {
+    let t = try_t_uv_eqm(u, v)?;
+
+    return try_lambda_trho_eqm(t, v.recip());
+}
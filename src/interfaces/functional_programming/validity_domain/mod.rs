@@ -0,0 +1,351 @@
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::joule_per_kilogram_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::errors::SteamTableError;
+use crate::region_4_vap_liq_equilibrium::sat_pressure_4;
+
+use super::hs_flash_eqm::try_hs_flash_region;
+use super::ph_flash_eqm::try_ph_flash_region;
+use super::ps_flash_eqm::{try_h_ps_eqm, try_ps_flash_region};
+use super::pt_flash_eqm::{h_tp_eqm_single_phase, s_tp_eqm_single_phase, try_region_fwd_eqn_single_phase};
+use super::pv_flash_eqm::try_pv_flash_region;
+use super::trho_flash_eqm::try_trho_flash_region;
+use super::uv_flash_eqm::try_uv_flash_region;
+
+/// the nearest valid point is found by moving the point onto
+/// the violated bound and checking again (the bounds of the second
+/// property usually depend on the first, eg. the enthalpy bounds
+/// depend on pressure), this is the maximum number of such moves
+const VALIDITY_DOMAIN_MAX_PROJECTIONS: usize = 10;
+
+/// result of checking a point against the validity range
+/// of a flash pair, eg. (p,h) or (h,s)
+///
+/// if the point is outside the validity range, violated_bound
+/// is the error the try_* flash functions would have returned
+/// (this contains the offending input and the bound it violated)
+/// and nearest_valid_point is the point moved onto the violated
+/// bound(s).
+///
+/// This is meant for solvers which need to clip their steps
+/// before calling the flash functions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValidityDomain<A, B> {
+    /// the point which was checked
+    pub point: (A, B),
+    /// the first bound which the point violated,
+    /// None if the point is valid
+    pub violated_bound: Option<SteamTableError>,
+    /// the point itself if it is valid, otherwise the point
+    /// moved onto the violated bound(s)
+    ///
+    /// this is None if no valid point could be found, for example
+    /// when the bound violated is not a bound on either of the
+    /// two properties (eg. a pressure bound for a (u,v) point)
+    pub nearest_valid_point: Option<(A, B)>,
+}
+
+impl<A: Copy, B: Copy> ValidityDomain<A, B> {
+
+    /// returns true if the point is within the validity range
+    pub fn is_valid(&self) -> bool {
+        return self.violated_bound.is_none();
+    }
+
+    /// checks a point against a validity check function,
+    /// and if the point is invalid, moves it onto the violated
+    /// bound using the projection function until it is valid
+    ///
+    /// the projection returns None if the violated bound cannot be
+    /// expressed in terms of the two properties
+    fn new<C, P>(point: (A, B), check: C, project: P) -> Self
+    where
+        C: Fn(A, B) -> Result<(), SteamTableError>,
+        P: Fn((A, B), SteamTableError) -> Option<(A, B)> {
+
+        let violated_bound = match check(point.0, point.1) {
+            Ok(()) => {
+                return Self {
+                    point,
+                    violated_bound: None,
+                    nearest_valid_point: Some(point),
+                };
+            },
+            Err(error) => error,
+        };
+
+        let mut nearest_valid_point = None;
+        let mut trial_point = point;
+        let mut error = violated_bound;
+
+        for _ in 0..VALIDITY_DOMAIN_MAX_PROJECTIONS {
+            trial_point = match project(trial_point, error) {
+                Some(projected_point) => projected_point,
+                None => break,
+            };
+
+            match check(trial_point.0, trial_point.1) {
+                Ok(()) => {
+                    nearest_valid_point = Some(trial_point);
+                    break;
+                },
+                Err(new_error) => error = new_error,
+            };
+        }
+
+        return Self {
+            point,
+            violated_bound: Some(violated_bound),
+            nearest_valid_point,
+        };
+    }
+}
+
+/// checks if the (T,p) point is within the validity range
+/// of the (T,p) flash
+pub fn is_valid_pt(t: ThermodynamicTemperature, p: Pressure) -> bool {
+    return try_region_fwd_eqn_single_phase(t, p).is_ok();
+}
+
+/// checks if the (p,h) point is within the validity range
+/// of the (p,h) flash
+pub fn is_valid_ph(p: Pressure, h: AvailableEnergy) -> bool {
+    return try_ph_flash_region(p, h).is_ok();
+}
+
+/// checks if the (p,s) point is within the validity range
+/// of the (p,s) flash
+pub fn is_valid_ps(p: Pressure, s: SpecificHeatCapacity) -> bool {
+    return try_ps_flash_region(p, s).is_ok();
+}
+
+/// checks if the (h,s) point is within the validity range
+/// of the (h,s) flash
+pub fn is_valid_hs(h: AvailableEnergy, s: SpecificHeatCapacity) -> bool {
+    return try_hs_flash_region(h, s).is_ok();
+}
+
+/// checks if the (T,rho) point is within the validity range
+/// of the (T,rho) flash
+pub fn is_valid_trho(t: ThermodynamicTemperature, rho: MassDensity) -> bool {
+    return try_trho_flash_region(t, rho).is_ok();
+}
+
+/// checks if the (p,v) point is within the validity range
+/// of the (p,v) flash
+pub fn is_valid_pv(p: Pressure, v: SpecificVolume) -> bool {
+    return try_pv_flash_region(p, v).is_ok();
+}
+
+/// checks if the (u,v) point is within the validity range
+/// of the (u,v) flash
+pub fn is_valid_uv(u: AvailableEnergy, v: SpecificVolume) -> bool {
+    return try_uv_flash_region(u, v).is_ok();
+}
+
+/// checks the (T,p) point against the validity range of the
+/// (T,p) flash, returning the violated bound and nearest valid point
+pub fn validity_domain_pt(t: ThermodynamicTemperature,
+    p: Pressure) -> ValidityDomain<ThermodynamicTemperature, Pressure> {
+
+    let check = |t, p| try_region_fwd_eqn_single_phase(t, p).map(|_| ());
+
+    let project = |(t, p), error| match error {
+        SteamTableError::TemperatureBelowBound { bound, .. }
+            | SteamTableError::TemperatureAboveBound { bound, .. } => Some((bound, p)),
+        SteamTableError::PressureBelowBound { bound, .. }
+            | SteamTableError::PressureAboveBound { bound, .. } => Some((t, bound)),
+        _ => None,
+    };
+
+    return ValidityDomain::new((t, p), check, project);
+}
+
+/// checks the (p,h) point against the validity range of the
+/// (p,h) flash, returning the violated bound and nearest valid point
+///
+/// pressure is moved first, then enthalpy is moved onto the
+/// isotherm bounding the validity range at that pressure
+pub fn validity_domain_ph(p: Pressure,
+    h: AvailableEnergy) -> ValidityDomain<Pressure, AvailableEnergy> {
+
+    let check = |p, h| try_ph_flash_region(p, h).map(|_| ());
+
+    let project = |(p, h), error| match error {
+        SteamTableError::PressureBelowBound { bound, .. }
+            | SteamTableError::PressureAboveBound { bound, .. } => Some((bound, h)),
+        SteamTableError::EnthalpyBelowBound { bound, .. }
+            | SteamTableError::EnthalpyAboveBound { bound, .. } => Some((p, bound)),
+        _ => None,
+    };
+
+    return ValidityDomain::new((p, h), check, project);
+}
+
+/// checks the (p,s) point against the validity range of the
+/// (p,s) flash, returning the violated bound and nearest valid point
+///
+/// pressure is moved first, then entropy is moved onto the
+/// isotherm bounding the validity range at that pressure
+pub fn validity_domain_ps(p: Pressure,
+    s: SpecificHeatCapacity) -> ValidityDomain<Pressure, SpecificHeatCapacity> {
+
+    let check = |p, s| try_ps_flash_region(p, s).map(|_| ());
+
+    let project = |(p, s), error| match error {
+        SteamTableError::PressureBelowBound { bound, .. }
+            | SteamTableError::PressureAboveBound { bound, .. } => Some((bound, s)),
+        SteamTableError::EntropyBelowBound { bound, .. }
+            | SteamTableError::EntropyAboveBound { bound, .. } => Some((p, bound)),
+        _ => None,
+    };
+
+    return ValidityDomain::new((p, s), check, project);
+}
+
+/// checks the (h,s) point against the validity range of the
+/// (h,s) flash, returning the violated bound and nearest valid point
+///
+/// enthalpy and entropy bounds are applied directly,
+/// for temperature bounds, the point is moved along the isentrope
+/// onto the bounding isotherm, and for pressure bounds, the point
+/// is moved along the isentrope onto the bounding isobar
+pub fn validity_domain_hs(h: AvailableEnergy,
+    s: SpecificHeatCapacity) -> ValidityDomain<AvailableEnergy, SpecificHeatCapacity> {
+
+    let check = |h, s| try_hs_flash_region(h, s).map(|_| ());
+
+    let project = |(h, s), error| match error {
+        SteamTableError::EnthalpyBelowBound { bound, .. }
+            | SteamTableError::EnthalpyAboveBound { bound, .. } => Some((bound, s)),
+        SteamTableError::EntropyBelowBound { bound, .. }
+            | SteamTableError::EntropyAboveBound { bound, .. } => Some((h, bound)),
+        SteamTableError::TemperatureBelowBound { bound, .. }
+            | SteamTableError::TemperatureAboveBound { bound, .. } => {
+            h_isentrope_on_isotherm(bound, s).map(|h| (h, s))
+        },
+        SteamTableError::PressureBelowBound { bound, .. }
+            | SteamTableError::PressureAboveBound { bound, .. } => {
+            try_h_ps_eqm(bound, s).ok().map(|h| (h, s))
+        },
+        _ => None,
+    };
+
+    return ValidityDomain::new((h, s), check, project);
+}
+
+/// checks the (T,rho) point against the validity range of the
+/// (T,rho) flash, returning the violated bound and nearest valid point
+///
+/// Note: density must be positive, so a point violating the
+/// density bound has no nearest valid point
+pub fn validity_domain_trho(t: ThermodynamicTemperature,
+    rho: MassDensity) -> ValidityDomain<ThermodynamicTemperature, MassDensity> {
+
+    let check = |t, rho| try_trho_flash_region(t, rho).map(|_| ());
+
+    let project = |(_t, rho), error| match error {
+        SteamTableError::TemperatureBelowBound { bound, .. }
+            | SteamTableError::TemperatureAboveBound { bound, .. } => Some((bound, rho)),
+        _ => None,
+    };
+
+    return ValidityDomain::new((t, rho), check, project);
+}
+
+/// checks the (p,v) point against the validity range of the
+/// (p,v) flash, returning the violated bound and nearest valid point
+///
+/// pressure is moved first, then specific volume is moved onto the
+/// isotherm bounding the validity range at that pressure
+pub fn validity_domain_pv(p: Pressure,
+    v: SpecificVolume) -> ValidityDomain<Pressure, SpecificVolume> {
+
+    let check = |p, v| try_pv_flash_region(p, v).map(|_| ());
+
+    let project = |(p, v), error| match error {
+        SteamTableError::PressureBelowBound { bound, .. }
+            | SteamTableError::PressureAboveBound { bound, .. } => Some((bound, v)),
+        SteamTableError::SpecificVolumeBelowBound { bound, .. }
+            | SteamTableError::SpecificVolumeAboveBound { bound, .. } => Some((p, bound)),
+        _ => None,
+    };
+
+    return ValidityDomain::new((p, v), check, project);
+}
+
+/// checks the (u,v) point against the validity range of the
+/// (u,v) flash, returning the violated bound and nearest valid point
+///
+/// internal energy is moved onto the bounds of the isochore,
+/// if the isochore itself lies outside the validity range
+/// (eg. pressure exceeds 100 MPa at 273.15 K), there is no
+/// nearest valid point
+pub fn validity_domain_uv(u: AvailableEnergy,
+    v: SpecificVolume) -> ValidityDomain<AvailableEnergy, SpecificVolume> {
+
+    let check = |u, v| try_uv_flash_region(u, v).map(|_| ());
+
+    let project = |(_u, v), error| match error {
+        SteamTableError::InternalEnergyBelowBound { bound, .. }
+            | SteamTableError::InternalEnergyAboveBound { bound, .. } => Some((bound, v)),
+        _ => None,
+    };
+
+    return ValidityDomain::new((u, v), check, project);
+}
+
+/// enthalpy at the intersection of an isentrope with an isotherm,
+/// used to move (h,s) points violating a temperature bound
+/// back into the validity range
+///
+/// entropy decreases with pressure along an isotherm,
+/// so we bisect on ln(p) from the saturation pressure at 273.15 K
+/// to 100 MPa, returns None if the isentrope does not cross the
+/// isotherm within this pressure range
+fn h_isentrope_on_isotherm(t: ThermodynamicTemperature,
+    s: SpecificHeatCapacity) -> Option<AvailableEnergy> {
+
+    let t_kelvin = t.get::<kelvin>();
+
+    // the (T,p) single phase equations are not valid within
+    // the vapour dome, so only do this above 1073.15 K
+    // where the (h,s) flash temperature bounds are
+    if t_kelvin < 1073.15 {
+        return None;
+    };
+
+    let s_target = s.get::<joule_per_kilogram_kelvin>();
+    let s_at_ln_p = |ln_p_mpa: f64| -> f64 {
+        let p = Pressure::new::<megapascal>(ln_p_mpa.exp());
+        s_tp_eqm_single_phase(t, p).get::<joule_per_kilogram_kelvin>()
+    };
+
+    let p_min = sat_pressure_4(ThermodynamicTemperature::new::<kelvin>(273.15));
+    let mut ln_p_low = p_min.get::<megapascal>().ln();
+    let mut ln_p_high = 100.0_f64.ln();
+
+    if s_target > s_at_ln_p(ln_p_low) || s_target < s_at_ln_p(ln_p_high) {
+        return None;
+    };
+
+    for _ in 0..100 {
+        let ln_p_mid = 0.5 * (ln_p_low + ln_p_high);
+
+        if s_at_ln_p(ln_p_mid) > s_target {
+            ln_p_low = ln_p_mid;
+        } else {
+            ln_p_high = ln_p_mid;
+        };
+
+        if ln_p_high - ln_p_low < 1.0e-12 {
+            break;
+        };
+    }
+
+    let p = Pressure::new::<megapascal>((0.5 * (ln_p_low + ln_p_high)).exp());
+
+    return Some(h_tp_eqm_single_phase(t, p));
+}
@@ -12,6 +12,11 @@ pub mod functional_programming;
 /// this is basically a simple control volume
 pub mod object_oriented_programming;
 
+/// a trait for swapping the IAPWS-IF97 equations 
+/// (If97) for other property backends, 
+/// eg. tabulated, IAPWS-95 or mocked properties
+pub mod steam_property_provider;
+
 /// these tests show you how to use the interfaces 
 ///
 /// i may attempt to produce part or whole of the 
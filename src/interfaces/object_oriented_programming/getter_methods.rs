@@ -1,5 +1,10 @@
 use uom::si::f64::*;
-impl super::TampinesSteamTableCV {
+
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+use crate::interfaces::steam_property_provider::SteamPropertyProvider;
+
+impl<P: SteamPropertyProvider> super::TampinesSteamTableCV<P> {
     /// Returns the pressure of the control volume.
     pub fn get_pressure(&self) -> Pressure {
         self.pressure
@@ -29,8 +34,81 @@ impl super::TampinesSteamTableCV {
     pub fn get_volume(&self) -> Volume {
         self.volume
     }
+    /// returns the property backend of the control volume
+    pub fn get_provider(&self) -> &P {
+        return &self.provider;
+    }
+
     /// returns the mass within the control volume 
     pub fn get_mass(&self) -> Mass {
         return self.volume/self.specific_volume;
     }
+
+    /// returns the derived properties of the control volume,
+    /// these were flashed when the state was last set
+    fn get_steam_state(&self) -> &SteamState {
+        return &self.state;
+    }
+
+    /// returns the steam quality (vapour mass fraction)
+    pub fn get_quality(&self) -> f64 {
+        return self.get_steam_state().x;
+    }
+
+    /// returns the IF97 region of the fluid in the control volume,
+    /// None if the property backend does not use the IF97 regions
+    pub fn get_region(&self) -> Option<FwdEqnRegion> {
+        return self.get_steam_state().region;
+    }
+
+    /// returns the specific internal energy
+    pub fn get_internal_energy(&self) -> AvailableEnergy {
+        return self.get_steam_state().u;
+    }
+
+    /// returns the isobaric (const pressure) heat capacity
+    pub fn get_cp(&self) -> SpecificHeatCapacity {
+        return self.get_steam_state().cp;
+    }
+
+    /// returns the isochoric (const vol) heat capacity
+    pub fn get_cv(&self) -> SpecificHeatCapacity {
+        return self.get_steam_state().cv;
+    }
+
+    /// returns the speed of sound
+    pub fn get_speed_of_sound(&self) -> Velocity {
+        return self.get_steam_state().w;
+    }
+
+    /// returns the dynamic viscosity
+    pub fn get_dynamic_viscosity(&self) -> DynamicViscosity {
+        return self.get_steam_state().mu;
+    }
+
+    /// returns the thermal conductivity
+    pub fn get_thermal_conductivity(&self) -> ThermalConductivity {
+        return self.get_steam_state().lambda;
+    }
+
+    /// returns the prandtl number, mu cp/lambda
+    pub fn get_prandtl_number(&self) -> Ratio {
+        return self.get_steam_state().prandtl;
+    }
+
+    /// returns the density of the fluid in the control volume
+    /// (no flash is needed for this)
+    pub fn get_density(&self) -> MassDensity {
+        return self.specific_volume.recip();
+    }
+
+    /// returns the total enthalpy within the control volume
+    pub fn get_total_enthalpy(&self) -> Energy {
+        return self.get_mass() * self.specific_enthalpy;
+    }
+
+    /// returns the total internal energy within the control volume
+    pub fn get_total_internal_energy(&self) -> Energy {
+        return self.get_mass() * self.get_internal_energy();
+    }
 }
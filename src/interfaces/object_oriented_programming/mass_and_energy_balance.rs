@@ -0,0 +1,188 @@
+use uom::si::f64::*;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::steam_property_provider::SteamPropertyProvider;
+
+/// the control volume is rigid, so for a transient,
+/// the mass and total internal energy are balanced:
+///
+/// dm/dt = sum (m_dot_in) - sum (m_dot_out)
+/// dU/dt = sum (m_dot_in h_in) - sum (m_dot_out h) + Q_dot
+///
+/// and the new state is found from the (u,v) flash 
+/// of the property backend, where v = V/m and u = U/m
+///
+/// the flow work is included in the enthalpy of the
+/// inflows and outflows, and there is no shaft work
+/// since the volume is fixed
+impl<P: SteamPropertyProvider> super::TampinesSteamTableCV<P> {
+
+    /// adds heat to the control volume at fixed mass and volume,
+    /// (negative heat removes heat), returns an error and leaves
+    /// the control volume unchanged if the new state is outside
+    /// the validity range
+    pub fn try_add_heat(&mut self, heat: Energy) -> Result<(), SteamTableError> {
+
+        let mass = self.get_mass();
+        let internal_energy = self.get_total_internal_energy_from_enthalpy();
+
+        return self.try_set_mass_and_internal_energy(mass, internal_energy + heat);
+    }
+
+    /// adds heat to the control volume at fixed mass and volume,
+    /// panics if the new state is outside the validity range
+    pub fn add_heat(&mut self, heat: Energy) {
+        match self.try_add_heat(heat) {
+            Ok(()) => (),
+            Err(error) => panic!("u,v flash failed: {}", error),
+        }
+    }
+
+    /// adds mass with a given specific enthalpy to the
+    /// control volume (eg. feedwater or a surge into a pressuriser),
+    /// returns an error and leaves the control volume unchanged
+    /// if the new state is outside the validity range
+    pub fn try_add_mass_with_enthalpy(&mut self,
+        mass_added: Mass,
+        h_in: AvailableEnergy) -> Result<(), SteamTableError> {
+
+        let mass = self.get_mass() + mass_added;
+        let internal_energy = self.get_total_internal_energy_from_enthalpy()
+            + mass_added * h_in;
+
+        return self.try_set_mass_and_internal_energy(mass, internal_energy);
+    }
+
+    /// adds mass with a given specific enthalpy to the
+    /// control volume, panics if the new state is outside
+    /// the validity range
+    pub fn add_mass_with_enthalpy(&mut self,
+        mass_added: Mass,
+        h_in: AvailableEnergy) {
+        match self.try_add_mass_with_enthalpy(mass_added, h_in) {
+            Ok(()) => (),
+            Err(error) => panic!("u,v flash failed: {}", error),
+        }
+    }
+
+    /// removes mass from the control volume at the current
+    /// specific enthalpy (eg. vessel blowdown through a valve),
+    /// returns an error and leaves the control volume unchanged
+    /// if all the mass is removed or if the new state is outside
+    /// the validity range
+    ///
+    /// Note: in region 4 the mass removed is the mixture,
+    /// not the liquid or vapour phase alone
+    pub fn try_remove_mass_at_current_state(&mut self,
+        mass_removed: Mass) -> Result<(), SteamTableError> {
+
+        let mass = self.get_mass() - mass_removed;
+        let internal_energy = self.get_total_internal_energy_from_enthalpy()
+            - mass_removed * self.specific_enthalpy;
+
+        return self.try_set_mass_and_internal_energy(mass, internal_energy);
+    }
+
+    /// removes mass from the control volume at the current
+    /// specific enthalpy, panics if all the mass is removed
+    /// or if the new state is outside the validity range
+    pub fn remove_mass_at_current_state(&mut self,
+        mass_removed: Mass) {
+        match self.try_remove_mass_at_current_state(mass_removed) {
+            Ok(()) => (),
+            Err(error) => panic!("u,v flash failed: {}", error),
+        }
+    }
+
+    /// advances the control volume by one (explicit euler)
+    /// timestep given the inflows (mass flowrate and specific
+    /// enthalpy), outflows (mass flowrate, leaving at the current
+    /// specific enthalpy) and the heat rate into the control volume
+    ///
+    /// returns an error and leaves the control volume unchanged
+    /// if all the mass is removed or if the new state is outside
+    /// the validity range,
+    /// the calling code may then retry with a smaller timestep
+    pub fn try_advance(&mut self,
+        dt: Time,
+        inflows: &[(MassRate, AvailableEnergy)],
+        outflows: &[MassRate],
+        heat_rate: Power) -> Result<(), SteamTableError> {
+
+        let mut mass = self.get_mass();
+        let mut internal_energy = self.get_total_internal_energy_from_enthalpy()
+            + heat_rate * dt;
+
+        for (mass_flowrate, h_in) in inflows {
+            mass += *mass_flowrate * dt;
+            internal_energy += *mass_flowrate * dt * *h_in;
+        }
+
+        for mass_flowrate in outflows {
+            mass -= *mass_flowrate * dt;
+            internal_energy -= *mass_flowrate * dt * self.specific_enthalpy;
+        }
+
+        return self.try_set_mass_and_internal_energy(mass, internal_energy);
+    }
+
+    /// advances the control volume by one (explicit euler)
+    /// timestep, panics if all the mass is removed or if the
+    /// new state is outside the validity range
+    pub fn advance(&mut self,
+        dt: Time,
+        inflows: &[(MassRate, AvailableEnergy)],
+        outflows: &[MassRate],
+        heat_rate: Power) {
+        match self.try_advance(dt, inflows, outflows, heat_rate) {
+            Ok(()) => (),
+            Err(error) => panic!("u,v flash failed: {}", error),
+        }
+    }
+
+    /// total internal energy using u = h - pv,
+    /// this uses the stored state directly so that no flash
+    /// is needed before each balance
+    fn get_total_internal_energy_from_enthalpy(&self) -> Energy {
+        let u = self.specific_enthalpy - self.pressure * self.specific_volume;
+
+        return self.get_mass() * u;
+    }
+
+    /// flashes the new state at fixed volume given the
+    /// mass and total internal energy, the control volume
+    /// is only changed if the flash succeeds
+    fn try_set_mass_and_internal_energy(&mut self,
+        mass: Mass,
+        internal_energy: Energy) -> Result<(), SteamTableError> {
+
+        let (u, v) = try_uv_from_mass_and_internal_energy(
+            mass, internal_energy, self.get_volume())?;
+
+        let state = self.provider.try_flash_uv(u, v)?;
+        self.set_steam_state(state);
+
+        return Ok(());
+    }
+}
+
+/// specific internal energy and specific volume given the
+/// mass and total internal energy within a volume
+pub(super) fn try_uv_from_mass_and_internal_energy(mass: Mass,
+    internal_energy: Energy,
+    volume: Volume) -> Result<(AvailableEnergy, SpecificVolume), SteamTableError> {
+
+    // we cannot have an empty (or negative mass) control volume
+    if mass.value <= 0.0 {
+        return Err(SteamTableError::DensityBelowBound {
+            rho: mass/volume,
+            bound: MassDensity::new::<kilogram_per_cubic_meter>(0.0),
+        });
+    };
+
+    let v: SpecificVolume = volume/mass;
+    let u: AvailableEnergy = internal_energy/mass;
+
+    return Ok((u, v));
+}
@@ -1,11 +1,37 @@
 use uom::si::f64::*;
 
+use crate::errors::SteamTableError;
 use crate::interfaces::functional_programming::*;
+use crate::interfaces::functional_programming::out_of_range_policy::{OutOfRangePolicy, OutOfRangeReport, PolicyFlashState};
+use crate::interfaces::functional_programming::steam_state::SteamState;
+use crate::interfaces::steam_property_provider::If97;
+#[cfg(feature = "serde")]
+use crate::interfaces::steam_property_provider::SteamPropertyProvider;
 
 /// this is the bread and butter for tampines steam tables, 
 /// the control volume
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct TampinesSteamTableCV {
+///
+/// the control volume is generic over the property backend 
+/// (see SteamPropertyProvider), which defaults to the 
+/// IAPWS-IF97 equations (If97), 
+/// the new_from_* constructors and set_* setters are 
+/// for If97 only, while new_with_provider and set_state 
+/// work with any provider
+///
+/// with the serde feature, the control volume can be serialized 
+/// (eg. to checkpoint and restart a transient), the fields are 
+/// pressure, temperature, specific_volume, specific_enthalpy,
+/// specific_entropy and volume, in SI base units 
+/// (Pa, K, m^3/kg, J/kg, J/(kg K) and m^3),
+/// the derived properties and the provider are not serialized, 
+/// so the provider is its Default on deserialization
+/// (as is the out of range policy), and the derived properties
+/// are flashed again from pressure and enthalpy using that provider
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerializedSteamTableCV",
+    bound(deserialize = "P: SteamPropertyProvider + Default")))]
+pub struct TampinesSteamTableCV<P = If97> {
     /// these are intensive properties
     pressure: Pressure,
     temperature: ThermodynamicTemperature,
@@ -16,6 +42,40 @@ pub struct TampinesSteamTableCV {
     /// these are extensive properties, 
     /// Volume should be fixed for a control volume by definition
     volume: Volume,
+
+    /// derived properties (quality, cp, viscosity etc.),
+    /// these are flashed by every constructor (and so every setter)
+    /// so that the getters do not need to flash again
+    #[cfg_attr(feature = "serde", serde(skip))]
+    state: SteamState,
+
+    /// the property backend used by the getters, 
+    /// the mass and energy balances and set_state
+    #[cfg_attr(feature = "serde", serde(skip))]
+    provider: P,
+
+    /// what the setters do when given a point outside the
+    /// validity range, this is Error unless the control volume
+    /// was constructed with a try_new_from_*_with_policy
+    /// constructor or set_out_of_range_policy was used
+    #[cfg_attr(feature = "serde", serde(skip))]
+    out_of_range_policy: OutOfRangePolicy,
+}
+
+/// two control volumes are equal if their properties are equal,
+/// the derived properties are left out since different flashes
+/// (eg. (p,h) and (T,p)) give slightly different derived properties
+/// at the same point, and so is the out of range policy
+/// used by the setters
+impl<P> PartialEq for TampinesSteamTableCV<P> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.pressure == other.pressure
+            && self.temperature == other.temperature
+            && self.specific_volume == other.specific_volume
+            && self.specific_enthalpy == other.specific_enthalpy
+            && self.specific_entropy == other.specific_entropy
+            && self.volume == other.volume;
+    }
 }
 
 impl TampinesSteamTableCV {
@@ -44,6 +104,9 @@ impl TampinesSteamTableCV {
             specific_enthalpy,
             specific_entropy,
             volume,
+            state: ph_flash_eqm::flash(pressure, specific_enthalpy),
+            provider: If97,
+            out_of_range_policy: OutOfRangePolicy::Error,
         };
     }
 
@@ -76,6 +139,9 @@ impl TampinesSteamTableCV {
             specific_enthalpy,
             specific_entropy,
             volume,
+            state: ph_flash_eqm::flash(pressure, specific_enthalpy),
+            provider: If97,
+            out_of_range_policy: OutOfRangePolicy::Error,
         };
     }
     /// creates a new control volume assuming quality is 0
@@ -107,6 +173,9 @@ impl TampinesSteamTableCV {
             specific_enthalpy,
             specific_entropy,
             volume,
+            state: ph_flash_eqm::flash(pressure, specific_enthalpy),
+            provider: If97,
+            out_of_range_policy: OutOfRangePolicy::Error,
         };
     }
 
@@ -130,6 +199,9 @@ impl TampinesSteamTableCV {
             specific_enthalpy,
             specific_entropy,
             volume,
+            state: ph_flash_eqm::flash(pressure, specific_enthalpy),
+            provider: If97,
+            out_of_range_policy: OutOfRangePolicy::Error,
         };
 
     }
@@ -153,16 +225,165 @@ impl TampinesSteamTableCV {
             specific_enthalpy,
             specific_entropy,
             volume,
+            state: ph_flash_eqm::flash(pressure, specific_enthalpy),
+            provider: If97,
+            out_of_range_policy: OutOfRangePolicy::Error,
         };
 
     }
 
+    /// creates a new control volume from internal energy and 
+    /// specific volume, panics if the (u,v) point is outside 
+    /// the validity range
+    pub fn new_from_uv(
+        u: AvailableEnergy,
+        v: SpecificVolume,
+        volume: Volume) -> Self {
+
+        match Self::try_new_from_uv(u, v, volume) {
+            Ok(cv) => cv,
+            Err(error) => panic!("u,v flash failed: {}", error),
+        }
+    }
+
+    /// creates a new control volume from internal energy and 
+    /// specific volume, returning an error if the (u,v) point 
+    /// is outside the validity range
+    ///
+    /// the mass and energy balances use the same (u,v) flash 
+    /// (through the provider), since the volume of the control 
+    /// volume is fixed
+    pub fn try_new_from_uv(
+        u: AvailableEnergy,
+        v: SpecificVolume,
+        volume: Volume) -> Result<Self, SteamTableError> {
+
+        let state = uv_flash_eqm::try_flash(u, v)?;
+
+        return Ok(Self::new_from_steam_state(If97, state, volume));
+    }
+
+    /// creates a new control volume from temperature, pressure 
+    /// and quality, applying the out of range policy if the 
+    /// (T,p) point is outside the validity range
+    ///
+    /// the report tells you if the point was clamped or extrapolated
+    pub fn try_new_from_tp_quality_with_policy(
+        temperature: ThermodynamicTemperature,
+        pressure: Pressure,
+        volume: Volume,
+        x: f64,
+        policy: OutOfRangePolicy) -> Result<(Self, OutOfRangeReport), SteamTableError> {
+
+        let state = pt_flash_eqm::try_tp_flash_with_policy(
+            temperature, pressure, x, policy)?;
+
+        return Ok((Self::new_from_policy_flash_state(state, volume, policy), state.report));
+    }
+
+    /// creates a new control volume from pressure and enthalpy, 
+    /// applying the out of range policy if the 
+    /// (p,h) point is outside the validity range
+    ///
+    /// the report tells you if the point was clamped or extrapolated
+    pub fn try_new_from_ph_with_policy(
+        p: Pressure,
+        h: AvailableEnergy,
+        volume: Volume,
+        policy: OutOfRangePolicy) -> Result<(Self, OutOfRangeReport), SteamTableError> {
+
+        let state = ph_flash_eqm::try_ph_flash_with_policy(p, h, policy)?;
+
+        return Ok((Self::new_from_policy_flash_state(state, volume, policy), state.report));
+    }
+
+    /// creates a new control volume from pressure and entropy, 
+    /// applying the out of range policy if the 
+    /// (p,s) point is outside the validity range
+    ///
+    /// the report tells you if the point was clamped or extrapolated
+    pub fn try_new_from_ps_with_policy(
+        p: Pressure,
+        s: SpecificHeatCapacity,
+        volume: Volume,
+        policy: OutOfRangePolicy) -> Result<(Self, OutOfRangeReport), SteamTableError> {
+
+        let state = ps_flash_eqm::try_ps_flash_with_policy(p, s, policy)?;
+
+        return Ok((Self::new_from_policy_flash_state(state, volume, policy), state.report));
+    }
+
+    /// creates a new control volume from enthalpy and entropy, 
+    /// applying the out of range policy if the 
+    /// (h,s) point is outside the validity range
+    ///
+    /// the report tells you if the point was clamped or extrapolated
+    pub fn try_new_from_hs_with_policy(
+        h: AvailableEnergy,
+        s: SpecificHeatCapacity,
+        volume: Volume,
+        policy: OutOfRangePolicy) -> Result<(Self, OutOfRangeReport), SteamTableError> {
+
+        let state = hs_flash_eqm::try_hs_flash_with_policy(h, s, policy)?;
+
+        return Ok((Self::new_from_policy_flash_state(state, volume, policy), state.report));
+    }
+
 
+    /// the policy flashes return all properties at once (SteamState),
+    /// so these are kept for the getters straight away,
+    /// and the policy is kept for the setters
+    fn new_from_policy_flash_state(state: PolicyFlashState,
+        volume: Volume,
+        policy: OutOfRangePolicy) -> Self {
+
+        let mut cv = Self::new_from_steam_state(If97, state.state, volume);
+        cv.out_of_range_policy = policy;
 
+        return cv;
+    }
 }
 
 
 
+/// the serialized fields of the control volume
+/// (see TampinesSteamTableCV)
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerializedSteamTableCV {
+    pressure: Pressure,
+    temperature: ThermodynamicTemperature,
+    specific_volume: SpecificVolume,
+    specific_enthalpy: AvailableEnergy,
+    specific_entropy: SpecificHeatCapacity,
+    volume: Volume,
+}
+
+/// flashes the derived properties again on deserialization,
+/// returning an error if the (p,h) point is outside
+/// the validity range of the provider
+#[cfg(feature = "serde")]
+impl<P: SteamPropertyProvider + Default> TryFrom<SerializedSteamTableCV> for TampinesSteamTableCV<P> {
+    type Error = SteamTableError;
+
+    fn try_from(fields: SerializedSteamTableCV) -> Result<Self, SteamTableError> {
+
+        let provider = P::default();
+        let state = provider.try_flash_ph(fields.pressure, fields.specific_enthalpy)?;
+
+        return Ok(Self {
+            pressure: fields.pressure,
+            temperature: fields.temperature,
+            specific_volume: fields.specific_volume,
+            specific_enthalpy: fields.specific_enthalpy,
+            specific_entropy: fields.specific_entropy,
+            volume: fields.volume,
+            state,
+            provider,
+            out_of_range_policy: OutOfRangePolicy::Error,
+        });
+    }
+}
 
 /// vibe coded getter methods
 pub mod getter_methods;
@@ -171,3 +392,13 @@ pub mod getter_methods;
 /// this will deal with setting new thermodynamic equilibrium
 /// based on user input parameters
 pub mod setter_methods;
+
+/// transient mass and energy balances at fixed volume 
+/// (adding heat, adding and removing mass, and 
+/// time stepping with inflows and outflows)
+pub mod mass_and_energy_balance;
+
+/// constructing and setting the control volume state 
+/// from any two independent properties (see StateSpec)
+pub mod state_spec;
+pub use state_spec::*;
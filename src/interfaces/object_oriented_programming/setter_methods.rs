@@ -1,36 +1,163 @@
 use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::*;
+use crate::interfaces::functional_programming::out_of_range_policy::{OutOfRangePolicy, OutOfRangeReport};
 impl super::TampinesSteamTableCV {
 
+    /// sets the state using temperature, pressure and quality,
+    /// the out of range policy of the control volume is applied
+    /// if the (T,p) point is outside the validity range
+    ///
+    /// panics if the policy is Error and the point is outside
+    /// the validity range
     pub fn set_tpx(&mut self,
         t: ThermodynamicTemperature,
         p: Pressure,
         x: f64){
 
-        let volume = self.get_volume();
+        if self.out_of_range_policy == OutOfRangePolicy::Error {
+            let volume = self.get_volume();
 
-        *self = Self::new_from_tp_quality(t, p, volume, x);
-        
+            *self = Self::new_from_tp_quality(t, p, volume, x);
+            return;
+        };
 
+        match self.try_set_tpx(t, p, x) {
+            Ok(_report) => (),
+            Err(error) => panic!("t,p flash failed: {}", error),
+        }
     }
 
+    /// sets the state using pressure and enthalpy,
+    /// the out of range policy of the control volume is applied
+    /// if the (p,h) point is outside the validity range
+    ///
+    /// panics if the policy is Error and the point is outside
+    /// the validity range
     pub fn set_ph(&mut self,
         p: Pressure,
         h: AvailableEnergy,){
 
-        let volume = self.get_volume();
+        if self.out_of_range_policy == OutOfRangePolicy::Error {
+            let volume = self.get_volume();
 
-        *self = Self::new_from_ph(p, h, volume);
-        
+            *self = Self::new_from_ph(p, h, volume);
+            return;
+        };
 
+        match self.try_set_ph(p, h) {
+            Ok(_report) => (),
+            Err(error) => panic!("p,h flash failed: {}", error),
+        }
     }
+
+    /// sets the state using pressure and entropy,
+    /// the out of range policy of the control volume is applied
+    /// if the (p,s) point is outside the validity range
+    ///
+    /// panics if the policy is Error and the point is outside
+    /// the validity range
     pub fn set_ps(&mut self,
         p: Pressure,
         s: SpecificHeatCapacity,){
 
+        if self.out_of_range_policy == OutOfRangePolicy::Error {
+            let volume = self.get_volume();
+
+            *self = Self::new_from_ps(p, s, volume);
+            return;
+        };
+
+        match self.try_set_ps(p, s) {
+            Ok(_report) => (),
+            Err(error) => panic!("p,s flash failed: {}", error),
+        }
+    }
+
+    pub fn set_uv(&mut self,
+        u: AvailableEnergy,
+        v: SpecificVolume,){
+
         let volume = self.get_volume();
 
-        *self = Self::new_from_ps(p, s, volume);
-        
+        *self = Self::new_from_uv(u, v, volume);
+
+
+    }
+
+    /// sets the out of range policy used by the setters
+    pub fn set_out_of_range_policy(&mut self, policy: OutOfRangePolicy) {
+        self.out_of_range_policy = policy;
+    }
+
+    /// returns the out of range policy used by the setters
+    pub fn get_out_of_range_policy(&self) -> OutOfRangePolicy {
+        return self.out_of_range_policy;
+    }
+
+    /// sets the state using temperature, pressure and quality,
+    /// applying the out of range policy of the control volume,
+    /// returns an error and leaves the control volume unchanged
+    /// if the policy is Error and the point is outside the
+    /// validity range
+    ///
+    /// the report tells you if the point was clamped or extrapolated
+    pub fn try_set_tpx(&mut self,
+        t: ThermodynamicTemperature,
+        p: Pressure,
+        x: f64) -> Result<OutOfRangeReport, SteamTableError> {
+
+        let flashed = pt_flash_eqm::try_tp_flash_with_policy(t, p, x,
+            self.out_of_range_policy)?;
+
+        self.set_steam_state(flashed.state);
+
+        return Ok(flashed.report);
+    }
+
+    /// sets the state using pressure and enthalpy,
+    /// applying the out of range policy of the control volume
+    /// (see try_set_tpx)
+    pub fn try_set_ph(&mut self,
+        p: Pressure,
+        h: AvailableEnergy) -> Result<OutOfRangeReport, SteamTableError> {
+
+        let flashed = ph_flash_eqm::try_ph_flash_with_policy(p, h,
+            self.out_of_range_policy)?;
+
+        self.set_steam_state(flashed.state);
+
+        return Ok(flashed.report);
+    }
+
+    /// sets the state using pressure and entropy,
+    /// applying the out of range policy of the control volume
+    /// (see try_set_tpx)
+    pub fn try_set_ps(&mut self,
+        p: Pressure,
+        s: SpecificHeatCapacity) -> Result<OutOfRangeReport, SteamTableError> {
+
+        let flashed = ps_flash_eqm::try_ps_flash_with_policy(p, s,
+            self.out_of_range_policy)?;
+
+        self.set_steam_state(flashed.state);
+
+        return Ok(flashed.report);
+    }
+
+    /// sets the state using enthalpy and entropy,
+    /// applying the out of range policy of the control volume
+    /// (see try_set_tpx)
+    pub fn try_set_hs(&mut self,
+        h: AvailableEnergy,
+        s: SpecificHeatCapacity) -> Result<OutOfRangeReport, SteamTableError> {
+
+        let flashed = hs_flash_eqm::try_hs_flash_with_policy(h, s,
+            self.out_of_range_policy)?;
+
+        self.set_steam_state(flashed.state);
 
+        return Ok(flashed.report);
     }
 }
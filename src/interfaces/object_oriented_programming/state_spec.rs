@@ -0,0 +1,249 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::out_of_range_policy::OutOfRangePolicy;
+use crate::interfaces::functional_programming::saturation_eqm::SaturationState;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+use crate::interfaces::steam_property_provider::{If97, SteamPropertyProvider};
+
+use super::mass_and_energy_balance::try_uv_from_mass_and_internal_energy;
+use super::TampinesSteamTableCV;
+
+/// any two independent properties which specify the state
+/// of the fluid in a control volume, so that higher level code
+/// can carry a state specification around without knowing
+/// which flash is needed
+///
+/// see TampinesSteamTableCV::new and set_state
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StateSpec {
+    /// temperature and pressure, single phase only,
+    /// (use Px or Tx on the saturation line)
+    Tp {
+        t: ThermodynamicTemperature,
+        p: Pressure,
+    },
+    /// pressure and specific enthalpy
+    Ph {
+        p: Pressure,
+        h: AvailableEnergy,
+    },
+    /// pressure and specific entropy
+    Ps {
+        p: Pressure,
+        s: SpecificHeatCapacity,
+    },
+    /// specific enthalpy and specific entropy
+    Hs {
+        h: AvailableEnergy,
+        s: SpecificHeatCapacity,
+    },
+    /// saturation pressure and steam quality
+    /// (x from 0 to 1, otherwise the flash returns an error)
+    Px {
+        p: Pressure,
+        x: f64,
+    },
+    /// saturation temperature and steam quality
+    /// (x from 0 to 1, otherwise the flash returns an error)
+    Tx {
+        t: ThermodynamicTemperature,
+        x: f64,
+    },
+    /// temperature and specific volume
+    Tv {
+        t: ThermodynamicTemperature,
+        v: SpecificVolume,
+    },
+    /// pressure and specific volume
+    Pv {
+        p: Pressure,
+        v: SpecificVolume,
+    },
+    /// specific internal energy and specific volume
+    Uv {
+        u: AvailableEnergy,
+        v: SpecificVolume,
+    },
+    /// temperature and density
+    TRho {
+        t: ThermodynamicTemperature,
+        rho: MassDensity,
+    },
+    /// mass and total internal energy within the control volume,
+    /// the volume is that of the control volume
+    MassAndInternalEnergy {
+        mass: Mass,
+        internal_energy: Energy,
+    },
+}
+
+impl TampinesSteamTableCV {
+
+    /// creates a new control volume given any two independent
+    /// properties, using the IAPWS-IF97 equations,
+    /// panics if the point is outside the validity range 
+    /// of the flash used
+    pub fn new(spec: StateSpec, volume: Volume) -> Self {
+        return Self::new_with_provider(If97, spec, volume);
+    }
+
+    /// creates a new control volume given any two independent
+    /// properties, using the IAPWS-IF97 equations,
+    /// returning an error if the point is outside the
+    /// validity range of the flash used
+    pub fn try_new(spec: StateSpec, volume: Volume) -> Result<Self, SteamTableError> {
+        return Self::try_new_with_provider(If97, spec, volume);
+    }
+}
+
+impl<P: SteamPropertyProvider> TampinesSteamTableCV<P> {
+
+    /// creates a new control volume given any two independent
+    /// properties and the property backend, 
+    /// panics if the point is outside the validity range 
+    /// of the flash used
+    pub fn new_with_provider(provider: P, 
+        spec: StateSpec, 
+        volume: Volume) -> Self {
+        match Self::try_new_with_provider(provider, spec, volume) {
+            Ok(cv) => cv,
+            Err(error) => panic!("flash failed for {:?}: {}", spec, error),
+        }
+    }
+
+    /// creates a new control volume given any two independent
+    /// properties and the property backend, 
+    /// returning an error if the point is outside the
+    /// validity range of the flash used
+    ///
+    /// the flash returns all properties at once (SteamState),
+    /// so these are kept for the getters straight away
+    pub fn try_new_with_provider(provider: P, 
+        spec: StateSpec, 
+        volume: Volume) -> Result<Self, SteamTableError> {
+
+        let state = try_flash_state_spec(&provider, spec, volume)?;
+
+        return Ok(Self::new_from_steam_state(provider, state, volume));
+    }
+
+    /// sets the state of the control volume given any two
+    /// independent properties, keeping the volume fixed,
+    /// panics if the point is outside the validity range
+    pub fn set_state(&mut self, spec: StateSpec) {
+        match self.try_set_state(spec) {
+            Ok(()) => (),
+            Err(error) => panic!("flash failed for {:?}: {}", spec, error),
+        }
+    }
+
+    /// sets the state of the control volume given any two
+    /// independent properties, keeping the volume fixed,
+    /// returns an error and leaves the control volume unchanged
+    /// if the point is outside the validity range
+    pub fn try_set_state(&mut self, spec: StateSpec) -> Result<(), SteamTableError> {
+
+        let state = try_flash_state_spec(&self.provider, spec, self.volume)?;
+
+        self.set_steam_state(state);
+
+        return Ok(());
+    }
+
+    /// the flash has already computed the derived properties,
+    /// so these are kept for the getters
+    pub(super) fn new_from_steam_state(provider: P, 
+        state: SteamState, 
+        volume: Volume) -> Self {
+
+        return Self {
+            pressure: state.p,
+            temperature: state.t,
+            specific_volume: state.v,
+            specific_enthalpy: state.h,
+            specific_entropy: state.s,
+            volume,
+            state,
+            provider,
+            out_of_range_policy: OutOfRangePolicy::Error,
+        };
+    }
+
+    /// sets the intensive properties at fixed volume, 
+    /// the flash has already computed the derived properties,
+    /// so these are kept for the getters
+    pub(super) fn set_steam_state(&mut self, state: SteamState) {
+
+        self.pressure = state.p;
+        self.temperature = state.t;
+        self.specific_volume = state.v;
+        self.specific_enthalpy = state.h;
+        self.specific_entropy = state.s;
+        self.state = state;
+    }
+}
+
+/// dispatches the state specification to the appropriate flash
+///
+/// for saturated mixtures (Px and Tx), the saturation state gives 
+/// the saturation temperature and mixture density, and the 
+/// (T,rho) flash gives the rest
+fn try_flash_state_spec<P: SteamPropertyProvider>(provider: &P,
+    spec: StateSpec,
+    volume: Volume) -> Result<SteamState, SteamTableError> {
+
+    match spec {
+        StateSpec::Tp { t, p } => provider.try_flash_pt(t, p),
+        StateSpec::Ph { p, h } => provider.try_flash_ph(p, h),
+        StateSpec::Ps { p, s } => provider.try_flash_ps(p, s),
+        StateSpec::Hs { h, s } => provider.try_flash_hs(h, s),
+        StateSpec::Px { p, x } => {
+            let saturation_state = provider.try_saturation_at_p(p)?;
+            try_flash_saturated_mixture(provider, saturation_state, x)
+        },
+        StateSpec::Tx { t, x } => {
+            let saturation_state = provider.try_saturation_at_t(t)?;
+            try_flash_saturated_mixture(provider, saturation_state, x)
+        },
+        StateSpec::Tv { t, v } => provider.try_flash_trho(t, v.recip()),
+        StateSpec::Pv { p, v } => provider.try_flash_pv(p, v),
+        StateSpec::Uv { u, v } => provider.try_flash_uv(u, v),
+        StateSpec::TRho { t, rho } => provider.try_flash_trho(t, rho),
+        StateSpec::MassAndInternalEnergy { mass, internal_energy } => {
+            let (u, v) = try_uv_from_mass_and_internal_energy(
+                mass, internal_energy, volume)?;
+            provider.try_flash_uv(u, v)
+        },
+    }
+}
+
+/// saturated mixture at steam quality x,
+/// returns an error if x is outside 0 to 1
+///
+/// the saturation pressure and mixture enthalpy and entropy
+/// are those of the saturation state
+fn try_flash_saturated_mixture<P: SteamPropertyProvider>(provider: &P,
+    saturation_state: SaturationState,
+    x: f64) -> Result<SteamState, SteamTableError> {
+
+    if x < 0.0 {
+        return Err(SteamTableError::QualityBelowBound { x, bound: 0.0 });
+    };
+    if x > 1.0 {
+        return Err(SteamTableError::QualityAboveBound { x, bound: 1.0 });
+    };
+
+    let mut state = provider.try_flash_trho(saturation_state.t_sat,
+        saturation_state.rho_mixture(x))?;
+
+    state.p = saturation_state.p_sat;
+    state.v = saturation_state.v_mixture(x);
+    state.h = saturation_state.h_mixture(x);
+    state.s = saturation_state.s_mixture(x);
+    state.u = saturation_state.u_mixture(x);
+    state.x = x;
+
+    return Ok(state);
+}
@@ -0,0 +1,213 @@
+use uom::si::f64::*;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::*;
+use crate::interfaces::functional_programming::saturation_eqm::SaturationState;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+
+/// a source of water/steam properties,
+///
+/// TampinesSteamTableCV (and any plant model built on this trait)
+/// is generic over the provider, so the IAPWS-IF97 equations
+/// (If97) can be swapped for a faster tabulated backend,
+/// IAPWS-95, or a mocked provider for testing
+///
+/// only the try_* flashes need to be implemented,
+/// the panicking flashes and the transport property
+/// functions are provided in terms of them, though a provider
+/// may override these if it has a cheaper way of doing so
+///
+/// the flashes return a SteamState, so a provider must fill in
+/// all intensive properties (or NaN for those it does not have),
+/// the IF97 region is None for providers which do not use
+/// the IF97 regions
+pub trait SteamPropertyProvider {
+
+    /// single phase (T,p) flash
+    fn try_flash_pt(&self,
+        t: ThermodynamicTemperature,
+        p: Pressure) -> Result<SteamState, SteamTableError>;
+
+    /// (p,h) flash
+    fn try_flash_ph(&self,
+        p: Pressure,
+        h: AvailableEnergy) -> Result<SteamState, SteamTableError>;
+
+    /// (p,s) flash
+    fn try_flash_ps(&self,
+        p: Pressure,
+        s: SpecificHeatCapacity) -> Result<SteamState, SteamTableError>;
+
+    /// (h,s) flash
+    fn try_flash_hs(&self,
+        h: AvailableEnergy,
+        s: SpecificHeatCapacity) -> Result<SteamState, SteamTableError>;
+
+    /// (p,v) flash
+    fn try_flash_pv(&self,
+        p: Pressure,
+        v: SpecificVolume) -> Result<SteamState, SteamTableError>;
+
+    /// (u,v) flash, used for mass and energy balances
+    /// on rigid control volumes
+    fn try_flash_uv(&self,
+        u: AvailableEnergy,
+        v: SpecificVolume) -> Result<SteamState, SteamTableError>;
+
+    /// (T,rho) flash
+    fn try_flash_trho(&self,
+        t: ThermodynamicTemperature,
+        rho: MassDensity) -> Result<SteamState, SteamTableError>;
+
+    /// saturated liquid and vapour properties given saturation pressure
+    fn try_saturation_at_p(&self,
+        p: Pressure) -> Result<SaturationState, SteamTableError>;
+
+    /// saturated liquid and vapour properties given saturation temperature
+    fn try_saturation_at_t(&self,
+        t: ThermodynamicTemperature) -> Result<SaturationState, SteamTableError>;
+
+    /// single phase (T,p) flash, panics if the (T,p) point
+    /// is outside the validity range
+    fn flash_pt(&self,
+        t: ThermodynamicTemperature,
+        p: Pressure) -> SteamState {
+        match self.try_flash_pt(t, p) {
+            Ok(state) => state,
+            Err(error) => panic!("t,p flash failed: {}", error),
+        }
+    }
+
+    /// (p,h) flash, panics if the (p,h) point
+    /// is outside the validity range
+    fn flash_ph(&self,
+        p: Pressure,
+        h: AvailableEnergy) -> SteamState {
+        match self.try_flash_ph(p, h) {
+            Ok(state) => state,
+            Err(error) => panic!("p,h flash failed: {}", error),
+        }
+    }
+
+    /// (p,s) flash, panics if the (p,s) point
+    /// is outside the validity range
+    fn flash_ps(&self,
+        p: Pressure,
+        s: SpecificHeatCapacity) -> SteamState {
+        match self.try_flash_ps(p, s) {
+            Ok(state) => state,
+            Err(error) => panic!("p,s flash failed: {}", error),
+        }
+    }
+
+    /// saturated liquid and vapour properties given saturation pressure,
+    /// panics if the pressure is outside the saturation pressure
+    /// at 273.15 K to the critical pressure
+    fn saturation_at_p(&self,
+        p: Pressure) -> SaturationState {
+        match self.try_saturation_at_p(p) {
+            Ok(state) => state,
+            Err(error) => panic!("p,x flash failed: {}", error),
+        }
+    }
+
+    /// saturated liquid and vapour properties given saturation temperature,
+    /// panics if the temperature is outside 273.15 K to the
+    /// critical temperature
+    fn saturation_at_t(&self,
+        t: ThermodynamicTemperature) -> SaturationState {
+        match self.try_saturation_at_t(t) {
+            Ok(state) => state,
+            Err(error) => panic!("t,x flash failed: {}", error),
+        }
+    }
+
+    /// dynamic viscosity given pressure and enthalpy
+    fn try_mu_ph(&self,
+        p: Pressure,
+        h: AvailableEnergy) -> Result<DynamicViscosity, SteamTableError> {
+        return Ok(self.try_flash_ph(p, h)?.mu);
+    }
+
+    /// thermal conductivity given pressure and enthalpy
+    fn try_lambda_ph(&self,
+        p: Pressure,
+        h: AvailableEnergy) -> Result<ThermalConductivity, SteamTableError> {
+        return Ok(self.try_flash_ph(p, h)?.lambda);
+    }
+
+    /// dynamic viscosity given temperature and pressure (single phase)
+    fn try_mu_pt(&self,
+        t: ThermodynamicTemperature,
+        p: Pressure) -> Result<DynamicViscosity, SteamTableError> {
+        return Ok(self.try_flash_pt(t, p)?.mu);
+    }
+
+    /// thermal conductivity given temperature and pressure (single phase)
+    fn try_lambda_pt(&self,
+        t: ThermodynamicTemperature,
+        p: Pressure) -> Result<ThermalConductivity, SteamTableError> {
+        return Ok(self.try_flash_pt(t, p)?.lambda);
+    }
+}
+
+/// the IAPWS-IF97 equations of this library,
+/// this is the default provider for TampinesSteamTableCV
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct If97;
+
+impl SteamPropertyProvider for If97 {
+
+    fn try_flash_pt(&self,
+        t: ThermodynamicTemperature,
+        p: Pressure) -> Result<SteamState, SteamTableError> {
+        return pt_flash_eqm::try_flash(t, p);
+    }
+
+    fn try_flash_ph(&self,
+        p: Pressure,
+        h: AvailableEnergy) -> Result<SteamState, SteamTableError> {
+        return ph_flash_eqm::try_flash(p, h);
+    }
+
+    fn try_flash_ps(&self,
+        p: Pressure,
+        s: SpecificHeatCapacity) -> Result<SteamState, SteamTableError> {
+        return ps_flash_eqm::try_flash(p, s);
+    }
+
+    fn try_flash_hs(&self,
+        h: AvailableEnergy,
+        s: SpecificHeatCapacity) -> Result<SteamState, SteamTableError> {
+        return hs_flash_eqm::try_flash(h, s);
+    }
+
+    fn try_flash_pv(&self,
+        p: Pressure,
+        v: SpecificVolume) -> Result<SteamState, SteamTableError> {
+        return pv_flash_eqm::try_flash(p, v);
+    }
+
+    fn try_flash_uv(&self,
+        u: AvailableEnergy,
+        v: SpecificVolume) -> Result<SteamState, SteamTableError> {
+        return uv_flash_eqm::try_flash(u, v);
+    }
+
+    fn try_flash_trho(&self,
+        t: ThermodynamicTemperature,
+        rho: MassDensity) -> Result<SteamState, SteamTableError> {
+        return trho_flash_eqm::try_flash(t, rho);
+    }
+
+    fn try_saturation_at_p(&self,
+        p: Pressure) -> Result<SaturationState, SteamTableError> {
+        return SaturationState::try_new_from_p(p);
+    }
+
+    fn try_saturation_at_t(&self,
+        t: ThermodynamicTemperature) -> Result<SaturationState, SteamTableError> {
+        return SaturationState::try_new_from_t(t);
+    }
+}
@@ -0,0 +1,345 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::energy::megajoule;
+use uom::si::f64::*;
+use uom::si::mass::kilogram;
+use uom::si::mass_rate::kilogram_per_second;
+use uom::si::power::megawatt;
+use uom::si::pressure::megapascal;
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::time::second;
+use uom::si::volume::cubic_meter;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::ph_flash_eqm;
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+use crate::interfaces::object_oriented_programming::{StateSpec, TampinesSteamTableCV};
+
+/// the derived property getters should match the (p,h) flash
+#[test]
+pub fn control_volume_derived_property_getters(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1500.0);
+    let volume = Volume::new::<cubic_meter>(2.0);
+
+    let cv = TampinesSteamTableCV::new_from_ph(p, h, volume);
+    let state = ph_flash_eqm::flash(p, h);
+
+    assert_eq!(cv.get_region(), Some(FwdEqnRegion::Region4));
+    assert_eq!(cv.get_quality(), state.x);
+    assert_eq!(cv.get_internal_energy(), state.u);
+    assert_eq!(cv.get_cp(), state.cp);
+    assert_eq!(cv.get_cv(), state.cv);
+    assert_eq!(cv.get_speed_of_sound(), state.w);
+    assert_eq!(cv.get_dynamic_viscosity(), state.mu);
+    assert_eq!(cv.get_thermal_conductivity(), state.lambda);
+    assert_eq!(cv.get_prandtl_number(), state.prandtl);
+
+    assert_relative_eq!(cv.get_density().value,
+        cv.get_mass().value/volume.value, max_relative = 1e-12);
+    assert_relative_eq!(cv.get_total_enthalpy().value,
+        cv.get_mass().value * h.value, max_relative = 1e-12);
+    assert_relative_eq!(cv.get_total_internal_energy().value,
+        cv.get_mass().value * state.u.value, max_relative = 1e-12);
+
+    // internal energy is enthalpy minus flow work
+    let pv = (p * cv.get_specific_volume()).value;
+    assert_relative_eq!(cv.get_internal_energy().value,
+        h.value - pv, max_relative = 1e-6);
+}
+
+/// the derived properties are flashed again when the state changes,
+/// and asking for them does not affect equality
+#[test]
+pub fn control_volume_derived_properties_follow_state_change(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(300.0);
+    let p = Pressure::new::<megapascal>(3.0);
+    let volume = Volume::new::<cubic_meter>(1.0);
+
+    let mut cv = TampinesSteamTableCV::new_from_tp_quality_0(t, p, volume);
+    let cv_before = cv;
+
+    assert_eq!(cv.get_region(), Some(FwdEqnRegion::Region1));
+    assert_eq!(cv.get_quality(), 0.0);
+    let liquid_cp = cv.get_cp();
+
+    // the getters do not change equality
+    assert_eq!(cv, cv_before);
+
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(3500.0);
+    let p = Pressure::new::<megapascal>(5.0);
+    cv.set_ph(p, h);
+
+    assert_eq!(cv.get_region(), Some(FwdEqnRegion::Region2));
+    assert_eq!(cv.get_quality(), 1.0);
+    assert_eq!(cv.get_cp(), ph_flash_eqm::cp_ph_eqm(p, h));
+    assert!(cv.get_cp() != liquid_cp);
+    assert!(cv != cv_before);
+}
+
+/// the (u,v) constructor should recover the (p,h) state
+///
+/// compressed liquid is left out since its pressure is 
+/// very sensitive to specific volume
+#[test]
+pub fn control_volume_new_from_uv(){
+
+    let volume = Volume::new::<cubic_meter>(1.0);
+    let cases = [(0.1, 2700.0), (1.0, 1500.0), (5.0, 3500.0), (25.0, 1800.0)];
+
+    for (p_mpa, h_kj_per_kg) in cases {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let h = AvailableEnergy::new::<kilojoule_per_kilogram>(h_kj_per_kg);
+
+        let ph_cv = TampinesSteamTableCV::new_from_ph(p, h, volume);
+        let u = h - p * ph_cv.get_specific_volume();
+        let uv_cv = TampinesSteamTableCV::new_from_uv(u, ph_cv.get_specific_volume(), volume);
+
+        // the region 3 (p,h) backward equations are only 
+        // consistent with the forward equations to about 0.1%
+        let tol = 1e-3;
+        assert_relative_eq!(uv_cv.get_pressure().value, p.value, max_relative = tol);
+        assert_relative_eq!(uv_cv.get_temperature().value,
+            ph_cv.get_temperature().value, max_relative = tol);
+        assert_relative_eq!(uv_cv.get_specific_enthalpy().value, h.value, max_relative = tol);
+        assert_relative_eq!(uv_cv.get_specific_entropy().value,
+            ph_cv.get_specific_entropy().value, max_relative = tol);
+        assert_eq!(uv_cv.get_specific_volume(), ph_cv.get_specific_volume());
+    }
+}
+
+/// heating a rigid vessel with a steam water mixture
+/// keeps the mass constant and raises the pressure
+#[test]
+pub fn control_volume_add_heat(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1500.0);
+    let volume = Volume::new::<cubic_meter>(1.0);
+
+    let mut cv = TampinesSteamTableCV::new_from_ph(p, h, volume);
+    let mass = cv.get_mass();
+    let u_before = h - p * cv.get_specific_volume();
+    let heat = Energy::new::<megajoule>(10.0);
+
+    cv.add_heat(heat);
+
+    assert_relative_eq!(cv.get_mass().value, mass.value, max_relative = 1e-12);
+    assert!(cv.get_pressure() > p);
+    assert_eq!(cv.get_region(), Some(FwdEqnRegion::Region4));
+
+    let u_after = cv.get_specific_enthalpy() - cv.get_pressure() * cv.get_specific_volume();
+    assert_relative_eq!((u_after - u_before).value * mass.value, heat.value,
+        max_relative = 1e-4);
+
+    // removing the heat brings the vessel back to where it was
+    cv.add_heat(-heat);
+    assert_relative_eq!(cv.get_pressure().value, p.value, max_relative = 1e-4);
+    assert_relative_eq!(cv.get_specific_enthalpy().value, h.value, max_relative = 1e-4);
+}
+
+/// adding cold water to a pressuriser like vessel, and then 
+/// blowing down the vessel
+#[test]
+pub fn control_volume_add_and_remove_mass(){
+
+    let p = Pressure::new::<megapascal>(15.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1800.0);
+    let volume = Volume::new::<cubic_meter>(10.0);
+
+    let mut cv = TampinesSteamTableCV::new_from_ph(p, h, volume);
+    let mass = cv.get_mass();
+    let total_u = cv.get_mass() * (h - p * cv.get_specific_volume());
+
+    let mass_added = Mass::new::<kilogram>(100.0);
+    let h_in = AvailableEnergy::new::<kilojoule_per_kilogram>(500.0);
+    cv.add_mass_with_enthalpy(mass_added, h_in);
+
+    assert_relative_eq!(cv.get_mass().value, (mass + mass_added).value, max_relative = 1e-12);
+    let new_total_u = cv.get_mass() 
+        * (cv.get_specific_enthalpy() - cv.get_pressure() * cv.get_specific_volume());
+    assert_relative_eq!(new_total_u.value, (total_u + mass_added * h_in).value,
+        max_relative = 1e-4);
+    // cold water condenses some of the steam
+    assert!(cv.get_pressure() < p);
+
+    // blowdown, the vessel depressurises
+    let p_before_blowdown = cv.get_pressure();
+    let mass_before_blowdown = cv.get_mass();
+    let mass_removed = Mass::new::<kilogram>(500.0);
+    cv.remove_mass_at_current_state(mass_removed);
+
+    assert_relative_eq!(cv.get_mass().value, 
+        (mass_before_blowdown - mass_removed).value, max_relative = 1e-12);
+    assert!(cv.get_pressure() < p_before_blowdown);
+
+    // removing all the mass is an error, and leaves the 
+    // control volume unchanged
+    let cv_before = cv;
+    let error = cv.try_remove_mass_at_current_state(cv.get_mass()).unwrap_err();
+    assert!(matches!(error, SteamTableError::DensityBelowBound { .. }));
+    assert_eq!(cv, cv_before);
+}
+
+/// time stepping with balanced inflows and outflows at the 
+/// current enthalpy keeps the state, and heat only is the same 
+/// as add_heat
+#[test]
+pub fn control_volume_advance(){
+
+    let p = Pressure::new::<megapascal>(5.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(2000.0);
+    let volume = Volume::new::<cubic_meter>(2.0);
+
+    let mut cv = TampinesSteamTableCV::new_from_ph(p, h, volume);
+    let dt = Time::new::<second>(0.5);
+    let mass_flowrate = MassRate::new::<kilogram_per_second>(3.0);
+    let no_heat = Power::new::<megawatt>(0.0);
+
+    cv.advance(dt, &[(mass_flowrate, h)], &[mass_flowrate], no_heat);
+
+    assert_relative_eq!(cv.get_pressure().value, p.value, max_relative = 1e-4);
+    assert_relative_eq!(cv.get_specific_enthalpy().value, h.value, max_relative = 1e-4);
+
+    let mut heated_cv = cv;
+    let heat_rate = Power::new::<megawatt>(1.0);
+    heated_cv.advance(dt, &[], &[], heat_rate);
+    cv.add_heat(heat_rate * dt);
+
+    assert_eq!(heated_cv, cv);
+}
+
+/// every state specification of the same point should give 
+/// the same control volume (to within the consistency 
+/// of the backward equations)
+#[test]
+pub fn control_volume_new_from_state_spec(){
+
+    let volume = Volume::new::<cubic_meter>(3.0);
+    let cases = [
+        (0.1, 2700.0, FwdEqnRegion::Region2),
+        (1.0, 1500.0, FwdEqnRegion::Region4),
+        (5.0, 3500.0, FwdEqnRegion::Region2),
+        (25.0, 2400.0, FwdEqnRegion::Region3),
+        (30.0, 5000.0, FwdEqnRegion::Region5),
+    ];
+
+    for (p_mpa, h_kj_per_kg, region) in cases {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let h = AvailableEnergy::new::<kilojoule_per_kilogram>(h_kj_per_kg);
+
+        let reference = TampinesSteamTableCV::new(StateSpec::Ph { p, h }, volume);
+        assert_eq!(reference.get_region(), Some(region));
+
+        let t = reference.get_temperature();
+        let v = reference.get_specific_volume();
+        let s = reference.get_specific_entropy();
+        let u = reference.get_internal_energy();
+        let x = reference.get_quality();
+
+        let mut specs = vec![
+            StateSpec::Ps { p, s },
+            StateSpec::Hs { h, s },
+            StateSpec::Tv { t, v },
+            StateSpec::TRho { t, rho: v.recip() },
+            StateSpec::Pv { p, v },
+            StateSpec::Uv { u, v },
+            StateSpec::MassAndInternalEnergy { 
+                mass: reference.get_mass(),
+                internal_energy: reference.get_total_internal_energy(),
+            },
+        ];
+
+        if region == FwdEqnRegion::Region4 {
+            specs.push(StateSpec::Px { p, x });
+            specs.push(StateSpec::Tx { t, x });
+        } else {
+            specs.push(StateSpec::Tp { t, p });
+        };
+
+
+        for spec in specs {
+            let cv = TampinesSteamTableCV::new(spec, volume);
+
+            let tol = 1e-3;
+            assert_eq!(cv.get_volume(), volume);
+            assert_eq!(cv.get_region(), Some(region), "{:?}", spec);
+            assert_relative_eq!(cv.get_pressure().value, p.value, max_relative = tol);
+            assert_relative_eq!(cv.get_temperature().value, t.value, max_relative = tol);
+            assert_relative_eq!(cv.get_specific_volume().value, v.value, max_relative = tol);
+            assert_relative_eq!(cv.get_specific_enthalpy().value, h.value, max_relative = tol);
+            assert_relative_eq!(cv.get_specific_entropy().value, s.value, max_relative = tol);
+        }
+    }
+}
+
+/// set_state keeps the volume, and leaves the control volume 
+/// unchanged if the flash fails
+#[test]
+pub fn control_volume_set_state(){
+
+    let volume = Volume::new::<cubic_meter>(3.0);
+    let t = ThermodynamicTemperature::new::<kelvin>(300.0);
+    let p = Pressure::new::<megapascal>(3.0);
+
+    let mut cv = TampinesSteamTableCV::new(StateSpec::Tp { t, p }, volume);
+    assert_eq!(cv.get_region(), Some(FwdEqnRegion::Region1));
+
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(3500.0);
+    let p = Pressure::new::<megapascal>(5.0);
+    cv.set_state(StateSpec::Ph { p, h });
+    let cv_ph = TampinesSteamTableCV::new_from_ph(p, h, volume);
+    assert_eq!(cv.get_pressure(), p);
+    assert_eq!(cv.get_specific_enthalpy(), h);
+    assert_relative_eq!(cv.get_temperature().get::<kelvin>(),
+        cv_ph.get_temperature().get::<kelvin>(), max_relative = 1e-12);
+    assert_relative_eq!(cv.get_specific_volume().value,
+        cv_ph.get_specific_volume().value, max_relative = 1e-12);
+    assert_relative_eq!(cv.get_specific_entropy().value,
+        cv_ph.get_specific_entropy().value, max_relative = 1e-12);
+
+    let cv_before = cv;
+    let p = Pressure::new::<megapascal>(120.0);
+    assert!(cv.try_set_state(StateSpec::Ph { p, h }).is_err());
+    assert_eq!(cv, cv_before);
+
+    // (T,p) is single phase only
+    let t = ThermodynamicTemperature::new::<kelvin>(373.15);
+    // (within a relative tolerance of the saturation pressure)
+    let p = crate::region_4_vap_liq_equilibrium::sat_pressure_4(t) * (1.0 + 1e-12);
+    let error = cv.try_set_state(StateSpec::Tp { t, p }).unwrap_err();
+    assert!(matches!(error, SteamTableError::RegionNotImplemented { .. }));
+    assert_eq!(cv, cv_before);
+
+    // steam quality must be between 0 and 1 for saturated mixtures
+    let error = cv.try_set_state(StateSpec::Tx { t, x: 1.2 }).unwrap_err();
+    assert!(matches!(error, SteamTableError::QualityAboveBound { .. }));
+    let p = Pressure::new::<megapascal>(1.0);
+    let error = cv.try_set_state(StateSpec::Px { p, x: -0.1 }).unwrap_err();
+    assert!(matches!(error, SteamTableError::QualityBelowBound { .. }));
+    assert_eq!(cv, cv_before);
+}
+
+/// the control volume is Copy (the derived properties are
+/// flashed by the constructors and setters rather than cached
+/// on first use), so it can be shared between threads
+#[test]
+pub fn control_volume_is_copy_and_sync(){
+
+    fn assert_copy_send_sync<T: Copy + Send + Sync>() {}
+    assert_copy_send_sync::<TampinesSteamTableCV>();
+
+    let volume = Volume::new::<cubic_meter>(1.0);
+    let p = Pressure::new::<megapascal>(1.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1500.0);
+
+    let cv = TampinesSteamTableCV::new_from_ph(p, h, volume);
+    let mut copied_cv = cv;
+    copied_cv.set_ph(p, AvailableEnergy::new::<kilojoule_per_kilogram>(2500.0));
+
+    assert_eq!(cv, TampinesSteamTableCV::new_from_ph(p, h, volume));
+    assert_eq!(cv.get_quality(), ph_flash_eqm::flash(p, h).x);
+    assert!(copied_cv.get_quality() > cv.get_quality());
+}
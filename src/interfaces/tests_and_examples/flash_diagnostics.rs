@@ -0,0 +1,243 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::{joule_per_kilogram, kilojoule_per_kilogram};
+use uom::si::f64::*;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+use uom::si::pressure::{megapascal, pascal};
+use uom::si::specific_heat_capacity::{joule_per_kilogram_kelvin, kilojoule_per_kilogram_kelvin};
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::backward_eqn_hs_region_1_to_4::saturated_liquid_line::h1_prime_s_boundary_enthalpy;
+use crate::interfaces::functional_programming::hs_flash_eqm::{hs_flash_with_diagnostics, try_hs_flash_with_diagnostics, BackwdEqnSubRegion};
+use crate::interfaces::functional_programming::ph_flash_eqm::{ph_flash_with_diagnostics, t_ph_eqm, try_ph_flash_with_diagnostics};
+use crate::interfaces::functional_programming::ps_flash_eqm::try_ps_flash_with_diagnostics;
+use crate::interfaces::functional_programming::pt_flash_eqm::{try_tp_flash_with_diagnostics, FwdEqnRegion};
+use crate::interfaces::functional_programming::pv_flash_eqm::try_pv_flash_with_diagnostics;
+use crate::interfaces::functional_programming::trho_flash_eqm::try_trho_flash_with_diagnostics;
+use crate::interfaces::functional_programming::uv_flash_eqm::try_uv_flash_with_diagnostics;
+use crate::region_5_steam_at_800_plus_degc::h_tp_5;
+
+/// the (p,h) flash reports the region 2 and 3 backward 
+/// equation subregions
+#[test]
+pub fn flash_diagnostics_ph_subregions(){
+
+    let cases = [
+        (3.0, 3000.0, FwdEqnRegion::Region2, BackwdEqnSubRegion::Region2a),
+        (5.0, 3500.0, FwdEqnRegion::Region2, BackwdEqnSubRegion::Region2b),
+        (10.0, 2800.0, FwdEqnRegion::Region2, BackwdEqnSubRegion::Region2c),
+        (25.0, 1800.0, FwdEqnRegion::Region3, BackwdEqnSubRegion::Region3a),
+        (25.0, 2400.0, FwdEqnRegion::Region3, BackwdEqnSubRegion::Region3b),
+        (3.0, 500.0, FwdEqnRegion::Region1, BackwdEqnSubRegion::Region1),
+        (1.0, 1000.0, FwdEqnRegion::Region4, BackwdEqnSubRegion::Region4),
+    ];
+
+    for (p_mpa, h_kj_per_kg, region, subregion) in cases {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let h = AvailableEnergy::new::<kilojoule_per_kilogram>(h_kj_per_kg);
+
+        let report = try_ph_flash_with_diagnostics(p, h).unwrap();
+
+        assert_eq!(report.region, region);
+        assert_eq!(report.subregion, Some(subregion));
+        // backward equations are used directly
+        assert_eq!(report.iterations, 0);
+        assert!(!report.boundary_correction_applied);
+        assert_eq!(report.t, t_ph_eqm(p, h));
+
+        // the backward equations are consistent with the 
+        // forward equations to within a fraction of a kJ/kg
+        let h_residual = report.residuals.h.unwrap().get::<kilojoule_per_kilogram>();
+        assert!(h_residual.abs() < 0.1);
+        assert!(report.residuals.s.is_none());
+    }
+}
+
+/// region 5 has no (p,h) backward equation, so it is iterative 
+#[test]
+pub fn flash_diagnostics_ph_region_5_iterations(){
+
+    let p = Pressure::new::<megapascal>(30.0);
+    let t = ThermodynamicTemperature::new::<kelvin>(1500.0);
+    let h = h_tp_5(t, p);
+
+    let report = ph_flash_with_diagnostics(p, h);
+
+    assert_eq!(report.region, FwdEqnRegion::Region5);
+    assert_eq!(report.subregion, Some(BackwdEqnSubRegion::Region5));
+    assert!(report.iterations > 0);
+    assert_relative_eq!(report.t.get::<kelvin>(), 1500.0, max_relative = 1e-9);
+
+    let h_residual = report.residuals.h.unwrap().get::<joule_per_kilogram>();
+    assert!(h_residual.abs() < 1e-3);
+}
+
+/// the (p,s) flash reports the region 2 and 3 backward 
+/// equation subregions
+#[test]
+pub fn flash_diagnostics_ps_subregions(){
+
+    let cases = [
+        (3.0, 7.0, FwdEqnRegion::Region2, BackwdEqnSubRegion::Region2a),
+        (5.0, 6.0, FwdEqnRegion::Region2, BackwdEqnSubRegion::Region2b),
+        (30.0, 5.5, FwdEqnRegion::Region2, BackwdEqnSubRegion::Region2c),
+        (25.0, 4.0, FwdEqnRegion::Region3, BackwdEqnSubRegion::Region3a),
+        (25.0, 4.8, FwdEqnRegion::Region3, BackwdEqnSubRegion::Region3b),
+        (3.0, 1.0, FwdEqnRegion::Region1, BackwdEqnSubRegion::Region1),
+        (1.0, 4.0, FwdEqnRegion::Region4, BackwdEqnSubRegion::Region4),
+        (30.0, 8.0, FwdEqnRegion::Region5, BackwdEqnSubRegion::Region5),
+    ];
+
+    for (p_mpa, s_kj_per_kg_k, region, subregion) in cases {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(s_kj_per_kg_k);
+
+        let report = try_ps_flash_with_diagnostics(p, s).unwrap();
+
+        assert_eq!(report.region, region);
+        assert_eq!(report.subregion, Some(subregion));
+        assert_eq!(report.iterations > 0, region == FwdEqnRegion::Region5);
+
+        let s_residual = report.residuals.s.unwrap().get::<joule_per_kilogram_kelvin>();
+        assert!(s_residual.abs() < 1.0);
+        assert!(report.residuals.h.is_none());
+    }
+}
+
+/// the (h,s) flash reports iterations and residuals, 
+/// region 4 above 623.15 K and region 1 are iterative
+#[test]
+pub fn flash_diagnostics_hs_iterations_and_residuals(){
+
+    let cases = [
+        (1800.0, 4.0, BackwdEqnSubRegion::Region4, true),
+        (400.0, 1.0, BackwdEqnSubRegion::Region1, true),
+        (2800.0, 6.0, BackwdEqnSubRegion::Region2b, false),
+        (3000.0, 8.0, BackwdEqnSubRegion::Region2a, false),
+        (2400.0, 4.8, BackwdEqnSubRegion::Region3b, false),
+    ];
+
+    for (h_kj_per_kg, s_kj_per_kg_k, subregion, iterative) in cases {
+        let h = AvailableEnergy::new::<kilojoule_per_kilogram>(h_kj_per_kg);
+        let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(s_kj_per_kg_k);
+
+        let report = try_hs_flash_with_diagnostics(h, s).unwrap();
+
+        assert_eq!(report.subregion, Some(subregion.clone()));
+        assert_eq!(report.region, subregion.into());
+        assert_eq!(report.iterations > 0, iterative);
+        assert!(!report.boundary_correction_applied);
+
+        let h_residual = report.residuals.h.unwrap().get::<kilojoule_per_kilogram>();
+        let s_residual = report.residuals.s.unwrap().get::<kilojoule_per_kilogram_kelvin>();
+        assert!(h_residual.abs() < 0.1);
+        assert!(s_residual.abs() < 1e-3);
+
+        // iterative solutions converge to the forward equations
+        if iterative {
+            assert!(h_residual.abs() < 1e-6);
+            assert!(s_residual.abs() < 1e-9);
+        };
+    }
+}
+
+/// points just below the saturated liquid line are assigned to 
+/// region 1 by the 0.0034 kJ/kg correction on page 82
+#[test]
+pub fn flash_diagnostics_hs_boundary_correction(){
+
+    let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(1.0);
+    let h_boundary = h1_prime_s_boundary_enthalpy(s);
+
+    // within the correction
+    let h = h_boundary - AvailableEnergy::new::<kilojoule_per_kilogram>(0.001);
+    let report = hs_flash_with_diagnostics(h, s);
+
+    assert_eq!(report.subregion, Some(BackwdEqnSubRegion::Region1));
+    assert!(report.boundary_correction_applied);
+
+    // beyond the correction, the point is two phase
+    let h = h_boundary - AvailableEnergy::new::<kilojoule_per_kilogram>(0.01);
+    let report = hs_flash_with_diagnostics(h, s);
+
+    assert_eq!(report.subregion, Some(BackwdEqnSubRegion::Region4));
+    assert!(!report.boundary_correction_applied);
+
+    // well within region 1
+    let h = h_boundary + AvailableEnergy::new::<kilojoule_per_kilogram>(100.0);
+    let report = hs_flash_with_diagnostics(h, s);
+
+    assert_eq!(report.subregion, Some(BackwdEqnSubRegion::Region1));
+    assert!(!report.boundary_correction_applied);
+}
+
+/// the (T,p) flash is explicit, except in region 3 where the 
+/// v(T,p) backward equations are used
+#[test]
+pub fn flash_diagnostics_tp(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(300.0);
+    let p = Pressure::new::<megapascal>(3.0);
+    let report = try_tp_flash_with_diagnostics(t, p, 0.0).unwrap();
+
+    assert_eq!(report.region, FwdEqnRegion::Region1);
+    assert_eq!(report.subregion, None);
+    assert_eq!(report.iterations, 0);
+    assert_eq!(report.residuals, Default::default());
+
+    let t = ThermodynamicTemperature::new::<kelvin>(650.0);
+    let p = Pressure::new::<megapascal>(25.0);
+    let report = try_tp_flash_with_diagnostics(t, p, 0.0).unwrap();
+
+    assert_eq!(report.region, FwdEqnRegion::Region3);
+    let p_residual = report.residuals.p.unwrap().get::<pascal>();
+    assert!(p_residual.abs() < 1e-4 * p.get::<pascal>());
+}
+
+/// the (T,rho) flash iterates on pressure in regions 1, 2 and 5
+#[test]
+pub fn flash_diagnostics_trho(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(300.0);
+    let rho = MassDensity::new::<kilogram_per_cubic_meter>(1000.0);
+    let report = try_trho_flash_with_diagnostics(t, rho).unwrap();
+
+    assert_eq!(report.region, FwdEqnRegion::Region1);
+    assert!(report.iterations > 0);
+    let v_residual = report.residuals.v.unwrap().get::<cubic_meter_per_kilogram>();
+    assert!(v_residual.abs() < 1e-12);
+
+    // two phase, no iterations needed
+    let t = ThermodynamicTemperature::new::<kelvin>(400.0);
+    let rho = MassDensity::new::<kilogram_per_cubic_meter>(10.0);
+    let report = try_trho_flash_with_diagnostics(t, rho).unwrap();
+
+    assert_eq!(report.region, FwdEqnRegion::Region4);
+    assert_eq!(report.iterations, 0);
+    assert!(report.residuals.v.is_none());
+}
+
+/// the (p,v) and (u,v) flashes are iterative
+#[test]
+pub fn flash_diagnostics_pv_and_uv(){
+
+    let p = Pressure::new::<megapascal>(25.0);
+    let v = SpecificVolume::new::<cubic_meter_per_kilogram>(0.002);
+    let report = try_pv_flash_with_diagnostics(p, v).unwrap();
+
+    assert_eq!(report.region, FwdEqnRegion::Region3);
+    assert_eq!(report.subregion, Some(BackwdEqnSubRegion::Region3a));
+    assert!(report.iterations > 0);
+    assert_relative_eq!(report.v.get::<cubic_meter_per_kilogram>(), 0.002,
+        max_relative = 1e-9);
+
+    let u = AvailableEnergy::new::<kilojoule_per_kilogram>(2000.0);
+    let v = SpecificVolume::new::<cubic_meter_per_kilogram>(0.1);
+    let report = try_uv_flash_with_diagnostics(u, v).unwrap();
+
+    assert_eq!(report.region, FwdEqnRegion::Region4);
+    assert_eq!(report.subregion, None);
+    assert!(report.iterations > 0);
+    let u_residual = report.residuals.u.unwrap().get::<joule_per_kilogram>();
+    assert!(u_residual.abs() < 1e-6);
+}
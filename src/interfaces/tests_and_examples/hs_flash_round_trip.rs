@@ -0,0 +1,152 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::ratio::ratio;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::interfaces::functional_programming::hs_flash_eqm::validity_range::{hs_is_below_isobar_p_100_mpa_in_region1, try_h_isobar_p_100_mpa};
+use crate::interfaces::functional_programming::hs_flash_eqm::{hs_flash_region, tpvx_hs_flash_eqm, BackwdEqnSubRegion};
+use crate::interfaces::functional_programming::ph_flash_eqm::s_ph_eqm;
+use crate::interfaces::functional_programming::pt_flash_eqm::{h_tp_eqm_single_phase, h_tp_eqm_two_phase, s_tp_eqm_single_phase};
+use crate::region_1_subcooled_liquid::{h_tp_1, s_tp_1, v_tp_1};
+use crate::region_4_vap_liq_equilibrium::sat_pressure_4;
+
+/// flashes a region 1 (T,p) point into (h,s) and back
+fn region_1_round_trip(t_kelvin: f64, p_mpa: f64){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+    let p = Pressure::new::<megapascal>(p_mpa);
+    let h = h_tp_1(t, p);
+    let s = s_tp_1(t, p);
+
+    assert_eq!(hs_flash_region(h, s), BackwdEqnSubRegion::Region1);
+
+    let (t_test, p_test, v_test, x_test) = tpvx_hs_flash_eqm(h, s);
+
+    assert_relative_eq!(t_test.get::<kelvin>(), t_kelvin, max_relative=1e-9);
+    assert_relative_eq!(p_test.get::<megapascal>(), p_mpa, max_relative=1e-7);
+    assert_relative_eq!(
+        v_test.get::<cubic_meter_per_kilogram>(),
+        v_tp_1(t, p).get::<cubic_meter_per_kilogram>(),
+        max_relative=1e-9
+        );
+    assert_relative_eq!(x_test.get::<ratio>(), 0.0);
+}
+
+#[test]
+pub fn hs_flash_region_1_round_trip(){
+    region_1_round_trip(300.0, 3.0);
+    region_1_round_trip(300.0, 80.0);
+    region_1_round_trip(500.0, 3.0);
+}
+
+/// at low pressures, the backward equation p_hs_1 is 
+/// not accurate, so the newton raphson iteration must 
+/// take care of this
+#[test]
+pub fn hs_flash_region_1_low_pressure_round_trip(){
+    region_1_round_trip(300.0, 0.01);
+    region_1_round_trip(280.0, 0.005);
+}
+
+/// below s''(623.15 K), there is no backward equation 
+/// for the saturation temperature, so we iterate on it 
+#[test]
+pub fn hs_flash_region_4_low_quality_high_temperature_round_trip(){
+
+    for (t_sat_kelvin, quality) in [(630.0, 0.1), (640.0, 0.3), (640.0, 0.9), (500.0, 0.05)] {
+
+        let t_sat = ThermodynamicTemperature::new::<kelvin>(t_sat_kelvin);
+        let p_sat = sat_pressure_4(t_sat);
+        let h = h_tp_eqm_two_phase(t_sat, p_sat, quality);
+        let s = s_ph_eqm(p_sat, h);
+
+        assert_eq!(hs_flash_region(h, s), BackwdEqnSubRegion::Region4);
+
+        let (t_test, p_test, _v_test, x_test) = tpvx_hs_flash_eqm(h, s);
+
+        assert_relative_eq!(t_test.get::<kelvin>(), t_sat_kelvin, max_relative=1e-8);
+        assert_relative_eq!(
+            p_test.get::<megapascal>(), 
+            p_sat.get::<megapascal>(), 
+            max_relative=1e-7);
+        assert_relative_eq!(x_test.get::<ratio>(), quality, max_relative=1e-6);
+    }
+}
+
+/// checks the 100 MPa isobar boundary in region 1
+#[test]
+pub fn hs_below_isobar_p_100_mpa_in_region_1(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(400.0);
+    let p_max = Pressure::new::<megapascal>(100.0);
+    let h_isobar = h_tp_1(t, p_max);
+    let s_isobar = s_tp_1(t, p_max);
+
+    let dh = AvailableEnergy::new::<kilojoule_per_kilogram>(1.0);
+
+    assert!(hs_is_below_isobar_p_100_mpa_in_region1(h_isobar - dh, s_isobar));
+    assert!(!hs_is_below_isobar_p_100_mpa_in_region1(h_isobar + dh, s_isobar));
+
+    // outside region 1 entirely
+    let s_outside = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(5.0);
+    assert!(!hs_is_below_isobar_p_100_mpa_in_region1(h_isobar, s_outside));
+}
+
+/// (h,s) points above the 100 MPa isobar in region 1 
+/// are outside the validity range of the (h,s) flash
+#[test]
+pub fn hs_above_isobar_p_100_mpa_in_region_1_is_error(){
+    use crate::errors::SteamTableError;
+    use crate::interfaces::functional_programming::hs_flash_eqm::try_hs_flash_region;
+
+    let t = ThermodynamicTemperature::new::<kelvin>(400.0);
+    let p_max = Pressure::new::<megapascal>(100.0);
+    let h_isobar = h_tp_1(t, p_max);
+    let s_isobar = s_tp_1(t, p_max);
+
+    let dh = AvailableEnergy::new::<kilojoule_per_kilogram>(1.0);
+
+    assert_eq!(hs_flash_region(h_isobar - dh, s_isobar), BackwdEqnSubRegion::Region1);
+
+    let error = try_hs_flash_region(h_isobar + dh, s_isobar).unwrap_err();
+    assert!(matches!(error, SteamTableError::EnthalpyAboveBound { .. }));
+}
+
+/// the 100 MPa isobar bounds the (h,s) flash in regions 3 and 2 
+/// as well, using the same isobar as in region 1
+///
+/// the isobar enthalpy is from the (p,s) backward equations,
+/// so it agrees with the forward equations to within 0.01%
+#[test]
+pub fn hs_above_isobar_p_100_mpa_in_regions_2_and_3_is_error(){
+    use crate::errors::SteamTableError;
+    use crate::interfaces::functional_programming::hs_flash_eqm::try_hs_flash_region;
+
+    let p_max = Pressure::new::<megapascal>(100.0);
+    let dh = AvailableEnergy::new::<kilojoule_per_kilogram>(1.0);
+
+    for (t_kelvin, region_below_isobar) in [
+        (650.0, BackwdEqnSubRegion::Region3a), 
+        (850.0, BackwdEqnSubRegion::Region3b), 
+        (900.0, BackwdEqnSubRegion::Region2c)] {
+
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let h_isobar = h_tp_eqm_single_phase(t, p_max);
+        let s_isobar = s_tp_eqm_single_phase(t, p_max);
+
+        assert_relative_eq!(
+            try_h_isobar_p_100_mpa(s_isobar).unwrap().get::<kilojoule_per_kilogram>(),
+            h_isobar.get::<kilojoule_per_kilogram>(),
+            max_relative=1e-4
+            );
+
+        assert_eq!(hs_flash_region(h_isobar - dh, s_isobar), region_below_isobar);
+
+        let error = try_hs_flash_region(h_isobar + dh, s_isobar).unwrap_err();
+        assert!(matches!(error, SteamTableError::EnthalpyAboveBound { .. }));
+    }
+}
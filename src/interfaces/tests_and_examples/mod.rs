@@ -8,6 +8,50 @@ pub mod ph_flash_regions;
 /// note: does not include out of bounds just yet..
 pub mod hs_flash_regions;
 
+/// these are tests to check that the try_* flash functions 
+/// return errors rather than panicking when out of bounds
+pub mod try_flash_errors;
+
+/// these are tests to check the flash algorithms 
+/// for region 5 (1073.15 K to 2273.15 K, up to 50 MPa)
+pub mod region_5_flash;
+
+/// these are tests to check that the hs flash 
+/// returns the original (T,p) point in regions 1, 4 and 
+/// the 100 MPa isobar boundary
+pub mod hs_flash_round_trip;
+
+/// these are tests to check the (p,v) flash 
+/// across regions 1 to 5
+pub mod pv_flash;
+
+/// these are tests to check the (T,rho) flash 
+/// across regions 1 to 5
+pub mod trho_flash;
+
+/// these are tests to check the (u,v) flash 
+/// across regions 1 to 5, including the vapour dome
+pub mod uv_flash;
+
+/// these are tests to check the saturated liquid and vapour
+/// properties from the SaturationState
+pub mod saturation_state;
+
+/// these are tests to check the metastable vapour (T,p) flash
+pub mod pt_flash_metastable;
+
+/// these are tests to check the (p,h) and (p,s) flashes
+/// with newton refinement on the forward equations
+pub mod refined_flash;
+
+/// these are tests to check the validity domain queries
+/// for each flash pair
+pub mod validity_domain;
+
+/// these are tests to check the out of range policies
+/// (error, clamp to boundary and extrapolate)
+pub mod out_of_range_policy;
+
 /// aims to reproduce steam tables using ph flash
 #[cfg(test)]
 pub mod ph_flash_steam_table;
@@ -22,3 +66,37 @@ pub mod ps_flash_steam_table;
 //#[cfg(test)]
 //#[ignore]
 //pub mod hs_flash_steam_table;
+
+/// these are tests to check the flash diagnostics
+/// (region, subregion, iterations and residuals)
+pub mod flash_diagnostics;
+
+/// these are tests to check the single call flashes 
+/// returning a full SteamState
+pub mod steam_state;
+
+/// these are tests for the control volume 
+/// (TampinesSteamTableCV) getters and setters
+pub mod control_volume;
+
+/// these are tests for the SteamPropertyProvider trait, 
+/// the If97 backend and a mocked backend
+pub mod steam_property_provider;
+
+/// these are tests for serialising and deserialising the 
+/// control volume, state types and region enums 
+/// (serde feature)
+#[cfg(feature = "serde")]
+pub mod serde_support;
+
+/// these are tests for the two phase viscosity and 
+/// thermal conductivity mixing models
+pub mod two_phase_mixing_model;
+
+/// these are tests for the homogeneous equilibrium cv, 
+/// speed of sound and isentropic exponent in region 4
+pub mod two_phase_equilibrium_derivatives;
+
+/// these are tests for the analytic (p,h) jacobian of 
+/// density and temperature, in and out of the vapour dome
+pub mod ph_jacobian;
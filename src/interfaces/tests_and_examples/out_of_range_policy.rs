@@ -0,0 +1,255 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::volume::cubic_meter;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::hs_flash_eqm::try_hs_flash_with_policy;
+use crate::interfaces::functional_programming::out_of_range_policy::OutOfRangePolicy;
+use crate::interfaces::functional_programming::ph_flash_eqm::{t_ph_eqm, try_ph_flash_with_policy, try_t_ph_eqm};
+use crate::interfaces::functional_programming::ps_flash_eqm::try_ps_flash_with_policy;
+use crate::interfaces::functional_programming::pt_flash_eqm::{s_tp_eqm_single_phase, try_tp_flash_with_policy, FwdEqnRegion};
+use crate::interfaces::functional_programming::validity_domain::is_valid_hs;
+use crate::interfaces::object_oriented_programming::TampinesSteamTableCV;
+use crate::region_1_subcooled_liquid::{h_tp_1, s_tp_1, v_tp_1};
+use crate::region_3_single_phase_plus_supercritical_steam::{h_rho_t_3, p_rho_t_3};
+
+/// within the validity range, the policy does nothing
+#[test]
+pub fn out_of_range_policy_within_range(){
+
+    let p = Pressure::new::<megapascal>(3.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(500.0);
+
+    for policy in [OutOfRangePolicy::Error,
+        OutOfRangePolicy::ClampToBoundary,
+        OutOfRangePolicy::ExtrapolateForwardEquation] {
+
+        let state = try_ph_flash_with_policy(p, h, policy).unwrap();
+
+        assert!(state.report.is_within_range());
+        assert!(!state.report.was_clamped());
+        assert!(!state.report.was_extrapolated());
+        assert_eq!(state.state.t, t_ph_eqm(p, h));
+        assert_eq!(state.state.p, p);
+        assert_eq!(state.state.h, h);
+    }
+}
+
+/// the Error policy returns the same error as the try_* flash
+#[test]
+pub fn out_of_range_policy_error(){
+
+    let p = Pressure::new::<megapascal>(120.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1000.0);
+
+    let error = try_ph_flash_with_policy(p, h, OutOfRangePolicy::Error).unwrap_err();
+
+    assert!(matches!(error, SteamTableError::PressureAboveBound { .. }));
+    assert_eq!(error, try_t_ph_eqm(p, h).unwrap_err());
+    assert_eq!(OutOfRangePolicy::default(), OutOfRangePolicy::Error);
+}
+
+/// pressure above 100 MPa, clamped and extrapolated
+#[test]
+pub fn out_of_range_policy_ph_clamp_and_extrapolate(){
+
+    let p = Pressure::new::<megapascal>(120.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1000.0);
+
+    let clamped = try_ph_flash_with_policy(p, h,
+        OutOfRangePolicy::ClampToBoundary).unwrap();
+
+    assert!(clamped.report.was_clamped());
+    assert!(matches!(clamped.report.violated_bound,
+        Some(SteamTableError::PressureAboveBound { .. })));
+    assert_relative_eq!(clamped.state.p.get::<megapascal>(), 100.0);
+    assert_eq!(clamped.state.h, h);
+    assert_eq!(clamped.state.t, t_ph_eqm(clamped.state.p, h));
+
+    let extrapolated = try_ph_flash_with_policy(p, h,
+        OutOfRangePolicy::ExtrapolateForwardEquation).unwrap();
+
+    assert!(extrapolated.report.was_extrapolated());
+    assert_eq!(extrapolated.state.p, p);
+    assert_eq!(extrapolated.state.x, 0.0);
+    // the extrapolated temperature is refined using the
+    // forward equation
+    assert_relative_eq!(
+        h_tp_1(extrapolated.state.t, p).get::<kilojoule_per_kilogram>(),
+        1000.0,
+        max_relative=1e-9);
+    assert_relative_eq!(
+        extrapolated.state.v.get::<cubic_meter_per_kilogram>(),
+        v_tp_1(extrapolated.state.t, p).get::<cubic_meter_per_kilogram>(),
+        max_relative=1e-12);
+}
+
+/// temperature below 273.15 K in the (T,p) flash and
+/// entropy below the 273.15 K isotherm in the (p,s) flash
+#[test]
+pub fn out_of_range_policy_below_273_15_k(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(270.0);
+    let t_min = ThermodynamicTemperature::new::<kelvin>(273.15);
+    let p = Pressure::new::<megapascal>(1.0);
+
+    assert!(matches!(
+        try_tp_flash_with_policy(t, p, 0.0, OutOfRangePolicy::Error).unwrap_err(),
+        SteamTableError::TemperatureBelowBound { .. }));
+
+    let clamped = try_tp_flash_with_policy(t, p, 0.0,
+        OutOfRangePolicy::ClampToBoundary).unwrap();
+    assert!(clamped.report.was_clamped());
+    assert_relative_eq!(clamped.state.t.get::<kelvin>(), 273.15);
+    assert_relative_eq!(
+        clamped.state.h.get::<kilojoule_per_kilogram>(),
+        h_tp_1(t_min, p).get::<kilojoule_per_kilogram>(),
+        max_relative=1e-12);
+
+    let extrapolated = try_tp_flash_with_policy(t, p, 0.0,
+        OutOfRangePolicy::ExtrapolateForwardEquation).unwrap();
+    assert!(extrapolated.report.was_extrapolated());
+    assert_eq!(extrapolated.state.t, t);
+    assert_eq!(extrapolated.state.h, h_tp_1(t, p));
+    assert_eq!(extrapolated.state.s, s_tp_1(t, p));
+
+    // (p,s) flash, entropy of liquid at 270 K
+    let s = s_tp_1(t, p);
+    let clamped = try_ps_flash_with_policy(p, s,
+        OutOfRangePolicy::ClampToBoundary).unwrap();
+    assert!(matches!(clamped.report.violated_bound,
+        Some(SteamTableError::EntropyBelowBound { .. })));
+    assert_relative_eq!(
+        clamped.state.s.get::<kilojoule_per_kilogram_kelvin>(),
+        s_tp_eqm_single_phase(t_min, p).get::<kilojoule_per_kilogram_kelvin>(),
+        max_relative=1e-12);
+    assert_relative_eq!(clamped.state.t.get::<kelvin>(), 273.15, max_relative=1e-5);
+
+    let extrapolated = try_ps_flash_with_policy(p, s,
+        OutOfRangePolicy::ExtrapolateForwardEquation).unwrap();
+    assert!(extrapolated.report.was_extrapolated());
+    assert_relative_eq!(extrapolated.state.t.get::<kelvin>(), 270.0, max_relative=1e-9);
+}
+
+/// clamped (h,s) points are within the validity range
+#[test]
+pub fn out_of_range_policy_hs_clamp(){
+
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(2800.0);
+    let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(13.0);
+
+    assert!(try_hs_flash_with_policy(h, s, OutOfRangePolicy::Error).is_err());
+
+    let clamped = try_hs_flash_with_policy(h, s,
+        OutOfRangePolicy::ClampToBoundary).unwrap();
+
+    assert!(clamped.report.was_clamped());
+    assert!(is_valid_hs(clamped.state.h, clamped.state.s));
+}
+
+/// control volumes constructed with a policy report
+/// whether the point was clamped
+#[test]
+pub fn out_of_range_policy_control_volume(){
+
+    let p = Pressure::new::<megapascal>(120.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1000.0);
+    let volume = Volume::new::<cubic_meter>(1.0);
+
+    assert!(TampinesSteamTableCV::try_new_from_ph_with_policy(
+        p, h, volume, OutOfRangePolicy::Error).is_err());
+
+    let (cv, report) = TampinesSteamTableCV::try_new_from_ph_with_policy(
+        p, h, volume, OutOfRangePolicy::ClampToBoundary).unwrap();
+
+    assert!(report.was_clamped());
+    assert_relative_eq!(cv.get_pressure().get::<megapascal>(), 100.0);
+    assert_eq!(cv.get_specific_enthalpy(), h);
+    assert!(cv.get_mass().value > 0.0);
+
+    let t = ThermodynamicTemperature::new::<kelvin>(400.0);
+    let p = Pressure::new::<megapascal>(1.0);
+    let (cv, report) = TampinesSteamTableCV::try_new_from_tp_quality_with_policy(
+        t, p, volume, 0.0, OutOfRangePolicy::ClampToBoundary).unwrap();
+
+    assert!(report.is_within_range());
+    assert_eq!(cv, TampinesSteamTableCV::new_from_tp_quality(t, p, volume, 0.0));
+}
+
+/// region 3 points above 100 MPa are extrapolated using the
+/// (rho,T) forward equation, so the pressure and enthalpy
+/// of the extrapolated state are consistent with it
+#[test]
+pub fn out_of_range_policy_region_3_extrapolation(){
+
+    let p = Pressure::new::<megapascal>(120.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(2000.0);
+
+    let extrapolated = try_ph_flash_with_policy(p, h,
+        OutOfRangePolicy::ExtrapolateForwardEquation).unwrap();
+
+    assert!(extrapolated.report.was_extrapolated());
+    assert_eq!(extrapolated.state.region, Some(FwdEqnRegion::Region3));
+    assert_relative_eq!(
+        p_rho_t_3(extrapolated.state.rho(), extrapolated.state.t).get::<megapascal>(),
+        120.0,
+        max_relative=1e-9);
+    assert_relative_eq!(
+        h_rho_t_3(extrapolated.state.rho(), extrapolated.state.t).get::<kilojoule_per_kilogram>(),
+        2000.0,
+        max_relative=1e-9);
+
+    // (T,p) flash at the extrapolated temperature
+    let t = extrapolated.state.t;
+    let extrapolated_tp = try_tp_flash_with_policy(t, p, 0.0,
+        OutOfRangePolicy::ExtrapolateForwardEquation).unwrap();
+
+    assert!(extrapolated_tp.report.was_extrapolated());
+    assert_eq!(extrapolated_tp.state.region, Some(FwdEqnRegion::Region3));
+    assert_relative_eq!(
+        extrapolated_tp.state.h.get::<kilojoule_per_kilogram>(),
+        2000.0,
+        max_relative=1e-6);
+}
+
+/// the setters use the out of range policy of the control volume
+#[test]
+pub fn out_of_range_policy_control_volume_setters(){
+
+    let p = Pressure::new::<megapascal>(3.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(500.0);
+    let volume = Volume::new::<cubic_meter>(1.0);
+    let p_out_of_range = Pressure::new::<megapascal>(120.0);
+
+    let mut cv = TampinesSteamTableCV::new_from_ph(p, h, volume);
+
+    assert_eq!(cv.get_out_of_range_policy(), OutOfRangePolicy::Error);
+    assert!(matches!(cv.try_set_ph(p_out_of_range, h).unwrap_err(),
+        SteamTableError::PressureAboveBound { .. }));
+    // the control volume is unchanged on error
+    assert_eq!(cv, TampinesSteamTableCV::new_from_ph(p, h, volume));
+
+    cv.set_out_of_range_policy(OutOfRangePolicy::ClampToBoundary);
+    let report = cv.try_set_ph(p_out_of_range, h).unwrap();
+
+    assert!(report.was_clamped());
+    assert_relative_eq!(cv.get_pressure().get::<megapascal>(), 100.0);
+
+    // constructors with a policy pass it on to the setters
+    let (mut cv, _report) = TampinesSteamTableCV::try_new_from_ph_with_policy(
+        p, h, volume, OutOfRangePolicy::ExtrapolateForwardEquation).unwrap();
+
+    cv.set_ph(p_out_of_range, h);
+
+    assert_eq!(cv.get_pressure(), p_out_of_range);
+    assert_eq!(cv.get_specific_enthalpy(), h);
+    assert_relative_eq!(
+        h_tp_1(cv.get_temperature(), p_out_of_range).get::<kilojoule_per_kilogram>(),
+        500.0,
+        max_relative=1e-9);
+}
@@ -422,10 +422,9 @@ pub fn region_4_test_4(){
 
 /// region 5
 ///
-/// should panic
+/// above the 1073.15 K isotherm and below 50 MPa
 #[test]
-#[should_panic]
-pub fn region_5_test_1_should_panic(){
+pub fn region_5_test_1(){
 
     let reference_region = FwdEqnRegion::Region5;
     let p = Pressure::new::<megapascal>(23.0);
@@ -0,0 +1,208 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::thermodynamic_temperature::{degree_celsius, kelvin};
+
+use crate::interfaces::functional_programming::ph_flash_eqm;
+use crate::interfaces::functional_programming::ph_flash_eqm::{jacobian, try_jacobian};
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+use crate::interfaces::functional_programming::saturation_eqm::SaturationState;
+use crate::region_1_subcooled_liquid::{h_tp_1, v_tp_1};
+use crate::region_2_vapour::{h_tp_2, v_tp_2};
+use crate::region_5_steam_at_800_plus_degc::{h_tp_5, v_tp_5};
+
+/// the single phase jacobian against central differences
+/// of the region 1, 2 and 5 forward equations
+///
+/// h(T,p) and rho(T,p) are differentiated numerically, then
+/// (dT/dh)_p = 1/(dh/dT)_p
+/// (dT/dp)_h = -(dh/dp)_T/(dh/dT)_p
+/// (drho/dh)_p = (drho/dT)_p/(dh/dT)_p
+/// (drho/dp)_h = (drho/dp)_T - (drho/dT)_p (dh/dp)_T/(dh/dT)_p
+#[test]
+pub fn single_phase_jacobian_against_forward_equations_test(){
+
+    // (region, T in degC, p in MPa)
+    let points = [
+        (FwdEqnRegion::Region1, 20.0, 0.1),
+        (FwdEqnRegion::Region1, 150.0, 5.0),
+        (FwdEqnRegion::Region1, 300.0, 50.0),
+        (FwdEqnRegion::Region2, 150.0, 0.1),
+        (FwdEqnRegion::Region2, 400.0, 5.0),
+        (FwdEqnRegion::Region2, 600.0, 50.0),
+        (FwdEqnRegion::Region5, 1000.0, 1.0),
+        (FwdEqnRegion::Region5, 1500.0, 30.0),
+    ];
+
+    for (region, t_degc, p_mpa) in points {
+
+        let h_tp: fn(ThermodynamicTemperature, Pressure) -> AvailableEnergy = match region {
+            FwdEqnRegion::Region1 => h_tp_1,
+            FwdEqnRegion::Region2 => h_tp_2,
+            _ => h_tp_5,
+        };
+        let v_tp: fn(ThermodynamicTemperature, Pressure) -> SpecificVolume = match region {
+            FwdEqnRegion::Region1 => v_tp_1,
+            FwdEqnRegion::Region2 => v_tp_2,
+            _ => v_tp_5,
+        };
+
+        let t = ThermodynamicTemperature::new::<degree_celsius>(t_degc);
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let h = h_tp(t, p);
+        assert_eq!(ph_flash_eqm::ph_flash_region(p, h), region);
+
+        // flash the forward equations at the temperature
+        // returned by the backward equation, so that both
+        // are evaluated at the same point
+        let t = ph_flash_eqm::t_ph_eqm(p, h);
+
+        let dt = 1.0e-3;
+        let dp = 1.0e-4 * p.value;
+        let t_plus = ThermodynamicTemperature::new::<kelvin>(t.get::<kelvin>() + dt);
+        let t_minus = ThermodynamicTemperature::new::<kelvin>(t.get::<kelvin>() - dt);
+        let p_plus = p + Pressure::new::<uom::si::pressure::pascal>(dp);
+        let p_minus = p - Pressure::new::<uom::si::pressure::pascal>(dp);
+
+        let dh_dt = (h_tp(t_plus, p) - h_tp(t_minus, p)).value/(2.0 * dt);
+        let dh_dp = (h_tp(t, p_plus) - h_tp(t, p_minus)).value/(2.0 * dp);
+        let drho_dt = (v_tp(t_plus, p).recip() - v_tp(t_minus, p).recip()).value
+            /(2.0 * dt);
+        let drho_dp = (v_tp(t, p_plus).recip() - v_tp(t, p_minus).recip()).value
+            /(2.0 * dp);
+
+        let jacobian = jacobian(p, h);
+
+        assert_relative_eq!(jacobian.dt_dh_p.value, dh_dt.recip(),
+            max_relative = 1e-6);
+        assert_relative_eq!(jacobian.dt_dp_h.value, -dh_dp/dh_dt,
+            max_relative = 1e-6);
+        assert_relative_eq!(jacobian.drho_dh_p.value, drho_dt/dh_dt,
+            max_relative = 1e-6);
+        assert_relative_eq!(jacobian.drho_dp_h.value,
+            drho_dp - drho_dt * dh_dp/dh_dt,
+            max_relative = 1e-6);
+    }
+}
+
+/// the region 3 jacobian against central differences
+/// of the (p,h) flash
+///
+/// the flash uses the region 3 backward equations T(p,h) and v(p,h),
+/// which are only consistent with the forward equations
+/// to about 1e-4, so the tolerance is looser
+#[test]
+pub fn region_3_jacobian_against_ph_flash_test(){
+
+    // (p in MPa, h in kJ/kg)
+    let points = [
+        (25.0, 1800.0),
+        (50.0, 2000.0),
+        (50.0, 2400.0),
+        (80.0, 2600.0),
+    ];
+
+    for (p_mpa, h_kj_per_kg) in points {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let h = AvailableEnergy::new::<kilojoule_per_kilogram>(h_kj_per_kg);
+        assert_eq!(ph_flash_eqm::ph_flash_region(p, h), FwdEqnRegion::Region3);
+
+        let (dt_dh_p, dt_dp_h, drho_dh_p, drho_dp_h) =
+            ph_flash_central_differences(p, h);
+
+        let jacobian = jacobian(p, h);
+
+        assert_relative_eq!(jacobian.dt_dh_p.value, dt_dh_p,
+            max_relative = 1e-2);
+        assert_relative_eq!(jacobian.dt_dp_h.value, dt_dp_h,
+            max_relative = 1e-2);
+        assert_relative_eq!(jacobian.drho_dh_p.value, drho_dh_p,
+            max_relative = 1e-2);
+        assert_relative_eq!(jacobian.drho_dp_h.value, drho_dp_h,
+            max_relative = 1e-2);
+    }
+}
+
+/// the two phase jacobian against central differences
+/// of the (p,h) flash in the vapour dome
+///
+/// below 623.15 K, the saturated phases are from the
+/// region 1 and 2 forward equations, so the flash
+/// is consistent with the analytic derivatives
+#[test]
+pub fn two_phase_jacobian_against_ph_flash_test(){
+
+    for p_mpa in [0.01, 0.1, 1.0, 5.0, 10.0, 15.0] {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let saturation_state = SaturationState::new_from_p(p);
+
+        for x in [0.05, 0.3, 0.5, 0.7, 0.95] {
+            let h = saturation_state.h_mixture(x);
+            assert_eq!(ph_flash_eqm::ph_flash_region(p, h), FwdEqnRegion::Region4);
+
+            let (_dt_dh_p, dt_dp_h, drho_dh_p, drho_dp_h) =
+                ph_flash_central_differences(p, h);
+
+            let jacobian = jacobian(p, h);
+
+            // temperature only depends on pressure in the dome
+            assert_eq!(jacobian.dt_dh_p.value, 0.0);
+            assert_relative_eq!(jacobian.dt_dp_h.value, dt_dp_h,
+                max_relative = 1e-6);
+            assert_relative_eq!(jacobian.drho_dh_p.value, drho_dh_p,
+                max_relative = 1e-6);
+            assert_relative_eq!(jacobian.drho_dp_h.value, drho_dp_h,
+                max_relative = 1e-6);
+        }
+    }
+}
+
+/// the jacobian is discontinuous across the saturation line,
+/// just inside the dome, density is much more sensitive to
+/// enthalpy than in the saturated liquid,
+/// and out of range points return an error
+#[test]
+pub fn jacobian_saturation_line_and_out_of_range_test(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+    let saturation_state = SaturationState::new_from_p(p);
+    let h_liq = saturation_state.liquid.h;
+    let dh = AvailableEnergy::new::<kilojoule_per_kilogram>(1.0);
+
+    let subcooled = jacobian(p, h_liq - dh);
+    let two_phase = jacobian(p, h_liq + dh);
+
+    // subcooled liquid density barely changes with enthalpy,
+    // in the dome, it drops with (v''-v')/(h''-h')
+    assert!(two_phase.drho_dh_p.value.abs() > 100.0 * subcooled.drho_dh_p.value.abs());
+    assert!(subcooled.dt_dh_p.value > 0.0);
+    assert_eq!(two_phase.dt_dh_p.value, 0.0);
+
+    let p_out_of_range = Pressure::new::<megapascal>(120.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(2000.0);
+    assert!(try_jacobian(p_out_of_range, h).is_err());
+}
+
+/// (dT/dh)_p, (dT/dp)_h, (drho/dh)_p and (drho/dp)_h
+/// by central differences of the (p,h) flash, in SI units
+fn ph_flash_central_differences(p: Pressure,
+    h: AvailableEnergy) -> (f64, f64, f64, f64) {
+
+    let dh = 1.0e-5 * h.value;
+    let dp = 1.0e-6 * p.value;
+    let h_plus = h + AvailableEnergy::new::<uom::si::available_energy::joule_per_kilogram>(dh);
+    let h_minus = h - AvailableEnergy::new::<uom::si::available_energy::joule_per_kilogram>(dh);
+    let p_plus = p + Pressure::new::<uom::si::pressure::pascal>(dp);
+    let p_minus = p - Pressure::new::<uom::si::pressure::pascal>(dp);
+
+    let t_ph = |p, h| ph_flash_eqm::t_ph_eqm(p, h).get::<kelvin>();
+    let rho_ph = |p, h| ph_flash_eqm::v_ph_eqm(p, h).recip().value;
+
+    let dt_dh_p = (t_ph(p, h_plus) - t_ph(p, h_minus))/(2.0 * dh);
+    let dt_dp_h = (t_ph(p_plus, h) - t_ph(p_minus, h))/(2.0 * dp);
+    let drho_dh_p = (rho_ph(p, h_plus) - rho_ph(p, h_minus))/(2.0 * dh);
+    let drho_dp_h = (rho_ph(p_plus, h) - rho_ph(p_minus, h))/(2.0 * dp);
+
+    return (dt_dh_p, dt_dp_h, drho_dh_p, drho_dp_h);
+}
@@ -0,0 +1,118 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::dynamic_viscosity::micropascal_second;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::thermal_conductivity::milliwatt_per_meter_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::velocity::meter_per_second;
+
+use crate::dynamic_viscosity::mu_tp_eqm_single_phase;
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::pt_flash_metastable::*;
+use crate::region_4_vap_liq_equilibrium::sat_temp_4;
+use crate::thermal_conductivity::lambda_tp_eqm_single_phase;
+
+/// metastable vapour (T,p) flash, using the metastable
+/// vapour verification table (table 18 of IAPWS-IF97)
+/// [T (K), p (MPa), v (m3/kg), h (kJ/kg), u (kJ/kg), s (kJ/(kg K)), cp (kJ/(kg K)), w (m/s)]
+#[test]
+pub fn pt_flash_metastable_verification_table(){
+
+    let table: Vec<[f64; 8]> = vec![
+        [450.0, 1.0, 0.192516540, 0.276881115e4, 0.257629461e4, 0.656660377e1, 0.276349265e1, 0.498408101e3],
+        [440.0, 1.0, 0.186212297, 0.274015123e4, 0.255393894e4, 0.650218759e1, 0.298166443e1, 0.489363295e3],
+        [450.0, 1.5, 0.121685206, 0.272134539e4, 0.253881758e4, 0.629170440e1, 0.362795578e1, 0.481941819e3],
+    ];
+
+    for [t_kelvin, p_mpa, v_ref, h_ref, u_ref, s_ref, cp_ref, w_ref] in table {
+
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+
+        assert!(is_metastable_vapour(t, p));
+
+        assert_relative_eq!(
+            v_tp_metastable(t, p).get::<cubic_meter_per_kilogram>(), v_ref,
+            max_relative=1e-8);
+        assert_relative_eq!(
+            h_tp_metastable(t, p).get::<kilojoule_per_kilogram>(), h_ref,
+            max_relative=1e-8);
+        assert_relative_eq!(
+            u_tp_metastable(t, p).get::<kilojoule_per_kilogram>(), u_ref,
+            max_relative=1e-8);
+        assert_relative_eq!(
+            s_tp_metastable(t, p).get::<kilojoule_per_kilogram_kelvin>(), s_ref,
+            max_relative=1e-8);
+        assert_relative_eq!(
+            cp_tp_metastable(t, p).get::<kilojoule_per_kilogram_kelvin>(), cp_ref,
+            max_relative=1e-8);
+        assert_relative_eq!(
+            w_tp_metastable(t, p).get::<meter_per_second>(), w_ref,
+            max_relative=1e-8);
+    }
+}
+
+/// at the saturated vapour line, the metastable vapour
+/// transport properties should be close to those of the
+/// stable (region 2) vapour
+#[test]
+pub fn pt_flash_metastable_transport_at_saturated_vapour_line(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+    let t_sat = sat_temp_4(p);
+
+    // slightly below saturation pressure so that the
+    // equilibrium (T,p) flash gives vapour
+    let p_vap = p * 0.99999;
+
+    assert_relative_eq!(
+        mu_tp_metastable(t_sat, p).get::<micropascal_second>(),
+        mu_tp_eqm_single_phase(t_sat, p_vap).get::<micropascal_second>(),
+        max_relative=1e-3);
+    assert_relative_eq!(
+        lambda_tp_metastable(t_sat, p).get::<milliwatt_per_meter_kelvin>(),
+        lambda_tp_eqm_single_phase(t_sat, p_vap).get::<milliwatt_per_meter_kelvin>(),
+        max_relative=1e-3);
+}
+
+/// points outside the metastable vapour region should
+/// return errors
+#[test]
+pub fn pt_flash_metastable_out_of_bounds_is_error(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+
+    // superheated vapour is not metastable
+    let t_superheated = ThermodynamicTemperature::new::<kelvin>(500.0);
+    assert!(!is_metastable_vapour(t_superheated, p));
+    assert!(matches!(try_h_tp_metastable(t_superheated, p).unwrap_err(),
+        SteamTableError::TemperatureAboveBound { .. }));
+
+    // too far below the saturation temperature, past the
+    // 5% equilibrium moisture line
+    let t_too_cold = ThermodynamicTemperature::new::<kelvin>(400.0);
+    let error = try_v_tp_metastable(t_too_cold, p).unwrap_err();
+
+    match error {
+        SteamTableError::TemperatureBelowBound { bound, .. } => {
+            // the moisture line should lie between this point
+            // and the verification table point at 440 K
+            assert!(bound.get::<kelvin>() > 400.0);
+            assert!(bound.get::<kelvin>() < 440.0);
+
+            // just above the moisture line is fine
+            let t_above_bound = ThermodynamicTemperature::new::<kelvin>(
+                bound.get::<kelvin>() + 1.0e-6);
+            assert!(is_metastable_vapour(t_above_bound, p));
+        },
+        _ => panic!("expected TemperatureBelowBound, got {:?}", error),
+    };
+
+    let p_too_high = Pressure::new::<megapascal>(12.0);
+    let t = ThermodynamicTemperature::new::<kelvin>(580.0);
+    assert!(matches!(try_cp_tp_metastable(t, p_too_high).unwrap_err(),
+        SteamTableError::PressureAboveBound { .. }));
+}
@@ -0,0 +1,199 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::ph_flash_eqm::v_ph_eqm;
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+use crate::interfaces::functional_programming::pv_flash_eqm::*;
+use crate::region_1_subcooled_liquid::{h_tp_1, s_tp_1, v_tp_1};
+use crate::region_2_vapour::{h_tp_2, v_tp_2};
+use crate::region_3_single_phase_plus_supercritical_steam::p_rho_t_3;
+use crate::region_4_vap_liq_equilibrium::sat_temp_4;
+use crate::region_5_steam_at_800_plus_degc::{h_tp_5, v_tp_5};
+
+/// (p,v) flash in region 1 should return the temperature
+/// and properties of the original (T,p) point
+///
+/// the (p,v) flash is built on v_ph_eqm, which uses the 
+/// T(p,h) backward equation (accurate to tens of mK), and 
+/// liquid specific volume hardly changes with temperature, 
+/// so enthalpy is only recovered to about 1e-3
+#[test]
+pub fn pv_flash_region_1_round_trip(){
+
+    for (t_kelvin, p_mpa) in [(300.0, 3.0), (300.0, 80.0), (500.0, 3.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let v = v_tp_1(t, p);
+
+        assert_eq!(pv_flash_region(p, v), FwdEqnRegion::Region1);
+
+        // the specific volume should be consistent with the ph flash
+        let h = h_pv_eqm(p, v);
+        assert_relative_eq!(
+            v_ph_eqm(p, h).get::<cubic_meter_per_kilogram>(),
+            v.get::<cubic_meter_per_kilogram>(),
+            max_relative=1e-10);
+
+        assert_relative_eq!(
+            h.get::<kilojoule_per_kilogram>(),
+            h_tp_1(t, p).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-3);
+        assert_relative_eq!(
+            s_pv_eqm(p, v).get::<kilojoule_per_kilogram_kelvin>(),
+            s_tp_1(t, p).get::<kilojoule_per_kilogram_kelvin>(),
+            max_relative=1e-3);
+        assert_relative_eq!(t_pv_eqm(p, v).get::<kelvin>(), t_kelvin, max_relative=1e-4);
+        assert_relative_eq!(x_pv_flash(p, v), 0.0);
+    }
+}
+
+/// (p,v) flash in region 2 and 5 should return the temperature
+/// and enthalpy of the original (T,p) point
+///
+/// in region 2, this is limited by the accuracy of the 
+/// T(p,h) backward equations used in the ph flash
+#[test]
+pub fn pv_flash_vapour_round_trip(){
+
+    for (t_kelvin, p_mpa) in [(500.0, 0.5), (700.0, 30.0), (1000.0, 80.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let v = v_tp_2(t, p);
+
+        assert_eq!(pv_flash_region(p, v), FwdEqnRegion::Region2);
+
+        assert_relative_eq!(
+            h_pv_eqm(p, v).get::<kilojoule_per_kilogram>(),
+            h_tp_2(t, p).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-4);
+        assert_relative_eq!(t_pv_eqm(p, v).get::<kelvin>(), t_kelvin, max_relative=1e-4);
+        assert_relative_eq!(x_pv_flash(p, v), 1.0);
+    }
+
+    for (t_kelvin, p_mpa) in [(1500.0, 0.5), (1500.0, 30.0), (2000.0, 30.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let v = v_tp_5(t, p);
+
+        assert_eq!(pv_flash_region(p, v), FwdEqnRegion::Region5);
+
+        assert_relative_eq!(
+            h_pv_eqm(p, v).get::<kilojoule_per_kilogram>(),
+            h_tp_5(t, p).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-6);
+        assert_relative_eq!(t_pv_eqm(p, v).get::<kelvin>(), t_kelvin, max_relative=1e-6);
+    }
+}
+
+/// (p,v) flash in region 3, the region 3 backward equations
+/// are used within the ph flash, so this is only as accurate
+/// as those
+#[test]
+pub fn pv_flash_region_3(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(650.0);
+    let rho = MassDensity::new::<kilogram_per_cubic_meter>(500.0);
+    let p = p_rho_t_3(rho, t);
+    let v = rho.recip();
+
+    assert_eq!(pv_flash_region(p, v), FwdEqnRegion::Region3);
+
+    // the specific volume should be consistent with the ph flash
+    let h = h_pv_eqm(p, v);
+    assert_relative_eq!(
+        v_ph_eqm(p, h).get::<cubic_meter_per_kilogram>(),
+        v.get::<cubic_meter_per_kilogram>(),
+        max_relative=1e-10);
+
+    assert_relative_eq!(t_pv_eqm(p, v).get::<kelvin>(), 650.0, max_relative=1e-4);
+    assert_relative_eq!(
+        h.get::<kilojoule_per_kilogram>(),
+        0.186343019e4,
+        max_relative=1e-4);
+}
+
+/// above the critical pressure, the (p,v) flash in region 3 starts 
+/// from the 3a or 3b subregion bracket given by the backward 
+/// equations, rather than the whole enthalpy range 
+/// (which took 13 iterations for this point)
+#[test]
+pub fn pv_flash_region_3_starts_from_subregion_bracket(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(650.0);
+    let rho = MassDensity::new::<kilogram_per_cubic_meter>(500.0);
+    let p = p_rho_t_3(rho, t);
+    let v = rho.recip();
+
+    let report = pv_flash_with_diagnostics(p, v);
+
+    assert_eq!(report.region, FwdEqnRegion::Region3);
+    assert!(report.iterations <= 8);
+}
+
+/// within the vapour dome, the steam quality follows from the
+/// specific volume at constant pressure
+#[test]
+pub fn pv_flash_region_4_two_phase(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+    let t_sat = sat_temp_4(p);
+    let v_liq = v_tp_1(t_sat, p);
+    let v_vap = v_tp_2(t_sat, p);
+    let quality = 0.3;
+
+    let v = v_vap * quality + v_liq * (1.0 - quality);
+
+    assert_eq!(pv_flash_region(p, v), FwdEqnRegion::Region4);
+
+    assert_relative_eq!(x_pv_flash(p, v), quality, max_relative=1e-9);
+    assert_relative_eq!(
+        t_pv_eqm(p, v).get::<kelvin>(),
+        t_sat.get::<kelvin>(),
+        max_relative=1e-12);
+}
+
+/// liquid water is densest at about 277 K, so the (p,v) flash
+/// returns the solution above this temperature
+#[test]
+pub fn pv_flash_near_maximum_density(){
+
+    let p = Pressure::new::<megapascal>(0.101325);
+    let t_cold = ThermodynamicTemperature::new::<kelvin>(275.0);
+    let v = v_tp_1(t_cold, p);
+
+    let t = t_pv_eqm(p, v).get::<kelvin>();
+
+    assert!(t > 277.0 && t < 280.0);
+    assert_relative_eq!(
+        v_tp_1(ThermodynamicTemperature::new::<kelvin>(t), p)
+        .get::<cubic_meter_per_kilogram>(),
+        v.get::<cubic_meter_per_kilogram>(),
+        max_relative=1e-9);
+}
+
+/// (p,v) points outside the validity range should return errors
+#[test]
+pub fn pv_flash_out_of_bounds_is_error(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+
+    let v_too_dense = SpecificVolume::new::<cubic_meter_per_kilogram>(0.0009);
+    assert!(matches!(try_h_pv_eqm(p, v_too_dense).unwrap_err(),
+        SteamTableError::SpecificVolumeBelowBound { .. }));
+
+    let v_too_large = SpecificVolume::new::<cubic_meter_per_kilogram>(100.0);
+    assert!(matches!(try_t_pv_eqm(p, v_too_large).unwrap_err(),
+        SteamTableError::SpecificVolumeAboveBound { .. }));
+
+    let p_too_high = Pressure::new::<megapascal>(110.0);
+    let v = SpecificVolume::new::<cubic_meter_per_kilogram>(0.001);
+    assert!(matches!(try_t_pv_eqm(p_too_high, v).unwrap_err(),
+        SteamTableError::PressureAboveBound { .. }));
+}
@@ -0,0 +1,247 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::{joule_per_kilogram, kilojoule_per_kilogram};
+use uom::si::f64::*;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::{joule_per_kilogram_kelvin, kilojoule_per_kilogram_kelvin};
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::interfaces::functional_programming::ph_flash_eqm::*;
+use crate::interfaces::functional_programming::ps_flash_eqm::*;
+use crate::interfaces::functional_programming::pt_flash_eqm::{region_fwd_eqn_single_phase, FwdEqnRegion};
+use crate::region_1_subcooled_liquid::{h_tp_1, s_tp_1};
+use crate::region_2_vapour::{h_tp_2, s_tp_2, v_tp_2};
+use crate::region_3_single_phase_plus_supercritical_steam::{h_rho_t_3, p_rho_t_3, s_rho_t_3, v_tp_3};
+use crate::region_5_steam_at_800_plus_degc::{h_tp_5, s_tp_5};
+
+/// refined (p,h) flash near the region 1/3 and 2/3 boundaries,
+/// and in region 5, should return the original temperature,
+/// and the forward equations should return the input enthalpy
+/// to within the tolerance
+#[test]
+pub fn ph_flash_refined_single_phase_round_trip(){
+
+    let h_tolerance = AvailableEnergy::new::<joule_per_kilogram>(1.0e-3);
+
+    let cases: Vec<(f64, f64, FwdEqnRegion)> = vec![
+        (620.0, 40.0, FwdEqnRegion::Region1),
+        (623.0, 80.0, FwdEqnRegion::Region1),
+        (700.0, 15.0, FwdEqnRegion::Region2),
+        (1500.0, 30.0, FwdEqnRegion::Region5),
+    ];
+
+    for (t_kelvin, p_mpa, region) in cases {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+
+        let h = match region {
+            FwdEqnRegion::Region1 => h_tp_1(t, p),
+            FwdEqnRegion::Region2 => h_tp_2(t, p),
+            _ => h_tp_5(t, p),
+        };
+
+        assert_eq!(ph_flash_region(p, h), region);
+
+        let t_refined = t_ph_eqm_refined(p, h, h_tolerance);
+        assert_relative_eq!(t_refined.get::<kelvin>(), t_kelvin, max_relative=1e-9);
+
+        let h_round_trip = match region {
+            FwdEqnRegion::Region1 => h_tp_1(t_refined, p),
+            FwdEqnRegion::Region2 => h_tp_2(t_refined, p),
+            _ => h_tp_5(t_refined, p),
+        };
+        assert!((h_round_trip - h).abs() < h_tolerance);
+    }
+}
+
+/// refined (p,h) flash in region 3, the (v,T) from the
+/// refinement should be the original point
+#[test]
+pub fn ph_flash_refined_region_3(){
+
+    let h_tolerance = AvailableEnergy::new::<joule_per_kilogram>(1.0e-3);
+
+    for (t_kelvin, rho_kg_per_m3) in [(650.0, 500.0), (650.0, 200.0), (750.0, 500.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let rho = MassDensity::new::<kilogram_per_cubic_meter>(rho_kg_per_m3);
+        let p = p_rho_t_3(rho, t);
+        let h = h_rho_t_3(rho, t);
+
+        assert_eq!(ph_flash_region(p, h), FwdEqnRegion::Region3);
+
+        assert_relative_eq!(
+            t_ph_eqm_refined(p, h, h_tolerance).get::<kelvin>(),
+            t_kelvin,
+            max_relative=1e-9);
+        assert_relative_eq!(
+            v_ph_eqm_refined(p, h, h_tolerance).get::<cubic_meter_per_kilogram>(),
+            rho_kg_per_m3.recip(),
+            max_relative=1e-9);
+        assert_relative_eq!(
+            s_ph_eqm_refined(p, h, h_tolerance).get::<kilojoule_per_kilogram_kelvin>(),
+            s_rho_t_3(rho, t).get::<kilojoule_per_kilogram_kelvin>(),
+            max_relative=1e-9);
+    }
+}
+
+/// in region 4, the refined (p,h) flash is the same as
+/// the ordinary (p,h) flash
+#[test]
+pub fn ph_flash_refined_region_4_unchanged(){
+
+    let h_tolerance = AvailableEnergy::new::<joule_per_kilogram>(1.0e-3);
+    let p = Pressure::new::<megapascal>(1.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1500.0);
+
+    assert_eq!(ph_flash_region(p, h), FwdEqnRegion::Region4);
+    assert_eq!(t_ph_eqm_refined(p, h, h_tolerance), t_ph_eqm(p, h));
+    assert_eq!(s_ph_eqm_refined(p, h, h_tolerance), s_ph_eqm(p, h));
+    assert_eq!(v_ph_eqm_refined(p, h, h_tolerance), v_ph_eqm(p, h));
+}
+
+/// refined (p,s) flash near the region 1/3 and 2/3 boundaries,
+/// and in region 5, should return the original temperature,
+/// and the forward equations should return the input entropy
+/// to within the tolerance
+#[test]
+pub fn ps_flash_refined_single_phase_round_trip(){
+
+    let s_tolerance = SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(1.0e-6);
+
+    let cases: Vec<(f64, f64, FwdEqnRegion)> = vec![
+        (620.0, 40.0, FwdEqnRegion::Region1),
+        (623.0, 80.0, FwdEqnRegion::Region1),
+        (700.0, 15.0, FwdEqnRegion::Region2),
+        (1500.0, 30.0, FwdEqnRegion::Region5),
+    ];
+
+    for (t_kelvin, p_mpa, region) in cases {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+
+        let s = match region {
+            FwdEqnRegion::Region1 => s_tp_1(t, p),
+            FwdEqnRegion::Region2 => s_tp_2(t, p),
+            _ => s_tp_5(t, p),
+        };
+
+        assert_eq!(ps_flash_region(p, s), region);
+
+        let t_refined = t_ps_eqm_refined(p, s, s_tolerance);
+        assert_relative_eq!(t_refined.get::<kelvin>(), t_kelvin, max_relative=1e-9);
+
+        let s_round_trip = match region {
+            FwdEqnRegion::Region1 => s_tp_1(t_refined, p),
+            FwdEqnRegion::Region2 => s_tp_2(t_refined, p),
+            _ => s_tp_5(t_refined, p),
+        };
+        assert!((s_round_trip - s).abs() < s_tolerance);
+    }
+
+    // the enthalpy should also be consistent
+    let t = ThermodynamicTemperature::new::<kelvin>(700.0);
+    let p = Pressure::new::<megapascal>(15.0);
+    assert_relative_eq!(
+        h_ps_eqm_refined(p, s_tp_2(t, p), s_tolerance).get::<kilojoule_per_kilogram>(),
+        h_tp_2(t, p).get::<kilojoule_per_kilogram>(),
+        max_relative=1e-9);
+    assert_relative_eq!(
+        v_ps_eqm_refined(p, s_tp_2(t, p), s_tolerance).get::<cubic_meter_per_kilogram>(),
+        v_tp_2(t, p).get::<cubic_meter_per_kilogram>(),
+        max_relative=1e-9);
+}
+
+/// refined (p,s) flash in region 3, the (v,T) from the
+/// refinement should be the original point
+#[test]
+pub fn ps_flash_refined_region_3(){
+
+    let s_tolerance = SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(1.0e-6);
+
+    for (t_kelvin, rho_kg_per_m3) in [(650.0, 500.0), (650.0, 200.0), (750.0, 500.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let rho = MassDensity::new::<kilogram_per_cubic_meter>(rho_kg_per_m3);
+        let p = p_rho_t_3(rho, t);
+        let s = s_rho_t_3(rho, t);
+
+        assert_eq!(ps_flash_region(p, s), FwdEqnRegion::Region3);
+
+        assert_relative_eq!(
+            t_ps_eqm_refined(p, s, s_tolerance).get::<kelvin>(),
+            t_kelvin,
+            max_relative=1e-9);
+        assert_relative_eq!(
+            v_ps_eqm_refined(p, s, s_tolerance).get::<cubic_meter_per_kilogram>(),
+            rho_kg_per_m3.recip(),
+            max_relative=1e-9);
+        assert_relative_eq!(
+            h_ps_eqm_refined(p, s, s_tolerance).get::<kilojoule_per_kilogram>(),
+            h_rho_t_3(rho, t).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-9);
+    }
+}
+
+/// on the 623.15 K isotherm, the refined (p,h) point sits on the
+/// region 1/3 boundary, and the refinement should keep it there
+/// rather than falling back to the backward equation estimate
+///
+/// at 25 MPa, the region 3 enthalpy at 623.15 K is picked as
+/// region 1 by the backward equations, so the refined point
+/// should be a region 1 point just below 623.15 K
+#[test]
+pub fn ph_flash_refined_region_1_3_boundary(){
+
+    let h_tolerance = AvailableEnergy::new::<joule_per_kilogram>(1.0e-3);
+    let t = ThermodynamicTemperature::new::<kelvin>(623.15);
+
+    for p_mpa in [25.0, 40.0, 80.0] {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let h = h_tp_1(t, p);
+
+        assert_eq!(ph_flash_region(p, h), FwdEqnRegion::Region1);
+        assert_relative_eq!(
+            t_ph_eqm_refined(p, h, h_tolerance).get::<kelvin>(),
+            623.15,
+            max_relative=1e-9);
+    }
+
+    let p = Pressure::new::<megapascal>(25.0);
+    let h = h_rho_t_3(v_tp_3(t, p).recip(), t);
+    assert_eq!(ph_flash_region(p, h), FwdEqnRegion::Region1);
+
+    let t_refined = t_ph_eqm_refined(p, h, h_tolerance);
+    assert!(t_refined < t);
+    assert_eq!(region_fwd_eqn_single_phase(t_refined, p), FwdEqnRegion::Region1);
+    assert!((h_tp_1(t_refined, p) - h).abs() < h_tolerance);
+}
+
+/// on the 1073.15 K isotherm above 30 MPa, the region 2 enthalpy 
+/// (or entropy) is below the region 5 one, so points in between 
+/// are picked as region 2, but the refined region 2 point lies 
+/// above 1073.15 K (region 5)
+///
+/// the backward equation estimate does not meet the tolerance 
+/// there, so the refined flash should return an error rather 
+/// than the unrefined estimate
+#[test]
+pub fn refined_flash_leaving_region_is_error(){
+    use crate::errors::SteamTableError;
+
+    let t = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let p = Pressure::new::<megapascal>(40.0);
+
+    let h_tolerance = AvailableEnergy::new::<joule_per_kilogram>(1.0e-3);
+    let h = 0.5 * (h_tp_2(t, p) + h_tp_5(t, p));
+
+    assert_eq!(ph_flash_region(p, h), FwdEqnRegion::Region2);
+    assert!(matches!(try_t_ph_eqm_refined(p, h, h_tolerance).unwrap_err(),
+        SteamTableError::FlashDidNotConverge { .. }));
+
+    let s_tolerance = SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(1.0e-6);
+    let s = 0.5 * (s_tp_2(t, p) + s_tp_5(t, p));
+
+    assert_eq!(ps_flash_region(p, s), FwdEqnRegion::Region2);
+    assert!(matches!(try_t_ps_eqm_refined(p, s, s_tolerance).unwrap_err(),
+        SteamTableError::FlashDidNotConverge { .. }));
+}
@@ -0,0 +1,265 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::velocity::meter_per_second;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::ps_flash_eqm::{h_ps_eqm, ps_flash_region, t_ps_eqm, try_t_ps_eqm, u_ps_eqm, v_ps_eqm, w_ps_eqm};
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+use crate::interfaces::functional_programming::ph_flash_eqm::{cp_ph_eqm, lambda_ph_eqm, mu_ph_eqm, ph_flash_region, s_ph_eqm, t_ph_eqm, try_t_ph_eqm, v_ph_eqm, w_ph_eqm};
+use crate::interfaces::functional_programming::pt_flash_eqm::mu_tp_eqm_single_phase;
+use crate::interfaces::functional_programming::hs_flash_eqm::{hs_flash_region, p_hs_eqm, t_hs_eqm, try_t_hs_eqm, v_hs_eqm, BackwdEqnSubRegion};
+use crate::thermal_conductivity::lambda_tp_eqm_single_phase;
+use uom::si::dynamic_viscosity::micropascal_second;
+use uom::si::thermal_conductivity::milliwatt_per_meter_kelvin;
+
+/// ps flash for region 5, using table 2.27 
+/// (set values from the region 5 verification tests) 
+/// [T (K), p (MPa), v (m3/kg), h (kJ/kg), u (kJ/kg), s (kJ/(kg K)), w (m/s)]
+fn region_5_verification_table() -> Vec<[f64; 7]> {
+    vec![
+        [1500.0, 0.5, 0.138455090e1, 0.521976855e4, 0.452749310e4, 0.965408875e1, 0.917068690e3],
+        [1500.0, 30.0, 0.230761299e-1, 0.516723514e4, 0.447495124e4, 0.772970133e1, 0.928548002e3],
+        [2000.0, 30.0, 0.311385219e-1, 0.657122604e4, 0.563707038e4, 0.853640523e1, 0.106736948e4],
+    ]
+}
+
+#[test]
+pub fn ps_flash_region_5_verification_table(){
+
+    for [t_ref_kelvin, p_mpa, v_ref, h_ref, u_ref, s_ref, w_ref] 
+        in region_5_verification_table() {
+
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(s_ref);
+
+        assert_eq!(ps_flash_region(p, s), FwdEqnRegion::Region5);
+
+        assert_relative_eq!(
+            t_ps_eqm(p, s).get::<kelvin>(),
+            t_ref_kelvin,
+            max_relative=1e-8
+            );
+        assert_relative_eq!(
+            v_ps_eqm(p, s).get::<cubic_meter_per_kilogram>(),
+            v_ref,
+            max_relative=1e-8
+            );
+        assert_relative_eq!(
+            h_ps_eqm(p, s).get::<kilojoule_per_kilogram>(),
+            h_ref,
+            max_relative=1e-8
+            );
+        assert_relative_eq!(
+            u_ps_eqm(p, s).get::<kilojoule_per_kilogram>(),
+            u_ref,
+            max_relative=1e-8
+            );
+        assert_relative_eq!(
+            w_ps_eqm(p, s).get::<meter_per_second>(),
+            w_ref,
+            max_relative=1e-8
+            );
+    }
+}
+
+/// the 1073.15 K isotherm belongs to region 5 (as in the (T,p) flash), 
+/// anything just below it is region 2
+#[test]
+pub fn ps_flash_region_2_to_5_boundary(){
+    use crate::interfaces::functional_programming::pt_flash_eqm::region_fwd_eqn_single_phase;
+    use crate::region_5_steam_at_800_plus_degc::s_tp_5;
+
+    let p = Pressure::new::<megapascal>(10.0);
+    let t_boundary = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let s_boundary = s_tp_5(t_boundary, p);
+
+    assert_eq!(region_fwd_eqn_single_phase(t_boundary, p), FwdEqnRegion::Region5);
+    assert_eq!(ps_flash_region(p, s_boundary), FwdEqnRegion::Region5);
+
+    let s_below = s_boundary 
+        - SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(1e-3);
+    assert_eq!(ps_flash_region(p, s_below), FwdEqnRegion::Region2);
+}
+
+/// region 5 only goes up to 50 MPa, so above that 
+/// the 1073.15 K isotherm is still the upper bound 
+#[test]
+pub fn ps_flash_above_1073_15_k_and_50_mpa_is_error(){
+    use crate::region_2_vapour::s_tp_2;
+
+    let p = Pressure::new::<megapascal>(60.0);
+    let t_boundary = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let s_above = s_tp_2(t_boundary, p)
+        + SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(1e-2);
+
+    let error = try_t_ps_eqm(p, s_above).unwrap_err();
+
+    assert!(matches!(error, SteamTableError::EntropyAboveBound { .. }));
+}
+
+/// ph flash for region 5, using the same verification table 
+#[test]
+pub fn ph_flash_region_5_verification_table(){
+
+    let cp_ref_table = [0.261609445e1, 0.272724317e1, 0.288569882e1];
+
+    for ([t_ref_kelvin, p_mpa, v_ref, h_ref, _u_ref, s_ref, w_ref], cp_ref)
+        in region_5_verification_table().into_iter().zip(cp_ref_table) {
+
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let h = AvailableEnergy::new::<kilojoule_per_kilogram>(h_ref);
+
+        assert_eq!(ph_flash_region(p, h), FwdEqnRegion::Region5);
+
+        assert_relative_eq!(
+            t_ph_eqm(p, h).get::<kelvin>(),
+            t_ref_kelvin,
+            max_relative=1e-8
+            );
+        assert_relative_eq!(
+            v_ph_eqm(p, h).get::<cubic_meter_per_kilogram>(),
+            v_ref,
+            max_relative=1e-8
+            );
+        assert_relative_eq!(
+            s_ph_eqm(p, h).get::<kilojoule_per_kilogram_kelvin>(),
+            s_ref,
+            max_relative=1e-8
+            );
+        assert_relative_eq!(
+            cp_ph_eqm(p, h).get::<kilojoule_per_kilogram_kelvin>(),
+            cp_ref,
+            max_relative=1e-8
+            );
+        assert_relative_eq!(
+            w_ph_eqm(p, h).get::<meter_per_second>(),
+            w_ref,
+            max_relative=1e-8
+            );
+    }
+}
+
+/// transport properties in region 5 via ph flash should 
+/// match those from the pt flash
+#[test]
+pub fn ph_flash_region_5_transport_properties(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(1500.0);
+    let p = Pressure::new::<megapascal>(30.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(0.516723514e4);
+
+    assert_relative_eq!(
+        mu_ph_eqm(p, h).get::<micropascal_second>(),
+        mu_tp_eqm_single_phase(t, p).get::<micropascal_second>(),
+        max_relative=1e-6
+        );
+    assert_relative_eq!(
+        lambda_ph_eqm(p, h).get::<milliwatt_per_meter_kelvin>(),
+        lambda_tp_eqm_single_phase(t, p).get::<milliwatt_per_meter_kelvin>(),
+        max_relative=1e-6
+        );
+}
+
+/// the 1073.15 K isotherm belongs to region 5 (as in the (T,p) flash), 
+/// anything just below it is region 2
+#[test]
+pub fn ph_flash_region_2_to_5_boundary(){
+    use crate::region_5_steam_at_800_plus_degc::h_tp_5;
+
+    let p = Pressure::new::<megapascal>(10.0);
+    let t_boundary = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let h_boundary = h_tp_5(t_boundary, p);
+
+    assert_eq!(ph_flash_region(p, h_boundary), FwdEqnRegion::Region5);
+
+    let h_below = h_boundary 
+        - AvailableEnergy::new::<kilojoule_per_kilogram>(1.0);
+    assert_eq!(ph_flash_region(p, h_below), FwdEqnRegion::Region2);
+}
+
+/// region 5 only goes up to 50 MPa, so above that 
+/// the 1073.15 K isotherm is still the upper bound 
+#[test]
+pub fn ph_flash_above_1073_15_k_and_50_mpa_is_error(){
+    use crate::region_2_vapour::h_tp_2;
+
+    let p = Pressure::new::<megapascal>(60.0);
+    let t_boundary = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let h_above = h_tp_2(t_boundary, p)
+        + AvailableEnergy::new::<kilojoule_per_kilogram>(10.0);
+
+    let error = try_t_ph_eqm(p, h_above).unwrap_err();
+
+    assert!(matches!(error, SteamTableError::EnthalpyAboveBound { .. }));
+}
+
+/// hs flash for region 5, using table 2.27 
+/// (set values from the region 5 verification tests) 
+#[test]
+pub fn hs_flash_region_5_verification_table(){
+
+    for [t_ref_kelvin, p_mpa, v_ref, h_ref, _u_ref, s_ref, _w_ref] 
+        in region_5_verification_table() {
+
+        let h = AvailableEnergy::new::<kilojoule_per_kilogram>(h_ref);
+        let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(s_ref);
+
+        assert_eq!(hs_flash_region(h, s), BackwdEqnSubRegion::Region5);
+
+        assert_relative_eq!(
+            t_hs_eqm(h, s).get::<kelvin>(),
+            t_ref_kelvin,
+            max_relative=1e-8
+            );
+        assert_relative_eq!(
+            p_hs_eqm(h, s).get::<megapascal>(),
+            p_mpa,
+            max_relative=1e-7
+            );
+        assert_relative_eq!(
+            v_hs_eqm(h, s).get::<cubic_meter_per_kilogram>(),
+            v_ref,
+            max_relative=1e-7
+            );
+    }
+}
+
+/// points just above the 1073.15 K isotherm in the h-s diagram 
+/// should be flashed into region 5, 
+/// and points just below it should not
+#[test]
+pub fn hs_flash_region_2_to_5_boundary(){
+    use crate::region_5_steam_at_800_plus_degc::{h_tp_5, s_tp_5};
+
+    let t_boundary = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let p = Pressure::new::<megapascal>(10.0);
+    let h_boundary = h_tp_5(t_boundary, p);
+    let s_boundary = s_tp_5(t_boundary, p);
+
+    let h_above = h_boundary + AvailableEnergy::new::<kilojoule_per_kilogram>(1.0);
+    let h_below = h_boundary - AvailableEnergy::new::<kilojoule_per_kilogram>(1.0);
+
+    assert_eq!(hs_flash_region(h_above, s_boundary), BackwdEqnSubRegion::Region5);
+    assert_ne!(hs_flash_region(h_below, s_boundary), BackwdEqnSubRegion::Region5);
+}
+
+/// region 5 only goes up to 2273.15 K, so (h,s) points 
+/// above that isotherm should return an error
+#[test]
+pub fn hs_flash_above_2273_15_k_is_error(){
+    use crate::region_5_steam_at_800_plus_degc::{h_tp_5, s_tp_5};
+
+    let t = ThermodynamicTemperature::new::<kelvin>(2400.0);
+    let p = Pressure::new::<megapascal>(10.0);
+    let h = h_tp_5(t, p);
+    let s = s_tp_5(t, p);
+
+    let error = try_t_hs_eqm(h, s).unwrap_err();
+
+    assert!(matches!(error, SteamTableError::TemperatureAboveBound { .. }));
+}
@@ -0,0 +1,291 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::dynamic_viscosity::micropascal_second;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::radiant_exposure::joule_per_square_meter;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::thermal_conductivity::milliwatt_per_meter_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::velocity::meter_per_second;
+
+use crate::constants::t_crit_water;
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::saturation_eqm::*;
+use crate::interfaces::functional_programming::trho_flash_eqm::{h_trho_eqm, s_trho_eqm, x_trho_flash};
+use crate::region_1_subcooled_liquid::{h_tp_1, s_tp_1, u_tp_1, v_tp_1};
+use crate::region_2_vapour::{h_tp_2, s_tp_2, u_tp_2, v_tp_2};
+use crate::region_4_vap_liq_equilibrium::sat_pressure_4;
+use crate::thermal_conductivity::lambda_tp_eqm_single_phase;
+
+/// below 623.15 K, the saturated liquid and vapour properties
+/// should be the same as those of regions 1 and 2 at
+/// saturation pressure
+#[test]
+pub fn saturation_state_regions_1_and_2(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(450.0);
+    let p_sat = sat_pressure_4(t);
+    let sat_state = SaturationState::new_from_t(t);
+
+    assert_relative_eq!(
+        sat_state.p_sat.get::<megapascal>(),
+        p_sat.get::<megapascal>(),
+        max_relative=1e-12);
+
+    assert_relative_eq!(
+        sat_state.liquid.h.get::<kilojoule_per_kilogram>(),
+        h_tp_1(t, p_sat).get::<kilojoule_per_kilogram>(),
+        max_relative=1e-12);
+    assert_relative_eq!(
+        sat_state.vapour.h.get::<kilojoule_per_kilogram>(),
+        h_tp_2(t, p_sat).get::<kilojoule_per_kilogram>(),
+        max_relative=1e-12);
+    assert_relative_eq!(
+        sat_state.liquid.s.get::<kilojoule_per_kilogram_kelvin>(),
+        s_tp_1(t, p_sat).get::<kilojoule_per_kilogram_kelvin>(),
+        max_relative=1e-12);
+    assert_relative_eq!(
+        sat_state.vapour.s.get::<kilojoule_per_kilogram_kelvin>(),
+        s_tp_2(t, p_sat).get::<kilojoule_per_kilogram_kelvin>(),
+        max_relative=1e-12);
+    assert_relative_eq!(
+        sat_state.liquid.v.get::<cubic_meter_per_kilogram>(),
+        v_tp_1(t, p_sat).get::<cubic_meter_per_kilogram>(),
+        max_relative=1e-12);
+    assert_relative_eq!(
+        sat_state.vapour.v.get::<cubic_meter_per_kilogram>(),
+        v_tp_2(t, p_sat).get::<cubic_meter_per_kilogram>(),
+        max_relative=1e-12);
+    assert_relative_eq!(
+        sat_state.liquid.u.get::<kilojoule_per_kilogram>(),
+        u_tp_1(t, p_sat).get::<kilojoule_per_kilogram>(),
+        max_relative=1e-12);
+    assert_relative_eq!(
+        sat_state.vapour.u.get::<kilojoule_per_kilogram>(),
+        u_tp_2(t, p_sat).get::<kilojoule_per_kilogram>(),
+        max_relative=1e-12);
+}
+
+/// saturated properties at 1 MPa,
+/// compared against published IAPWS-IF97 steam tables
+/// (to the precision given in the tables)
+#[test]
+pub fn saturation_state_at_1_mpa(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+    let sat_state = SaturationState::new_from_p(p);
+
+    assert_relative_eq!(sat_state.t_sat.get::<kelvin>(), 453.0356, max_relative=1e-6);
+    assert_relative_eq!(
+        sat_state.liquid.h.get::<kilojoule_per_kilogram>(), 762.683,
+        max_relative=1e-5);
+    assert_relative_eq!(
+        sat_state.vapour.h.get::<kilojoule_per_kilogram>(), 2777.12,
+        max_relative=1e-5);
+    assert_relative_eq!(
+        sat_state.liquid.v.get::<cubic_meter_per_kilogram>(), 0.00112723,
+        max_relative=1e-5);
+    assert_relative_eq!(
+        sat_state.vapour.v.get::<cubic_meter_per_kilogram>(), 0.194362,
+        max_relative=1e-4);
+    assert_relative_eq!(
+        sat_state.latent_heat().get::<kilojoule_per_kilogram>(), 2014.44,
+        max_relative=1e-5);
+}
+
+/// saturated transport properties and surface tension at
+/// 373.15 K compared against IAPWS reference values
+/// (to within 1%)
+#[test]
+pub fn saturation_state_transport_properties_at_373_15_k(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(373.15);
+    let sat_state = SaturationState::new_from_t(t);
+
+    assert_relative_eq!(
+        sat_state.liquid.mu.get::<micropascal_second>(), 281.8,
+        max_relative=1e-2);
+    assert_relative_eq!(
+        sat_state.vapour.mu.get::<micropascal_second>(), 12.27,
+        max_relative=1e-2);
+    assert_relative_eq!(
+        sat_state.liquid.lambda.get::<milliwatt_per_meter_kelvin>(), 679.1,
+        max_relative=1e-2);
+
+    // saturated vapour thermal conductivity should be close to
+    // that of vapour just below saturation pressure
+    let p_vap = sat_state.p_sat * 0.99999;
+    assert_relative_eq!(
+        sat_state.vapour.lambda.get::<milliwatt_per_meter_kelvin>(),
+        lambda_tp_eqm_single_phase(t, p_vap).get::<milliwatt_per_meter_kelvin>(),
+        max_relative=1e-4);
+    assert_relative_eq!(
+        sat_state.liquid.cp.get::<kilojoule_per_kilogram_kelvin>(), 4.2157,
+        max_relative=1e-2);
+    assert_relative_eq!(
+        sat_state.liquid.w.get::<meter_per_second>(), 1543.0,
+        max_relative=1e-2);
+    assert_relative_eq!(
+        sat_state.surface_tension.get::<joule_per_square_meter>(), 58.91e-3,
+        max_relative=1e-2);
+}
+
+/// near the critical point, the saturated liquid and vapour
+/// densities come from the region 3 backward equations,
+/// the mixture properties should be consistent with the
+/// (T,rho) flash within the vapour dome
+#[test]
+pub fn saturation_state_mixture_near_critical_point(){
+
+    for t_kelvin in [630.0, 640.0, 645.0, 646.9] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let sat_state = SaturationState::new_from_t(t);
+
+        assert!(sat_state.liquid.v < sat_state.vapour.v);
+        assert!(sat_state.latent_heat().value > 0.0);
+
+        let quality = 0.4;
+        let rho_mix = sat_state.rho_mixture(quality);
+
+        assert_relative_eq!(x_trho_flash(t, rho_mix), quality, max_relative=1e-9);
+        assert_relative_eq!(
+            h_trho_eqm(t, rho_mix).get::<kilojoule_per_kilogram>(),
+            sat_state.h_mixture(quality).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-9);
+        assert_relative_eq!(
+            s_trho_eqm(t, rho_mix).get::<kilojoule_per_kilogram_kelvin>(),
+            sat_state.s_mixture(quality).get::<kilojoule_per_kilogram_kelvin>(),
+            max_relative=1e-9);
+    }
+
+    // at the critical temperature itself, this should still work
+    let sat_state = SaturationState::new_from_t(t_crit_water());
+    assert!(sat_state.latent_heat().value.abs() < 1.0e5);
+}
+
+/// saturation states built from pressure and temperature
+/// should agree, and mixture properties at quality 0 and 1
+/// are the saturated liquid and vapour
+#[test]
+pub fn saturation_state_from_p_and_t_agree(){
+
+    let p = Pressure::new::<megapascal>(18.0);
+    let sat_state_p = SaturationState::new_from_p(p);
+    let sat_state_t = SaturationState::new_from_t(sat_state_p.t_sat);
+
+    assert_relative_eq!(
+        sat_state_t.p_sat.get::<megapascal>(), 18.0,
+        max_relative=1e-9);
+    assert_relative_eq!(
+        sat_state_t.vapour.h.get::<kilojoule_per_kilogram>(),
+        sat_state_p.vapour.h.get::<kilojoule_per_kilogram>(),
+        max_relative=1e-9);
+
+    assert_eq!(sat_state_p.h_mixture(0.0), sat_state_p.liquid.h);
+    assert_eq!(sat_state_p.h_mixture(1.0), sat_state_p.vapour.h);
+    assert_eq!(sat_state_p.u_mixture(1.5), sat_state_p.vapour.u);
+}
+
+/// saturation states outside the triple point to critical point
+/// range should return errors
+#[test]
+pub fn saturation_state_out_of_bounds_is_error(){
+
+    let t_too_high = ThermodynamicTemperature::new::<kelvin>(650.0);
+    assert!(matches!(SaturationState::try_new_from_t(t_too_high).unwrap_err(),
+        SteamTableError::TemperatureAboveBound { .. }));
+
+    let t_too_low = ThermodynamicTemperature::new::<kelvin>(270.0);
+    assert!(matches!(SaturationState::try_new_from_t(t_too_low).unwrap_err(),
+        SteamTableError::TemperatureBelowBound { .. }));
+
+    let p_too_high = Pressure::new::<megapascal>(25.0);
+    assert!(matches!(SaturationState::try_new_from_p(p_too_high).unwrap_err(),
+        SteamTableError::PressureAboveBound { .. }));
+}
+
+/// the slopes of the saturated liquid and vapour properties 
+/// against central differences of the saturation state
+/// with respect to saturation pressure
+///
+/// above 623.15 K (16.529 MPa), the saturated densities come from the 
+/// region 3 v(T,p) backward equations, which are only consistent 
+/// with the forward equations to about 1e-4, so the central 
+/// differences (which follow the backward equations) 
+/// differ from the forward equation slopes by about 5e-4
+#[test]
+pub fn saturation_derivatives_against_central_difference(){
+
+    for (p_mpa, tolerance) in [(0.001, 1e-7), (0.1, 1e-7), (1.0, 1e-7), 
+        (5.0, 1e-7), (10.0, 1e-7), (15.0, 1e-7), (18.0, 1e-3), (21.0, 1e-3)] {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let dp = Pressure::new::<megapascal>(1.0e-5 * p_mpa);
+
+        let plus = SaturationState::new_from_p(p + dp);
+        let minus = SaturationState::new_from_p(p - dp);
+        let derivatives = SaturationDerivatives::new_from_p(p);
+
+        let central_difference = |plus: f64, minus: f64| {
+            (plus - minus)/(2.0 * dp.value)
+        };
+
+        assert_relative_eq!(derivatives.dh_liq_dp.value,
+            central_difference(plus.liquid.h.value, minus.liquid.h.value),
+            max_relative = tolerance);
+        assert_relative_eq!(derivatives.dh_vap_dp.value,
+            central_difference(plus.vapour.h.value, minus.vapour.h.value),
+            max_relative = tolerance);
+        assert_relative_eq!(derivatives.dv_liq_dp.value,
+            central_difference(plus.liquid.v.value, minus.liquid.v.value),
+            max_relative = tolerance);
+        assert_relative_eq!(derivatives.dv_vap_dp.value,
+            central_difference(plus.vapour.v.value, minus.vapour.v.value),
+            max_relative = tolerance);
+        assert_relative_eq!(derivatives.ds_liq_dp.value,
+            central_difference(plus.liquid.s.value, minus.liquid.s.value),
+            max_relative = tolerance);
+        assert_relative_eq!(derivatives.ds_vap_dp.value,
+            central_difference(plus.vapour.s.value, minus.vapour.s.value),
+            max_relative = tolerance);
+        assert_relative_eq!(derivatives.d_latent_heat_dp().value,
+            central_difference(plus.latent_heat().value, minus.latent_heat().value),
+            max_relative = tolerance);
+        assert_relative_eq!(derivatives.dt_sat_dp.value * derivatives.dp_sat_dt.value,
+            1.0, max_relative = 1e-12);
+    }
+}
+
+/// the Clapeyron equation, dp/dT = (s''-s')/(v''-v'),
+/// is consistent with the region 4 saturation equation,
+/// and the derivatives are available up to the critical point
+#[test]
+pub fn saturation_derivatives_clapeyron_and_critical_point(){
+
+    for t_kelvin in [300.0, 400.0, 500.0, 600.0, 640.0, 646.0] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let sat_state = SaturationState::new_from_t(t);
+        let derivatives = SaturationDerivatives::new_from_t(t);
+
+        let clapeyron = (sat_state.vapour.s - sat_state.liquid.s).value
+            /(sat_state.vapour.v - sat_state.liquid.v).value;
+
+        assert_relative_eq!(derivatives.dp_sat_dt.value, clapeyron, 
+            max_relative = 5e-3);
+    }
+
+    let derivatives = SaturationDerivatives::new_from_t(t_crit_water());
+    assert!(derivatives.dp_sat_dt.value.is_finite());
+    assert!(derivatives.dh_liq_dp.value.is_finite());
+    assert!(derivatives.dh_vap_dp.value.is_finite());
+
+    // the enthalpy of the saturated liquid rises with pressure, 
+    // and that of the saturated vapour falls at high pressure
+    let derivatives = SaturationDerivatives::new_from_p(Pressure::new::<megapascal>(20.0));
+    assert!(derivatives.dh_liq_dp.value > 0.0);
+    assert!(derivatives.dh_vap_dp.value < 0.0);
+
+    assert!(matches!(SaturationDerivatives::try_new_from_p(Pressure::new::<megapascal>(23.0)),
+        Err(SteamTableError::PressureAboveBound { .. })));
+}
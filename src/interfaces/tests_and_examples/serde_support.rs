@@ -0,0 +1,82 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::volume::cubic_meter;
+
+use crate::interfaces::functional_programming::hs_flash_eqm::BackwdEqnSubRegion;
+use crate::interfaces::functional_programming::ph_flash_eqm;
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+use crate::interfaces::functional_programming::saturation_eqm::SaturationState;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+use crate::interfaces::object_oriented_programming::{StateSpec, TampinesSteamTableCV};
+
+/// the control volume should be the same after a round trip,
+/// and the derived properties recomputed from the deserialised
+/// control volume should be the same as the original
+#[test]
+pub fn control_volume_serde_round_trip(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1500.0);
+    let volume = Volume::new::<cubic_meter>(2.0);
+
+    let cv = TampinesSteamTableCV::new(StateSpec::Ph { p, h }, volume);
+
+    let json = serde_json::to_string(&cv).unwrap();
+    let restarted_cv: TampinesSteamTableCV = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restarted_cv, cv);
+    assert_relative_eq!(restarted_cv.get_quality(), cv.get_quality());
+    assert_relative_eq!(restarted_cv.get_mass().value, cv.get_mass().value);
+    assert_relative_eq!(restarted_cv.get_dynamic_viscosity().value, 
+        cv.get_dynamic_viscosity().value);
+
+    // quantities are serialised in SI base units (Pa and J/kg),
+    // and the derived properties and provider are not serialised
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_relative_eq!(value["pressure"].as_f64().unwrap(), 1.0e6);
+    assert_relative_eq!(value["specific_enthalpy"].as_f64().unwrap(), 1.5e6);
+    assert_relative_eq!(value["volume"].as_f64().unwrap(), 2.0);
+    assert!(value.get("state").is_none());
+    assert!(value.get("provider").is_none());
+}
+
+/// region enums are serialised by variant name
+#[test]
+pub fn region_enums_serde_round_trip(){
+
+    let region = FwdEqnRegion::Region4;
+    let json = serde_json::to_string(&region).unwrap();
+    assert_eq!(json, "\"Region4\"");
+    assert_eq!(serde_json::from_str::<FwdEqnRegion>(&json).unwrap(), region);
+
+    let subregion = BackwdEqnSubRegion::Region2b;
+    let json = serde_json::to_string(&subregion).unwrap();
+    assert_eq!(json, "\"Region2b\"");
+    assert_eq!(serde_json::from_str::<BackwdEqnSubRegion>(&json).unwrap(), subregion);
+}
+
+/// state types (for sending over IPC) should be the same 
+/// after a round trip
+#[test]
+pub fn state_types_serde_round_trip(){
+
+    let p = Pressure::new::<megapascal>(5.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(3500.0);
+    let t = ThermodynamicTemperature::new::<kelvin>(500.0);
+
+    let steam_state = ph_flash_eqm::flash(p, h);
+    let json = serde_json::to_string(&steam_state).unwrap();
+    assert_eq!(serde_json::from_str::<SteamState>(&json).unwrap(), steam_state);
+
+    let saturation_state = SaturationState::new_from_t(t);
+    let json = serde_json::to_string(&saturation_state).unwrap();
+    assert_eq!(serde_json::from_str::<SaturationState>(&json).unwrap(), 
+        saturation_state);
+
+    let spec = StateSpec::Tx { t, x: 0.3 };
+    let json = serde_json::to_string(&spec).unwrap();
+    assert_eq!(serde_json::from_str::<StateSpec>(&json).unwrap(), spec);
+}
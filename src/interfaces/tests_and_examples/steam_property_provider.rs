@@ -0,0 +1,153 @@
+use std::cell::Cell;
+
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::energy::megajoule;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::volume::cubic_meter;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::saturation_eqm::SaturationState;
+use crate::interfaces::functional_programming::steam_state::SteamState;
+use crate::interfaces::functional_programming::{ph_flash_eqm, pt_flash_eqm};
+use crate::interfaces::object_oriented_programming::{StateSpec, TampinesSteamTableCV};
+use crate::interfaces::steam_property_provider::{If97, SteamPropertyProvider};
+
+/// a mocked provider which uses IF97, but doubles the viscosity,
+/// counts the number of (u,v) flashes and, like a backend which
+/// does not use the IF97 regions, leaves the region out
+#[derive(Debug, Default)]
+struct DoubleViscosityProvider {
+    uv_flashes: Cell<usize>,
+}
+
+impl DoubleViscosityProvider {
+    fn double_viscosity(state: Result<SteamState, SteamTableError>)
+        -> Result<SteamState, SteamTableError> {
+        let mut state = state?;
+        state.mu *= 2.0;
+        state.region = None;
+        return Ok(state);
+    }
+}
+
+impl SteamPropertyProvider for DoubleViscosityProvider {
+
+    fn try_flash_pt(&self, t: ThermodynamicTemperature, p: Pressure)
+        -> Result<SteamState, SteamTableError> {
+        return Self::double_viscosity(If97.try_flash_pt(t, p));
+    }
+
+    fn try_flash_ph(&self, p: Pressure, h: AvailableEnergy)
+        -> Result<SteamState, SteamTableError> {
+        return Self::double_viscosity(If97.try_flash_ph(p, h));
+    }
+
+    fn try_flash_ps(&self, p: Pressure, s: SpecificHeatCapacity)
+        -> Result<SteamState, SteamTableError> {
+        return Self::double_viscosity(If97.try_flash_ps(p, s));
+    }
+
+    fn try_flash_hs(&self, h: AvailableEnergy, s: SpecificHeatCapacity)
+        -> Result<SteamState, SteamTableError> {
+        return Self::double_viscosity(If97.try_flash_hs(h, s));
+    }
+
+    fn try_flash_pv(&self, p: Pressure, v: SpecificVolume)
+        -> Result<SteamState, SteamTableError> {
+        return Self::double_viscosity(If97.try_flash_pv(p, v));
+    }
+
+    fn try_flash_uv(&self, u: AvailableEnergy, v: SpecificVolume)
+        -> Result<SteamState, SteamTableError> {
+        self.uv_flashes.set(self.uv_flashes.get() + 1);
+        return Self::double_viscosity(If97.try_flash_uv(u, v));
+    }
+
+    fn try_flash_trho(&self, t: ThermodynamicTemperature, rho: MassDensity)
+        -> Result<SteamState, SteamTableError> {
+        return Self::double_viscosity(If97.try_flash_trho(t, rho));
+    }
+
+    fn try_saturation_at_p(&self, p: Pressure)
+        -> Result<SaturationState, SteamTableError> {
+        return If97.try_saturation_at_p(p);
+    }
+
+    fn try_saturation_at_t(&self, t: ThermodynamicTemperature)
+        -> Result<SaturationState, SteamTableError> {
+        return If97.try_saturation_at_t(t);
+    }
+}
+
+/// the If97 provider is the same as the functional programming flashes
+#[test]
+pub fn if97_provider_matches_flash_functions(){
+
+    let p = Pressure::new::<megapascal>(5.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(3500.0);
+    let t = ThermodynamicTemperature::new::<kelvin>(500.0);
+
+    assert_eq!(If97.flash_ph(p, h), ph_flash_eqm::flash(p, h));
+    assert_eq!(If97.flash_pt(t, p), pt_flash_eqm::flash(t, p));
+    assert_eq!(If97.try_mu_ph(p, h).unwrap(), ph_flash_eqm::mu_ph_eqm(p, h));
+    assert_eq!(If97.try_lambda_ph(p, h).unwrap(), ph_flash_eqm::lambda_ph_eqm(p, h));
+    assert_eq!(If97.try_mu_pt(t, p).unwrap(), pt_flash_eqm::flash(t, p).mu);
+    assert_eq!(If97.try_lambda_pt(t, p).unwrap(), pt_flash_eqm::flash(t, p).lambda);
+    assert_eq!(If97.saturation_at_p(p), SaturationState::new_from_p(p));
+    assert_eq!(If97.saturation_at_t(t), SaturationState::new_from_t(t));
+
+    let s = If97.flash_ph(p, h).s;
+    assert_eq!(If97.flash_ps(p, s), crate::interfaces::functional_programming::ps_flash_eqm::flash(p, s));
+
+    // out of range points are errors
+    let p = Pressure::new::<megapascal>(120.0);
+    assert!(matches!(If97.try_flash_ph(p, h),
+        Err(SteamTableError::PressureAboveBound { .. })));
+}
+
+/// the control volume uses the provider it was created with
+/// for the getters and the mass and energy balances
+#[test]
+pub fn control_volume_with_mocked_provider(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1500.0);
+    let volume = Volume::new::<cubic_meter>(1.0);
+    let spec = StateSpec::Ph { p, h };
+
+    let if97_cv = TampinesSteamTableCV::new(spec, volume);
+    let mut mocked_cv = TampinesSteamTableCV::new_with_provider(
+        DoubleViscosityProvider::default(), spec, volume);
+
+    assert_eq!(mocked_cv.get_temperature(), if97_cv.get_temperature());
+    assert_relative_eq!(mocked_cv.get_dynamic_viscosity().value,
+        2.0 * if97_cv.get_dynamic_viscosity().value);
+    assert_eq!(mocked_cv.provider_uv_flashes(), 0);
+    assert_eq!(mocked_cv.get_region(), None);
+    assert!(if97_cv.get_region().is_some());
+
+    let heat = Energy::new::<megajoule>(1.0);
+    mocked_cv.add_heat(heat);
+    let mut heated_if97_cv = if97_cv;
+    heated_if97_cv.add_heat(heat);
+
+    assert_eq!(mocked_cv.provider_uv_flashes(), 1);
+    assert_eq!(mocked_cv.get_pressure(), heated_if97_cv.get_pressure());
+    assert_relative_eq!(mocked_cv.get_dynamic_viscosity().value,
+        2.0 * heated_if97_cv.get_dynamic_viscosity().value);
+
+    mocked_cv.set_state(StateSpec::Uv {
+        u: heated_if97_cv.get_internal_energy(),
+        v: heated_if97_cv.get_specific_volume(),
+    });
+    assert_eq!(mocked_cv.provider_uv_flashes(), 2);
+}
+
+impl TampinesSteamTableCV<DoubleViscosityProvider> {
+    fn provider_uv_flashes(&self) -> usize {
+        return self.get_provider().uv_flashes.get();
+    }
+}
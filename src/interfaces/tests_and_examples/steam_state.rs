@@ -0,0 +1,244 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::ratio::ratio;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::hs_flash_eqm;
+use crate::interfaces::functional_programming::ph_flash_eqm;
+use crate::interfaces::functional_programming::ph_flash_eqm::{alpha_v_ph_eqm, cp_ph_eqm, cv_ph_eqm, kappa_ph_eqm, kappa_t_ph_eqm, lambda_ph_eqm, mu_ph_eqm, s_ph_eqm, t_ph_eqm, u_ph_eqm, v_ph_eqm, w_ph_eqm, x_ph_flash};
+use crate::interfaces::functional_programming::ps_flash_eqm;
+use crate::interfaces::functional_programming::pt_flash_eqm;
+use crate::interfaces::functional_programming::pt_flash_eqm::{cp_tp_eqm_single_phase, h_tp_eqm_single_phase, s_tp_eqm_single_phase, v_tp_eqm_single_phase, FwdEqnRegion};
+use crate::surface_tension::water_surf_tension;
+use crate::thermal_conductivity::lambda_tp_eqm_single_phase;
+use crate::dynamic_viscosity::mu_tp_eqm_single_phase;
+
+/// in regions 1, 2 and 5, the (p,h) flash should give the same
+/// properties as the individual *_ph_eqm functions
+#[test]
+pub fn steam_state_ph_flash_matches_individual_functions_single_phase(){
+
+    let cases = [
+        (3.0, 500.0, FwdEqnRegion::Region1),
+        (0.001, 3000.0, FwdEqnRegion::Region2),
+        (5.0, 3500.0, FwdEqnRegion::Region2),
+        (10.0, 2800.0, FwdEqnRegion::Region2),
+        (30.0, 5000.0, FwdEqnRegion::Region5),
+    ];
+
+    for (p_mpa, h_kj_per_kg, region) in cases {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let h = AvailableEnergy::new::<kilojoule_per_kilogram>(h_kj_per_kg);
+
+        let state = ph_flash_eqm::flash(p, h);
+
+        assert_eq!(state.region, Some(region));
+        assert_eq!(state.p, p);
+        assert_eq!(state.h, h);
+        assert_eq!(state.x, x_ph_flash(p, h));
+
+        let tol = 1e-9;
+        assert_relative_eq!(state.t.value, t_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.v.value, v_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.u.value, u_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.s.value, s_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.cp.value, cp_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.cv.value, cv_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.w.value, w_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.kappa.value, kappa_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.alpha_v.value, alpha_v_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.kappa_t.value, kappa_t_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.mu.value, mu_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.lambda.value, lambda_ph_eqm(p, h).value, max_relative = tol);
+    }
+}
+
+/// region 3 (p,h) flash, properties come from the (T,rho)
+/// forward equations at the backward equation temperature
+/// and density
+#[test]
+pub fn steam_state_ph_flash_region_3(){
+
+    let cases = [
+        (25.0, 1800.0, 1.0),
+        (25.0, 2400.0, 1.0),
+        (20.0, 1700.0, 0.0),
+    ];
+
+    for (p_mpa, h_kj_per_kg, x) in cases {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let h = AvailableEnergy::new::<kilojoule_per_kilogram>(h_kj_per_kg);
+
+        let state = ph_flash_eqm::flash(p, h);
+
+        assert_eq!(state.region, Some(FwdEqnRegion::Region3));
+        assert_eq!(state.x, x);
+
+        let tol = 1e-9;
+        assert_relative_eq!(state.t.value, t_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.v.value, v_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.mu.value, mu_ph_eqm(p, h).value, max_relative = tol);
+
+        // the backward equations are consistent with the
+        // forward equations to within a fraction of a percent
+        let tol = 1e-3;
+        assert_relative_eq!(state.u.value, u_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.s.value, s_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.w.value, w_ph_eqm(p, h).value, max_relative = tol);
+        assert_relative_eq!(state.cp.value, cp_ph_eqm(p, h).value, max_relative = tol);
+    }
+}
+
+/// region 4 (p,h) flash below 623.15 K, where the saturated liquid
+/// and vapour are in regions 1 and 2
+#[test]
+pub fn steam_state_ph_flash_region_4(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1500.0);
+
+    let state = ph_flash_eqm::flash(p, h);
+
+    assert_eq!(state.region, Some(FwdEqnRegion::Region4));
+    assert_eq!(state.h, h);
+
+    let tol = 1e-7;
+    assert_relative_eq!(state.t.value, t_ph_eqm(p, h).value, max_relative = tol);
+    assert_relative_eq!(state.x, x_ph_flash(p, h), max_relative = tol);
+    assert_relative_eq!(state.v.value, v_ph_eqm(p, h).value, max_relative = tol);
+    assert_relative_eq!(state.u.value, u_ph_eqm(p, h).value, max_relative = tol);
+    assert_relative_eq!(state.s.value, s_ph_eqm(p, h).value, max_relative = tol);
+    assert_relative_eq!(state.mu.value, mu_ph_eqm(p, h).value, max_relative = tol);
+
+    // the quality is strictly between saturated liquid and vapour
+    assert!(state.x > 0.0 && state.x < 1.0);
+    assert!(state.sigma.is_some());
+}
+
+/// the (p,s) and (h,s) flashes should give the same state as the
+/// (p,h) flash at the same point
+#[test]
+pub fn steam_state_ps_and_hs_flash_match_ph_flash(){
+
+    let cases = [
+        (3.0, 500.0),
+        (5.0, 3500.0),
+        (25.0, 1800.0),
+        (1.0, 1500.0),
+        (30.0, 5000.0),
+    ];
+
+    for (p_mpa, h_kj_per_kg) in cases {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let h = AvailableEnergy::new::<kilojoule_per_kilogram>(h_kj_per_kg);
+
+        let ph_state = ph_flash_eqm::flash(p, h);
+        let ps_state = ps_flash_eqm::flash(p, ph_state.s);
+        let hs_state = hs_flash_eqm::flash(h, ph_state.s);
+
+        assert_eq!(ps_state.region, ph_state.region);
+        assert_eq!(hs_state.region, ph_state.region);
+        assert_eq!(ps_state.s, ph_state.s);
+        assert_eq!(hs_state.h, h);
+
+        // the backward equations are only consistent with
+        // each other to within the backward equation tolerances
+        //
+        // (cp is very sensitive to temperature near the critical point,
+        // so it has a looser tolerance)
+        //
+        // pressure is not compared since it is very sensitive
+        // to (h,s) in the compressed liquid
+        let tol = 1e-3;
+        for state in [ps_state, hs_state] {
+            assert_relative_eq!(state.t.value, ph_state.t.value, max_relative = tol);
+            assert_relative_eq!(state.v.value, ph_state.v.value, max_relative = tol);
+            assert_relative_eq!(state.cp.value, ph_state.cp.value, max_relative = 1e-2);
+            assert_relative_eq!(state.mu.value, ph_state.mu.value, max_relative = tol);
+            assert_relative_eq!(state.lambda.value, ph_state.lambda.value, max_relative = tol);
+        }
+    }
+}
+
+/// single phase (T,p) flash matches the individual (T,p) functions
+#[test]
+pub fn steam_state_tp_flash_single_phase(){
+
+    let cases = [
+        (300.0, 3.0, FwdEqnRegion::Region1, 0.0),
+        (700.0, 10.0, FwdEqnRegion::Region2, 1.0),
+        (630.0, 20.0, FwdEqnRegion::Region3, 0.0),
+        (750.0, 50.0, FwdEqnRegion::Region3, 1.0),
+        (1500.0, 0.5, FwdEqnRegion::Region5, 1.0),
+    ];
+
+    for (t_kelvin, p_mpa, region, x) in cases {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+
+        let state = pt_flash_eqm::flash(t, p);
+
+        assert_eq!(state.region, Some(region));
+        assert_eq!(state.x, x);
+        assert_eq!(state.t, t);
+        assert_eq!(state.p, p);
+
+        let tol = 1e-9;
+        assert_relative_eq!(state.v.value, v_tp_eqm_single_phase(t, p).value, max_relative = tol);
+        assert_relative_eq!(state.h.value, h_tp_eqm_single_phase(t, p).value, max_relative = tol);
+        assert_relative_eq!(state.s.value, s_tp_eqm_single_phase(t, p).value, max_relative = tol);
+        assert_relative_eq!(state.cp.value, cp_tp_eqm_single_phase(t, p).value, max_relative = tol);
+        assert_relative_eq!(state.mu.value, mu_tp_eqm_single_phase(t, p).value, max_relative = tol);
+        assert_relative_eq!(state.lambda.value, lambda_tp_eqm_single_phase(t, p).value, max_relative = tol);
+    }
+}
+
+/// the (T,p) flash needs steam quality on the saturation line,
+/// and the flashes return errors outside the validity range
+#[test]
+pub fn steam_state_flash_errors(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(373.15);
+    let p_sat = crate::region_4_vap_liq_equilibrium::sat_pressure_4(t);
+
+    let error = pt_flash_eqm::try_flash(t, p_sat).unwrap_err();
+    assert_eq!(error, SteamTableError::RegionNotImplemented {
+        flash: "(T,p) single phase", region: FwdEqnRegion::Region4 });
+
+    let p = Pressure::new::<megapascal>(120.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1500.0);
+    let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(5.0);
+
+    assert!(ph_flash_eqm::try_flash(p, h).is_err());
+    assert!(ps_flash_eqm::try_flash(p, s).is_err());
+
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(-100.0);
+    assert!(hs_flash_eqm::try_flash(h, s).is_err());
+}
+
+/// surface tension is only given below the critical temperature,
+/// and the prandtl number is mu cp/lambda
+#[test]
+pub fn steam_state_surface_tension_and_prandtl_number(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(300.0);
+    let p = Pressure::new::<megapascal>(3.0);
+    let state = pt_flash_eqm::flash(t, p);
+
+    assert_relative_eq!(state.sigma.unwrap().value, water_surf_tension(t).value);
+    let prandtl: f64 = (state.mu * state.cp / state.lambda).get::<ratio>();
+    assert_relative_eq!(state.prandtl.get::<ratio>(), prandtl);
+    // liquid water at room temperature has a prandtl number of about 5.8
+    assert_relative_eq!(state.prandtl.get::<ratio>(), 5.8, max_relative = 0.05);
+
+    let t = ThermodynamicTemperature::new::<kelvin>(1500.0);
+    let p = Pressure::new::<megapascal>(0.5);
+    let state = pt_flash_eqm::flash(t, p);
+
+    assert!(state.sigma.is_none());
+    assert_relative_eq!(state.rho().value, state.v.value.recip());
+}
@@ -0,0 +1,272 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::dynamic_viscosity::micropascal_second;
+use uom::si::f64::*;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::thermal_conductivity::milliwatt_per_meter_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::velocity::meter_per_second;
+
+use crate::dynamic_viscosity::mu_tp_eqm_single_phase;
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+use crate::interfaces::functional_programming::trho_flash_eqm::*;
+use crate::region_1_subcooled_liquid::{cp_tp_1, cv_tp_1, h_tp_1, s_tp_1, u_tp_1, v_tp_1, w_tp_1};
+use crate::region_2_vapour::{h_tp_2, s_tp_2, v_tp_2};
+use crate::region_4_vap_liq_equilibrium::sat_pressure_4;
+use crate::region_5_steam_at_800_plus_degc::{h_tp_5, v_tp_5};
+use crate::thermal_conductivity::lambda_tp_eqm_single_phase;
+
+/// (T,rho) flash for region 3, using the region 3
+/// verification table (table 33 of IAPWS-IF97)
+/// [T (K), rho (kg/m3), p (MPa), h (kJ/kg), u (kJ/kg), s (kJ/(kg K)), cp (kJ/(kg K)), w (m/s)]
+#[test]
+pub fn trho_flash_region_3_verification_table(){
+
+    let table: Vec<[f64; 8]> = vec![
+        [650.0, 500.0, 0.255837018e2, 0.186343019e4, 0.181226279e4, 0.405427273e1, 0.138935717e2, 0.502005554e3],
+        [650.0, 200.0, 0.222930643e2, 0.237512401e4, 0.226365868e4, 0.485438792e1, 0.446579342e2, 0.383444594e3],
+        [750.0, 500.0, 0.783095639e2, 0.225868845e4, 0.210206932e4, 0.446971906e1, 0.634165359e1, 0.760696041e3],
+    ];
+
+    for [t_kelvin, rho_kg_per_m3, p_ref, h_ref, u_ref, s_ref, cp_ref, w_ref] in table {
+
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let rho = MassDensity::new::<kilogram_per_cubic_meter>(rho_kg_per_m3);
+
+        assert_eq!(trho_flash_region(t, rho), FwdEqnRegion::Region3);
+
+        assert_relative_eq!(p_trho_eqm(t, rho).get::<megapascal>(), p_ref, max_relative=1e-8);
+        assert_relative_eq!(h_trho_eqm(t, rho).get::<kilojoule_per_kilogram>(), h_ref, max_relative=1e-8);
+        assert_relative_eq!(u_trho_eqm(t, rho).get::<kilojoule_per_kilogram>(), u_ref, max_relative=1e-8);
+        assert_relative_eq!(s_trho_eqm(t, rho).get::<kilojoule_per_kilogram_kelvin>(), s_ref, max_relative=1e-8);
+        assert_relative_eq!(cp_trho_eqm(t, rho).get::<kilojoule_per_kilogram_kelvin>(), cp_ref, max_relative=1e-8);
+        assert_relative_eq!(w_trho_eqm(t, rho).get::<meter_per_second>(), w_ref, max_relative=1e-8);
+    }
+}
+
+/// (T,rho) flash in region 1 should return the pressure
+/// and properties of the original (T,p) point
+#[test]
+pub fn trho_flash_region_1_round_trip(){
+
+    for (t_kelvin, p_mpa) in [(300.0, 3.0), (300.0, 80.0), (500.0, 3.0), (300.0, 0.01)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let rho = v_tp_1(t, p).recip();
+
+        assert_eq!(trho_flash_region(t, rho), FwdEqnRegion::Region1);
+
+        assert_relative_eq!(p_trho_eqm(t, rho).get::<megapascal>(), p_mpa, max_relative=1e-7);
+        assert_relative_eq!(
+            h_trho_eqm(t, rho).get::<kilojoule_per_kilogram>(),
+            h_tp_1(t, p).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-9);
+        assert_relative_eq!(
+            s_trho_eqm(t, rho).get::<kilojoule_per_kilogram_kelvin>(),
+            s_tp_1(t, p).get::<kilojoule_per_kilogram_kelvin>(),
+            max_relative=1e-9);
+        assert_relative_eq!(
+            u_trho_eqm(t, rho).get::<kilojoule_per_kilogram>(),
+            u_tp_1(t, p).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-9);
+        assert_relative_eq!(
+            cp_trho_eqm(t, rho).get::<kilojoule_per_kilogram_kelvin>(),
+            cp_tp_1(t, p).get::<kilojoule_per_kilogram_kelvin>(),
+            max_relative=1e-9);
+        assert_relative_eq!(
+            cv_trho_eqm(t, rho).get::<kilojoule_per_kilogram_kelvin>(),
+            cv_tp_1(t, p).get::<kilojoule_per_kilogram_kelvin>(),
+            max_relative=1e-9);
+        assert_relative_eq!(
+            w_trho_eqm(t, rho).get::<meter_per_second>(),
+            w_tp_1(t, p).get::<meter_per_second>(),
+            max_relative=1e-9);
+        assert_relative_eq!(x_trho_flash(t, rho), 0.0);
+    }
+}
+
+/// (T,rho) flash in region 2 should return the pressure
+/// and properties of the original (T,p) point
+#[test]
+pub fn trho_flash_region_2_round_trip(){
+
+    for (t_kelvin, p_mpa) in [(300.0, 0.0035), (500.0, 0.5), (700.0, 30.0), (1000.0, 80.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let rho = v_tp_2(t, p).recip();
+
+        assert_eq!(trho_flash_region(t, rho), FwdEqnRegion::Region2);
+
+        assert_relative_eq!(p_trho_eqm(t, rho).get::<megapascal>(), p_mpa, max_relative=1e-9);
+        assert_relative_eq!(
+            h_trho_eqm(t, rho).get::<kilojoule_per_kilogram>(),
+            h_tp_2(t, p).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-9);
+        assert_relative_eq!(
+            s_trho_eqm(t, rho).get::<kilojoule_per_kilogram_kelvin>(),
+            s_tp_2(t, p).get::<kilojoule_per_kilogram_kelvin>(),
+            max_relative=1e-9);
+        assert_relative_eq!(x_trho_flash(t, rho), 1.0);
+    }
+}
+
+/// (T,rho) flash in region 5 should return the pressure
+/// and properties of the original (T,p) point
+#[test]
+pub fn trho_flash_region_5_round_trip(){
+
+    for (t_kelvin, p_mpa) in [(1500.0, 0.5), (1500.0, 30.0), (2000.0, 30.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let rho = v_tp_5(t, p).recip();
+
+        assert_eq!(trho_flash_region(t, rho), FwdEqnRegion::Region5);
+
+        assert_relative_eq!(p_trho_eqm(t, rho).get::<megapascal>(), p_mpa, max_relative=1e-9);
+        assert_relative_eq!(
+            h_trho_eqm(t, rho).get::<kilojoule_per_kilogram>(),
+            h_tp_5(t, p).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-9);
+    }
+}
+
+/// the 1073.15 K isotherm belongs to region 5 (up to 50 MPa),
+/// as in the (T,p), (p,h) and (p,s) flashes, and to region 2 above
+#[test]
+pub fn trho_flash_region_2_5_boundary_isotherm(){
+    use crate::interfaces::functional_programming::pt_flash_eqm::region_fwd_eqn_single_phase;
+
+    let t = ThermodynamicTemperature::new::<kelvin>(1073.15);
+
+    for p_mpa in [0.5, 30.0, 50.0] {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let rho = v_tp_5(t, p).recip();
+
+        assert_eq!(region_fwd_eqn_single_phase(t, p), FwdEqnRegion::Region5);
+        assert_eq!(trho_flash_region(t, rho), FwdEqnRegion::Region5);
+        assert_relative_eq!(p_trho_eqm(t, rho).get::<megapascal>(), p_mpa, max_relative=1e-9);
+    }
+
+    let p = Pressure::new::<megapascal>(80.0);
+    let rho = v_tp_2(t, p).recip();
+
+    assert_eq!(region_fwd_eqn_single_phase(t, p), FwdEqnRegion::Region2);
+    assert_eq!(trho_flash_region(t, rho), FwdEqnRegion::Region2);
+    assert_relative_eq!(p_trho_eqm(t, rho).get::<megapascal>(), 80.0, max_relative=1e-9);
+}
+
+/// within the vapour dome, the (T,rho) flash returns saturation
+/// pressure and the steam quality from the specific volume
+#[test]
+pub fn trho_flash_region_4_two_phase(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(450.0);
+    let p_sat = sat_pressure_4(t);
+    let v_liq = v_tp_1(t, p_sat);
+    let v_vap = v_tp_2(t, p_sat);
+    let quality = 0.3;
+
+    let v = v_vap * quality + v_liq * (1.0 - quality);
+    let rho = v.recip();
+
+    assert_eq!(trho_flash_region(t, rho), FwdEqnRegion::Region4);
+
+    let (p, x) = px_trho_flash_eqm(t, rho);
+
+    assert_relative_eq!(p.get::<megapascal>(), p_sat.get::<megapascal>(), max_relative=1e-12);
+    assert_relative_eq!(x, quality, max_relative=1e-9);
+
+    let h_ref = h_tp_2(t, p_sat) * quality + h_tp_1(t, p_sat) * (1.0 - quality);
+
+    assert_relative_eq!(
+        h_trho_eqm(t, rho).get::<kilojoule_per_kilogram>(),
+        h_ref.get::<kilojoule_per_kilogram>(),
+        max_relative=1e-9);
+}
+
+/// near the critical point, the saturated densities come
+/// from region 3
+#[test]
+pub fn trho_flash_region_4_two_phase_near_critical_point(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(640.0);
+    let rho = MassDensity::new::<kilogram_per_cubic_meter>(300.0);
+
+    assert_eq!(trho_flash_region(t, rho), FwdEqnRegion::Region4);
+
+    let (p, x) = px_trho_flash_eqm(t, rho);
+
+    assert_relative_eq!(p.get::<megapascal>(), sat_pressure_4(t).get::<megapascal>());
+    assert!(x > 0.0 && x < 1.0);
+
+    // the compressed liquid just outside the dome is region 3
+    let rho_liquid = MassDensity::new::<kilogram_per_cubic_meter>(550.0);
+    assert_eq!(trho_flash_region(t, rho_liquid), FwdEqnRegion::Region3);
+    assert_relative_eq!(x_trho_flash(t, rho_liquid), 0.0);
+}
+
+/// transport properties from (T,rho) flash should
+/// match those from the pt flash in single phase regions
+#[test]
+pub fn trho_flash_transport_properties(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(300.0);
+    let p = Pressure::new::<megapascal>(3.0);
+    let rho = v_tp_1(t, p).recip();
+
+    assert_relative_eq!(
+        mu_trho_eqm(t, rho).get::<micropascal_second>(),
+        mu_tp_eqm_single_phase(t, p).get::<micropascal_second>(),
+        max_relative=1e-8
+        );
+    assert_relative_eq!(
+        lambda_trho_eqm(t, rho).get::<milliwatt_per_meter_kelvin>(),
+        lambda_tp_eqm_single_phase(t, p).get::<milliwatt_per_meter_kelvin>(),
+        max_relative=1e-8
+        );
+
+    let t = ThermodynamicTemperature::new::<kelvin>(650.0);
+    let p = Pressure::new::<megapascal>(10.0);
+    let rho = v_tp_2(t, p).recip();
+
+    assert_relative_eq!(
+        lambda_trho_eqm(t, rho).get::<milliwatt_per_meter_kelvin>(),
+        lambda_tp_eqm_single_phase(t, p).get::<milliwatt_per_meter_kelvin>(),
+        max_relative=1e-8
+        );
+}
+
+/// (T,rho) points outside the validity range should return errors
+#[test]
+pub fn trho_flash_out_of_bounds_is_error(){
+
+    let rho = MassDensity::new::<kilogram_per_cubic_meter>(1.0);
+
+    let t_too_hot = ThermodynamicTemperature::new::<kelvin>(2300.0);
+    assert!(matches!(try_p_trho_eqm(t_too_hot, rho).unwrap_err(),
+        SteamTableError::TemperatureAboveBound { .. }));
+
+    let t_too_cold = ThermodynamicTemperature::new::<kelvin>(270.0);
+    assert!(matches!(try_h_trho_eqm(t_too_cold, rho).unwrap_err(),
+        SteamTableError::TemperatureBelowBound { .. }));
+
+    let t = ThermodynamicTemperature::new::<kelvin>(500.0);
+    let zero_density = MassDensity::new::<kilogram_per_cubic_meter>(0.0);
+    assert!(matches!(try_p_trho_eqm(t, zero_density).unwrap_err(),
+        SteamTableError::DensityBelowBound { .. }));
+
+    // liquid compressed beyond 100 MPa
+    let p_above_bound = Pressure::new::<megapascal>(150.0);
+    let rho_above_bound = v_tp_1(t, p_above_bound).recip();
+    assert!(matches!(try_p_trho_eqm(t, rho_above_bound).unwrap_err(),
+        SteamTableError::PressureAboveBound { .. }));
+
+    // region 5 above 50 MPa
+    let t = ThermodynamicTemperature::new::<kelvin>(1500.0);
+    let rho_above_bound = v_tp_5(t, Pressure::new::<megapascal>(60.0)).recip();
+    assert!(matches!(try_p_trho_eqm(t, rho_above_bound).unwrap_err(),
+        SteamTableError::PressureAboveBound { .. }));
+}
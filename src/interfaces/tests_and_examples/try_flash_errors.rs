@@ -0,0 +1,162 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::hs_flash_eqm::try_tpvx_hs_flash_eqm;
+use crate::interfaces::functional_programming::ph_flash_eqm::{t_ph_eqm, try_t_ph_eqm, try_v_ph_eqm};
+use crate::interfaces::functional_programming::ps_flash_eqm::{try_t_ps_eqm, try_v_ps_eqm};
+use crate::interfaces::functional_programming::pt_flash_eqm::try_h_tp_eqm_single_phase;
+
+/// ph flash within validity range should give the same 
+/// result as the panicking version
+#[test]
+pub fn try_t_ph_eqm_within_range(){
+    let p = Pressure::new::<megapascal>(16.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1000.0);
+
+    let t_ref = t_ph_eqm(p, h);
+    let t_test = try_t_ph_eqm(p, h).unwrap();
+
+    assert_relative_eq!(
+        t_ref.get::<kelvin>(),
+        t_test.get::<kelvin>(),
+        max_relative=1e-12
+        );
+}
+
+/// above 100 MPa, ph flash should return an error 
+/// instead of panicking
+#[test]
+pub fn try_t_ph_eqm_above_100_mpa(){
+    let p = Pressure::new::<megapascal>(121.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(3000.0);
+
+    let error = try_t_ph_eqm(p, h).unwrap_err();
+
+    assert_eq!(
+        error,
+        SteamTableError::PressureAboveBound { 
+            p, bound: Pressure::new::<megapascal>(100.0) }
+        );
+}
+
+/// below triple pt pressure, ph flash should return an error 
+/// instead of panicking
+#[test]
+pub fn try_v_ph_eqm_below_triple_pt_pressure(){
+    let p = Pressure::new::<megapascal>(6.11e-9);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(2087.5);
+
+    let error = try_v_ph_eqm(p, h).unwrap_err();
+
+    match error {
+        SteamTableError::PressureBelowBound { p: p_error, bound } => {
+            assert_eq!(p, p_error);
+            assert_relative_eq!(
+                bound.get::<megapascal>(),
+                0.000611,
+                max_relative=1e-3
+                );
+        },
+        _ => panic!("expected PressureBelowBound, got {:?}", error),
+    };
+}
+
+/// enthalpy below 273.15 K isotherm should return an error 
+#[test]
+pub fn try_t_ph_eqm_below_273_15_k(){
+    let p = Pressure::new::<megapascal>(10.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(-5.0);
+
+    let error = try_t_ph_eqm(p, h).unwrap_err();
+
+    assert!(matches!(error, 
+            SteamTableError::EnthalpyBelowBound { .. }));
+}
+
+/// entropy above the 2273.15 K isotherm should return an error 
+#[test]
+pub fn try_t_ps_eqm_above_2273_15_k(){
+    let p = Pressure::new::<megapascal>(1.0);
+    let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(11.5);
+
+    let error = try_t_ps_eqm(p, s).unwrap_err();
+
+    assert!(matches!(error, 
+            SteamTableError::EntropyAboveBound { .. }));
+}
+
+/// above 100 MPa, ps flash should return an error 
+/// instead of panicking
+#[test]
+pub fn try_v_ps_eqm_above_100_mpa(){
+    let p = Pressure::new::<megapascal>(101.0);
+    let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(5.0);
+
+    let error = try_v_ps_eqm(p, s).unwrap_err();
+
+    assert!(matches!(error, 
+            SteamTableError::PressureAboveBound { .. }));
+}
+
+/// entropy above 11.92 kJ/(kg K) is out of the hs flash range
+#[test]
+pub fn try_tpvx_hs_flash_eqm_entropy_too_high(){
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(2600.0);
+    let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(12.0);
+
+    let error = try_tpvx_hs_flash_eqm(h, s).unwrap_err();
+
+    assert_eq!(
+        error,
+        SteamTableError::EntropyAboveBound { 
+            s, 
+            bound: SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(11.92) 
+        }
+        );
+}
+
+/// enthalpy too high for the given entropy in hs flash
+#[test]
+pub fn try_tpvx_hs_flash_eqm_enthalpy_too_high(){
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(4500.0);
+    let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(6.0);
+
+    let error = try_tpvx_hs_flash_eqm(h, s).unwrap_err();
+
+    assert!(matches!(error, 
+            SteamTableError::EnthalpyAboveBound { .. }));
+}
+
+/// region 5 only goes up to 50 MPa
+#[test]
+pub fn try_h_tp_eqm_single_phase_region_5_above_50_mpa(){
+    let t = ThermodynamicTemperature::new::<kelvin>(1500.0);
+    let p = Pressure::new::<megapascal>(60.0);
+
+    let error = try_h_tp_eqm_single_phase(t, p).unwrap_err();
+
+    assert_eq!(
+        error,
+        SteamTableError::PressureAboveBound { 
+            p, bound: Pressure::new::<megapascal>(50.0) }
+        );
+}
+
+/// the error message should state the offending input and bound
+#[test]
+pub fn error_message_carries_input_and_bound(){
+    let t = ThermodynamicTemperature::new::<kelvin>(2300.0);
+    let p = Pressure::new::<megapascal>(1.0);
+
+    let error = try_h_tp_eqm_single_phase(t, p).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "temperature 2300 K is above the upper bound of 2273.15 K"
+        );
+}
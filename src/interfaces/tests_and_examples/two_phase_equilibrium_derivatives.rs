@@ -0,0 +1,243 @@
+use approx::assert_relative_eq;
+use uom::si::f64::*;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::thermodynamic_temperature::{degree_celsius, kelvin};
+
+use crate::interfaces::functional_programming::ph_flash_eqm;
+use crate::interfaces::functional_programming::ps_flash_eqm;
+use crate::interfaces::functional_programming::pt_flash_eqm;
+use crate::interfaces::functional_programming::saturation_eqm::SaturationState;
+use crate::interfaces::functional_programming::two_phase_equilibrium_derivatives::*;
+use crate::region_4_vap_liq_equilibrium::{dp_sat_dt_4, sat_pressure_4};
+
+/// the slope of the saturation line is that of the region 4
+/// saturation equation, checked against a central difference
+#[test]
+pub fn dp_sat_dt_two_phase_test(){
+
+    for t_degc in [1.0, 50.0, 100.0, 200.0, 300.0, 340.0, 370.0] {
+        let t = ThermodynamicTemperature::new::<degree_celsius>(t_degc);
+        let dt = 1.0e-3;
+        let t_plus = ThermodynamicTemperature::new::<kelvin>(t.get::<kelvin>() + dt);
+        let t_minus = ThermodynamicTemperature::new::<kelvin>(t.get::<kelvin>() - dt);
+
+        let dp_dt_numerical = (sat_pressure_4(t_plus) - sat_pressure_4(t_minus)).value
+            /(2.0 * dt);
+
+        let dp_sat_dt = TwoPhaseEquilibriumDerivatives::new(t, 0.5).dp_sat_dt;
+
+        assert_eq!(dp_sat_dt, dp_sat_dt_4(t));
+        assert_relative_eq!(dp_sat_dt.value, dp_dt_numerical, 
+            max_relative = 1e-6);
+    }
+}
+
+/// cv of the mixture against a central difference of the
+/// mixture internal energy at constant mixture volume,
+/// the speed of sound against a central difference of pressure 
+/// and mixture volume at constant mixture entropy
+///
+/// 350 degC (623.15 K) is skipped, since the central difference 
+/// would straddle the region 1 and region 3 boundary
+#[test]
+pub fn cv_and_w_two_phase_against_numerical_derivatives_test(){
+
+    for t_degc in [20.0, 100.0, 200.0, 300.0, 340.0, 360.0] {
+        for x in [0.01, 0.3, 0.9, 0.99] {
+            let t = ThermodynamicTemperature::new::<degree_celsius>(t_degc);
+            let saturation_state = SaturationState::new_from_t(t);
+            let v = saturation_state.v_mixture(x).value;
+            let s = saturation_state.s_mixture(x).value;
+
+            let dt = 1.0e-3;
+            let mut u = [0.0; 2];
+            let mut p = [0.0; 2];
+            let mut v_const_s = [0.0; 2];
+            for (i, sign) in [-1.0, 1.0].iter().enumerate() {
+                let t_shifted = ThermodynamicTemperature::new::<kelvin>(
+                    t.get::<kelvin>() + sign * dt);
+                let shifted = SaturationState::new_from_t(t_shifted);
+                let (v_liq, v_vap) = (shifted.liquid.v.value, shifted.vapour.v.value);
+                let (u_liq, u_vap) = (shifted.liquid.u.value, shifted.vapour.u.value);
+                let (s_liq, s_vap) = (shifted.liquid.s.value, shifted.vapour.s.value);
+
+                // quality at the same mixture volume and entropy
+                let x_const_v = (v - v_liq)/(v_vap - v_liq);
+                let x_const_s = (s - s_liq)/(s_vap - s_liq);
+
+                u[i] = u_liq + x_const_v * (u_vap - u_liq);
+                p[i] = shifted.p_sat.value;
+                v_const_s[i] = v_liq + x_const_s * (v_vap - v_liq);
+            }
+
+            let cv_numerical = (u[1] - u[0])/(2.0 * dt);
+            let w_numerical = (-v * v * (p[1] - p[0])/(v_const_s[1] - v_const_s[0])).sqrt();
+
+            let derivatives = TwoPhaseEquilibriumDerivatives::new(t, x);
+
+            assert_relative_eq!(derivatives.cv.value, cv_numerical, max_relative = 1e-3);
+            assert_relative_eq!(derivatives.w.value, w_numerical, max_relative = 1e-3);
+            assert_relative_eq!(derivatives.kappa.value, 
+                derivatives.w.value.powi(2)/(saturation_state.p_sat.value * v),
+                max_relative = 1e-12);
+
+            // cp, alpha_v and kappa_t are infinite along the dome
+            assert!(derivatives.cp.value.is_infinite());
+            assert!(derivatives.alpha_v.value.is_infinite());
+            assert!(derivatives.kappa_t.value.is_infinite());
+        }
+    }
+}
+
+/// on the saturation lines (x = 0 and x = 1 exactly), the saturated
+/// liquid and vapour values are returned, just inside the dome
+/// the mixture values are used
+#[test]
+pub fn two_phase_derivatives_on_saturation_lines_test(){
+
+    let t = ThermodynamicTemperature::new::<degree_celsius>(200.0);
+    let saturation_state = SaturationState::new_from_t(t);
+    let p = saturation_state.p_sat;
+
+    // subcooled by 1e-6 K, so that the (T,p) flash does not
+    // treat the point as being on the saturation line
+    let liquid = pt_flash_eqm::flash(
+        ThermodynamicTemperature::new::<kelvin>(t.get::<kelvin>() - 1e-6), p);
+    let on_liquid_line = TwoPhaseEquilibriumDerivatives::new(t, 0.0);
+    let just_inside_dome = TwoPhaseEquilibriumDerivatives::new(t, 1.0e-6);
+    let inside_dome = TwoPhaseEquilibriumDerivatives::new(t, 1.0e-3);
+
+    assert_relative_eq!(on_liquid_line.cp.value, liquid.cp.value, max_relative = 1e-6);
+    assert_relative_eq!(on_liquid_line.w.value, liquid.w.value, max_relative = 1e-6);
+    // only x = 0 exactly is the saturated liquid
+    assert!(just_inside_dome.cp.value.is_infinite());
+    assert!(just_inside_dome.w < on_liquid_line.w * 0.1);
+    assert!(inside_dome.cp.value.is_infinite());
+    assert!(inside_dome.w < on_liquid_line.w * 0.1);
+
+    let on_vapour_line = TwoPhaseEquilibriumDerivatives::new(t, 1.0);
+    assert_relative_eq!(on_vapour_line.cv.value, 
+        saturation_state.vapour.cv.value, max_relative = 1e-9);
+    assert_relative_eq!(on_vapour_line.w.value, 
+        saturation_state.vapour.w.value, max_relative = 1e-9);
+
+    // the saturation line slope is the same either way
+    assert_eq!(on_liquid_line.dp_sat_dt, inside_dome.dp_sat_dt);
+}
+
+/// a region 4 point from a (p,h) or (p,s) flash within 
+/// SATURATED_PHASE_QUALITY_TOLERANCE of the saturation lines 
+/// gets the saturated liquid or vapour derivatives, 
+/// as the backward equations cannot resolve such a small quality
+#[test]
+pub fn two_phase_derivatives_within_flash_quality_tolerance_test(){
+
+    let t = ThermodynamicTemperature::new::<degree_celsius>(200.0);
+    let saturation_state = SaturationState::new_from_t(t);
+    let p = saturation_state.p_sat;
+    let within_tolerance = 0.5 * SATURATED_PHASE_QUALITY_TOLERANCE;
+
+    assert_eq!(TwoPhaseEquilibriumDerivatives::new_from_flash(t, within_tolerance),
+        TwoPhaseEquilibriumDerivatives::new(t, 0.0));
+    assert_eq!(TwoPhaseEquilibriumDerivatives::new_from_flash(t, 1.0 - within_tolerance),
+        TwoPhaseEquilibriumDerivatives::new(t, 1.0));
+    assert_eq!(TwoPhaseEquilibriumDerivatives::new_from_flash(t, 1.0e-3),
+        TwoPhaseEquilibriumDerivatives::new(t, 1.0e-3));
+
+    // (p,h) and (p,s) points just inside the dome
+    let liquid = saturation_state.liquid;
+    let vapour = saturation_state.vapour;
+    let h = liquid.h + within_tolerance * (vapour.h - liquid.h);
+    let s = liquid.s + within_tolerance * (vapour.s - liquid.s);
+
+    let cp_liquid = liquid.cp.get::<kilojoule_per_kilogram_kelvin>();
+    assert_relative_eq!(cp_liquid, 
+        ph_flash_eqm::cp_ph_eqm(p, h).get::<kilojoule_per_kilogram_kelvin>(), 
+        max_relative = 1e-6);
+    assert_relative_eq!(cp_liquid, 
+        ps_flash_eqm::cp_ps_eqm(p, s).get::<kilojoule_per_kilogram_kelvin>(), 
+        max_relative = 1e-6);
+    assert_relative_eq!(liquid.w.value, ph_flash_eqm::w_ph_eqm(p, h).value, 
+        max_relative = 1e-6);
+    assert_relative_eq!(liquid.w.value, ps_flash_eqm::w_ps_eqm(p, s).value, 
+        max_relative = 1e-6);
+
+    // and the same steam quality through the (T,p) flash
+    assert_relative_eq!(cp_liquid, 
+        pt_flash_eqm::cp_tp_eqm_two_phase(t, p, within_tolerance)
+        .get::<kilojoule_per_kilogram_kelvin>(), 
+        max_relative = 1e-6);
+}
+
+/// the homogeneous equilibrium speed of sound drops far below
+/// that of either phase
+#[test]
+pub fn homogeneous_equilibrium_speed_of_sound_test(){
+
+    let t = ThermodynamicTemperature::new::<degree_celsius>(99.6);
+    let saturation_state = SaturationState::new_from_t(t);
+
+    for x in [0.01, 0.1, 0.5, 0.9] {
+        let w = w_two_phase_eqm(t, x);
+        assert!(w < saturation_state.liquid.w);
+        assert!(w < saturation_state.vapour.w);
+    }
+
+    // the mixture cv is larger than that of either phase,
+    // since some liquid evaporates (or vapour condenses) 
+    // on heating at constant volume
+    let cv = cv_two_phase_eqm(t, 0.5);
+    assert!(cv.get::<kilojoule_per_kilogram_kelvin>() > 
+        saturation_state.liquid.cv.get::<kilojoule_per_kilogram_kelvin>());
+}
+
+/// the region 4 arms of the (T,p,x), (p,h) and (p,s) flashes
+/// use the homogeneous equilibrium derivatives
+#[test]
+pub fn region_4_flashes_use_two_phase_derivatives_test(){
+
+    let x = 0.4;
+    let p = Pressure::new::<megapascal>(5.0);
+    let saturation_state = SaturationState::new_from_p(p);
+    let t = saturation_state.t_sat;
+    let h = saturation_state.h_mixture(x);
+    let s = saturation_state.s_mixture(x);
+
+    let derivatives = TwoPhaseEquilibriumDerivatives::new(t, x);
+
+    assert_relative_eq!(pt_flash_eqm::cv_tp_eqm_two_phase(t, p, x).value,
+        derivatives.cv.value, max_relative = 1e-6);
+    assert_relative_eq!(pt_flash_eqm::w_tp_eqm_two_phase(t, p, x).value,
+        derivatives.w.value, max_relative = 1e-6);
+    assert_relative_eq!(pt_flash_eqm::kappa_tp_eqm_two_phase(t, p, x).value,
+        derivatives.kappa.value, max_relative = 1e-6);
+    assert!(pt_flash_eqm::cp_tp_eqm_two_phase(t, p, x).value.is_infinite());
+    assert!(pt_flash_eqm::alpha_v_tp_eqm_two_phase(t, p, x).value.is_infinite());
+    assert!(pt_flash_eqm::multiphase_flashing::kappa_t_tp_eqm(t, p, x).value.is_infinite());
+
+    assert_relative_eq!(ph_flash_eqm::cv_ph_eqm(p, h).value,
+        derivatives.cv.value, max_relative = 1e-4);
+    assert_relative_eq!(ph_flash_eqm::w_ph_eqm(p, h).value,
+        derivatives.w.value, max_relative = 1e-4);
+    assert_relative_eq!(ph_flash_eqm::kappa_ph_eqm(p, h).value,
+        derivatives.kappa.value, max_relative = 1e-4);
+    assert!(ph_flash_eqm::cp_ph_eqm(p, h).value.is_infinite());
+    assert!(ph_flash_eqm::alpha_v_ph_eqm(p, h).value.is_infinite());
+    assert!(ph_flash_eqm::kappa_t_ph_eqm(p, h).value.is_infinite());
+
+    assert_relative_eq!(ps_flash_eqm::cv_ps_eqm(p, s).value,
+        derivatives.cv.value, max_relative = 1e-4);
+    assert_relative_eq!(ps_flash_eqm::w_ps_eqm(p, s).value,
+        derivatives.w.value, max_relative = 1e-4);
+    assert_relative_eq!(ps_flash_eqm::kappa_ps_eqm(p, s).value,
+        derivatives.kappa.value, max_relative = 1e-4);
+    assert!(ps_flash_eqm::cp_ps_eqm(p, s).value.is_infinite());
+    assert!(ps_flash_eqm::alpsa_v_ps_eqm(p, s).value.is_infinite());
+    assert!(ps_flash_eqm::kappa_t_ps_eqm(p, s).value.is_infinite());
+
+    let state = ph_flash_eqm::flash(p, h);
+    assert_relative_eq!(state.cv.value, derivatives.cv.value, max_relative = 1e-4);
+    assert_relative_eq!(state.w.value, derivatives.w.value, max_relative = 1e-4);
+    assert!(state.cp.value.is_infinite());
+}
@@ -0,0 +1,201 @@
+use approx::assert_relative_eq;
+use uom::si::f64::*;
+use uom::si::pressure::{bar, megapascal};
+use uom::si::thermodynamic_temperature::degree_celsius;
+
+use crate::interfaces::functional_programming::hs_flash_eqm::{lambda_hs_eqm, lambda_hs_eqm_with_mixing_model, mu_hs_eqm, mu_hs_eqm_with_mixing_model};
+use crate::interfaces::functional_programming::ph_flash_eqm::{lambda_ph_eqm, lambda_ph_eqm_with_mixing_model, mu_ph_eqm, mu_ph_eqm_with_mixing_model};
+use crate::interfaces::functional_programming::ps_flash_eqm::{lambda_ps_eqm_with_mixing_model, mu_ps_eqm_with_mixing_model};
+use crate::interfaces::functional_programming::pt_flash_eqm::{lambda_tp_eqm_two_phase, lambda_tp_eqm_two_phase_with_mixing_model, mu_tp_eqm_two_phase, mu_tp_eqm_two_phase_with_mixing_model};
+use crate::interfaces::functional_programming::saturation_eqm::SaturationState;
+use crate::interfaces::functional_programming::two_phase_mixing_model::*;
+
+const VISCOSITY_MODELS: [TwoPhaseViscosityModel; 6] = [
+    TwoPhaseViscosityModel::MixtureDensity,
+    TwoPhaseViscosityModel::McAdams,
+    TwoPhaseViscosityModel::Cicchitti,
+    TwoPhaseViscosityModel::Dukler,
+    TwoPhaseViscosityModel::BeattieWhalley,
+    TwoPhaseViscosityModel::Lin,
+];
+
+const CONDUCTIVITY_MODELS: [TwoPhaseConductivityModel; 3] = [
+    TwoPhaseConductivityModel::MixtureDensity,
+    TwoPhaseConductivityModel::MassWeighted,
+    TwoPhaseConductivityModel::VolumeWeighted,
+];
+
+/// each viscosity model against its formula, 
+/// worked out by hand from the saturated liquid and vapour properties
+#[test]
+pub fn two_phase_viscosity_models_test(){
+
+    let saturation_state = SaturationState::new_from_p(Pressure::new::<bar>(10.0));
+    let x = 0.3;
+
+    let mu_liq = saturation_state.liquid.mu.value;
+    let mu_vap = saturation_state.vapour.mu.value;
+    let rho_liq = saturation_state.liquid.v.recip().value;
+    let rho_vap = saturation_state.vapour.v.recip().value;
+    let rho_mix = (x/rho_vap + (1.0 - x)/rho_liq).recip();
+    let beta = x * rho_liq/(x * rho_liq + (1.0 - x) * rho_vap);
+
+    let mu = |model: TwoPhaseViscosityModel| {
+        model.mu_two_phase(&saturation_state, x).value
+    };
+
+    assert_relative_eq!(mu(TwoPhaseViscosityModel::McAdams),
+        1.0/(x/mu_vap + (1.0 - x)/mu_liq), max_relative = 1e-12);
+    assert_relative_eq!(mu(TwoPhaseViscosityModel::Cicchitti),
+        x * mu_vap + (1.0 - x) * mu_liq, max_relative = 1e-12);
+    assert_relative_eq!(mu(TwoPhaseViscosityModel::Dukler),
+        rho_mix * (x * mu_vap/rho_vap + (1.0 - x) * mu_liq/rho_liq), 
+        max_relative = 1e-12);
+    assert_relative_eq!(mu(TwoPhaseViscosityModel::BeattieWhalley),
+        beta * mu_vap + (1.0 - beta) * (1.0 + 2.5 * beta) * mu_liq, 
+        max_relative = 1e-12);
+    assert_relative_eq!(mu(TwoPhaseViscosityModel::Lin),
+        mu_liq * mu_vap/(mu_vap + x.powf(1.4) * (mu_liq - mu_vap)), 
+        max_relative = 1e-12);
+
+    // mcadams is the harmonic mean, so it is lower than 
+    // the cicchitti (arithmetic) mean
+    assert!(mu(TwoPhaseViscosityModel::McAdams) 
+        < mu(TwoPhaseViscosityModel::Cicchitti));
+}
+
+/// each thermal conductivity model against its formula
+#[test]
+pub fn two_phase_conductivity_models_test(){
+
+    let saturation_state = SaturationState::new_from_p(Pressure::new::<bar>(10.0));
+    let x = 0.3;
+
+    let lambda_liq = saturation_state.liquid.lambda.value;
+    let lambda_vap = saturation_state.vapour.lambda.value;
+    let rho_liq = saturation_state.liquid.v.recip().value;
+    let rho_vap = saturation_state.vapour.v.recip().value;
+    let beta = x * rho_liq/(x * rho_liq + (1.0 - x) * rho_vap);
+
+    assert_relative_eq!(homogeneous_void_fraction(x, 
+        saturation_state.liquid.v.recip(), saturation_state.vapour.v.recip()),
+        beta, max_relative = 1e-12);
+
+    let lambda = |model: TwoPhaseConductivityModel| {
+        model.lambda_two_phase(&saturation_state, x).value
+    };
+
+    assert_relative_eq!(lambda(TwoPhaseConductivityModel::MassWeighted),
+        x * lambda_vap + (1.0 - x) * lambda_liq, max_relative = 1e-12);
+    assert_relative_eq!(lambda(TwoPhaseConductivityModel::VolumeWeighted),
+        beta * lambda_vap + (1.0 - beta) * lambda_liq, max_relative = 1e-12);
+}
+
+/// at x = 0 and x = 1, all models give the saturated liquid 
+/// and saturated vapour properties
+///
+/// the mixture density models use the same correlation 
+/// (without critical enhancement) as the saturated phases
+#[test]
+pub fn two_phase_mixing_models_saturated_limits_test(){
+
+    for p_bar in [0.1, 1.0, 10.0, 100.0, 200.0] {
+        let saturation_state = SaturationState::new_from_p(Pressure::new::<bar>(p_bar));
+
+        for model in VISCOSITY_MODELS {
+            assert_relative_eq!(model.mu_two_phase(&saturation_state, 0.0).value,
+                saturation_state.liquid.mu.value, max_relative = 1e-12);
+            assert_relative_eq!(model.mu_two_phase(&saturation_state, 1.0).value,
+                saturation_state.vapour.mu.value, max_relative = 1e-12);
+        }
+
+        for model in CONDUCTIVITY_MODELS {
+            assert_relative_eq!(model.lambda_two_phase(&saturation_state, 0.0).value,
+                saturation_state.liquid.lambda.value, max_relative = 1e-12);
+            assert_relative_eq!(model.lambda_two_phase(&saturation_state, 1.0).value,
+                saturation_state.vapour.lambda.value, max_relative = 1e-12);
+        }
+    }
+}
+
+/// the default mixing model gives the same values as the 
+/// existing (T,p,x), (p,h) and (h,s) functions in region 4
+#[test]
+pub fn default_mixing_model_matches_eqm_functions_test(){
+
+    let model = TwoPhaseMixingModel::default();
+    let x = 0.4;
+
+    for t_degc in [50.0, 150.0, 250.0, 330.0] {
+        let t = ThermodynamicTemperature::new::<degree_celsius>(t_degc);
+        let saturation_state = SaturationState::new_from_t(t);
+        let p = saturation_state.p_sat;
+        let h = saturation_state.h_mixture(x);
+        let s = saturation_state.s_mixture(x);
+
+        let mu = model.mu_two_phase(&saturation_state, x);
+        let lambda = model.lambda_two_phase(&saturation_state, x);
+
+        assert_relative_eq!(mu.value, mu_tp_eqm_two_phase(t, p, x).value, 
+            max_relative = 1e-6);
+        assert_relative_eq!(lambda.value, lambda_tp_eqm_two_phase(t, p, x).value, 
+            max_relative = 1e-6);
+        assert_relative_eq!(mu.value, mu_ph_eqm(p, h).value, 
+            max_relative = 1e-4);
+        assert_relative_eq!(lambda.value, lambda_ph_eqm(p, h).value, 
+            max_relative = 1e-4);
+        assert_relative_eq!(mu.value, mu_hs_eqm(h, s).value, 
+            max_relative = 1e-3);
+        assert_relative_eq!(lambda.value, lambda_hs_eqm(h, s).value, 
+            max_relative = 1e-3);
+    }
+}
+
+/// the (T,p), (p,h), (p,s) and (h,s) functions give the same 
+/// two phase properties for each mixing model,
+/// and the same single phase properties regardless of mixing model
+#[test]
+pub fn mixing_model_consistent_across_interfaces_test(){
+
+    let x = 0.6;
+    let saturation_state = SaturationState::new_from_p(Pressure::new::<bar>(40.0));
+    let t = saturation_state.t_sat;
+    let p = saturation_state.p_sat;
+    let h = saturation_state.h_mixture(x);
+    let s = saturation_state.s_mixture(x);
+
+    for viscosity in VISCOSITY_MODELS {
+        for thermal_conductivity in CONDUCTIVITY_MODELS {
+            let model = TwoPhaseMixingModel::new(viscosity, thermal_conductivity);
+
+            let mu = model.mu_two_phase(&saturation_state, x).value;
+            let lambda = model.lambda_two_phase(&saturation_state, x).value;
+
+            assert_relative_eq!(mu_tp_eqm_two_phase_with_mixing_model(t, p, x, model).value,
+                mu, max_relative = 1e-6);
+            assert_relative_eq!(mu_ph_eqm_with_mixing_model(p, h, model).value,
+                mu, max_relative = 1e-4);
+            assert_relative_eq!(mu_ps_eqm_with_mixing_model(p, s, model).value,
+                mu, max_relative = 1e-4);
+            assert_relative_eq!(mu_hs_eqm_with_mixing_model(h, s, model).value,
+                mu, max_relative = 1e-3);
+
+            assert_relative_eq!(lambda_tp_eqm_two_phase_with_mixing_model(t, p, x, model).value,
+                lambda, max_relative = 1e-6);
+            assert_relative_eq!(lambda_ph_eqm_with_mixing_model(p, h, model).value,
+                lambda, max_relative = 1e-4);
+            assert_relative_eq!(lambda_ps_eqm_with_mixing_model(p, s, model).value,
+                lambda, max_relative = 1e-4);
+            assert_relative_eq!(lambda_hs_eqm_with_mixing_model(h, s, model).value,
+                lambda, max_relative = 1e-3);
+
+            // superheated steam, the mixing model is not used
+            let p_single_phase = Pressure::new::<megapascal>(1.0);
+            let h_single_phase = saturation_state.vapour.h * 1.1;
+            assert_eq!(mu_ph_eqm_with_mixing_model(p_single_phase, h_single_phase, model),
+                mu_ph_eqm(p_single_phase, h_single_phase));
+            assert_eq!(lambda_ph_eqm_with_mixing_model(p_single_phase, h_single_phase, model),
+                lambda_ph_eqm(p_single_phase, h_single_phase));
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::dynamic_viscosity::micropascal_second;
+use uom::si::f64::*;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::thermal_conductivity::milliwatt_per_meter_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::pt_flash_eqm::FwdEqnRegion;
+use crate::interfaces::functional_programming::trho_flash_eqm::{lambda_trho_eqm, mu_trho_eqm, u_trho_eqm, x_trho_flash};
+use crate::interfaces::functional_programming::uv_flash_eqm::*;
+use crate::region_1_subcooled_liquid::{h_tp_1, s_tp_1, u_tp_1, v_tp_1};
+use crate::region_2_vapour::{h_tp_2, u_tp_2, v_tp_2};
+use crate::region_3_single_phase_plus_supercritical_steam::{p_rho_t_3, u_rho_t_3};
+use crate::region_4_vap_liq_equilibrium::sat_pressure_4;
+use crate::region_5_steam_at_800_plus_degc::{h_tp_5, u_tp_5, v_tp_5};
+
+/// (u,v) flash for region 3, using the region 3
+/// verification table (table 33 of IAPWS-IF97)
+/// [T (K), rho (kg/m3), p (MPa), h (kJ/kg), u (kJ/kg)]
+///
+/// the tabulated internal energies only have 9 significant
+/// figures, so T, p and h are only checked to 1e-7
+#[test]
+pub fn uv_flash_region_3_verification_table(){
+
+    let table: Vec<[f64; 5]> = vec![
+        [650.0, 500.0, 0.255837018e2, 0.186343019e4, 0.181226279e4],
+        [650.0, 200.0, 0.222930643e2, 0.237512401e4, 0.226365868e4],
+        [750.0, 500.0, 0.783095639e2, 0.225868845e4, 0.210206932e4],
+    ];
+
+    for [t_ref, rho_kg_per_m3, p_ref, h_ref, u_ref] in table {
+
+        let u = AvailableEnergy::new::<kilojoule_per_kilogram>(u_ref);
+        let v = MassDensity::new::<kilogram_per_cubic_meter>(rho_kg_per_m3).recip();
+
+        assert_eq!(uv_flash_region(u, v), FwdEqnRegion::Region3);
+
+        assert_relative_eq!(t_uv_eqm(u, v).get::<kelvin>(), t_ref, max_relative=1e-7);
+        assert_relative_eq!(p_uv_eqm(u, v).get::<megapascal>(), p_ref, max_relative=1e-7);
+        assert_relative_eq!(
+            h_uv_eqm(u, v).get::<kilojoule_per_kilogram>(),
+            h_ref,
+            max_relative=1e-7);
+    }
+}
+
+/// (u,v) flash in the single phase regions 1, 2 and 5
+/// should return the original (T,p) point
+#[test]
+pub fn uv_flash_single_phase_round_trip(){
+
+    for (t_kelvin, p_mpa) in [(300.0, 3.0), (300.0, 80.0), (500.0, 3.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let u = u_tp_1(t, p);
+        let v = v_tp_1(t, p);
+
+        assert_eq!(uv_flash_region(u, v), FwdEqnRegion::Region1);
+        assert_relative_eq!(t_uv_eqm(u, v).get::<kelvin>(), t_kelvin, max_relative=1e-9);
+        assert_relative_eq!(p_uv_eqm(u, v).get::<megapascal>(), p_mpa, max_relative=1e-6);
+        assert_relative_eq!(
+            h_uv_eqm(u, v).get::<kilojoule_per_kilogram>(),
+            h_tp_1(t, p).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-8);
+        assert_relative_eq!(
+            s_uv_eqm(u, v).get::<kilojoule_per_kilogram_kelvin>(),
+            s_tp_1(t, p).get::<kilojoule_per_kilogram_kelvin>(),
+            max_relative=1e-8);
+        assert_relative_eq!(x_uv_flash(u, v), 0.0);
+    }
+
+    for (t_kelvin, p_mpa) in [(500.0, 0.5), (700.0, 10.0), (1000.0, 80.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let u = u_tp_2(t, p);
+        let v = v_tp_2(t, p);
+
+        assert_eq!(uv_flash_region(u, v), FwdEqnRegion::Region2);
+        assert_relative_eq!(t_uv_eqm(u, v).get::<kelvin>(), t_kelvin, max_relative=1e-9);
+        assert_relative_eq!(p_uv_eqm(u, v).get::<megapascal>(), p_mpa, max_relative=1e-8);
+        assert_relative_eq!(
+            h_uv_eqm(u, v).get::<kilojoule_per_kilogram>(),
+            h_tp_2(t, p).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-9);
+        assert_relative_eq!(x_uv_flash(u, v), 1.0);
+    }
+
+    for (t_kelvin, p_mpa) in [(1500.0, 0.5), (1500.0, 30.0), (2000.0, 30.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let u = u_tp_5(t, p);
+        let v = v_tp_5(t, p);
+
+        assert_eq!(uv_flash_region(u, v), FwdEqnRegion::Region5);
+        assert_relative_eq!(t_uv_eqm(u, v).get::<kelvin>(), t_kelvin, max_relative=1e-9);
+        assert_relative_eq!(p_uv_eqm(u, v).get::<megapascal>(), p_mpa, max_relative=1e-8);
+        assert_relative_eq!(
+            h_uv_eqm(u, v).get::<kilojoule_per_kilogram>(),
+            h_tp_5(t, p).get::<kilojoule_per_kilogram>(),
+            max_relative=1e-9);
+    }
+}
+
+/// within the vapour dome, both internal energy and specific
+/// volume are quality weighted, the (u,v) flash should
+/// return the saturation temperature and steam quality
+///
+/// this checks both the region 1/2 part of the dome
+/// and the region 3 part of the dome
+#[test]
+pub fn uv_flash_region_4_two_phase(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(450.0);
+    let p_sat = sat_pressure_4(t);
+    let quality = 0.3;
+
+    let u = u_tp_2(t, p_sat) * quality + u_tp_1(t, p_sat) * (1.0 - quality);
+    let v = v_tp_2(t, p_sat) * quality + v_tp_1(t, p_sat) * (1.0 - quality);
+
+    assert_eq!(uv_flash_region(u, v), FwdEqnRegion::Region4);
+    assert_relative_eq!(t_uv_eqm(u, v).get::<kelvin>(), 450.0, max_relative=1e-9);
+    assert_relative_eq!(x_uv_flash(u, v), quality, max_relative=1e-8);
+    assert_relative_eq!(
+        p_uv_eqm(u, v).get::<megapascal>(),
+        p_sat.get::<megapascal>(),
+        max_relative=1e-8);
+
+    // near critical, within region 3, we go from (T,rho)
+    // to (u,v) using the (T,rho) flash
+    let t = ThermodynamicTemperature::new::<kelvin>(645.0);
+    let rho = MassDensity::new::<kilogram_per_cubic_meter>(300.0);
+    let u = u_trho_eqm(t, rho);
+    let x_ref = x_trho_flash(t, rho);
+    let v = rho.recip();
+
+    assert_eq!(uv_flash_region(u, v), FwdEqnRegion::Region4);
+    assert_relative_eq!(t_uv_eqm(u, v).get::<kelvin>(), 645.0, max_relative=1e-9);
+    assert_relative_eq!(x_uv_flash(u, v), x_ref, max_relative=1e-7);
+}
+
+/// near the critical point in region 3 (supercritical),
+/// the (u,v) flash should return the original (T,rho) point
+#[test]
+pub fn uv_flash_near_critical_region_3(){
+
+    for (t_kelvin, rho_kg_per_m3) in [(647.2, 322.0), (648.0, 330.0), (660.0, 250.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let rho = MassDensity::new::<kilogram_per_cubic_meter>(rho_kg_per_m3);
+        let u = u_rho_t_3(rho, t);
+        let v = rho.recip();
+
+        assert_eq!(uv_flash_region(u, v), FwdEqnRegion::Region3);
+        assert_relative_eq!(t_uv_eqm(u, v).get::<kelvin>(), t_kelvin, max_relative=1e-9);
+        assert_relative_eq!(
+            p_uv_eqm(u, v).get::<megapascal>(),
+            p_rho_t_3(rho, t).get::<megapascal>(),
+            max_relative=1e-8);
+    }
+}
+
+/// (u,v) points which are exactly on the saturation line
+/// should return the saturation temperature and pressure
+/// with steam quality of 0 (saturated liquid) or
+/// 1 (saturated vapour)
+#[test]
+pub fn uv_flash_on_saturation_line(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(450.0);
+    let p_sat = sat_pressure_4(t);
+
+    // saturated liquid
+    let u = u_tp_1(t, p_sat);
+    let v = v_tp_1(t, p_sat);
+
+    assert_relative_eq!(t_uv_eqm(u, v).get::<kelvin>(), 450.0, max_relative=1e-9);
+    assert_relative_eq!(
+        p_uv_eqm(u, v).get::<megapascal>(),
+        p_sat.get::<megapascal>(),
+        max_relative=1e-6);
+    assert_relative_eq!(x_uv_flash(u, v), 0.0, epsilon=1e-8);
+
+    // saturated vapour
+    let u = u_tp_2(t, p_sat);
+    let v = v_tp_2(t, p_sat);
+
+    assert_relative_eq!(t_uv_eqm(u, v).get::<kelvin>(), 450.0, max_relative=1e-9);
+    assert_relative_eq!(
+        p_uv_eqm(u, v).get::<megapascal>(),
+        p_sat.get::<megapascal>(),
+        max_relative=1e-8);
+    assert_relative_eq!(x_uv_flash(u, v), 1.0, epsilon=1e-8);
+}
+
+/// transport properties from the (u,v) flash should be
+/// the same as those from the (T,rho) flash
+#[test]
+pub fn uv_flash_transport_properties(){
+
+    for (t_kelvin, rho_kg_per_m3) in [(350.0, 980.0), (450.0, 100.0), (700.0, 150.0)] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let rho = MassDensity::new::<kilogram_per_cubic_meter>(rho_kg_per_m3);
+        let u = u_trho_eqm(t, rho);
+        let v = rho.recip();
+
+        assert_relative_eq!(
+            mu_uv_eqm(u, v).get::<micropascal_second>(),
+            mu_trho_eqm(t, rho).get::<micropascal_second>(),
+            max_relative=1e-8);
+        assert_relative_eq!(
+            lambda_uv_eqm(u, v).get::<milliwatt_per_meter_kelvin>(),
+            lambda_trho_eqm(t, rho).get::<milliwatt_per_meter_kelvin>(),
+            max_relative=1e-8);
+    }
+}
+
+/// (u,v) points outside the validity range should return errors
+#[test]
+pub fn uv_flash_out_of_bounds_is_error(){
+
+    let v = SpecificVolume::new::<cubic_meter_per_kilogram>(0.0011);
+
+    let u_too_low = AvailableEnergy::new::<kilojoule_per_kilogram>(-100.0);
+    assert!(matches!(try_t_uv_eqm(u_too_low, v).unwrap_err(),
+        SteamTableError::InternalEnergyBelowBound { .. }));
+
+    // liquid at this specific volume reaches 100 MPa well
+    // before 2273.15 K
+    let u_too_high = AvailableEnergy::new::<kilojoule_per_kilogram>(3000.0);
+    assert!(matches!(try_p_uv_eqm(u_too_high, v).unwrap_err(),
+        SteamTableError::InternalEnergyAboveBound { .. }));
+
+    let v_vapour = SpecificVolume::new::<cubic_meter_per_kilogram>(1.0);
+    let u_too_high = AvailableEnergy::new::<kilojoule_per_kilogram>(10000.0);
+    assert!(matches!(try_t_uv_eqm(u_too_high, v_vapour).unwrap_err(),
+        SteamTableError::InternalEnergyAboveBound { .. }));
+}
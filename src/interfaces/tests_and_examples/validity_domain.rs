@@ -0,0 +1,194 @@
+use approx::assert_relative_eq;
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::mass_density::kilogram_per_cubic_meter;
+use uom::si::pressure::megapascal;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::specific_volume::cubic_meter_per_kilogram;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::errors::SteamTableError;
+use crate::interfaces::functional_programming::pt_flash_eqm::{h_tp_eqm_single_phase, s_tp_eqm_single_phase};
+use crate::interfaces::functional_programming::validity_domain::*;
+
+/// valid points should be returned as is
+#[test]
+pub fn validity_domain_valid_points(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(500.0);
+    let p = Pressure::new::<megapascal>(5.0);
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(1000.0);
+    let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(5.0);
+    let v = SpecificVolume::new::<cubic_meter_per_kilogram>(0.05);
+    let rho = MassDensity::new::<kilogram_per_cubic_meter>(20.0);
+    let u = AvailableEnergy::new::<kilojoule_per_kilogram>(2000.0);
+
+    assert!(is_valid_pt(t, p));
+    assert!(is_valid_ph(p, h));
+    assert!(is_valid_ps(p, s));
+    assert!(is_valid_hs(AvailableEnergy::new::<kilojoule_per_kilogram>(2800.0),
+        SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(6.0)));
+    assert!(is_valid_trho(t, rho));
+    assert!(is_valid_pv(p, v));
+    assert!(is_valid_uv(u, v));
+
+    let domain = validity_domain_ph(p, h);
+    assert!(domain.is_valid());
+    assert_eq!(domain.violated_bound, None);
+    assert_eq!(domain.nearest_valid_point, Some((p, h)));
+}
+
+/// (T,p) points outside the validity range are moved
+/// onto the violated bound
+#[test]
+pub fn validity_domain_pt_out_of_range(){
+
+    let t = ThermodynamicTemperature::new::<kelvin>(2500.0);
+    let p = Pressure::new::<megapascal>(1.0);
+    let domain = validity_domain_pt(t, p);
+
+    assert!(!is_valid_pt(t, p));
+    assert!(!domain.is_valid());
+    assert!(matches!(domain.violated_bound,
+        Some(SteamTableError::TemperatureAboveBound { .. })));
+    let (t_valid, p_valid) = domain.nearest_valid_point.unwrap();
+    assert_relative_eq!(t_valid.get::<kelvin>(), 2273.15);
+    assert_eq!(p_valid, p);
+
+    // region 5 only goes up to 50 MPa
+    let t = ThermodynamicTemperature::new::<kelvin>(1200.0);
+    let p = Pressure::new::<megapascal>(70.0);
+    let domain = validity_domain_pt(t, p);
+
+    assert!(matches!(domain.violated_bound,
+        Some(SteamTableError::PressureAboveBound { .. })));
+    let (t_valid, p_valid) = domain.nearest_valid_point.unwrap();
+    assert_eq!(t_valid, t);
+    assert_relative_eq!(p_valid.get::<megapascal>(), 50.0);
+    assert!(is_valid_pt(t_valid, p_valid));
+}
+
+/// (p,h) and (p,s) points outside the validity range
+/// are moved onto the bounding isobars and isotherms
+#[test]
+pub fn validity_domain_ph_and_ps_out_of_range(){
+
+    let p = Pressure::new::<megapascal>(1.0);
+    let t_min = ThermodynamicTemperature::new::<kelvin>(273.15);
+
+    // enthalpy below the 273.15 K isotherm
+    let h = AvailableEnergy::new::<kilojoule_per_kilogram>(-50.0);
+    let domain = validity_domain_ph(p, h);
+
+    assert!(!is_valid_ph(p, h));
+    assert!(matches!(domain.violated_bound,
+        Some(SteamTableError::EnthalpyBelowBound { .. })));
+    let (p_valid, h_valid) = domain.nearest_valid_point.unwrap();
+    assert_eq!(p_valid, p);
+    assert_relative_eq!(
+        h_valid.get::<kilojoule_per_kilogram>(),
+        h_tp_eqm_single_phase(t_min, p).get::<kilojoule_per_kilogram>(),
+        max_relative=1e-12);
+
+    // pressure and enthalpy both out of range, pressure is
+    // reported first, but both are moved
+    let p_too_high = Pressure::new::<megapascal>(120.0);
+    let h_too_high = AvailableEnergy::new::<kilojoule_per_kilogram>(8000.0);
+    let domain = validity_domain_ph(p_too_high, h_too_high);
+
+    assert!(matches!(domain.violated_bound,
+        Some(SteamTableError::PressureAboveBound { .. })));
+    let (p_valid, h_valid) = domain.nearest_valid_point.unwrap();
+    assert_relative_eq!(p_valid.get::<megapascal>(), 100.0);
+    assert!(h_valid < h_too_high);
+    assert!(is_valid_ph(p_valid, h_valid));
+
+    // entropy above the 2273.15 K isotherm
+    let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(20.0);
+    let domain = validity_domain_ps(p, s);
+
+    assert!(!is_valid_ps(p, s));
+    assert!(matches!(domain.violated_bound,
+        Some(SteamTableError::EntropyAboveBound { .. })));
+    let (p_valid, s_valid) = domain.nearest_valid_point.unwrap();
+    assert_eq!(p_valid, p);
+    assert_relative_eq!(
+        s_valid.get::<kilojoule_per_kilogram_kelvin>(),
+        s_tp_eqm_single_phase(ThermodynamicTemperature::new::<kelvin>(2273.15), p)
+            .get::<kilojoule_per_kilogram_kelvin>(),
+        max_relative=1e-12);
+}
+
+/// (h,s) points outside the validity range should have
+/// a nearest valid point which is within the validity range
+#[test]
+pub fn validity_domain_hs_out_of_range(){
+
+    let points = [
+        (2800.0, -1.0),
+        (2800.0, 13.0),
+        (100.0, 5.0),
+        (4200.0, 5.0),
+    ];
+
+    for (h_kj_per_kg, s_kj_per_kg_k) in points {
+        let h = AvailableEnergy::new::<kilojoule_per_kilogram>(h_kj_per_kg);
+        let s = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(s_kj_per_kg_k);
+        let domain = validity_domain_hs(h, s);
+
+        assert!(!is_valid_hs(h, s));
+        assert!(!domain.is_valid());
+
+        let (h_valid, s_valid) = domain.nearest_valid_point.unwrap();
+        assert!(is_valid_hs(h_valid, s_valid));
+    }
+}
+
+/// (T,rho), (p,v) and (u,v) points outside the validity range
+#[test]
+pub fn validity_domain_trho_pv_uv_out_of_range(){
+
+    // temperature too low
+    let t = ThermodynamicTemperature::new::<kelvin>(200.0);
+    let rho = MassDensity::new::<kilogram_per_cubic_meter>(1000.0);
+    let domain = validity_domain_trho(t, rho);
+
+    assert!(matches!(domain.violated_bound,
+        Some(SteamTableError::TemperatureBelowBound { .. })));
+    let (t_valid, rho_valid) = domain.nearest_valid_point.unwrap();
+    assert_relative_eq!(t_valid.get::<kelvin>(), 273.15);
+    assert_eq!(rho_valid, rho);
+
+    // negative density has no nearest valid point
+    let rho_negative = MassDensity::new::<kilogram_per_cubic_meter>(-1.0);
+    let domain = validity_domain_trho(t_valid, rho_negative);
+    assert!(matches!(domain.violated_bound,
+        Some(SteamTableError::DensityBelowBound { .. })));
+    assert_eq!(domain.nearest_valid_point, None);
+
+    // specific volume smaller than that of liquid water
+    let p = Pressure::new::<megapascal>(1.0);
+    let v = SpecificVolume::new::<cubic_meter_per_kilogram>(1.0e-4);
+    let domain = validity_domain_pv(p, v);
+
+    assert!(!is_valid_pv(p, v));
+    assert!(matches!(domain.violated_bound,
+        Some(SteamTableError::SpecificVolumeBelowBound { .. })));
+    let (p_valid, v_valid) = domain.nearest_valid_point.unwrap();
+    assert_eq!(p_valid, p);
+    assert!(v_valid > v);
+    assert!(is_valid_pv(p_valid, v_valid));
+
+    // internal energy too low for the isochore
+    let u = AvailableEnergy::new::<kilojoule_per_kilogram>(-100.0);
+    let v = SpecificVolume::new::<cubic_meter_per_kilogram>(0.1);
+    let domain = validity_domain_uv(u, v);
+
+    assert!(!is_valid_uv(u, v));
+    assert!(matches!(domain.violated_bound,
+        Some(SteamTableError::InternalEnergyBelowBound { .. })));
+    let (u_valid, v_valid) = domain.nearest_valid_point.unwrap();
+    assert_eq!(v_valid, v);
+    assert!(u_valid > u);
+    assert!(is_valid_uv(u_valid, v_valid));
+}
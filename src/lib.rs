@@ -77,6 +77,10 @@ pub mod surface_tension;
 /// dielectric constant 
 pub mod dielectric_constant;
 
+/// error types returned by the try_* flash functions 
+/// instead of panicking
+pub mod errors;
+
 /// allows for easy importing as with most rust 
 /// crates. 
 pub mod prelude;
@@ -1,2 +1,4 @@
 pub use crate::interfaces::functional_programming;
 pub use crate::interfaces::object_oriented_programming::*;
+pub use crate::interfaces::steam_property_provider::*;
+pub use crate::errors::SteamTableError;
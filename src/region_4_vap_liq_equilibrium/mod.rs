@@ -27,6 +27,11 @@ pub use sat_temp::*;
 pub mod sat_pressure;
 pub use sat_pressure::*;
 
+/// derivatives of the saturation pressure and temperature
+/// equations, dp_sat/dT and dT_sat/dp
+pub mod sat_derivatives;
+pub use sat_derivatives::*;
+
 use uom::si::{f64::*, pressure::megapascal, thermodynamic_temperature::kelvin};
 
 /// returns dimensionless pressure 
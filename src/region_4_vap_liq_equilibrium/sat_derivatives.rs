@@ -0,0 +1,83 @@
+use super::{beta_dimensionless_pressure_4, region_4_coeff_index, sat_pressure_4, sat_temp_4, theta_dimensionless_temp_4};
+use uom::si::{f64::*, pressure::megapascal, temperature_interval, thermodynamic_temperature::kelvin};
+use uom::si::{Quantity, ISQ, SI};
+use uom::typenum::{N1, N2, P1, P2, Z0};
+
+/// units of dp/dT, Pa/K
+pub type PressurePerTemperature = Quantity<ISQ<N1, P1, N2, Z0, N1, Z0, Z0>, SI<f64>, f64>;
+
+/// units of dT/dp, K/Pa
+pub type TemperaturePerPressure = Quantity<ISQ<P1, N1, P2, Z0, P1, Z0, Z0>, SI<f64>, f64>;
+
+/// returns the slope of the saturation pressure curve, dp_sat/dT,
+/// given saturation temperature
+///
+/// this is the analytical derivative of the region 4 
+/// saturation equation, which is an implicit quadratic
+/// in beta and theta:
+///
+/// F = beta^2 A(theta) + beta B(theta) + C(theta) = 0
+///
+/// so that dbeta/dtheta = - (dF/dtheta)/(dF/dbeta),
+/// and p = beta^4 (1 MPa), theta = T + n9/(T - n10) (T in K)
+pub fn dp_sat_dt_4(t: ThermodynamicTemperature) -> PressurePerTemperature {
+
+    let p_sat = sat_pressure_4(t);
+    let beta = beta_dimensionless_pressure_4(p_sat);
+    let theta = theta_dimensionless_temp_4(t);
+
+    return dp_sat_dt_given_beta_theta(beta, theta, t);
+}
+
+/// returns the slope of the saturation temperature curve, dT_sat/dp,
+/// given saturation pressure
+///
+/// this is the reciprocal of dp_sat/dT at the saturation temperature
+/// (the region 4 saturation temperature and pressure equations
+/// are the same quadratic, so they are consistent)
+pub fn dt_sat_dp_4(p: Pressure) -> TemperaturePerPressure {
+
+    let t_sat = sat_temp_4(p);
+    let beta = beta_dimensionless_pressure_4(p);
+    let theta = theta_dimensionless_temp_4(t_sat);
+
+    return dp_sat_dt_given_beta_theta(beta, theta, t_sat).recip();
+}
+
+#[inline]
+fn dp_sat_dt_given_beta_theta(beta: f64, 
+    theta: f64,
+    t: ThermodynamicTemperature) -> PressurePerTemperature {
+
+    let n1 = region_4_coeff_index(1);
+    let n2 = region_4_coeff_index(2);
+    let n3 = region_4_coeff_index(3);
+    let n4 = region_4_coeff_index(4);
+    let n5 = region_4_coeff_index(5);
+    let n6 = region_4_coeff_index(6);
+    let n7 = region_4_coeff_index(7);
+    let n9 = region_4_coeff_index(9);
+    let n10 = region_4_coeff_index(10);
+
+    // A, B and C as in the saturation pressure equation
+    let a = theta.powi(2) + n1 * theta + n2;
+    let b = n3 * theta.powi(2) + n4 * theta + n5;
+
+    // derivatives of F with respect to theta and beta
+    let df_dtheta = beta.powi(2) * (2.0 * theta + n1) 
+        + beta * (2.0 * n3 * theta + n4) 
+        + (2.0 * n6 * theta + n7);
+    let df_dbeta = 2.0 * beta * a + b;
+
+    let dbeta_dtheta = -df_dtheta/df_dbeta;
+
+    // theta = T + n9/(T - n10)
+    let t_kelvin = t.get::<kelvin>();
+    let dtheta_dt = 1.0 - n9/(t_kelvin - n10).powi(2);
+
+    // p = beta^4 (1 MPa)
+    let dp_dbeta = Pressure::new::<megapascal>(4.0 * beta.powi(3));
+
+    return dp_dbeta * dbeta_dtheta * dtheta_dt 
+        / TemperatureInterval::new::<temperature_interval::kelvin>(1.0);
+}
@@ -4,7 +4,7 @@ use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
 use uom::si::pressure::megapascal;
 use uom::si::available_energy::kilojoule_per_kilogram;
 
-use crate::region_4_vap_liq_equilibrium::{sat_pressure_4, sat_temp_4, tsat_hs_4};
+use crate::region_4_vap_liq_equilibrium::{dp_sat_dt_4, dt_sat_dp_4, sat_pressure_4, sat_temp_4, tsat_hs_4};
 
 #[test]
 pub fn sat_pressure_test_1(){
@@ -159,3 +159,44 @@ pub fn hs_backward_sat_temp_test_3(){
         max_relative=1e-8
         );
 }
+/// dp_sat/dT against a central difference of the 
+/// saturation pressure equation
+#[test]
+pub fn dp_sat_dt_test(){
+
+    for t_kelvin in [273.16, 300.0, 373.15, 500.0, 623.15, 640.0, 647.0] {
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let dt = 1.0e-4;
+
+        let p_plus = sat_pressure_4(ThermodynamicTemperature::new::<kelvin>(t_kelvin + dt));
+        let p_minus = sat_pressure_4(ThermodynamicTemperature::new::<kelvin>(t_kelvin - dt));
+        let dp_dt_numerical = (p_plus - p_minus).value/(2.0 * dt);
+
+        approx::assert_relative_eq!(
+            dp_sat_dt_4(t).value,
+            dp_dt_numerical,
+            max_relative=1e-7
+            );
+    }
+}
+/// dT_sat/dp against a central difference of the 
+/// saturation temperature equation
+#[test]
+pub fn dt_sat_dp_test(){
+
+    for p_mpa in [0.001, 0.1, 1.0, 10.0, 16.5292, 20.0, 22.0] {
+        let p = Pressure::new::<megapascal>(p_mpa);
+        let dp = 1.0e-6 * p_mpa;
+
+        let t_plus = sat_temp_4(Pressure::new::<megapascal>(p_mpa + dp));
+        let t_minus = sat_temp_4(Pressure::new::<megapascal>(p_mpa - dp));
+        let dt_dp_numerical = (t_plus.get::<kelvin>() - t_minus.get::<kelvin>())
+            /(2.0 * dp * 1.0e6);
+
+        approx::assert_relative_eq!(
+            dt_sat_dp_4(p).value,
+            dt_dp_numerical,
+            max_relative=1e-6
+            );
+    }
+}
@@ -0,0 +1,80 @@
+use uom::si::available_energy::kilojoule_per_kilogram;
+use uom::si::f64::*;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::errors::SteamTableError;
+
+use super::{cp_tp_5, h_tp_5};
+
+/// there are no IAPWS backward equations for region 5, 
+/// so we iterate on the forward equation h_tp_5 
+/// using newton raphson 
+///
+/// (dh/dT)_p = cp 
+///
+/// the iteration stops when the temperature correction is 
+/// less than this (in K)
+const T_PH_5_TOLERANCE_KELVIN: f64 = 1.0e-9;
+const T_PH_5_MAX_ITERATIONS: usize = 50;
+
+/// returns the region 5 temperature given pressure and enthalpy 
+/// by iterating on the forward equation h_tp_5
+///
+/// valid from 1073.15 K to 2273.15 K 
+/// pressure up to 50 MPa
+///
+/// panics if the newton raphson iteration does not converge
+pub fn t_ph_5(p: Pressure, h: AvailableEnergy) -> ThermodynamicTemperature {
+    match try_t_ph_5(p, h) {
+        Ok(t) => t,
+        Err(error) => panic!("region 5 p,h flash failed: {}", error),
+    }
+}
+
+/// returns the region 5 temperature given pressure and enthalpy, 
+/// or an error if the newton raphson iteration does not converge 
+/// within the maximum number of iterations
+pub fn try_t_ph_5(p: Pressure, 
+    h: AvailableEnergy) -> Result<ThermodynamicTemperature, SteamTableError> {
+    let (t, _iterations) = try_t_ph_5_with_iterations(p, h)?;
+
+    return Ok(t);
+}
+
+/// same as try_t_ph_5, but also returns the number of newton raphson
+/// iterations taken (used for flash diagnostics)
+pub(crate) fn try_t_ph_5_with_iterations(p: Pressure,
+    h: AvailableEnergy) -> Result<(ThermodynamicTemperature, usize), SteamTableError> {
+
+    let h_target = h.get::<kilojoule_per_kilogram>();
+
+    // initial guess, treat steam as having constant cp 
+    // starting from the 1073.15 K isotherm 
+    // h - h_ref = cp (T - T_ref) at const pressure
+    let t_ref = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let h_ref = h_tp_5(t_ref, p).get::<kilojoule_per_kilogram>();
+    let cp_ref = cp_tp_5(t_ref, p).get::<kilojoule_per_kilogram_kelvin>();
+
+    let mut t_kelvin = t_ref.get::<kelvin>() + (h_target - h_ref)/cp_ref;
+
+    let mut iterations: usize = 0;
+
+    for _ in 0..T_PH_5_MAX_ITERATIONS {
+        iterations += 1;
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let residual = h_tp_5(t, p).get::<kilojoule_per_kilogram>()
+            - h_target;
+        let dh_dt = cp_tp_5(t, p).get::<kilojoule_per_kilogram_kelvin>();
+
+        let delta_t_kelvin = residual/dh_dt;
+        t_kelvin -= delta_t_kelvin;
+
+        if delta_t_kelvin.abs() < T_PH_5_TOLERANCE_KELVIN {
+            return Ok((ThermodynamicTemperature::new::<kelvin>(t_kelvin), iterations));
+        };
+    }
+
+    return Err(SteamTableError::FlashDidNotConverge { 
+        flash: "region 5 (p,h)", iterations });
+}
@@ -0,0 +1,80 @@
+use uom::si::f64::*;
+use uom::si::specific_heat_capacity::kilojoule_per_kilogram_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::errors::SteamTableError;
+
+use super::{cp_tp_5, s_tp_5};
+
+/// there are no IAPWS backward equations for region 5, 
+/// so we iterate on the forward equation s_tp_5 
+/// using newton raphson 
+///
+/// (ds/dT)_p = cp/T 
+///
+/// the iteration stops when the temperature correction is 
+/// less than this (in K)
+const T_PS_5_TOLERANCE_KELVIN: f64 = 1.0e-9;
+const T_PS_5_MAX_ITERATIONS: usize = 50;
+
+/// returns the region 5 temperature given pressure and entropy 
+/// by iterating on the forward equation s_tp_5
+///
+/// valid from 1073.15 K to 2273.15 K 
+/// pressure up to 50 MPa
+///
+/// panics if the newton raphson iteration does not converge
+pub fn t_ps_5(p: Pressure, s: SpecificHeatCapacity) -> ThermodynamicTemperature {
+    match try_t_ps_5(p, s) {
+        Ok(t) => t,
+        Err(error) => panic!("region 5 p,s flash failed: {}", error),
+    }
+}
+
+/// returns the region 5 temperature given pressure and entropy, 
+/// or an error if the newton raphson iteration does not converge 
+/// within the maximum number of iterations
+pub fn try_t_ps_5(p: Pressure, 
+    s: SpecificHeatCapacity) -> Result<ThermodynamicTemperature, SteamTableError> {
+    let (t, _iterations) = try_t_ps_5_with_iterations(p, s)?;
+
+    return Ok(t);
+}
+
+/// same as try_t_ps_5, but also returns the number of newton raphson
+/// iterations taken (used for flash diagnostics)
+pub(crate) fn try_t_ps_5_with_iterations(p: Pressure,
+    s: SpecificHeatCapacity) -> Result<(ThermodynamicTemperature, usize), SteamTableError> {
+
+    let s_target = s.get::<kilojoule_per_kilogram_kelvin>();
+
+    // initial guess, treat steam as ideal gas with constant cp 
+    // starting from the 1073.15 K isotherm 
+    // s - s_ref = cp ln (T/T_ref) at const pressure
+    let t_ref = ThermodynamicTemperature::new::<kelvin>(1073.15);
+    let s_ref = s_tp_5(t_ref, p).get::<kilojoule_per_kilogram_kelvin>();
+    let cp_ref = cp_tp_5(t_ref, p).get::<kilojoule_per_kilogram_kelvin>();
+
+    let mut t_kelvin = t_ref.get::<kelvin>() * ((s_target - s_ref)/cp_ref).exp();
+
+    let mut iterations: usize = 0;
+
+    for _ in 0..T_PS_5_MAX_ITERATIONS {
+        iterations += 1;
+        let t = ThermodynamicTemperature::new::<kelvin>(t_kelvin);
+        let residual = s_tp_5(t, p).get::<kilojoule_per_kilogram_kelvin>()
+            - s_target;
+        let ds_dt = cp_tp_5(t, p).get::<kilojoule_per_kilogram_kelvin>()
+            / t_kelvin;
+
+        let delta_t_kelvin = residual/ds_dt;
+        t_kelvin -= delta_t_kelvin;
+
+        if delta_t_kelvin.abs() < T_PS_5_TOLERANCE_KELVIN {
+            return Ok((ThermodynamicTemperature::new::<kelvin>(t_kelvin), iterations));
+        };
+    }
+
+    return Err(SteamTableError::FlashDidNotConverge { 
+        flash: "region 5 (p,s)", iterations });
+}
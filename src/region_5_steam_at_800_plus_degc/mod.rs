@@ -28,6 +28,16 @@ pub use gamma_residual_plus_derivatives::*;
 pub mod intensive_properties;
 pub use intensive_properties::*;
 
+/// iterative inversion of the forward equations for 
+/// pressure and entropy (p,s) flash 
+pub mod backward_eqn_ps_5;
+pub use backward_eqn_ps_5::*;
+
+/// iterative inversion of the forward equations for 
+/// pressure and enthalpy (p,h) flash 
+pub mod backward_eqn_ph_5;
+pub use backward_eqn_ph_5::*;
+
 #[cfg(test)]
 mod tests;
 
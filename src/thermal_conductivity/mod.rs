@@ -14,6 +14,9 @@ use crate::interfaces::functional_programming::pt_flash_eqm::{cp_tp_eqm_single_p
 use crate::interfaces::functional_programming::pt_flash_eqm::cv_tp_eqm_single_phase;
 use crate::interfaces::functional_programming::pt_flash_eqm::kappa_t_tp_eqm;
 use crate::interfaces::functional_programming::pt_flash_eqm::v_tp_eqm_single_phase;
+use crate::interfaces::functional_programming::pt_flash_eqm::{region_fwd_eqn_two_phase, FwdEqnRegion};
+use crate::interfaces::functional_programming::trho_flash_eqm::{lambda_2_trho_single_phase, lambda_trho_single_phase, sat_liq_vap_regions_trho, sat_p_rho_liq_vap_trho};
+use crate::region_4_vap_liq_equilibrium::sat_temp_4;
 use crate::region_5_steam_at_800_plus_degc::InversePressure;
 
 const LAMBDA_0_COEFFS: [[f64; 2]; 5] = [
@@ -38,6 +41,21 @@ pub fn lambda_tp_eqm_single_phase(t: ThermodynamicTemperature,
     return lambda_star * dimensionless_lambda;
 
 }
+/// thermal conductivity given temperature, pressure and 
+/// steam quality 
+///
+/// in the two phase region, thermal conductivity is not a 
+/// property of the mixture as such, the saturated liquid and 
+/// vapour each have their own thermal conductivity 
+/// (see lambda_sat_liq_vap_t), 
+/// so here we define the two phase (mixture) thermal conductivity 
+/// in the same way as the (T,rho) flash and SteamState:
+///
+/// lambda = lambda_star (lambda_0 lambda_1 + lambda_2)
+///
+/// where lambda_0 lambda_1 are evaluated at the mixture density, 
+/// and lambda_2 is the quality weighted critical enhancement 
+/// term of the saturated liquid and vapour
 pub fn lambda_tp_eqm_two_phase(t: ThermodynamicTemperature,
     p: Pressure,
     x: f64) -> ThermalConductivity {
@@ -45,7 +63,7 @@ pub fn lambda_tp_eqm_two_phase(t: ThermodynamicTemperature,
     let rho = v_tp_eqm_two_phase(t, p, x).recip();
     let lambda_0 = lambda_0(t);
     let lambda_1 = lambda_1(rho, t);
-    let lambda_2 = lambda_2_crit_enhancement_term_tp_two_phase_estimate(t, p, x);
+    let lambda_2 = lambda_2_crit_enhancement_term_tp_two_phase(t, p, x);
     let lambda_star = ThermalConductivity::new::<watt_per_meter_kelvin>(1.0e-3);
 
     let dimensionless_lambda = lambda_0 * lambda_1 + lambda_2;
@@ -54,6 +72,34 @@ pub fn lambda_tp_eqm_two_phase(t: ThermodynamicTemperature,
 
 }
 
+/// thermal conductivity of the saturated liquid and 
+/// saturated vapour (in that order) given saturation temperature, 
+/// from 273.15 K up to the critical temperature
+///
+/// each phase is evaluated at its own saturated density,
+/// including the critical enhancement term (lambda_2) 
+/// from its own cp, cv and isothermal compressibility
+pub fn lambda_sat_liq_vap_t(t: ThermodynamicTemperature) 
+    -> (ThermalConductivity, ThermalConductivity) {
+
+    let (p_sat, rho_liq, rho_vap) = sat_p_rho_liq_vap_trho(t);
+    let (liq_region, vap_region) = sat_liq_vap_regions_trho(t);
+
+    let lambda_liq = lambda_trho_single_phase(liq_region, t, p_sat, rho_liq);
+    let lambda_vap = lambda_trho_single_phase(vap_region, t, p_sat, rho_vap);
+
+    return (lambda_liq, lambda_vap);
+}
+
+/// thermal conductivity of the saturated liquid and 
+/// saturated vapour (in that order) given saturation pressure, 
+/// from the triple point up to the critical pressure
+pub fn lambda_sat_liq_vap_p(p: Pressure) 
+    -> (ThermalConductivity, ThermalConductivity) {
+
+    return lambda_sat_liq_vap_t(sat_temp_4(p));
+}
+
 pub(crate) fn lambda_0(t: ThermodynamicTemperature) -> f64 {
     let t_c = t_crit_water();
     let theta_f64: f64 = (t/t_c).get::<ratio>();
@@ -174,80 +220,83 @@ pub(crate) fn lambda_1(rho: MassDensity,
     exponent.exp()
 
 }
-pub(crate) fn lambda_2_crit_enhancement_term_tp_two_phase_estimate(
+/// critical enhancement term (lambda_2) given temperature,
+/// pressure and steam quality
+///
+/// in region 4, this is the quality weighted lambda_2 
+/// of the saturated liquid and vapour 
+/// (see lambda_2_crit_enhancement_term_two_phase),
+/// otherwise it is the single phase lambda_2 using the 
+/// density, cp, cv and isothermal compressibility of the region 
+/// given by the steam quality 
+/// (so that the bubble and dew points use the properties 
+/// of the liquid and vapour respectively)
+pub(crate) fn lambda_2_crit_enhancement_term_tp_two_phase(
     t: ThermodynamicTemperature,
     p: Pressure,
     x: f64) -> f64 {
 
-    let rho = v_tp_eqm_two_phase(t, p, x).recip();
-    let t_c = t_crit_water();
-    let theta_f64: f64 = (t/t_c).get::<ratio>();
-    let rho_c = rho_crit_water();
-    let delta_f64: f64 = (rho/rho_c).get::<ratio>();
-
-    // this is dimensionless viscosity
-    let psi = psi_0_viscosity(t) * psi_1_viscosity(t, rho);
-
-    // these terms are independent of density
-    let n1 = 0.177_851_4e3;
-    let n2 = 0.636_619_772_367_581;
-    let n3 = 0.135_882_142_589_674e1;
-    let n4 = 0.508_474_576_271;
-    let n5 = 1.5;
-
-    // now, looks like we need to calculate certain properties 
-    // such as cp, cv and kappa_t
-    //
-    // those require p,h or tp flashing in order to work outside region 3 
-    //
-    // we only have rho and t now
-    // which doesn't exactly work outside region 3
-    // so without iterations, this will be a problem.
-    //
-    // However, perhaps one can assume pressure is given since 
-    // we often use tp or ph flashing, that makes things a lot easier
-    //
-    //
-    // so a pt flash would be good.
-    //
-    // However, it won't work in region 4 as there are two phases 
-    // to deal with
-    //
-    // it may be more reasonable to work with p,h flash from the get go
-    //
-
-    let mut cp = cp_tp_eqm_single_phase(t, p);
-
-    if cp.get::<kilojoule_per_kilogram_kelvin>() < 0.0 {
-        cp = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(1.0e13);
-    } else if cp.get::<kilojoule_per_kilogram_kelvin>() > 1.0e13 {
-        cp = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(1.0e13);
-    };
-    let cv = cv_tp_eqm_single_phase(t, p);
-    let kappa_t = kappa_t_tp_eqm(t, p);
+    match region_fwd_eqn_two_phase(t, p, x) {
+        FwdEqnRegion::Region4 => lambda_2_crit_enhancement_term_two_phase(t, x),
+        region => {
+            let rho = v_tp_eqm_two_phase(t, p, x).recip();
+            lambda_2_trho_single_phase(region, t, p, rho)
+        },
+    }
+}
 
-    let b: f64 = (cp/cv).get::<ratio>();
-    let captial_a: f64 = captial_a(n2, n3, delta_f64, theta_f64, 
-        kappa_t, n4, n5, b);
+/// critical enhancement term (lambda_2) for a saturated mixture 
+/// given saturation temperature and steam quality 
+/// (clamped between 0 and 1)
+///
+/// lambda_2 is computed for the saturated liquid and vapour 
+/// separately, each at its own saturated density with its own 
+/// cp, cv and isothermal compressibility, 
+/// and these are weighted by steam quality
+pub(crate) fn lambda_2_crit_enhancement_term_two_phase(
+    t: ThermodynamicTemperature,
+    x: f64) -> f64 {
 
+    let x_clamped = x.clamp(0.0, 1.0);
 
-    let gas_constant_r = 
-        SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(
-            0.461_518_05
-        );
-    
-    let lambda_2 = n1 * delta_f64 * theta_f64 / psi * 
-        cp/gas_constant_r * captial_a;
+    let (p_sat, rho_liq, rho_vap) = sat_p_rho_liq_vap_trho(t);
+    let (liq_region, vap_region) = sat_liq_vap_regions_trho(t);
 
+    let lambda_2_liq = lambda_2_trho_single_phase(liq_region, t, p_sat, rho_liq);
+    let lambda_2_vap = lambda_2_trho_single_phase(vap_region, t, p_sat, rho_vap);
 
-    return lambda_2.get::<ratio>();
+    return lambda_2_liq * (1.0 - x_clamped) + lambda_2_vap * x_clamped;
 }
 
 pub(crate) fn lambda_2_crit_enhancement_term_tp_single_phase(
     t: ThermodynamicTemperature,
     p: Pressure) -> f64 {
 
+    // cp, cv and kappa_t come from the (T,p) flash, 
+    // this doesn't work in region 4 as there are two phases 
+    // to deal with (see lambda_2_crit_enhancement_term_two_phase)
     let rho = v_tp_eqm_single_phase(t, p).recip();
+    let cp = cp_tp_eqm_single_phase(t, p);
+    let cv = cv_tp_eqm_single_phase(t, p);
+    let kappa_t = kappa_t_tp_eqm(t, p);
+
+    return lambda_2_crit_enhancement_term_rho_t(rho, t, cp, cv, kappa_t);
+}
+
+/// critical enhancement term (lambda_2) for thermal conductivity 
+/// given density and temperature, as well as the 
+/// cp, cv and isothermal compressibility at that state
+///
+/// this is useful where density is known, eg. the (T,rho) flash, 
+/// so that we don't need another (T,p) flash to get these 
+/// properties
+pub(crate) fn lambda_2_crit_enhancement_term_rho_t(
+    rho: MassDensity,
+    t: ThermodynamicTemperature,
+    cp: SpecificHeatCapacity,
+    cv: SpecificHeatCapacity,
+    kappa_t: InversePressure) -> f64 {
+
     let t_c = t_crit_water();
     let theta_f64: f64 = (t/t_c).get::<ratio>();
     let rho_c = rho_crit_water();
@@ -263,36 +312,14 @@ pub(crate) fn lambda_2_crit_enhancement_term_tp_single_phase(
     let n4 = 0.508_474_576_271;
     let n5 = 1.5;
 
-    // now, looks like we need to calculate certain properties 
-    // such as cp, cv and kappa_t
-    //
-    // those require p,h or tp flashing in order to work outside region 3 
-    //
-    // we only have rho and t now
-    // which doesn't exactly work outside region 3
-    // so without iterations, this will be a problem.
-    //
-    // However, perhaps one can assume pressure is given since 
-    // we often use tp or ph flashing, that makes things a lot easier
-    //
-    //
-    // so a pt flash would be good.
-    //
-    // However, it won't work in region 4 as there are two phases 
-    // to deal with
-    //
-    // it may be more reasonable to work with p,h flash from the get go
-    //
-
-    let mut cp = cp_tp_eqm_single_phase(t, p);
+    // cp is limited to 1.0e13 kJ/(kg K) near the critical point
+    let mut cp = cp;
 
     if cp.get::<kilojoule_per_kilogram_kelvin>() < 0.0 {
         cp = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(1.0e13);
     } else if cp.get::<kilojoule_per_kilogram_kelvin>() > 1.0e13 {
         cp = SpecificHeatCapacity::new::<kilojoule_per_kilogram_kelvin>(1.0e13);
     };
-    let cv = cv_tp_eqm_single_phase(t, p);
-    let kappa_t = kappa_t_tp_eqm(t, p);
 
     let b: f64 = (cp/cv).get::<ratio>();
     let captial_a: f64 = captial_a(n2, n3, delta_f64, theta_f64, 